@@ -14,6 +14,7 @@
 
 #![deny(unused_crate_dependencies)]
 
+mod orc_read;
 mod parquet_read;
 mod parquet_write;
 pub mod schema_projection;
@@ -21,6 +22,11 @@ pub mod schema_projection;
 pub use arrow;
 pub use arrow_format;
 pub use native;
+pub use orc_read::deserialize_orc_stripe_column;
+pub use orc_read::infer_orc_data_type;
+pub use orc_read::orc_tail_length;
+pub use orc_read::read_orc_metadata;
+pub use orc_read::OrcStripeReader;
 pub use parquet2 as parquet;
 pub use parquet_read::read_columns_async;
 pub use parquet_read::read_columns_many_async;
@@ -0,0 +1,750 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Deserialize columns of ORC stripes to arrow arrays.
+//!
+//! Compared with `arrow::io::orc::read`, columns without `PRESENT` streams,
+//! tinyint columns, dates and dictionary encoded strings are supported,
+//! which are common in files written by Hive.
+//! Structs, lists and maps are deserialized with their children.
+
+use std::io::Cursor;
+use std::io::Read;
+use std::io::Seek;
+use std::io::SeekFrom;
+
+use arrow::array::growable::make_growable;
+use arrow::array::Array;
+use arrow::array::BinaryArray;
+use arrow::array::BooleanArray;
+use arrow::array::ListArray;
+use arrow::array::MapArray;
+use arrow::array::PrimitiveArray;
+use arrow::array::StructArray;
+use arrow::array::Utf8Array;
+use arrow::bitmap::Bitmap;
+use arrow::bitmap::MutableBitmap;
+use arrow::datatypes::DataType;
+use arrow::datatypes::Field;
+use arrow::error::Error;
+use arrow::error::Result;
+use arrow::io::orc::format::proto::column_encoding::Kind as EncodingKind;
+use arrow::io::orc::format::proto::stream::Kind as StreamKind;
+use arrow::io::orc::format::proto::r#type::Kind as TypeKind;
+use arrow::io::orc::format::proto::CompressionKind;
+use arrow::io::orc::format::proto::Footer;
+use arrow::io::orc::format::proto::Metadata;
+use arrow::io::orc::format::proto::PostScript;
+use arrow::io::orc::format::proto::StripeFooter;
+use arrow::io::orc::format::proto::Type;
+use arrow::io::orc::format::read::decode;
+use arrow::io::orc::format::read::decompress::Decompressor;
+use arrow::io::orc::format::read::read_stripe_column;
+use arrow::io::orc::format::read::Column;
+use arrow::io::orc::format::read::FileMetadata;
+use arrow::offset::Offsets;
+use arrow::types::Index;
+use arrow::types::NativeType;
+use prost::Message;
+
+/// Get the length of the tail of ORC file from `last_bytes`, the last bytes of the file
+/// including the postscript. The tail consists of the metadata, the footer,
+/// the postscript and the length of postscript.
+pub fn orc_tail_length(last_bytes: &[u8]) -> Result<usize> {
+    let postscript = decode_postscript(last_bytes)?;
+    let postscript_len = last_bytes[last_bytes.len() - 1] as usize;
+    Ok(postscript.footer_length.unwrap_or_default() as usize
+        + postscript.metadata_length.unwrap_or_default() as usize
+        + postscript_len
+        + 1)
+}
+
+/// Read the metadata of ORC file from the tail of the file with length of [`orc_tail_length`].
+///
+/// Unlike `read_metadata` of `orc-format`, footers larger than 16KB are supported.
+pub fn read_orc_metadata(tail: &[u8]) -> Result<FileMetadata> {
+    let postscript = decode_postscript(tail)?;
+    let compression = postscript.compression();
+    let postscript_len = tail[tail.len() - 1] as usize;
+    let footer_end = tail.len() - postscript_len - 1;
+    let footer_len = postscript.footer_length.unwrap_or_default() as usize;
+    let metadata_len = postscript.metadata_length.unwrap_or_default() as usize;
+    if footer_end < footer_len + metadata_len {
+        return Err(Error::ExternalFormat(
+            "ORC file tail is shorter than the length in postscript".to_string(),
+        ));
+    }
+
+    let footer_start = footer_end - footer_len;
+    let footer = decompress(&tail[footer_start..footer_end], compression)?;
+    let footer = Footer::decode(footer.as_slice()).map_err(|e| {
+        Error::ExternalFormat(format!("failed to decode the footer of ORC file: {e}"))
+    })?;
+    let metadata = decompress(
+        &tail[footer_start - metadata_len..footer_start],
+        compression,
+    )?;
+    let metadata = Metadata::decode(metadata.as_slice()).map_err(|e| {
+        Error::ExternalFormat(format!("failed to decode the metadata of ORC file: {e}"))
+    })?;
+
+    Ok(FileMetadata {
+        postscript,
+        footer,
+        metadata,
+    })
+}
+
+fn decode_postscript(last_bytes: &[u8]) -> Result<PostScript> {
+    let postscript_len = match last_bytes.last() {
+        Some(len) if (*len as usize) < last_bytes.len() => *len as usize,
+        _ => return Err(Error::ExternalFormat("ORC file is too short".to_string())),
+    };
+    let end = last_bytes.len() - 1;
+    PostScript::decode(&last_bytes[end - postscript_len..end]).map_err(|e| {
+        Error::ExternalFormat(format!("failed to decode the postscript of ORC file: {e}"))
+    })
+}
+
+fn decompress(bytes: &[u8], compression: CompressionKind) -> Result<Vec<u8>> {
+    let mut buffer = vec![];
+    Decompressor::new(bytes, compression, vec![]).read_to_end(&mut buffer)?;
+    Ok(buffer)
+}
+
+/// Infer the arrow data type of the ORC column `column`.
+///
+/// Structs, lists and maps are inferred with the types of their children, all of which are nullable
+/// except the keys of maps.
+pub fn infer_orc_data_type(types: &[Type], column: u32) -> Result<DataType> {
+    let ty = get_orc_type(types, column)?;
+    let data_type = match ty.kind() {
+        TypeKind::Boolean => DataType::Boolean,
+        TypeKind::Byte => DataType::Int8,
+        TypeKind::Short => DataType::Int16,
+        TypeKind::Int => DataType::Int32,
+        TypeKind::Long => DataType::Int64,
+        TypeKind::Float => DataType::Float32,
+        TypeKind::Double => DataType::Float64,
+        TypeKind::String | TypeKind::Varchar | TypeKind::Char => DataType::Utf8,
+        TypeKind::Binary => DataType::Binary,
+        TypeKind::Date => DataType::Date32,
+        TypeKind::Struct => {
+            if ty.field_names.len() != ty.subtypes.len() {
+                return Err(Error::ExternalFormat(format!(
+                    "ORC struct column {column} has {} names for {} fields",
+                    ty.field_names.len(),
+                    ty.subtypes.len()
+                )));
+            }
+            let fields = ty
+                .field_names
+                .iter()
+                .zip(ty.subtypes.iter())
+                .map(|(name, child)| {
+                    Ok(Field::new(name, infer_orc_data_type(types, *child)?, true))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            DataType::Struct(fields)
+        }
+        TypeKind::List => {
+            let [item] = get_children::<1>(ty, column)?;
+            let item = Field::new("item", infer_orc_data_type(types, item)?, true);
+            DataType::List(Box::new(item))
+        }
+        TypeKind::Map => {
+            let [key, value] = get_children::<2>(ty, column)?;
+            let key = Field::new("key", infer_orc_data_type(types, key)?, false);
+            let value = Field::new("value", infer_orc_data_type(types, value)?, true);
+            let entries = Field::new("entries", DataType::Struct(vec![key, value]), false);
+            DataType::Map(Box::new(entries), false)
+        }
+        kind => {
+            return Err(Error::NotYetImplemented(format!(
+                "Reading {kind:?} from ORC"
+            )));
+        }
+    };
+    Ok(data_type)
+}
+
+fn get_orc_type(types: &[Type], column: u32) -> Result<&Type> {
+    types
+        .get(column as usize)
+        .ok_or_else(|| Error::ExternalFormat(format!("ORC column {column} not found")))
+}
+
+fn get_children<const N: usize>(ty: &Type, column: u32) -> Result<[u32; N]> {
+    ty.subtypes.as_slice().try_into().map_err(|_| {
+        Error::ExternalFormat(format!(
+            "ORC {:?} column {column} has {} children, expected {N}",
+            ty.kind(),
+            ty.subtypes.len()
+        ))
+    })
+}
+
+/// Deserialize the column `column` of the stripe `stripe` to an array of `data_type`,
+/// which is inferred by [`infer_orc_data_type`]. `footer` is the footer of the stripe,
+/// and `reader` reads the whole file, or only the stripe with [`OrcStripeReader`].
+///
+/// The children of structs, lists and maps are read and deserialized recursively.
+pub fn deserialize_orc_stripe_column<R: Read + Seek>(
+    reader: &mut R,
+    meta: &FileMetadata,
+    stripe: usize,
+    footer: &StripeFooter,
+    column: u32,
+    data_type: DataType,
+) -> Result<Box<dyn Array>> {
+    let num_rows = meta.footer.stripes[stripe].number_of_rows() as usize;
+    // columns without any streams are absent, like structs without nulls
+    let mut read_column = |column: u32| -> Result<Option<Column>> {
+        let has_streams = footer
+            .streams
+            .iter()
+            .any(|stream| stream.column() == column && stream.kind() != StreamKind::RowIndex);
+        if !has_streams {
+            return Ok(None);
+        }
+        let column = read_stripe_column(reader, meta, stripe, footer.clone(), column, vec![])?;
+        Ok(Some(column))
+    };
+    deserialize_nested_column(
+        &meta.footer.types,
+        column,
+        data_type,
+        num_rows,
+        &mut read_column,
+    )
+}
+
+/// children have values only for the non-null values of their parents,
+/// so `num_rows` of children are the number of values of their parents.
+fn deserialize_nested_column<F>(
+    types: &[Type],
+    column: u32,
+    data_type: DataType,
+    num_rows: usize,
+    read_column: &mut F,
+) -> Result<Box<dyn Array>>
+where
+    F: FnMut(u32) -> Result<Option<Column>>,
+{
+    let ty = get_orc_type(types, column)?;
+    let array = match data_type {
+        DataType::Struct(fields) => {
+            let (validity, num_values) = match read_column(column)? {
+                Some(column) => deserialize_validity_and_count(&column, num_rows)?,
+                None => (None, num_rows),
+            };
+            let values = fields
+                .iter()
+                .zip(ty.subtypes.iter())
+                .map(|(field, child)| {
+                    let array = deserialize_nested_column(
+                        types,
+                        *child,
+                        field.data_type.clone(),
+                        num_values,
+                        read_column,
+                    )?;
+                    Ok(spread_array(array, validity.as_ref()))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            StructArray::try_new(DataType::Struct(fields), values, validity)?.boxed()
+        }
+        DataType::List(field) => {
+            let [item] = get_children::<1>(ty, column)?;
+            let column = required_column(read_column(column)?, column)?;
+            let (offsets, validity) = deserialize_list_offsets(&column, num_rows)?;
+            let values = deserialize_nested_column(
+                types,
+                item,
+                field.data_type.clone(),
+                offsets.last().to_usize(),
+                read_column,
+            )?;
+            ListArray::<i32>::try_new(DataType::List(field), offsets.into(), values, validity)?
+                .boxed()
+        }
+        DataType::Map(field, sorted) => {
+            let [key, value] = get_children::<2>(ty, column)?;
+            let column = required_column(read_column(column)?, column)?;
+            let (offsets, validity) = deserialize_list_offsets(&column, num_rows)?;
+            let entries_type = field.data_type.clone();
+            let (key_type, value_type) = match &entries_type {
+                DataType::Struct(fields) if fields.len() == 2 => {
+                    (fields[0].data_type.clone(), fields[1].data_type.clone())
+                }
+                other => {
+                    return Err(Error::InvalidArgumentError(format!(
+                        "the entries of map must be a struct of key and value, got {other:?}"
+                    )));
+                }
+            };
+            let num_entries = offsets.last().to_usize();
+            let keys = deserialize_nested_column(types, key, key_type, num_entries, read_column)?;
+            let values =
+                deserialize_nested_column(types, value, value_type, num_entries, read_column)?;
+            let entries = StructArray::try_new(entries_type, vec![keys, values], None)?.boxed();
+            MapArray::try_new(
+                DataType::Map(field, sorted),
+                offsets.into(),
+                entries,
+                validity,
+            )?
+            .boxed()
+        }
+        data_type => {
+            let column = required_column(read_column(column)?, column)?;
+            deserialize_primitive_column(data_type, &column, num_rows)?
+        }
+    };
+    Ok(array)
+}
+
+fn required_column(column: Option<Column>, id: u32) -> Result<Column> {
+    column.ok_or_else(|| Error::ExternalFormat(format!("ORC column {id} has no streams")))
+}
+
+fn deserialize_primitive_column(
+    data_type: DataType,
+    column: &Column,
+    num_rows: usize,
+) -> Result<Box<dyn Array>> {
+    // only non-null values are stored in the streams
+    let (validity, num_values) = deserialize_validity_and_count(column, num_rows)?;
+
+    let array = match data_type {
+        DataType::Boolean => {
+            let values = deserialize_booleans(column, num_values)?;
+            let values = spread(values, validity.as_ref());
+            BooleanArray::try_new(data_type, MutableBitmap::from_iter(values).into(), validity)?
+                .boxed()
+        }
+        DataType::Int8 => {
+            let values = deserialize_bytes(column, num_values)?;
+            primitive_array(data_type, values, validity)?
+        }
+        DataType::Int16 => integer_array::<i16>(data_type, column, num_values, validity)?,
+        DataType::Int32 | DataType::Date32 => {
+            integer_array::<i32>(data_type, column, num_values, validity)?
+        }
+        DataType::Int64 => integer_array::<i64>(data_type, column, num_values, validity)?,
+        DataType::Float32 => {
+            let values = deserialize_floats::<f32>(column, num_values)?;
+            primitive_array(data_type, values, validity)?
+        }
+        DataType::Float64 => {
+            let values = deserialize_floats::<f64>(column, num_values)?;
+            primitive_array(data_type, values, validity)?
+        }
+        DataType::Utf8 => {
+            let (offsets, values) =
+                deserialize_binaries(column, num_rows, num_values, validity.as_ref())?;
+            Utf8Array::<i32>::try_new(data_type, offsets.into(), values.into(), validity)?.boxed()
+        }
+        DataType::Binary => {
+            let (offsets, values) =
+                deserialize_binaries(column, num_rows, num_values, validity.as_ref())?;
+            BinaryArray::<i32>::try_new(data_type, offsets.into(), values.into(), validity)?.boxed()
+        }
+        other => {
+            return Err(Error::NotYetImplemented(format!(
+                "Deserializing {other:?} from ORC"
+            )));
+        }
+    };
+    Ok(array)
+}
+
+/// the `PRESENT` stream is absent if there are no nulls
+fn deserialize_validity(column: &Column, num_rows: usize) -> Result<Option<Bitmap>> {
+    let stream = match column.get_stream(StreamKind::Present, vec![]) {
+        Ok(stream) => stream,
+        Err(_) => return Ok(None),
+    };
+    let validity = decode::BooleanIter::new(stream, num_rows)
+        .collect::<std::result::Result<MutableBitmap, _>>()?;
+    Ok(Some(validity.into()))
+}
+
+/// returns the validity and the number of non-null values
+fn deserialize_validity_and_count(
+    column: &Column,
+    num_rows: usize,
+) -> Result<(Option<Bitmap>, usize)> {
+    let validity = deserialize_validity(column, num_rows)?;
+    let num_values = match &validity {
+        Some(validity) => validity.len() - validity.unset_bits(),
+        None => num_rows,
+    };
+    Ok((validity, num_values))
+}
+
+/// the lengths of lists and maps are stored in the `LENGTH` stream, only for non-null values
+fn deserialize_list_offsets(
+    column: &Column,
+    num_rows: usize,
+) -> Result<(Offsets<i32>, Option<Bitmap>)> {
+    check_encoding(column, &[EncodingKind::Direct, EncodingKind::DirectV2])?;
+    let (validity, num_values) = deserialize_validity_and_count(column, num_rows)?;
+    let lengths = deserialize_unsigned_integers(column, StreamKind::Length, num_values)?;
+    let lengths = spread(lengths, validity.as_ref());
+    let offsets = Offsets::<i32>::try_from_lengths(lengths.into_iter().map(|l| l as usize))?;
+    Ok((offsets, validity))
+}
+
+/// fill default values at positions of nulls
+fn spread<T: Default>(values: Vec<T>, validity: Option<&Bitmap>) -> Vec<T> {
+    match validity {
+        None => values,
+        Some(validity) => {
+            let mut values = values.into_iter();
+            validity
+                .iter()
+                .map(|is_valid| {
+                    if is_valid {
+                        values.next().unwrap_or_default()
+                    } else {
+                        T::default()
+                    }
+                })
+                .collect()
+        }
+    }
+}
+
+/// fill nulls at positions of nulls, like [`spread`] for arrays
+fn spread_array(array: Box<dyn Array>, validity: Option<&Bitmap>) -> Box<dyn Array> {
+    let validity = match validity {
+        Some(validity) if validity.unset_bits() > 0 => validity,
+        _ => return array,
+    };
+    let mut growable = make_growable(&[array.as_ref()], true, validity.len());
+    let mut index = 0;
+    for is_valid in validity.iter() {
+        if is_valid {
+            growable.extend(0, index, 1);
+            index += 1;
+        } else {
+            growable.extend_validity(1);
+        }
+    }
+    growable.as_box()
+}
+
+fn primitive_array<T: NativeType>(
+    data_type: DataType,
+    values: Vec<T>,
+    validity: Option<Bitmap>,
+) -> Result<Box<dyn Array>> {
+    let values = spread(values, validity.as_ref());
+    Ok(PrimitiveArray::try_new(data_type, values.into(), validity)?.boxed())
+}
+
+fn integer_array<T: NativeType + TryFrom<i64>>(
+    data_type: DataType,
+    column: &Column,
+    num_values: usize,
+    validity: Option<Bitmap>,
+) -> Result<Box<dyn Array>> {
+    let values = deserialize_integers(column, num_values)?
+        .into_iter()
+        .map(|v| T::try_from(v).map_err(|_| Error::ExternalFormat(format!("value {v} uncastable"))))
+        .collect::<Result<Vec<T>>>()?;
+    primitive_array(data_type, values, validity)
+}
+
+fn check_encoding(column: &Column, expected: &[EncodingKind]) -> Result<EncodingKind> {
+    let kind = column.encoding().kind();
+    if expected.contains(&kind) {
+        Ok(kind)
+    } else {
+        Err(Error::NotYetImplemented(format!(
+            "Reading ORC columns with {kind:?} encoding"
+        )))
+    }
+}
+
+fn deserialize_booleans(column: &Column, num_values: usize) -> Result<Vec<bool>> {
+    if num_values == 0 {
+        return Ok(vec![]);
+    }
+    let stream = column.get_stream(StreamKind::Data, vec![])?;
+    Ok(decode::BooleanIter::new(stream, num_values).collect::<std::result::Result<_, _>>()?)
+}
+
+/// tinyint values are encoded with byte run length encoding
+fn deserialize_bytes(column: &Column, num_values: usize) -> Result<Vec<i8>> {
+    if num_values == 0 {
+        return Ok(vec![]);
+    }
+    let mut stream = column.get_stream(StreamKind::Data, vec![])?;
+    let mut values = Vec::with_capacity(num_values);
+    let mut header = [0u8; 1];
+    while values.len() < num_values {
+        stream.read_exact(&mut header)?;
+        let header = header[0] as i8;
+        if header >= 0 {
+            // a run of a value repeated 3 to 130 times
+            let mut value = [0u8; 1];
+            stream.read_exact(&mut value)?;
+            values.extend(std::iter::repeat(value[0] as i8).take(header as usize + 3));
+        } else {
+            // 1 to 128 literal values
+            let mut literals = vec![0u8; -(header as i16) as usize];
+            stream.read_exact(&mut literals)?;
+            values.extend(literals.into_iter().map(|v| v as i8));
+        }
+    }
+    values.truncate(num_values);
+    Ok(values)
+}
+
+fn deserialize_integers(column: &Column, num_values: usize) -> Result<Vec<i64>> {
+    let encoding = check_encoding(column, &[EncodingKind::Direct, EncodingKind::DirectV2])?;
+    if num_values == 0 {
+        return Ok(vec![]);
+    }
+    let stream = column.get_stream(StreamKind::Data, vec![])?;
+    if encoding == EncodingKind::Direct {
+        return decode_rle_v1(stream, num_values, true);
+    }
+    Ok(decode::SignedRleV2Iter::new(stream, num_values, vec![])
+        .collect::<std::result::Result<_, _>>()?)
+}
+
+fn deserialize_unsigned_integers(
+    column: &Column,
+    kind: StreamKind,
+    num_values: usize,
+) -> Result<Vec<u64>> {
+    if num_values == 0 {
+        return Ok(vec![]);
+    }
+    let stream = column.get_stream(kind, vec![])?;
+    if matches!(
+        column.encoding().kind(),
+        EncodingKind::Direct | EncodingKind::Dictionary
+    ) {
+        let values = decode_rle_v1(stream, num_values, false)?;
+        return Ok(values.into_iter().map(|v| v as u64).collect());
+    }
+    Ok(decode::UnsignedRleV2Iter::new(stream, num_values, vec![])
+        .collect::<std::result::Result<_, _>>()?)
+}
+
+/// integers of the `DIRECT` and `DICTIONARY` encodings, written by ORC 0.11,
+/// are encoded with run length encoding version 1.
+fn decode_rle_v1<R: Read>(mut stream: R, num_values: usize, signed: bool) -> Result<Vec<i64>> {
+    let read_value = |stream: &mut R| -> Result<i64> {
+        let value = read_varint(stream)?;
+        if signed {
+            // zigzag encoded
+            Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+        } else {
+            Ok(value as i64)
+        }
+    };
+
+    let mut values = Vec::with_capacity(num_values);
+    let mut header = [0u8; 1];
+    while values.len() < num_values {
+        stream.read_exact(&mut header)?;
+        let header = header[0] as i8;
+        if header >= 0 {
+            // a run of 3 to 130 values with a fixed delta between -128 and 127
+            let mut delta = [0u8; 1];
+            stream.read_exact(&mut delta)?;
+            let delta = delta[0] as i8 as i64;
+            let base = read_value(&mut stream)?;
+            values.extend((0..header as i64 + 3).map(|i| base.wrapping_add(i.wrapping_mul(delta))));
+        } else {
+            // 1 to 128 literal values
+            for _ in 0..-(header as i16) {
+                values.push(read_value(&mut stream)?);
+            }
+        }
+    }
+    values.truncate(num_values);
+    Ok(values)
+}
+
+/// base 128 varint, the lower 7 bits of each byte are the value bits.
+fn read_varint<R: Read>(stream: &mut R) -> Result<u64> {
+    let mut value = 0u64;
+    let mut shift = 0;
+    let mut byte = [0u8; 1];
+    loop {
+        stream.read_exact(&mut byte)?;
+        if shift >= 64 {
+            return Err(Error::ExternalFormat(
+                "ORC varint is longer than 64 bits".to_string(),
+            ));
+        }
+        value |= ((byte[0] & 0x7f) as u64) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+        shift += 7;
+    }
+}
+
+fn deserialize_floats<T: NativeType + decode::Float>(
+    column: &Column,
+    num_values: usize,
+) -> Result<Vec<T>> {
+    if num_values == 0 {
+        return Ok(vec![]);
+    }
+    let stream = column.get_stream(StreamKind::Data, vec![])?;
+    Ok(
+        decode::FloatIter::<T, _>::new(stream, num_values)
+            .collect::<std::result::Result<_, _>>()?,
+    )
+}
+
+fn read_bytes(column: &Column, kind: StreamKind, len: usize) -> Result<Vec<u8>> {
+    let mut values = vec![0; len];
+    if len > 0 {
+        column.get_stream(kind, vec![])?.read_exact(&mut values)?;
+    }
+    Ok(values)
+}
+
+/// strings and binaries are encoded directly as lengths and data,
+/// or as indexes of a dictionary.
+fn deserialize_binaries(
+    column: &Column,
+    num_rows: usize,
+    num_values: usize,
+    validity: Option<&Bitmap>,
+) -> Result<(Offsets<i32>, Vec<u8>)> {
+    let encoding = check_encoding(column, &[
+        EncodingKind::Direct,
+        EncodingKind::Dictionary,
+        EncodingKind::DirectV2,
+        EncodingKind::DictionaryV2,
+    ])?;
+
+    if matches!(encoding, EncodingKind::Direct | EncodingKind::DirectV2) {
+        let lengths = deserialize_unsigned_integers(column, StreamKind::Length, num_values)?;
+        let lengths = spread(lengths, validity);
+        let offsets = Offsets::<i32>::try_from_lengths(lengths.into_iter().map(|l| l as usize))?;
+        let values = read_bytes(column, StreamKind::Data, offsets.last().to_usize())?;
+        return Ok((offsets, values));
+    }
+
+    let dictionary_size = column.dictionary_size().unwrap_or_default();
+    let lengths = deserialize_unsigned_integers(column, StreamKind::Length, dictionary_size)?;
+    let dictionary_offsets = Offsets::<i64>::try_from_lengths(lengths.iter().map(|l| *l as usize))?;
+    let dictionary = read_bytes(
+        column,
+        StreamKind::DictionaryData,
+        dictionary_offsets.last().to_usize(),
+    )?;
+
+    let indexes = deserialize_unsigned_integers(column, StreamKind::Data, num_values)?;
+    let mut offsets = Offsets::<i32>::with_capacity(num_rows);
+    let mut values = Vec::new();
+    let mut indexes = indexes.into_iter();
+    for is_valid in validity
+        .map(|v| v.iter().collect::<Vec<_>>())
+        .unwrap_or_else(|| vec![true; num_rows])
+    {
+        if !is_valid {
+            offsets.try_push_usize(0)?;
+            continue;
+        }
+        let index = indexes.next().unwrap_or_default() as usize;
+        if index >= dictionary_size {
+            return Err(Error::ExternalFormat(format!(
+                "ORC dictionary index {index} out of range {dictionary_size}"
+            )));
+        }
+        let (start, end) = dictionary_offsets.start_end(index);
+        values.extend_from_slice(&dictionary[start..end]);
+        offsets.try_push_usize(end - start)?;
+    }
+    Ok((offsets, values))
+}
+
+/// A stripe read in memory, which is read by [`deserialize_orc_stripe_column`]
+/// with the positions in the whole file.
+pub struct OrcStripeReader {
+    offset: u64,
+    inner: Cursor<Vec<u8>>,
+}
+
+impl OrcStripeReader {
+    /// `data` is the stripe starting at `offset` of the file.
+    pub fn new(offset: u64, data: Vec<u8>) -> Self {
+        Self {
+            offset,
+            inner: Cursor::new(data),
+        }
+    }
+}
+
+impl Read for OrcStripeReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        self.inner.read(buf)
+    }
+}
+
+impl Seek for OrcStripeReader {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let pos = match pos {
+            SeekFrom::Start(pos) => {
+                SeekFrom::Start(pos.checked_sub(self.offset).ok_or_else(|| {
+                    std::io::Error::new(
+                        std::io::ErrorKind::InvalidInput,
+                        "seek to the position before the stripe",
+                    )
+                })?)
+            }
+            pos => pos,
+        };
+        Ok(self.inner.seek(pos)? + self.offset)
+    }
+}
+
+#[cfg(test)]
+mod orc_read_test {
+    use super::decode_rle_v1;
+
+    // the examples in the ORC specification
+    #[test]
+    fn test_decode_rle_v1() {
+        let values = decode_rle_v1([0x61u8, 0x00, 0x07].as_slice(), 100, false).unwrap();
+        assert_eq!(values, vec![7; 100]);
+
+        let values = decode_rle_v1([0x61u8, 0xff, 0x64].as_slice(), 100, false).unwrap();
+        assert_eq!(values, (1..=100).rev().collect::<Vec<_>>());
+
+        let bytes = [0xfbu8, 0x02, 0x03, 0x06, 0x07, 0x0b];
+        let values = decode_rle_v1(bytes.as_slice(), 5, false).unwrap();
+        assert_eq!(values, vec![2, 3, 6, 7, 11]);
+
+        // zigzag encoded literals, and a varint of 2 bytes
+        let bytes = [0xfdu8, 0x01, 0x02, 0xac, 0x02];
+        let values = decode_rle_v1(bytes.as_slice(), 3, true).unwrap();
+        assert_eq!(values, vec![-1, 1, 150]);
+
+        // truncated stream
+        assert!(decode_rle_v1([0xfbu8, 0x02].as_slice(), 5, false).is_err());
+    }
+}
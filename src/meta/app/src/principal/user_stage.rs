@@ -108,7 +108,13 @@ pub enum StageFileCompression {
 
 impl StageFileFormatType {
     pub fn has_inner_schema(&self) -> bool {
-        matches!(self, StageFileFormatType::Parquet)
+        matches!(
+            self,
+            StageFileFormatType::Parquet
+                | StageFileFormatType::Avro
+                | StageFileFormatType::Orc
+                | StageFileFormatType::ArrowIpc
+        )
     }
 }
 
@@ -167,6 +173,7 @@ pub enum StageFileFormatType {
     Orc,
     Parquet,
     Xml,
+    ArrowIpc,
     None,
 }
 
@@ -186,11 +193,11 @@ impl FromStr for StageFileFormatType {
             "PARQUET" => Ok(StageFileFormatType::Parquet),
             "XML" => Ok(StageFileFormatType::Xml),
             "JSON" => Ok(StageFileFormatType::Json),
-            "ORC" | "AVRO" => Err(format!(
-                "File format type '{s}' not implemented yet', must be one of ( CSV | TSV | NDJSON | PARQUET | XML)"
-            )),
+            "AVRO" => Ok(StageFileFormatType::Avro),
+            "ORC" => Ok(StageFileFormatType::Orc),
+            "ARROW" | "ARROWIPC" | "FEATHER" => Ok(StageFileFormatType::ArrowIpc),
             _ => Err(format!(
-                "Unknown file format type '{s}', must be one of ( CSV | TSV | NDJSON | PARQUET | XML)"
+                "Unknown file format type '{s}', must be one of ( CSV | TSV | NDJSON | PARQUET | XML | AVRO | ORC | ARROW)"
             )),
         }
     }
@@ -23,6 +23,7 @@ pub use catalog::CatalogNameIdent;
 pub use catalog::CatalogOption;
 pub use catalog::CatalogType;
 pub use catalog::CreateCatalogReq;
+pub use catalog::DeltaCatalogOption;
 pub use catalog::DropCatalogReq;
 pub use catalog::IcebergCatalogOption;
 pub use database::CreateDatabaseReply;
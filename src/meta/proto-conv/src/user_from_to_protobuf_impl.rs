@@ -361,6 +361,9 @@ impl FromToProto for mt::principal::StageFileFormatType {
                 Ok(mt::principal::StageFileFormatType::Parquet)
             }
             pb::stage_info::StageFileFormatType::Xml => Ok(mt::principal::StageFileFormatType::Xml),
+            pb::stage_info::StageFileFormatType::ArrowIpc => {
+                Ok(mt::principal::StageFileFormatType::ArrowIpc)
+            }
         }
     }
 
@@ -382,6 +385,9 @@ impl FromToProto for mt::principal::StageFileFormatType {
                 Ok(pb::stage_info::StageFileFormatType::Parquet)
             }
             mt::principal::StageFileFormatType::Xml => Ok(pb::stage_info::StageFileFormatType::Xml),
+            mt::principal::StageFileFormatType::ArrowIpc => {
+                Ok(pb::stage_info::StageFileFormatType::ArrowIpc)
+            }
             mt::principal::StageFileFormatType::None => Err(Incompatible {
                 reason: "StageFileFormatType::None cannot be converted to protobuf".to_string(),
             }),
@@ -61,6 +61,7 @@ const META_CHANGE_LOG: &[(u64, &str)] = &[
     (29, "2023-02-23: Add: metadata.proto/DataType EmptyMap types", ),
     (30, "2023-02-21: Add: config.proto/WebhdfsStorageConfig; Modify: user.proto/UserStageInfo::StageStorage", ),
     (31, "2023-02-21: Add: CopyOptions::max_files", ),
+    (32, "2023-03-08: Add: user.proto/StageFileFormatType::ArrowIpc", ),
     // Dear developer:
     //      If you're gonna add a new metadata version, you'll have to add a test for it.
     //      You could just copy an existing test file(e.g., `../tests/it/v024_table_meta.rs`)
@@ -38,3 +38,4 @@ mod v028_schema;
 mod v029_schema;
 mod v030_user_stage;
 mod v031_copy_max_file;
+mod v032_file_format_arrow_ipc;
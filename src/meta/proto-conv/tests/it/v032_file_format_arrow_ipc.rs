@@ -0,0 +1,49 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_meta_app as mt;
+
+use crate::common;
+
+// These bytes are built when a new version in introduced,
+// and are kept for backward compatibility test.
+//
+// *************************************************************
+// * These messages should never be updated,                   *
+// * only be added when a new version is added,                *
+// * or be removed when an old version is no longer supported. *
+// *************************************************************
+//
+// The message bytes are built from the output of `test_pb_from_to()`
+#[test]
+fn test_decode_v32_file_format_arrow_ipc() -> anyhow::Result<()> {
+    let file_format_options_v32 = vec![8, 8, 40, 9, 160, 6, 32, 168, 6, 24];
+
+    let want = || mt::principal::FileFormatOptions {
+        format: mt::principal::StageFileFormatType::ArrowIpc,
+        skip_header: 0,
+        field_delimiter: "".to_string(),
+        record_delimiter: "".to_string(),
+        nan_display: "".to_string(),
+        escape: "".to_string(),
+        compression: mt::principal::StageFileCompression::None,
+        row_tag: "".to_string(),
+        quote: "".to_string(),
+        name: None,
+    };
+    common::test_load_old(func_name!(), file_format_options_v32.as_slice(), 32, want())?;
+    common::test_pb_from_to(func_name!(), want())?;
+
+    Ok(())
+}
@@ -217,6 +217,13 @@ pub enum Expr {
         unit: IntervalKind,
         date: Box<Expr>,
     },
+    /// The lambda expression `x -> x + 1` or `(acc, x) -> acc + x`,
+    /// only allowed as the argument of higher-order functions.
+    Lambda {
+        span: Span,
+        params: Vec<Identifier>,
+        expr: Box<Expr>,
+    },
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -463,6 +470,9 @@ pub struct WindowSpec {
     pub partition_by: Vec<Expr>,
     pub order_by: Vec<OrderByExpr>,
     pub window_frame: Option<WindowFrame>,
+    /// `IGNORE NULLS` or `RESPECT NULLS` specified before `OVER`,
+    /// only used by navigation functions such as `lag` and `first_value`.
+    pub ignore_nulls: Option<bool>,
 }
 
 /// `RANGE UNBOUNDED PRECEDING` or `ROWS BETWEEN 5 PRECEDING AND CURRENT ROW`.
@@ -618,7 +628,8 @@ impl Expr {
             | Expr::Interval { span, .. }
             | Expr::DateAdd { span, .. }
             | Expr::DateSub { span, .. }
-            | Expr::DateTrunc { span, .. } => *span,
+            | Expr::DateTrunc { span, .. }
+            | Expr::Lambda { span, .. } => *span,
         }
     }
 }
@@ -1152,6 +1163,11 @@ impl Display for Expr {
                 write!(f, ")")?;
 
                 if let Some(window) = window {
+                    match window.ignore_nulls {
+                        Some(true) => write!(f, " IGNORE NULLS")?,
+                        Some(false) => write!(f, " RESPECT NULLS")?,
+                        None => {}
+                    }
                     write!(f, " OVER ({window})")?;
                 }
             }
@@ -1254,6 +1270,16 @@ impl Display for Expr {
             Expr::DateTrunc { unit, date, .. } => {
                 write!(f, "DATE_TRUNC({unit}, {date})")?;
             }
+            Expr::Lambda { params, expr, .. } => {
+                if params.len() == 1 {
+                    write!(f, "{}", params[0])?;
+                } else {
+                    write!(f, "(")?;
+                    write_comma_separated_list(f, params)?;
+                    write!(f, ")")?;
+                }
+                write!(f, " -> {expr}")?;
+            }
         }
 
         Ok(())
@@ -634,6 +634,23 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_lambda(&mut self, _span: Span, params: &'ast [Identifier], expr: &'ast Expr) {
+        self.visit_expr(expr);
+        let child = self.children.pop().unwrap();
+
+        let name = format!(
+            "Lambda {}",
+            params
+                .iter()
+                .map(|param| param.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+        let format_ctx = AstFormatContext::with_children(name, 1);
+        let node = FormatTreeNode::with_children(format_ctx, vec![child]);
+        self.children.push(node);
+    }
+
     fn visit_query(&mut self, query: &'ast Query) {
         let mut children = Vec::new();
         if let Some(with) = &query.with {
@@ -984,11 +1001,16 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
     fn visit_delete(
         &mut self,
         table_reference: &'ast TableReference,
+        using: &'ast Option<TableReference>,
         selection: &'ast Option<Expr>,
     ) {
         let mut children = Vec::new();
         self.visit_table_reference(table_reference);
         children.push(self.children.pop().unwrap());
+        if let Some(using) = using {
+            self.visit_table_reference(using);
+            children.push(self.children.pop().unwrap());
+        }
         if let Some(selection) = selection {
             self.visit_expr(selection);
             children.push(self.children.pop().unwrap());
@@ -1011,6 +1033,10 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
             self.visit_expr(&update_expr.expr);
             children.push(self.children.pop().unwrap());
         }
+        if let Some(from) = &update.from {
+            self.visit_table_reference(from);
+            children.push(self.children.pop().unwrap());
+        }
         if let Some(selection) = &update.selection {
             self.visit_expr(selection);
             children.push(self.children.pop().unwrap());
@@ -1021,6 +1047,61 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.children.push(node);
     }
 
+    fn visit_merge_into(&mut self, merge_into: &'ast MergeIntoStmt) {
+        let mut children = Vec::new();
+        self.visit_table_ref(&merge_into.catalog, &merge_into.database, &merge_into.table);
+        children.push(self.children.pop().unwrap());
+        self.visit_table_reference(&merge_into.source);
+        children.push(self.children.pop().unwrap());
+        self.visit_expr(&merge_into.join_expr);
+        children.push(self.children.pop().unwrap());
+
+        for clause in merge_into.merge_clauses.iter() {
+            let mut clause_children = Vec::new();
+            let (name, selection) = match clause {
+                MergeClause::Matched(clause) => {
+                    if let MatchOperation::Update { update_list } = &clause.operation {
+                        for update_expr in update_list.iter() {
+                            self.visit_identifier(&update_expr.name);
+                            clause_children.push(self.children.pop().unwrap());
+                            self.visit_expr(&update_expr.expr);
+                            clause_children.push(self.children.pop().unwrap());
+                        }
+                    }
+                    let name = match clause.operation {
+                        MatchOperation::Update { .. } => "MatchedUpdate",
+                        MatchOperation::Delete => "MatchedDelete",
+                    };
+                    (name, &clause.selection)
+                }
+                MergeClause::Unmatched(clause) => {
+                    for column in clause.columns.iter() {
+                        self.visit_identifier(column);
+                        clause_children.push(self.children.pop().unwrap());
+                    }
+                    for value in clause.values.iter() {
+                        self.visit_expr(value);
+                        clause_children.push(self.children.pop().unwrap());
+                    }
+                    ("UnmatchedInsert", &clause.selection)
+                }
+            };
+            if let Some(selection) = selection {
+                self.visit_expr(selection);
+                clause_children.push(self.children.pop().unwrap());
+            }
+            let format_ctx =
+                AstFormatContext::with_children(name.to_string(), clause_children.len());
+            let node = FormatTreeNode::with_children(format_ctx, clause_children);
+            children.push(node);
+        }
+
+        let name = "MergeInto".to_string();
+        let format_ctx = AstFormatContext::with_children(name, children.len());
+        let node = FormatTreeNode::with_children(format_ctx, children);
+        self.children.push(node);
+    }
+
     fn visit_show_databases(&mut self, stmt: &'ast ShowDatabasesStmt) {
         let mut children = Vec::new();
         if let Some(limit) = &stmt.limit {
@@ -2215,6 +2296,16 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
             children.push(having_node);
         }
 
+        if let Some(qualify) = &stmt.qualify {
+            self.visit_expr(qualify);
+            let qualify_child = self.children.pop().unwrap();
+            let qualify_name = "Qualify".to_string();
+            let qualify_format_ctx = AstFormatContext::with_children(qualify_name, 1);
+            let qualify_node =
+                FormatTreeNode::with_children(qualify_format_ctx, vec![qualify_child]);
+            children.push(qualify_node);
+        }
+
         let name = "SelectQuery".to_string();
         let format_ctx = AstFormatContext::with_children(name, children.len());
         let node = FormatTreeNode::with_children(format_ctx, children);
@@ -2411,6 +2502,17 @@ impl<'ast> Visitor<'ast> for AstFormatVisitor {
         self.visit_table_reference(&join.right);
         children.push(self.children.pop().unwrap());
 
+        if let Some(match_condition) = &join.match_condition {
+            self.visit_expr(match_condition);
+            let child = self.children.pop().unwrap();
+            let match_condition_name = "MatchCondition".to_string();
+            let match_condition_format_ctx =
+                AstFormatContext::with_children(match_condition_name, 1);
+            let match_condition_node =
+                FormatTreeNode::with_children(match_condition_format_ctx, vec![child]);
+            children.push(match_condition_node);
+        }
+
         match &join.condition {
             JoinCondition::On(expr) => {
                 self.visit_expr(expr);
@@ -112,9 +112,20 @@ fn pretty_source(source: InsertSource) -> RcDoc<'static> {
     })
 }
 
-pub(crate) fn pretty_delete(table: TableReference, selection: Option<Expr>) -> RcDoc<'static> {
+pub(crate) fn pretty_delete(
+    table: TableReference,
+    using: Option<TableReference>,
+    selection: Option<Expr>,
+) -> RcDoc<'static> {
     RcDoc::text("DELETE FROM")
         .append(RcDoc::line().nest(NEST_FACTOR).append(pretty_table(table)))
+        .append(if let Some(using) = using {
+            RcDoc::line()
+                .append(RcDoc::text("USING"))
+                .append(RcDoc::line().nest(NEST_FACTOR).append(pretty_table(using)))
+        } else {
+            RcDoc::nil()
+        })
         .append(if let Some(selection) = selection {
             RcDoc::line().append(RcDoc::text("WHERE")).append(
                 RcDoc::line()
@@ -135,6 +146,13 @@ pub(crate) fn pretty_update(update_stmt: UpdateStmt) -> RcDoc<'static> {
         )
         .append(RcDoc::line().append(RcDoc::text("SET")))
         .append(pretty_update_list(update_stmt.update_list))
+        .append(if let Some(from) = update_stmt.from {
+            RcDoc::line()
+                .append(RcDoc::text("FROM"))
+                .append(RcDoc::line().nest(NEST_FACTOR).append(pretty_table(from)))
+        } else {
+            RcDoc::nil()
+        })
         .append(if let Some(selection) = update_stmt.selection {
             RcDoc::line().append(RcDoc::text("WHERE")).append(
                 RcDoc::line()
@@ -407,5 +407,21 @@ pub(crate) fn pretty_expr(expr: Expr) -> RcDoc<'static> {
             .append(RcDoc::space())
             .append(pretty_expr(*date))
             .append(RcDoc::text(")")),
+        Expr::Lambda { params, expr, .. } => {
+            let params = if params.len() == 1 {
+                RcDoc::text(params[0].to_string())
+            } else {
+                parenthenized(interweave_comma(
+                    params
+                        .into_iter()
+                        .map(|param| RcDoc::text(param.to_string())),
+                ))
+            };
+            params
+                .append(RcDoc::space())
+                .append(RcDoc::text("->"))
+                .append(RcDoc::space())
+                .append(pretty_expr(*expr))
+        }
     }
 }
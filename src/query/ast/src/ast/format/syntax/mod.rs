@@ -32,8 +32,9 @@ pub fn pretty_statement(stmt: Statement, max_width: usize) -> Result<String> {
         Statement::Insert(insert_stmt) => pretty_insert(insert_stmt),
         Statement::Delete {
             table_reference,
+            using,
             selection,
-        } => pretty_delete(table_reference, selection),
+        } => pretty_delete(table_reference, using, selection),
         Statement::Copy(copy_stmt) => pretty_copy(copy_stmt),
         Statement::Update(update_stmt) => pretty_update(update_stmt),
         Statement::CreateTable(create_table_stmt) => pretty_create_table(create_table_stmt),
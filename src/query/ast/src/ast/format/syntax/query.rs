@@ -80,7 +80,8 @@ fn pretty_body(body: SetExpr) -> RcDoc<'static> {
         .append(pretty_from(select_stmt.from))
         .append(pretty_selection(select_stmt.selection))
         .append(pretty_group_by(select_stmt.group_by))
-        .append(pretty_having(select_stmt.having)),
+        .append(pretty_having(select_stmt.having))
+        .append(pretty_qualify(select_stmt.qualify)),
         SetExpr::Query(query) => parenthenized(pretty_query(*query)),
         SetExpr::SetOperation(set_operation) => pretty_body(*set_operation.left)
             .append(
@@ -266,6 +267,16 @@ fn pretty_having(having: Option<Expr>) -> RcDoc<'static> {
     }
 }
 
+fn pretty_qualify(qualify: Option<Expr>) -> RcDoc<'static> {
+    if let Some(qualify) = qualify {
+        RcDoc::line()
+            .append(RcDoc::text("QUALIFY").append(RcDoc::line().nest(NEST_FACTOR)))
+            .append(pretty_expr(qualify))
+    } else {
+        RcDoc::nil()
+    }
+}
+
 pub(crate) fn pretty_table(table: TableReference) -> RcDoc<'static> {
     match table {
         TableReference::Table {
@@ -364,8 +375,17 @@ pub(crate) fn pretty_table(table: TableReference) -> RcDoc<'static> {
                 JoinOperator::RightAnti => RcDoc::text("RIGHT ANTI JOIN"),
                 JoinOperator::LeftSemi => RcDoc::text("LEFT SEMI JOIN"),
                 JoinOperator::RightSemi => RcDoc::text("RIGHT SEMI JOIN"),
+                JoinOperator::AsofJoin => RcDoc::text("ASOF JOIN"),
             })
             .append(RcDoc::space().append(pretty_table(*join.right)))
+            .append(if let Some(match_condition) = join.match_condition {
+                RcDoc::space()
+                    .append(RcDoc::text("MATCH_CONDITION("))
+                    .append(pretty_expr(*match_condition))
+                    .append(RcDoc::text(")"))
+            } else {
+                RcDoc::nil()
+            })
             .append(match &join.condition {
                 JoinCondition::On(expr) => RcDoc::space()
                     .append(RcDoc::text("ON"))
@@ -85,6 +85,8 @@ pub struct SelectStmt {
     pub group_by: Option<GroupBy>,
     // `HAVING` clause
     pub having: Option<Expr>,
+    // `QUALIFY` clause, filters the result of window functions
+    pub qualify: Option<Expr>,
 }
 
 /// Group by Clause.
@@ -264,6 +266,8 @@ pub struct TableAlias {
 pub struct Join {
     pub op: JoinOperator,
     pub condition: JoinCondition,
+    // The inequality condition of `ASOF JOIN`, e.g. `MATCH_CONDITION(t.ts >= q.ts)`
+    pub match_condition: Option<Box<Expr>>,
     pub left: Box<TableReference>,
     pub right: Box<TableReference>,
 }
@@ -281,6 +285,8 @@ pub enum JoinOperator {
     RightAnti,
     // CrossJoin can only work with `JoinCondition::None`
     CrossJoin,
+    // AsofJoin must work with a match condition, and can only use equality `ON` conditions
+    AsofJoin,
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -459,8 +465,14 @@ impl Display for TableReference {
                     JoinOperator::CrossJoin => {
                         write!(f, " CROSS JOIN")?;
                     }
+                    JoinOperator::AsofJoin => {
+                        write!(f, " ASOF JOIN")?;
+                    }
                 }
                 write!(f, " {}", join.right)?;
+                if let Some(match_condition) = &join.match_condition {
+                    write!(f, " MATCH_CONDITION({match_condition})")?;
+                }
                 match &join.condition {
                     JoinCondition::On(expr) => {
                         write!(f, " ON {expr}")?;
@@ -586,6 +598,11 @@ impl Display for SelectStmt {
             write!(f, " HAVING {having}")?;
         }
 
+        // QUALIFY clause
+        if let Some(qualify) = &self.qualify {
+            write!(f, " QUALIFY {qualify}")?;
+        }
+
         Ok(())
     }
 }
@@ -20,6 +20,7 @@ mod database;
 mod explain;
 mod insert;
 mod kill;
+mod merge_into;
 mod presign;
 mod replace;
 mod share;
@@ -40,6 +41,7 @@ pub use database::*;
 pub use explain::*;
 pub use insert::*;
 pub use kill::*;
+pub use merge_into::*;
 pub use presign::*;
 pub use replace::*;
 pub use share::*;
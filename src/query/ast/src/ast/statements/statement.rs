@@ -78,11 +78,14 @@ pub enum Statement {
 
     Delete {
         table_reference: TableReference,
+        using: Option<TableReference>,
         selection: Option<Expr>,
     },
 
     Update(UpdateStmt),
 
+    MergeInto(MergeIntoStmt),
+
     // Catalogs
     ShowCatalogs(ShowCatalogsStmt),
     ShowCreateCatalog(ShowCreateCatalogStmt),
@@ -271,15 +274,19 @@ impl Display for Statement {
             Statement::Replace(replace) => write!(f, "{replace}")?,
             Statement::Delete {
                 table_reference,
+                using,
                 selection,
-                ..
             } => {
                 write!(f, "DELETE FROM {table_reference}")?;
+                if let Some(using) = using {
+                    write!(f, " USING {using} ")?;
+                }
                 if let Some(conditions) = selection {
                     write!(f, "WHERE {conditions} ")?;
                 }
             }
             Statement::Update(update) => write!(f, "{update}")?,
+            Statement::MergeInto(merge_into) => write!(f, "{merge_into}")?,
             Statement::Copy(stmt) => write!(f, "{stmt}")?,
             Statement::ShowSettings { like } => {
                 write!(f, "SHOW SETTINGS")?;
@@ -24,6 +24,7 @@ use crate::ast::TableReference;
 pub struct UpdateStmt {
     pub table: TableReference,
     pub update_list: Vec<UpdateExpr>,
+    pub from: Option<TableReference>,
     pub selection: Option<Expr>,
 }
 
@@ -37,6 +38,9 @@ impl Display for UpdateStmt {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
         write!(f, "UPDATE {} SET ", self.table)?;
         write_comma_separated_list(f, &self.update_list)?;
+        if let Some(from) = &self.from {
+            write!(f, " FROM {from}")?;
+        }
         if let Some(conditions) = &self.selection {
             write!(f, " WHERE {conditions}")?;
         }
@@ -278,6 +278,11 @@ pub enum ExprElement {
     Tuple {
         exprs: Vec<Expr>,
     },
+    /// Lambda function, like `x -> x + 1` or `(acc, x) -> acc + x`
+    Lambda {
+        params: Vec<Identifier>,
+        expr: Expr,
+    },
     /// Scalar function call
     FunctionCall {
         /// Set to true if the function is aggregate function with `DISTINCT`, like `COUNT(DISTINCT a)`
@@ -521,6 +526,11 @@ impl<'a, I: Iterator<Item = WithSpan<'a, ExprElement>>> PrattParser<I> for ExprP
                 subquery: Box::new(subquery),
             },
             ExprElement::Group(expr) => expr,
+            ExprElement::Lambda { params, expr } => Expr::Lambda {
+                span: transform_span(elem.span.0),
+                params,
+                expr: Box::new(expr),
+            },
             ExprElement::Array { exprs } => Expr::Array {
                 span: transform_span(elem.span.0),
                 exprs,
@@ -696,6 +706,15 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             column,
         },
     );
+    let column_position = map(rule! { ColumnPosition }, |token| ExprElement::ColumnRef {
+        database: None,
+        table: None,
+        column: Identifier {
+            span: transform_span(&[token.clone()]),
+            name: token.text().to_string(),
+            quote: None,
+        },
+    });
     let is_null = map(
         rule! {
             IS ~ NOT? ~ NULL
@@ -847,6 +866,19 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             }
         },
     );
+    let lambda_params = alt((
+        map(ident, |param| vec![param]),
+        map(
+            rule! { "(" ~ #comma_separated_list1(ident) ~ ")" },
+            |(_, params, _)| params,
+        ),
+    ));
+    let lambda = map(
+        rule! {
+            #lambda_params ~ "->" ~ #subexpr(0)
+        },
+        |(params, _, expr)| ExprElement::Lambda { params, expr },
+    );
 
     let window_frame_between = alt((
         map(
@@ -880,6 +912,7 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
                     end_bound: bw.1,
                 }
             }),
+            ignore_nulls: None,
         },
     );
 
@@ -901,14 +934,19 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
         rule! {
             #function_name
             ~ "(" ~ DISTINCT? ~ #comma_separated_list0(subexpr(0))? ~ ")"
+            ~ ((IGNORE | RESPECT) ~ NULLS)?
             ~ (OVER ~ "(" ~ #window_spec ~ ")")
         },
-        |(name, _, opt_distinct, opt_args, _, window)| ExprElement::FunctionCall {
-            distinct: opt_distinct.is_some(),
-            name,
-            args: opt_args.unwrap_or_default(),
-            params: vec![],
-            window: Some(window.2),
+        |(name, _, opt_distinct, opt_args, _, opt_nulls, window)| {
+            let mut window = window.2;
+            window.ignore_nulls = opt_nulls.map(|(token, _)| token.kind == IGNORE);
+            ExprElement::FunctionCall {
+                distinct: opt_distinct.is_some(),
+                name,
+                args: opt_args.unwrap_or_default(),
+                params: vec![],
+                window: Some(window),
+            }
         },
     );
 
@@ -1113,8 +1151,10 @@ pub fn expr_element(i: Input) -> IResult<WithSpan<ExprElement>> {
             | #function_call : "<function>"
             | #case : "`CASE ... END`"
             | #subquery : "`(SELECT ...)`"
+            | #lambda : "`<param> -> <expr>`"
             | #tuple : "`(<expr> [, ...])`"
             | #column_ref : "<column>"
+            | #column_position : "`$<number>`"
             | #map_access : "[<key>] | .<key> | :<key>"
             | #literal : "<literal>"
             | #array : "`[...]`"
@@ -147,10 +147,12 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
     let delete = map(
         rule! {
             DELETE ~ FROM ~ #table_reference_only
+            ~ ( USING ~ ^#table_reference )?
             ~ ( WHERE ~ ^#expr )?
         },
-        |(_, _, table_reference, opt_selection)| Statement::Delete {
+        |(_, _, table_reference, opt_using, opt_selection)| Statement::Delete {
             table_reference,
+            using: opt_using.map(|(_, using)| using),
             selection: opt_selection.map(|(_, selection)| selection),
         },
     );
@@ -159,17 +161,39 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
         rule! {
             UPDATE ~ #table_reference_only
             ~ SET ~ ^#comma_separated_list1(update_expr)
+            ~ ( FROM ~ ^#table_reference )?
             ~ ( WHERE ~ ^#expr )?
         },
-        |(_, table, _, update_list, opt_selection)| {
+        |(_, table, _, update_list, opt_from, opt_selection)| {
             Statement::Update(UpdateStmt {
                 table,
                 update_list,
+                from: opt_from.map(|(_, from)| from),
                 selection: opt_selection.map(|(_, selection)| selection),
             })
         },
     );
 
+    let merge_into = map(
+        rule! {
+            MERGE ~ INTO ~ #period_separated_idents_1_to_3 ~ #table_alias?
+            ~ USING ~ ^#table_reference_without_join
+            ~ ON ~ ^#expr
+            ~ #merge_clause+
+        },
+        |(_, _, (catalog, database, table), alias, _, source, _, join_expr, merge_clauses)| {
+            Statement::MergeInto(MergeIntoStmt {
+                catalog,
+                database,
+                table,
+                alias,
+                source,
+                join_expr,
+                merge_clauses,
+            })
+        },
+    );
+
     let show_settings = map(
         rule! {
             SHOW ~ SETTINGS ~ (LIKE ~ #literal_string)?
@@ -1151,6 +1175,7 @@ pub fn statement(i: Input) -> IResult<StatementMsg> {
         rule!(
             #insert : "`INSERT INTO [TABLE] <table> [(<column>, ...)] (FORMAT <format> | VALUES <values> | <query>)`"
             | #replace : "`REPLACE INTO [TABLE] <table> [(<column>, ...)] (FORMAT <format> | VALUES <values> | <query>)`"
+            | #merge_into : "`MERGE INTO <table> USING <source> ON <condition> { WHEN [NOT] MATCHED [AND <condition>] THEN <action> } ...`"
         ),
         rule!(
             #set_variable : "`SET <variable> = <value>`"
@@ -1830,6 +1855,7 @@ pub fn catalog_type(i: Input) -> IResult<CatalogType> {
         value(CatalogType::Default, rule! {DEFAULT}),
         value(CatalogType::Hive, rule! {HIVE}),
         value(CatalogType::Iceberg, rule! {ICEBERG}),
+        value(CatalogType::Delta, rule! {DELTA}),
     ));
     map(rule! { ^#catalog_type }, |catalog_type| catalog_type)(i)
 }
@@ -1968,6 +1994,48 @@ pub fn table_reference_only(i: Input) -> IResult<TableReference> {
     )(i)
 }
 
+pub fn merge_clause(i: Input) -> IResult<MergeClause> {
+    let update = map(
+        rule! {
+            UPDATE ~ SET ~ ^#comma_separated_list1(update_expr)
+        },
+        |(_, _, update_list)| MatchOperation::Update { update_list },
+    );
+    let delete = value(MatchOperation::Delete, rule! { DELETE });
+    let matched = map(
+        rule! {
+            WHEN ~ MATCHED ~ ( AND ~ ^#expr )? ~ THEN ~ ^( #update | #delete )
+        },
+        |(_, _, opt_selection, _, operation)| {
+            MergeClause::Matched(MatchedClause {
+                selection: opt_selection.map(|(_, selection)| selection),
+                operation,
+            })
+        },
+    );
+    let unmatched = map(
+        rule! {
+            WHEN ~ NOT ~ MATCHED ~ ( AND ~ ^#expr )? ~ THEN ~ ^INSERT
+            ~ ( "(" ~ ^#comma_separated_list1(ident) ~ ^")" )?
+            ~ ^VALUES ~ ^"(" ~ ^#comma_separated_list1(expr) ~ ^")"
+        },
+        |(_, _, _, opt_selection, _, _, opt_columns, _, _, values, _)| {
+            MergeClause::Unmatched(UnmatchedClause {
+                selection: opt_selection.map(|(_, selection)| selection),
+                columns: opt_columns
+                    .map(|(_, columns, _)| columns)
+                    .unwrap_or_default(),
+                values,
+            })
+        },
+    );
+
+    rule!(
+        #matched
+        | #unmatched
+    )(i)
+}
+
 pub fn update_expr(i: Input) -> IResult<UpdateExpr> {
     map(rule! { ( #ident ~ "=" ~ ^#expr ) }, |(name, _, expr)| {
         UpdateExpr { name, expr }
@@ -124,6 +124,10 @@ pub enum TokenKind {
     #[regex(r"[0-9]+")]
     LiteralInteger,
 
+    // `$1`, `$2`, ... refers to the columns of files in stages by position
+    #[regex(r"\$[0-9]+")]
+    ColumnPosition,
+
     #[regex(r"[0-9]+[eE][+-]?[0-9]+")]
     #[regex(r"([0-9]*\.[0-9]+([eE][+-]?[0-9]+)?)|([0-9]+\.[0-9]*([eE][+-]?[0-9]+)?)")]
     LiteralFloat,
@@ -269,6 +273,8 @@ pub enum TokenKind {
     AT,
     #[token("ASC", ignore(ascii_case))]
     ASC,
+    #[token("ASOF", ignore(ascii_case))]
+    ASOF,
     #[token("ANTI", ignore(ascii_case))]
     ANTI,
     #[token("BEFORE", ignore(ascii_case))]
@@ -369,6 +375,8 @@ pub enum TokenKind {
     DEFLATE,
     #[token("DELETE", ignore(ascii_case))]
     DELETE,
+    #[token("DELTA", ignore(ascii_case))]
+    DELTA,
     #[token("DESC", ignore(ascii_case))]
     DESC,
     #[token("DESCRIBE", ignore(ascii_case))]
@@ -491,6 +499,8 @@ pub enum TokenKind {
     IDENTIFIED,
     #[token("IF", ignore(ascii_case))]
     IF,
+    #[token("IGNORE", ignore(ascii_case))]
+    IGNORE,
     #[token("IN", ignore(ascii_case))]
     IN,
     #[token("INNER", ignore(ascii_case))]
@@ -553,10 +563,16 @@ pub enum TokenKind {
     MAX_FILE_SIZE,
     #[token("MASTER_KEY", ignore(ascii_case))]
     MASTER_KEY,
+    #[token("MATCHED", ignore(ascii_case))]
+    MATCHED,
+    #[token("MATCH_CONDITION", ignore(ascii_case))]
+    MATCH_CONDITION,
     #[token("MEMO", ignore(ascii_case))]
     MEMO,
     #[token("MEMORY", ignore(ascii_case))]
     MEMORY,
+    #[token("MERGE", ignore(ascii_case))]
+    MERGE,
     #[token("METRICS", ignore(ascii_case))]
     METRICS,
     #[token("MICROSECONDS", ignore(ascii_case))]
@@ -625,6 +641,8 @@ pub enum TokenKind {
     PROCESSLIST,
     #[token("PURGE", ignore(ascii_case))]
     PURGE,
+    #[token("QUALIFY", ignore(ascii_case))]
+    QUALIFY,
     #[token("QUARTER", ignore(ascii_case))]
     QUARTER,
     #[token("QUERY", ignore(ascii_case))]
@@ -647,6 +665,8 @@ pub enum TokenKind {
     RENAME,
     #[token("REPLACE", ignore(ascii_case))]
     REPLACE,
+    #[token("RESPECT", ignore(ascii_case))]
+    RESPECT,
     #[token("ROW", ignore(ascii_case))]
     ROW,
     #[token("ROWS", ignore(ascii_case))]
@@ -890,6 +910,7 @@ impl TokenKind {
                 | MySQLLiteralHex
                 | LiteralInteger
                 | LiteralFloat
+                | ColumnPosition
                 | DoubleEq
                 | Eq
                 | NotEq
@@ -995,6 +1016,7 @@ impl TokenKind {
             // | TokenKind::LEAST
             // | TokenKind::LOCALTIME
             // | TokenKind::LOCALTIMESTAMP
+            | TokenKind::MATCH_CONDITION
             // | TokenKind::NATIONAL
             // | TokenKind::NCHAR
             // | TokenKind::NONE
@@ -1094,6 +1116,7 @@ impl TokenKind {
             | TokenKind::AND
             | TokenKind::ANY
             | TokenKind::ASC
+            | TokenKind::ASOF
             | TokenKind::ANTI
             // | TokenKind::ASYMMETRIC
             // | TokenKind::AUTHORIZATION
@@ -1137,6 +1160,7 @@ impl TokenKind {
             | TokenKind::LIKE
             // | TokenKind::LOCALTIME
             // | TokenKind::LOCALTIMESTAMP
+            | TokenKind::MATCH_CONDITION
             | TokenKind::NATURAL
             | TokenKind::NOT
             | TokenKind::NULL
@@ -1189,6 +1213,7 @@ impl TokenKind {
             | TokenKind::OVER
             | TokenKind::ROWS
             | TokenKind::RANGE
+            | TokenKind::QUALIFY
             // | TokenKind::OVERLAPS
             // | TokenKind::RETURNING
             | TokenKind::STAGE
@@ -322,6 +322,10 @@ pub trait Visitor<'ast>: Sized {
         walk_expr(self, date);
     }
 
+    fn visit_lambda(&mut self, _span: Span, _params: &'ast [Identifier], expr: &'ast Expr) {
+        walk_expr(self, expr);
+    }
+
     fn visit_statement(&mut self, statement: &'ast Statement) {
         walk_statement(self, statement);
     }
@@ -374,12 +378,15 @@ pub trait Visitor<'ast>: Sized {
     fn visit_delete(
         &mut self,
         _table_reference: &'ast TableReference,
+        _using: &'ast Option<TableReference>,
         _selection: &'ast Option<Expr>,
     ) {
     }
 
     fn visit_update(&mut self, _update: &'ast UpdateStmt) {}
 
+    fn visit_merge_into(&mut self, _merge_into: &'ast MergeIntoStmt) {}
+
     fn visit_show_catalogs(&mut self, _stmt: &'ast ShowCatalogsStmt) {}
 
     fn visit_show_create_catalog(&mut self, _stmt: &'ast ShowCreateCatalogStmt) {}
@@ -561,6 +568,7 @@ pub trait Visitor<'ast>: Sized {
             selection,
             group_by,
             having,
+            qualify,
             ..
         } = stmt;
 
@@ -595,6 +603,10 @@ pub trait Visitor<'ast>: Sized {
         if let Some(having) = having {
             walk_expr(self, having);
         }
+
+        if let Some(qualify) = qualify {
+            walk_expr(self, qualify);
+        }
     }
 
     fn visit_select_target(&mut self, target: &'ast SelectTarget) {
@@ -614,12 +626,17 @@ pub trait Visitor<'ast>: Sized {
             left,
             right,
             condition,
+            match_condition,
             ..
         } = join;
 
         walk_table_reference(self, left);
         walk_table_reference(self, right);
 
+        if let Some(match_condition) = match_condition {
+            self.visit_expr(match_condition);
+        }
+
         walk_join_condition(self, condition);
     }
 }
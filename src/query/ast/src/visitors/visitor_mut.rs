@@ -326,6 +326,10 @@ pub trait VisitorMut: Sized {
         walk_expr_mut(self, date);
     }
 
+    fn visit_lambda(&mut self, _span: Span, _params: &mut [Identifier], expr: &mut Expr) {
+        walk_expr_mut(self, expr);
+    }
+
     fn visit_statement(&mut self, statement: &mut Statement) {
         walk_statement_mut(self, statement);
     }
@@ -380,12 +384,15 @@ pub trait VisitorMut: Sized {
     fn visit_delete(
         &mut self,
         _table_reference: &mut TableReference,
+        _using: &mut Option<TableReference>,
         _selection: &mut Option<Expr>,
     ) {
     }
 
     fn visit_update(&mut self, _update: &mut UpdateStmt) {}
 
+    fn visit_merge_into(&mut self, _merge_into: &mut MergeIntoStmt) {}
+
     fn visit_show_catalogs(&mut self, _stmt: &mut ShowCatalogsStmt) {}
 
     fn visit_show_create_catalog(&mut self, _stmt: &mut ShowCreateCatalogStmt) {}
@@ -567,6 +574,7 @@ pub trait VisitorMut: Sized {
             selection,
             group_by,
             having,
+            qualify,
             ..
         } = stmt;
 
@@ -601,6 +609,10 @@ pub trait VisitorMut: Sized {
         if let Some(having) = having {
             walk_expr_mut(self, having);
         }
+
+        if let Some(qualify) = qualify {
+            walk_expr_mut(self, qualify);
+        }
     }
 
     fn visit_select_target(&mut self, target: &mut SelectTarget) {
@@ -620,12 +632,17 @@ pub trait VisitorMut: Sized {
             left,
             right,
             condition,
+            match_condition,
             ..
         } = join;
 
         walk_table_reference_mut(self, left);
         walk_table_reference_mut(self, right);
 
+        if let Some(match_condition) = match_condition {
+            self.visit_expr(match_condition);
+        }
+
         walk_join_condition_mut(self, condition);
     }
 }
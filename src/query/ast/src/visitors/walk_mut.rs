@@ -139,6 +139,7 @@ pub fn walk_expr_mut<V: VisitorMut>(visitor: &mut V, expr: &mut Expr) {
             unit,
         } => visitor.visit_date_sub(*span, unit, interval, date),
         Expr::DateTrunc { span, unit, date } => visitor.visit_date_trunc(*span, unit, date),
+        Expr::Lambda { span, params, expr } => visitor.visit_lambda(*span, params, expr),
     }
 }
 
@@ -307,10 +308,11 @@ pub fn walk_statement_mut<V: VisitorMut>(visitor: &mut V, statement: &mut Statem
         Statement::Replace(replace) => visitor.visit_replace(replace),
         Statement::Delete {
             table_reference,
+            using,
             selection,
-            ..
-        } => visitor.visit_delete(table_reference, selection),
+        } => visitor.visit_delete(table_reference, using, selection),
         Statement::Update(update) => visitor.visit_update(update),
+        Statement::MergeInto(merge_into) => visitor.visit_merge_into(merge_into),
         Statement::Copy(stmt) => visitor.visit_copy(stmt),
         Statement::ShowSettings { like } => visitor.visit_show_settings(like),
         Statement::ShowProcessList => visitor.visit_show_process_list(),
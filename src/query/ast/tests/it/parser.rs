@@ -140,6 +140,7 @@ fn test_statement() {
         r#"select * from a right outer join b using(a);"#,
         r#"select * from a full outer join b using(a);"#,
         r#"select * from a inner join b using(a);"#,
+        r#"select * from a asof join b match_condition(a.t >= b.t) on a.a = b.a;"#,
         r#"select * from a where a.a = any (select b.a from b);"#,
         r#"select * from a where a.a = all (select b.a from b);"#,
         r#"select * from a where a.a = some (select b.a from b);"#,
@@ -605,6 +606,8 @@ fn test_expr() {
         r#"COUNT() OVER (ORDER BY hire_date ROWS UNBOUNDED PRECEDING)"#,
         r#"COUNT() OVER (ORDER BY hire_date ROWS CURRENT ROW)"#,
         r#"COUNT() OVER (ORDER BY hire_date ROWS 3 PRECEDING)"#,
+        r#"LAG(salary, 1, 0) IGNORE NULLS OVER (ORDER BY hire_date)"#,
+        r#"$1 + $2"#,
     ];
 
     for case in cases {
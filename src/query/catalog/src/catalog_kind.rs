@@ -15,3 +15,4 @@
 pub const CATALOG_DEFAULT: &str = "default";
 pub const CATALOG_HIVE: &str = "hive";
 pub const CATALOG_ICEBERG: &str = "iceberg";
+pub const CATALOG_DELTA: &str = "delta";
@@ -28,6 +28,8 @@ pub struct StageTableInfo {
     pub files_info: StageFilesInfo,
     pub stage_info: StageInfo,
     pub files_to_copy: Option<Vec<StageFileInfo>>,
+    // the files are queried by `SELECT ... FROM @stage` or transformed by `COPY INTO ... FROM (SELECT ...)`
+    pub is_select: bool,
 }
 
 impl StageTableInfo {
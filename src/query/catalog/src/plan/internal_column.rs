@@ -33,6 +33,12 @@ pub const SNAPSHOT_NAME: &str = "_snapshot_name";
 pub const SEGMENT_NAME: &str = "_segment_name";
 pub const BLOCK_NAME: &str = "_block_name";
 
+/// The `_row_id` of the first row of a block, the `_row_id` of the other rows
+/// are the offsets of the rows added to it.
+pub fn block_row_id_base(segment_id: usize, block_id: usize) -> u64 {
+    ((segment_id as u64) << NUM_SEGMENT_ID_BITS) + ((block_id as u64) << NUM_BLOCK_ID_BITS)
+}
+
 // meta data for generate internal columns
 #[derive(Debug)]
 pub struct InternalColumnMeta {
@@ -99,9 +105,7 @@ impl InternalColumn {
     pub fn generate_column_values(&self, meta: &InternalColumnMeta, num_rows: usize) -> BlockEntry {
         match &self.column_type {
             InternalColumnType::RowId => {
-                let block_id = meta.block_id as u64;
-                let seg_id = meta.segment_id as u64;
-                let high_32bit = (seg_id << NUM_SEGMENT_ID_BITS) + (block_id << NUM_BLOCK_ID_BITS);
+                let high_32bit = block_row_id_base(meta.segment_id, meta.block_id);
                 let mut row_ids = Vec::with_capacity(num_rows);
                 for i in 0..num_rows {
                     let row_id = high_32bit + i as u64;
@@ -290,6 +290,24 @@ pub trait Table: Sync + Send {
         )))
     }
 
+    /// Applies the rows produced by a MERGE INTO statement to the table.
+    ///
+    /// The columns of the input blocks are `_block_name`, `_row_id`, a boolean
+    /// which tells whether the row is deleted, and the new values of all the
+    /// columns of the table. The rows with a NULL `_block_name` are inserted,
+    /// the other rows replace (or delete) the target rows they locate. The
+    /// new values can be omitted if all the rows are deleted.
+    #[async_backtrace::framed]
+    async fn merge_into(&self, ctx: Arc<dyn TableContext>, pipeline: &mut Pipeline) -> Result<()> {
+        let (_, _) = (ctx, pipeline);
+
+        Err(ErrorCode::Unimplemented(format!(
+            "table {},  of engine type {}, does not support MERGE INTO",
+            self.name(),
+            self.get_table_info().engine(),
+        )))
+    }
+
     fn get_block_compact_thresholds(&self) -> BlockThresholds {
         BlockThresholds {
             max_rows_per_block: DEFAULT_BLOCK_MAX_ROWS,
@@ -34,9 +34,11 @@ use arrow_schema::TimeUnit;
 use common_arrow::arrow::bitmap::Bitmap;
 use common_arrow::arrow::buffer::Buffer as Buffer2;
 use common_arrow::arrow::types::NativeType;
+use ethnum::i256;
 use ordered_float::OrderedFloat;
 
 use crate::types::decimal::DecimalColumn;
+use crate::types::decimal::DecimalSize;
 use crate::types::nullable::NullableColumn;
 use crate::types::number::NumberColumn;
 use crate::types::string::StringColumn;
@@ -48,6 +50,25 @@ use crate::ARROW_EXT_TYPE_EMPTY_MAP;
 use crate::ARROW_EXT_TYPE_VARIANT;
 use crate::EXTENSION_KEY;
 
+const MILLISECONDS_PER_DAY: i64 = 24 * 3600 * 1000;
+
+fn decimal_scale(scale: i8) -> Result<u8, ArrowError> {
+    u8::try_from(scale).map_err(|_| {
+        ArrowError::InvalidArgumentError(format!("Negative decimal scale {scale} is not supported"))
+    })
+}
+
+fn timestamp_to_micros(buffer: &Buffer2<i64>, factor: i64) -> Result<Buffer2<i64>, ArrowError> {
+    buffer
+        .iter()
+        .map(|v| {
+            v.checked_mul(factor)
+                .ok_or_else(|| ArrowError::ComputeError(format!("Timestamp {v} is out of range")))
+        })
+        .collect::<Result<Vec<_>, _>>()
+        .map(|v| v.into())
+}
+
 fn numbers_into<TN: ArrowNativeType + NativeType, TA: ArrowPrimitiveType>(
     buf: Buffer2<TN>,
     data_type: DataType,
@@ -73,6 +94,7 @@ impl Column {
                 let null_buffer = NullBuffer::from(bitmap);
                 let array_data = ArrayData::builder(DataType::Boolean)
                     .len(len)
+                    .offset(null_buffer.offset())
                     .add_buffer(null_buffer.buffer().clone())
                     .build()?;
                 Arc::new(BooleanArray::from(array_data))
@@ -157,9 +179,9 @@ impl Column {
             }
 
             Column::Timestamp(buf) => {
-                numbers_into::<i64, arrow_array::types::Time64NanosecondType>(
+                numbers_into::<i64, arrow_array::types::TimestampMicrosecondType>(
                     buf,
-                    DataType::Timestamp(TimeUnit::Nanosecond, None),
+                    DataType::Timestamp(TimeUnit::Microsecond, None),
                 )?
             }
             Column::Date(buf) => {
@@ -170,9 +192,7 @@ impl Column {
                 let inner = col.column.into_arrow_rs()?;
                 let builder = ArrayDataBuilder::from(inner.data().clone());
 
-                let data = builder
-                    .null_bit_buffer(Some(null_buffer.buffer().clone()))
-                    .build()?;
+                let data = builder.nulls(Some(null_buffer)).build()?;
                 make_array(data)
             }
             _ => {
@@ -204,7 +224,7 @@ impl Column {
                 let values =
                     unsafe { std::mem::transmute::<_, Buffer2<u8>>(data.buffers()[1].clone()) };
 
-                Column::Variant(StringColumn {
+                Column::String(StringColumn {
                     offsets,
                     data: values,
                 })
@@ -302,11 +322,55 @@ impl Column {
             }
 
             DataType::Float64 => {
-                let buffer2: Buffer2<f32> = Buffer2::from(data.buffers()[0].clone());
+                let buffer2: Buffer2<f64> = Buffer2::from(data.buffers()[0].clone());
                 let buffer = unsafe { std::mem::transmute::<_, Buffer2<F64>>(buffer2) };
 
                 Column::Number(NumberColumn::Float64(buffer))
             }
+            DataType::Decimal128(precision, scale) => {
+                let buffer2: Buffer2<i128> = Buffer2::from(data.buffers()[0].clone());
+                let size = DecimalSize {
+                    precision: *precision,
+                    scale: decimal_scale(*scale)?,
+                };
+                Column::Decimal(DecimalColumn::Decimal128(buffer2, size))
+            }
+            DataType::Decimal256(precision, scale) => {
+                let buffer2: Buffer2<common_arrow::arrow::types::i256> =
+                    Buffer2::from(data.buffers()[0].clone());
+                let buffer = unsafe { std::mem::transmute::<_, Buffer2<i256>>(buffer2) };
+                let size = DecimalSize {
+                    precision: *precision,
+                    scale: decimal_scale(*scale)?,
+                };
+                Column::Decimal(DecimalColumn::Decimal256(buffer, size))
+            }
+            DataType::Date32 => {
+                let buffer2 = Buffer2::from(data.buffers()[0].clone());
+                Column::Date(buffer2)
+            }
+            DataType::Date64 => {
+                let buffer2: Buffer2<i64> = Buffer2::from(data.buffers()[0].clone());
+                let days = buffer2
+                    .iter()
+                    .map(|ms| ms.div_euclid(MILLISECONDS_PER_DAY) as i32)
+                    .collect::<Vec<_>>();
+                Column::Date(days.into())
+            }
+            DataType::Timestamp(unit, _) => {
+                let buffer2: Buffer2<i64> = Buffer2::from(data.buffers()[0].clone());
+                let buffer2 = match unit {
+                    TimeUnit::Microsecond => buffer2,
+                    TimeUnit::Second => timestamp_to_micros(&buffer2, 1_000_000)?,
+                    TimeUnit::Millisecond => timestamp_to_micros(&buffer2, 1_000)?,
+                    TimeUnit::Nanosecond => buffer2
+                        .iter()
+                        .map(|v| v.div_euclid(1_000))
+                        .collect::<Vec<_>>()
+                        .into(),
+                };
+                Column::Timestamp(buffer2)
+            }
 
             _ => Err(ArrowError::NotYetImplemented(format!(
                 "Column::from_arrow_rs() for {data_type} not implemented yet"
@@ -316,6 +380,8 @@ impl Column {
             let validity = Bitmap::from_null_buffer(nulls.clone());
             let column = NullableColumn { column, validity };
             Ok(Column::Nullable(Box::new(column)))
+        } else if field.is_nullable() && !matches!(column, Column::Null { .. }) {
+            Ok(column.wrap_nullable())
         } else {
             Ok(column)
         }
@@ -163,7 +163,12 @@ impl TryFrom<&ArrowField> for DataField {
     type Error = ArrowError;
 
     fn try_from(f: &ArrowField) -> Result<Self, ArrowError> {
-        let ty = f.try_into()?;
+        let ty: DataType = f.try_into()?;
+        let ty = if f.is_nullable() && ty != DataType::Null {
+            ty.wrap_nullable()
+        } else {
+            ty
+        };
         Ok(DataField::new(f.name(), ty))
     }
 }
@@ -42,6 +42,7 @@ use crate::utils::arrow::constant_bitmap;
 use crate::values::Column;
 use crate::values::ColumnBuilder;
 use crate::values::Scalar;
+use crate::values::ScalarRef;
 use crate::values::Value;
 use crate::BlockEntry;
 use crate::ColumnIndex;
@@ -195,6 +196,18 @@ impl<'a> Evaluator<'a> {
                 ctx.render_error(*span, &args, &function.signature.name)?;
                 Ok(result)
             }
+            Expr::LambdaFunctionCall {
+                name,
+                args,
+                lambda_expr,
+                ..
+            } => {
+                let args = args
+                    .iter()
+                    .map(|expr| self.partial_run(expr, validity.clone()))
+                    .collect::<Result<Vec<_>>>()?;
+                self.run_lambda(name, args, lambda_expr)
+            }
         };
 
         #[cfg(debug_assertions)]
@@ -871,6 +884,191 @@ impl<'a> Evaluator<'a> {
 
         unreachable!("expr is not a set returning function: {expr}")
     }
+
+    /// Evaluate a higher-order function. Instead of evaluating the lambda expression
+    /// row by row, the arrays are flattened so that the lambda expression is evaluated
+    /// over all the elements at once.
+    fn run_lambda(
+        &self,
+        func_name: &str,
+        args: Vec<Value<AnyType>>,
+        lambda_expr: &Expr,
+    ) -> Result<Value<AnyType>> {
+        match &args[0] {
+            Value::Scalar(Scalar::Array(values)) => {
+                let array = ArrayColumn {
+                    values: values.clone(),
+                    offsets: vec![0, values.len() as u64].into(),
+                };
+                let column = self.run_lambda_array(func_name, &array, lambda_expr)?;
+                Ok(Value::Scalar(column.index(0).unwrap().to_owned()))
+            }
+            Value::Scalar(Scalar::EmptyArray) if func_name != "array_reduce" => {
+                Ok(Value::Scalar(Scalar::EmptyArray))
+            }
+            Value::Scalar(_) => Ok(Value::Scalar(Scalar::Null)),
+            Value::Column(column) => Ok(Value::Column(self.run_lambda_column(
+                func_name,
+                column,
+                lambda_expr,
+            )?)),
+        }
+    }
+
+    fn run_lambda_column(
+        &self,
+        func_name: &str,
+        column: &Column,
+        lambda_expr: &Expr,
+    ) -> Result<Column> {
+        match column {
+            Column::Array(array) => self.run_lambda_array(func_name, array, lambda_expr),
+            Column::Nullable(nullable) => {
+                match self.run_lambda_column(func_name, &nullable.column, lambda_expr)? {
+                    Column::Null { len } => Ok(Column::Null { len }),
+                    Column::Nullable(inner) => Ok(Column::Nullable(Box::new(NullableColumn {
+                        column: inner.column,
+                        validity: (&inner.validity) & (&nullable.validity),
+                    }))),
+                    column => Ok(Column::Nullable(Box::new(NullableColumn {
+                        column,
+                        validity: nullable.validity.clone(),
+                    }))),
+                }
+            }
+            Column::EmptyArray { .. } if func_name != "array_reduce" => Ok(column.clone()),
+            _ => Ok(Column::Null { len: column.len() }),
+        }
+    }
+
+    fn run_lambda_array(
+        &self,
+        func_name: &str,
+        array: &ArrayColumn<AnyType>,
+        lambda_expr: &Expr,
+    ) -> Result<Column> {
+        let values = array.underlying_column();
+        let start = array.offsets[0];
+        let offsets = array
+            .offsets
+            .iter()
+            .map(|offset| offset - start)
+            .collect::<Vec<_>>();
+        let data_type = values.data_type();
+
+        match func_name {
+            "array_transform" => {
+                let num_rows = values.len();
+                let block = DataBlock::new(
+                    vec![BlockEntry {
+                        data_type,
+                        value: Value::Column(values),
+                    }],
+                    num_rows,
+                );
+                let evaluator = Evaluator::new(&block, self.func_ctx, self.fn_registry);
+                let result = evaluator.run(lambda_expr)?;
+                let values = result.convert_to_full_column(lambda_expr.data_type(), num_rows);
+                Ok(Column::Array(Box::new(ArrayColumn {
+                    values,
+                    offsets: offsets.into(),
+                })))
+            }
+            "array_filter" => {
+                let num_rows = values.len();
+                let block = DataBlock::new(
+                    vec![BlockEntry {
+                        data_type,
+                        value: Value::Column(values.clone()),
+                    }],
+                    num_rows,
+                );
+                let evaluator = Evaluator::new(&block, self.func_ctx, self.fn_registry);
+                let result = evaluator.run(lambda_expr)?;
+                let filter: Bitmap =
+                    match result.try_downcast::<NullableType<BooleanType>>().unwrap() {
+                        Value::Scalar(Some(true)) => constant_bitmap(true, num_rows).into(),
+                        Value::Scalar(_) => constant_bitmap(false, num_rows).into(),
+                        Value::Column(result) => (&result.column) & (&result.validity),
+                    };
+
+                let mut new_offsets = Vec::with_capacity(offsets.len());
+                new_offsets.push(0);
+                let mut len = 0;
+                for (start, end) in offsets.iter().tuple_windows() {
+                    len += (*start..*end)
+                        .filter(|i| filter.get_bit(*i as usize))
+                        .count() as u64;
+                    new_offsets.push(len);
+                }
+                Ok(Column::Array(Box::new(ArrayColumn {
+                    values: values.filter(&filter),
+                    offsets: new_offsets.into(),
+                })))
+            }
+            "array_reduce" => {
+                // Reduce the arrays position by position, the lambda expression is evaluated
+                // over the accumulated values and the elements at the same position of all
+                // the arrays that are long enough.
+                let num_rows = offsets.len() - 1;
+                let mut accs = offsets
+                    .iter()
+                    .tuple_windows()
+                    .map(|(start, end)| {
+                        (start < end).then(|| values.index(*start as usize).unwrap().to_owned())
+                    })
+                    .collect::<Vec<_>>();
+
+                // The rows whose arrays have elements at the current position, the rows are
+                // dropped once their own arrays end, so each element is visited only once.
+                let mut rows = (0..num_rows)
+                    .filter(|row| offsets[row + 1] - offsets[*row] > 1)
+                    .collect::<Vec<_>>();
+                let mut pos = 1;
+                while !rows.is_empty() {
+                    let mut acc_builder = ColumnBuilder::with_capacity(&data_type, rows.len());
+                    for row in rows.iter() {
+                        acc_builder.push(accs[*row].as_ref().unwrap().as_ref());
+                    }
+                    let indices = rows
+                        .iter()
+                        .map(|row| offsets[*row] + pos)
+                        .collect::<Vec<_>>();
+                    let block = DataBlock::new(
+                        vec![
+                            BlockEntry {
+                                data_type: data_type.clone(),
+                                value: Value::Column(acc_builder.build()),
+                            },
+                            BlockEntry {
+                                data_type: data_type.clone(),
+                                value: Value::Column(values.take(&indices)),
+                            },
+                        ],
+                        rows.len(),
+                    );
+                    let evaluator = Evaluator::new(&block, self.func_ctx, self.fn_registry);
+                    let result = evaluator.run(lambda_expr)?;
+                    for (i, row) in rows.iter().enumerate() {
+                        accs[*row] = Some(unsafe { result.index_unchecked(i) }.to_owned());
+                    }
+                    pos += 1;
+                    rows.retain(|row| offsets[row + 1] - offsets[*row] > pos);
+                }
+
+                let mut builder =
+                    ColumnBuilder::with_capacity(&data_type.wrap_nullable(), num_rows);
+                for acc in accs.iter() {
+                    match acc {
+                        Some(acc) => builder.push(acc.as_ref()),
+                        None => builder.push(ScalarRef::Null),
+                    }
+                }
+                Ok(builder.build())
+            }
+            _ => unreachable!("function `{func_name}` does not accept lambda expression"),
+        }
+    }
 }
 
 pub struct ConstantFolder<'a, Index: ColumnIndex> {
@@ -1206,6 +1404,49 @@ impl<'a, Index: ColumnIndex> ConstantFolder<'a, Index> {
 
                 (func_expr, func_domain)
             }
+            Expr::LambdaFunctionCall {
+                span,
+                name,
+                args,
+                lambda_expr,
+                lambda_display,
+                return_type,
+            } => {
+                let args_expr = args
+                    .iter()
+                    .map(|arg| self.fold_once(arg).0)
+                    .collect::<Vec<_>>();
+                let all_args_is_scalar = args_expr.iter().all(|arg| arg.as_constant().is_some());
+
+                let func_expr = Expr::LambdaFunctionCall {
+                    span: *span,
+                    name: name.clone(),
+                    args: args_expr,
+                    lambda_expr: lambda_expr.clone(),
+                    lambda_display: lambda_display.clone(),
+                    return_type: return_type.clone(),
+                };
+
+                if all_args_is_scalar {
+                    let block = DataBlock::empty();
+                    let evaluator = Evaluator::new(&block, self.func_ctx, self.fn_registry);
+                    // Since we know the expression is constant, it'll be safe to change its column index type.
+                    let func_expr = func_expr.project_column_ref(|_| unreachable!());
+                    if let Ok(Value::Scalar(scalar)) = evaluator.run(&func_expr) {
+                        return (
+                            Expr::Constant {
+                                span: *span,
+                                scalar,
+                                data_type: return_type.clone(),
+                            },
+                            None,
+                        );
+                    }
+                }
+
+                // The lambda expression may throw errors, so the domain is unknown.
+                (func_expr, None)
+            }
         };
 
         debug_assert_eq!(expr.data_type(), new_expr.data_type());
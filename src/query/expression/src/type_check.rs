@@ -76,7 +76,140 @@ pub fn check<Index: ColumnIndex>(
                 .try_collect()?;
             check_function(*span, name, params, &args_expr, fn_registry)
         }
+        RawExpr::LambdaFunctionCall {
+            span,
+            name,
+            args,
+            lambda_expr,
+            lambda_display,
+        } => {
+            let args_expr: Vec<_> = args
+                .iter()
+                .map(|arg| check(arg, fn_registry))
+                .try_collect()?;
+            check_lambda_function(
+                *span,
+                name,
+                args_expr,
+                lambda_expr,
+                lambda_display,
+                fn_registry,
+            )
+        }
+    }
+}
+
+/// Higher-order functions taking an array and a lambda expression as arguments.
+pub const LAMBDA_FUNCTIONS: [&str; 3] = ["array_transform", "array_filter", "array_reduce"];
+
+/// Returns the types of the lambda parameters of a higher-order function applied to
+/// an argument of type `arg_type`.
+///
+/// `array_transform` and `array_filter` take one parameter, the element of the array.
+/// `array_reduce` takes two parameters, the accumulated value and the element of the array.
+pub fn lambda_params_type(name: &str, arg_type: &DataType) -> Result<Vec<DataType>> {
+    let element_type = match arg_type.remove_nullable() {
+        DataType::Array(box element_type) => element_type,
+        DataType::EmptyArray | DataType::Null => DataType::Null,
+        _ => {
+            return Err(ErrorCode::SemanticError(format!(
+                "function `{name}` expects an array as the first argument, but got {arg_type}"
+            )));
+        }
+    };
+    match name {
+        "array_transform" | "array_filter" => Ok(vec![element_type]),
+        "array_reduce" => Ok(vec![element_type.clone(), element_type]),
+        _ => Err(ErrorCode::UnknownFunction(format!(
+            "function `{name}` does not accept lambda expression"
+        ))),
+    }
+}
+
+pub fn check_lambda_function<Index: ColumnIndex>(
+    span: Span,
+    name: &str,
+    args: Vec<Expr<Index>>,
+    lambda_expr: &RawExpr,
+    lambda_display: &str,
+    fn_registry: &FunctionRegistry,
+) -> Result<Expr<Index>> {
+    if args.len() != 1 {
+        return Err(ErrorCode::SemanticError(format!(
+            "function `{name}` expects 1 argument and a lambda expression, but got {} arguments",
+            args.len()
+        ))
+        .set_span(span));
+    }
+    let arg_type = args[0].data_type().clone();
+    let params_type = lambda_params_type(name, &arg_type).map_err(|err| err.set_span(span))?;
+
+    // The lambda parameters are bound to the elements of the array.
+    for (id, data_type) in lambda_expr.column_refs() {
+        if params_type.get(id) != Some(&data_type) {
+            return Err(ErrorCode::SemanticError(format!(
+                "invalid lambda expression `{lambda_display}` for function `{name}`"
+            ))
+            .set_span(span));
+        }
     }
+
+    let lambda_expr = check(lambda_expr, fn_registry)?;
+    let (lambda_expr, return_type) = match name {
+        "array_transform" => {
+            let return_type = match arg_type.remove_nullable() {
+                DataType::Array(_) => DataType::Array(Box::new(lambda_expr.data_type().clone())),
+                ty => ty,
+            };
+            let return_type = if arg_type.is_nullable() {
+                return_type.wrap_nullable()
+            } else {
+                return_type
+            };
+            (lambda_expr, return_type)
+        }
+        "array_filter" => {
+            if !matches!(
+                lambda_expr.data_type().remove_nullable(),
+                DataType::Boolean | DataType::Null
+            ) {
+                return Err(ErrorCode::SemanticError(format!(
+                    "the lambda expression of function `{name}` must return a boolean value, but got {}",
+                    lambda_expr.data_type()
+                ))
+                .set_span(span));
+            }
+            let lambda_expr = check_cast(
+                span,
+                false,
+                lambda_expr,
+                &DataType::Nullable(Box::new(DataType::Boolean)),
+                fn_registry,
+            )?;
+            (lambda_expr, arg_type)
+        }
+        "array_reduce" => {
+            // The result of the lambda is accumulated into the first parameter,
+            // so it's casted to the type of the elements.
+            let element_type = &params_type[0];
+            if element_type == &DataType::Null {
+                (lambda_expr, DataType::Null)
+            } else {
+                let lambda_expr = check_cast(span, false, lambda_expr, element_type, fn_registry)?;
+                (lambda_expr, element_type.wrap_nullable())
+            }
+        }
+        _ => unreachable!(),
+    };
+
+    Ok(Expr::LambdaFunctionCall {
+        span,
+        name: name.to_string(),
+        args,
+        lambda_expr: Box::new(lambda_expr),
+        lambda_display: lambda_display.to_string(),
+        return_type,
+    })
 }
 
 pub fn check_cast<Index: ColumnIndex>(
@@ -420,6 +420,19 @@ impl<Index: ColumnIndex> Display for RawExpr<Index> {
                 }
                 write!(f, ")")
             }
+            RawExpr::LambdaFunctionCall {
+                name,
+                args,
+                lambda_display,
+                ..
+            } => {
+                write!(f, "{name}")?;
+                write!(f, "(")?;
+                for arg in args {
+                    write!(f, "{arg}, ")?;
+                }
+                write!(f, "{lambda_display})")
+            }
         }
     }
 }
@@ -588,6 +601,19 @@ impl<Index: ColumnIndex> Display for Expr<Index> {
                 }
                 write!(f, ")")
             }
+            Expr::LambdaFunctionCall {
+                name,
+                args,
+                lambda_display,
+                ..
+            } => {
+                write!(f, "{name}")?;
+                write!(f, "(")?;
+                for arg in args {
+                    write!(f, "{arg}, ")?;
+                }
+                write!(f, "{lambda_display})")
+            }
         }
     }
 }
@@ -707,6 +733,23 @@ impl<Index: ColumnIndex> Expr<Index> {
                         }
                     }
                 }
+                Expr::LambdaFunctionCall {
+                    name,
+                    args,
+                    lambda_display,
+                    ..
+                } => {
+                    let mut s = String::new();
+                    s += name;
+                    s += "(";
+                    for arg in args {
+                        s += &arg.sql_display();
+                        s += ", ";
+                    }
+                    s += lambda_display;
+                    s += ")";
+                    s
+                }
             }
         }
 
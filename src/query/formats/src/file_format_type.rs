@@ -22,6 +22,7 @@ use common_settings::Settings;
 
 use crate::delimiter::RecordDelimiter;
 use crate::format_option_checker::get_format_option_checker;
+use crate::output_format::ArrowIpcOutputFormat;
 use crate::output_format::CSVOutputFormat;
 use crate::output_format::CSVWithNamesAndTypesOutputFormat;
 use crate::output_format::CSVWithNamesOutputFormat;
@@ -186,6 +187,7 @@ impl FileFormatOptionsExt {
                 }
             }
             StageFileFormatType::Parquet => Box::new(ParquetOutputFormat::create(schema, self)),
+            StageFileFormatType::ArrowIpc => Box::new(ArrowIpcOutputFormat::create(schema, self)),
             StageFileFormatType::Json => Box::new(JSONOutputFormat::create(schema, self)),
             others => {
                 return Err(ErrorCode::InvalidArgument(format!(
@@ -204,6 +206,7 @@ impl FileFormatTypeExt for StageFileFormatType {
             StageFileFormatType::Tsv => "text/tab-separated-values; charset=UTF-8",
             StageFileFormatType::Csv => "text/csv; charset=UTF-8",
             StageFileFormatType::Parquet => "application/octet-stream",
+            StageFileFormatType::ArrowIpc => "application/vnd.apache.arrow.file",
             StageFileFormatType::NdJson => "application/x-ndjson; charset=UTF-8",
             StageFileFormatType::Json => "application/json; charset=UTF-8",
             _ => "text/plain; charset=UTF-8",
@@ -31,6 +31,9 @@ pub fn get_format_option_checker(
         StageFileFormatType::Parquet => Ok(Box::new(ParquetFormatOptionChecker {})),
         StageFileFormatType::Xml => Ok(Box::new(XMLFormatOptionChecker {})),
         StageFileFormatType::Json => Ok(Box::new(JsonFormatOptionChecker {})),
+        StageFileFormatType::Avro => Ok(Box::new(AvroFormatOptionChecker {})),
+        StageFileFormatType::Orc => Ok(Box::new(OrcFormatOptionChecker {})),
+        StageFileFormatType::ArrowIpc => Ok(Box::new(ArrowIpcFormatOptionChecker {})),
         _ => Err(ErrorCode::Internal(format!(
             "unexpected format type {:?}",
             fmt
@@ -212,6 +215,27 @@ impl FormatOptionChecker for ParquetFormatOptionChecker {
     }
 }
 
+pub struct AvroFormatOptionChecker {}
+impl FormatOptionChecker for AvroFormatOptionChecker {
+    fn name(&self) -> String {
+        "Avro".to_string()
+    }
+}
+
+pub struct OrcFormatOptionChecker {}
+impl FormatOptionChecker for OrcFormatOptionChecker {
+    fn name(&self) -> String {
+        "Orc".to_string()
+    }
+}
+
+pub struct ArrowIpcFormatOptionChecker {}
+impl FormatOptionChecker for ArrowIpcFormatOptionChecker {
+    fn name(&self) -> String {
+        "ArrowIpc".to_string()
+    }
+}
+
 pub fn check_escape(option: &mut String, default: &str) -> Result<()> {
     if option.is_empty() {
         *option = default.to_string()
@@ -0,0 +1,75 @@
+// Copyright 2022 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use arrow_ipc::writer::FileWriter;
+use arrow_schema::Schema as ArrowSchema;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::DataBlock;
+use common_expression::DataSchema;
+use common_expression::TableSchemaRef;
+use common_io::constants::DEFAULT_BLOCK_BUFFER_SIZE;
+
+use crate::output_format::OutputFormat;
+use crate::FileFormatOptionsExt;
+
+// the footer of arrow ipc files contains the offsets of all record batches,
+// so blocks are buffered and written as a whole file when finalizing.
+#[derive(Default)]
+pub struct ArrowIpcOutputFormat {
+    schema: DataSchema,
+    data_blocks: Vec<DataBlock>,
+}
+
+impl ArrowIpcOutputFormat {
+    pub fn create(schema: TableSchemaRef, _options: &FileFormatOptionsExt) -> Self {
+        Self {
+            schema: DataSchema::from(schema),
+            data_blocks: vec![],
+        }
+    }
+}
+
+impl OutputFormat for ArrowIpcOutputFormat {
+    fn serialize_block(&mut self, block: &DataBlock) -> Result<Vec<u8>> {
+        self.data_blocks.push(block.clone());
+        Ok(vec![])
+    }
+
+    fn buffer_size(&mut self) -> usize {
+        self.data_blocks.iter().map(|b| b.memory_size()).sum()
+    }
+
+    fn finalize(&mut self) -> Result<Vec<u8>> {
+        let blocks = std::mem::take(&mut self.data_blocks);
+        if blocks.is_empty() {
+            return Ok(vec![]);
+        }
+        let arrow_schema = ArrowSchema::from(&self.schema);
+        let buf = Vec::with_capacity(DEFAULT_BLOCK_BUFFER_SIZE);
+        let mut writer = FileWriter::try_new(buf, &arrow_schema)
+            .map_err(|e| ErrorCode::Internal(format!("fail to create arrow ipc writer: {e}")))?;
+        for block in blocks {
+            let batch = block.to_record_batch(&self.schema).map_err(|e| {
+                ErrorCode::Unimplemented(format!("fail to convert block to arrow: {e}"))
+            })?;
+            writer
+                .write(&batch)
+                .map_err(|e| ErrorCode::Internal(format!("fail to write arrow ipc file: {e}")))?;
+        }
+        writer
+            .into_inner()
+            .map_err(|e| ErrorCode::Internal(format!("fail to write arrow ipc file: {e}")))
+    }
+}
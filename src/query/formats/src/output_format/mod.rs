@@ -14,6 +14,7 @@
 
 use common_exception::Result;
 use common_expression::DataBlock;
+pub mod arrow_ipc;
 pub mod csv;
 pub mod json;
 pub mod ndjson;
@@ -21,6 +22,7 @@ pub mod parquet;
 pub mod tsv;
 pub mod values;
 
+pub use arrow_ipc::ArrowIpcOutputFormat;
 pub use csv::CSVOutputFormat;
 pub use csv::CSVWithNamesAndTypesOutputFormat;
 pub use csv::CSVWithNamesOutputFormat;
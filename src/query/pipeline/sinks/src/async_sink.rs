@@ -39,6 +39,8 @@ pub trait AsyncSink: Send {
         Ok(())
     }
 
+    fn interrupt(&self) {}
+
     #[unboxed_simple]
     async fn consume(&mut self, data_block: DataBlock) -> Result<bool>;
 }
@@ -97,6 +99,12 @@ impl<T: AsyncSink + 'static> Processor for AsyncSinker<T> {
         self
     }
 
+    fn interrupt(&self) {
+        if let Some(inner) = &self.inner {
+            inner.interrupt()
+        }
+    }
+
     fn event(&mut self) -> Result<Event> {
         if !self.called_on_start {
             return Ok(Event::Async);
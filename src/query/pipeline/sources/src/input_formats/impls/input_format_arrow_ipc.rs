@@ -0,0 +1,324 @@
+//  Copyright 2023 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::io::Cursor;
+use std::mem;
+use std::sync::Arc;
+
+use arrow_array::RecordBatch;
+use arrow_ipc::convert::fb_to_schema;
+use arrow_ipc::reader::FileReader;
+use arrow_ipc::root_as_footer;
+use arrow_schema::Schema as ArrowSchema;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::infer_table_schema;
+use common_expression::DataBlock;
+use common_expression::DataSchema;
+use common_expression::TableSchemaRef;
+use common_meta_app::principal::FileFormatOptions;
+use common_meta_app::principal::StageInfo;
+use common_pipeline_core::Pipeline;
+use common_settings::Settings;
+use common_storage::StageFileInfo;
+use opendal::Operator;
+
+use crate::input_formats::input_pipeline::AligningStateTrait;
+use crate::input_formats::input_pipeline::BlockBuilderTrait;
+use crate::input_formats::input_pipeline::InputFormatPipe;
+use crate::input_formats::input_pipeline::RowBatchTrait;
+use crate::input_formats::input_split::FileInfo;
+use crate::input_formats::InputContext;
+use crate::input_formats::InputFormat;
+use crate::input_formats::SplitInfo;
+
+const ARROW_MAGIC: [u8; 6] = *b"ARROW1";
+// an arrow ipc file ends with the length of the footer and the magic
+const FOOTER_SUFFIX_SIZE: u64 = 10;
+
+pub struct InputFormatArrowIpc;
+
+#[async_trait::async_trait]
+impl InputFormat for InputFormatArrowIpc {
+    #[async_backtrace::framed]
+    async fn get_splits(
+        &self,
+        file_infos: Vec<StageFileInfo>,
+        _stage_info: &StageInfo,
+        _op: &Operator,
+        _settings: &Arc<Settings>,
+    ) -> Result<Vec<Arc<SplitInfo>>> {
+        // the record batches are located by the footer at the end of the file,
+        // so each file is one split and read as a whole.
+        let mut infos = vec![];
+        for info in file_infos {
+            let size = info.size as usize;
+            let file = Arc::new(FileInfo {
+                path: info.path.clone(),
+                size,
+                num_splits: 1,
+                compress_alg: None,
+            });
+            infos.push(Arc::new(SplitInfo {
+                file,
+                seq_in_file: 0,
+                offset: 0,
+                size,
+                num_file_splits: 1,
+                format_info: None,
+            }));
+        }
+        Ok(infos)
+    }
+
+    #[async_backtrace::framed]
+    async fn infer_schema(
+        &self,
+        path: &str,
+        op: &Operator,
+        _options: &FileFormatOptions,
+    ) -> Result<TableSchemaRef> {
+        let size = op.stat(path).await?.content_length();
+        let schema = read_arrow_schema(op, path, size).await?;
+        let schema = DataSchema::try_from(&schema).map_err(|e| {
+            ErrorCode::Unimplemented(format!("fail to infer schema of arrow file {path}: {e}"))
+        })?;
+        infer_table_schema(&Arc::new(schema))
+    }
+
+    fn exec_copy(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()> {
+        ArrowIpcFormatPipe::execute_copy_aligned(ctx, pipeline)
+    }
+
+    fn exec_stream(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()> {
+        ArrowIpcFormatPipe::execute_stream(ctx, pipeline)
+    }
+}
+
+// reads the schema from the footer, without reading the record batches
+#[async_backtrace::framed]
+async fn read_arrow_schema(op: &Operator, path: &str, size: u64) -> Result<ArrowSchema> {
+    if size < ARROW_MAGIC.len() as u64 + FOOTER_SUFFIX_SIZE {
+        return Err(ErrorCode::BadBytes(format!(
+            "invalid arrow file {}, file size {} is too small",
+            path, size
+        )));
+    }
+    let suffix = op.range_read(path, size - FOOTER_SUFFIX_SIZE..size).await?;
+    if suffix[4..] != ARROW_MAGIC {
+        return Err(ErrorCode::BadBytes(format!(
+            "invalid arrow file {}, the magic at the end of the file not found",
+            path
+        )));
+    }
+    let footer_length = i32::from_le_bytes([suffix[0], suffix[1], suffix[2], suffix[3]]);
+    let footer_end = size - FOOTER_SUFFIX_SIZE;
+    if footer_length < 0 || footer_length as u64 > footer_end {
+        return Err(ErrorCode::BadBytes(format!(
+            "invalid arrow file {}, footer length {} mismatches the file size {}",
+            path, footer_length, size
+        )));
+    }
+    let footer_data = op
+        .range_read(path, footer_end - footer_length as u64..footer_end)
+        .await?;
+    let footer = root_as_footer(&footer_data)
+        .map_err(|e| ErrorCode::BadBytes(format!("invalid footer of arrow file {}: {e}", path)))?;
+    match footer.schema() {
+        Some(schema) => Ok(fb_to_schema(schema)),
+        None => Err(ErrorCode::BadBytes(format!(
+            "invalid arrow file {}, schema not found in the footer",
+            path
+        ))),
+    }
+}
+
+pub struct ArrowIpcFormatPipe;
+
+#[async_trait::async_trait]
+impl InputFormatPipe for ArrowIpcFormatPipe {
+    type SplitMeta = ();
+    type ReadBatch = Vec<u8>;
+    type RowBatch = ArrowFileInMemory;
+    type AligningState = ArrowIpcAligningState;
+    type BlockBuilder = ArrowIpcBlockBuilder;
+
+    #[async_backtrace::framed]
+    async fn read_split(
+        ctx: Arc<InputContext>,
+        split_info: Arc<SplitInfo>,
+    ) -> Result<Self::RowBatch> {
+        let op = ctx.source.get_operator()?;
+        let data = op.read(&split_info.file.path).await?;
+        ArrowFileInMemory::try_create(&split_info, data)
+    }
+}
+
+// the record batches of an arrow ipc file, which are only decoded from the
+// ipc messages and still in the arrow memory layout.
+pub struct ArrowFileInMemory {
+    split_info: String,
+    size: usize,
+    batches: Vec<RecordBatch>,
+}
+
+impl ArrowFileInMemory {
+    fn try_create(split_info: &Arc<SplitInfo>, data: Vec<u8>) -> Result<Self> {
+        let size = data.len();
+        let path = &split_info.file.path;
+        let reader = FileReader::try_new(Cursor::new(data), None)
+            .map_err(|e| ErrorCode::BadBytes(format!("invalid arrow file {}: {e}", path)))?;
+        let batches = reader
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| {
+                ErrorCode::BadBytes(format!(
+                    "fail to read record batch of arrow file {}: {e}",
+                    path
+                ))
+            })?;
+        Ok(ArrowFileInMemory {
+            split_info: split_info.to_string(),
+            size,
+            batches,
+        })
+    }
+
+    // indexes of the arrow fields, in the order of the table schema
+    fn project(&self, schema: &TableSchemaRef) -> Result<Vec<usize>> {
+        let arrow_schema = match self.batches.first() {
+            Some(batch) => batch.schema(),
+            None => return Ok(vec![]),
+        };
+        schema
+            .fields()
+            .iter()
+            .map(|f| {
+                arrow_schema
+                    .fields()
+                    .iter()
+                    .rposition(|c| c.name().eq_ignore_ascii_case(f.name()))
+                    .ok_or_else(|| {
+                        ErrorCode::TableSchemaMismatch(format!(
+                            "schema field size mismatch, expected to find column: {}",
+                            f.name()
+                        ))
+                    })
+            })
+            .collect()
+    }
+
+    // the columns are in the order of the table schema, and of the types in the arrow file
+    fn deserialize(self, schema: &TableSchemaRef) -> Result<Vec<DataBlock>> {
+        let projection = self.project(schema)?;
+        self.batches
+            .iter()
+            .filter(|batch| batch.num_rows() > 0)
+            .map(|batch| {
+                let batch = batch.project(&projection).map_err(|e| {
+                    ErrorCode::Internal(format!("fail to project record batch: {e}"))
+                })?;
+                let (block, _) = DataBlock::from_record_batch(&batch).map_err(|e| {
+                    ErrorCode::Unimplemented(format!("fail to convert record batch: {e}"))
+                })?;
+                Ok(block)
+            })
+            .collect()
+    }
+}
+
+impl Debug for ArrowFileInMemory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "ArrowFileInMemory")
+    }
+}
+
+impl RowBatchTrait for ArrowFileInMemory {
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    fn rows(&self) -> usize {
+        self.batches.iter().map(|b| b.num_rows()).sum()
+    }
+}
+
+pub struct ArrowIpcBlockBuilder {
+    ctx: Arc<InputContext>,
+}
+
+impl BlockBuilderTrait for ArrowIpcBlockBuilder {
+    type Pipe = ArrowIpcFormatPipe;
+
+    fn create(ctx: Arc<InputContext>) -> Self {
+        ArrowIpcBlockBuilder { ctx }
+    }
+
+    fn deserialize(&mut self, batch: Option<ArrowFileInMemory>) -> Result<Vec<DataBlock>> {
+        if let Some(file) = batch {
+            let split_info = file.split_info.clone();
+            let blocks = file
+                .deserialize(&self.ctx.schema)
+                .map_err(|e| e.add_message(format!(" when deserializing {}", split_info)))?;
+
+            let num_rows_per_block = self.ctx.block_compact_thresholds.max_rows_per_block;
+            let mut output = vec![];
+            for block in blocks {
+                let block_total_rows = block.num_rows();
+                for idx in (0..block_total_rows).step_by(num_rows_per_block) {
+                    let end = (idx + num_rows_per_block).min(block_total_rows);
+                    output.push(block.slice(idx..end));
+                }
+            }
+            Ok(output)
+        } else {
+            Ok(vec![])
+        }
+    }
+}
+
+// used by streaming load, the whole file is buffered since the footer is at the end of it
+pub struct ArrowIpcAligningState {
+    split_info: Arc<SplitInfo>,
+    buffers: Vec<Vec<u8>>,
+}
+
+impl AligningStateTrait for ArrowIpcAligningState {
+    type Pipe = ArrowIpcFormatPipe;
+
+    fn try_create(_ctx: &Arc<InputContext>, split_info: &Arc<SplitInfo>) -> Result<Self> {
+        Ok(ArrowIpcAligningState {
+            split_info: split_info.clone(),
+            buffers: vec![],
+        })
+    }
+
+    fn align(&mut self, read_batch: Option<Vec<u8>>) -> Result<Vec<ArrowFileInMemory>> {
+        if let Some(data) = read_batch {
+            self.buffers.push(data);
+            return Ok(vec![]);
+        }
+
+        let file_in_memory = mem::take(&mut self.buffers).concat();
+        let file = ArrowFileInMemory::try_create(&self.split_info, file_in_memory)?;
+        tracing::info!(
+            "align arrow file {} of {} bytes to {} record batches",
+            self.split_info.file.path,
+            file.size,
+            file.batches.len()
+        );
+        Ok(vec![file])
+    }
+}
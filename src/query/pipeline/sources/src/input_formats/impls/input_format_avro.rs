@@ -0,0 +1,758 @@
+//  Copyright 2023 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::io::Cursor;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use apache_avro::from_avro_datum;
+use apache_avro::schema::Name;
+use apache_avro::types::Value as AvroValue;
+use apache_avro::Codec;
+use apache_avro::Schema as AvroSchema;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::types::decimal::DecimalDataType;
+use common_expression::types::decimal::DecimalScalar;
+use common_expression::types::decimal::DecimalSize;
+use common_expression::types::number::NumberScalar;
+use common_expression::types::number::F32;
+use common_expression::types::number::F64;
+use common_expression::types::DataType;
+use common_expression::types::NumberDataType;
+use common_expression::ColumnBuilder;
+use common_expression::DataBlock;
+use common_expression::Scalar;
+use common_expression::TableDataType;
+use common_expression::TableField;
+use common_expression::TableSchema;
+use common_expression::TableSchemaRef;
+use common_meta_app::principal::FileFormatOptions;
+use common_meta_app::principal::StageInfo;
+use common_pipeline_core::Pipeline;
+use common_settings::Settings;
+use common_storage::StageFileInfo;
+use ethnum::i256;
+use opendal::Operator;
+
+use crate::input_formats::input_pipeline::read_full;
+use crate::input_formats::input_pipeline::AligningStateTrait;
+use crate::input_formats::input_pipeline::BlockBuilderTrait;
+use crate::input_formats::input_pipeline::InputFormatPipe;
+use crate::input_formats::input_pipeline::RowBatchTrait;
+use crate::input_formats::input_split::FileInfo;
+use crate::input_formats::InputContext;
+use crate::input_formats::InputFormat;
+use crate::input_formats::SplitInfo;
+
+const AVRO_MAGIC: [u8; 4] = [b'O', b'b', b'j', 1];
+const SYNC_MARKER_SIZE: usize = 16;
+// the size to read each time when reading the header for infer_schema
+const HEADER_READ_STEP: usize = 64 * 1024;
+
+// returns None from the enclosing function if the buffer is not long enough
+macro_rules! try_ready {
+    ($e:expr) => {
+        match $e? {
+            Some(v) => v,
+            None => return Ok(None),
+        }
+    };
+}
+
+pub struct InputFormatAvro {}
+
+impl InputFormatAvro {
+    pub fn create() -> Self {
+        Self {}
+    }
+}
+
+#[async_trait::async_trait]
+impl InputFormat for InputFormatAvro {
+    #[async_backtrace::framed]
+    async fn get_splits(
+        &self,
+        file_infos: Vec<StageFileInfo>,
+        _stage_info: &StageInfo,
+        _op: &Operator,
+        _settings: &Arc<Settings>,
+    ) -> Result<Vec<Arc<SplitInfo>>> {
+        // blocks of avro files are compressed by the codec in the header,
+        // so the files are not compressed as a whole and each file is one split.
+        let mut infos = vec![];
+        for info in file_infos {
+            let size = info.size as usize;
+            let file = Arc::new(FileInfo {
+                path: info.path.clone(),
+                size,
+                num_splits: 1,
+                compress_alg: None,
+            });
+            infos.push(Arc::new(SplitInfo {
+                file,
+                seq_in_file: 0,
+                offset: 0,
+                size,
+                num_file_splits: 1,
+                format_info: None,
+            }));
+        }
+        Ok(infos)
+    }
+
+    #[async_backtrace::framed]
+    async fn infer_schema(
+        &self,
+        path: &str,
+        op: &Operator,
+        _options: &FileFormatOptions,
+    ) -> Result<TableSchemaRef> {
+        let mut reader = op.reader(path).await?;
+        let mut buf = vec![];
+        loop {
+            let mut chunk = vec![0u8; HEADER_READ_STEP];
+            let n = read_full(&mut reader, &mut chunk).await?;
+            buf.extend_from_slice(&chunk[..n]);
+            if let Some((header, _)) = AvroFileHeader::try_read(&buf)? {
+                return Ok(Arc::new(avro_table_schema(&header.schema)?));
+            }
+            if n < HEADER_READ_STEP {
+                return Err(ErrorCode::BadBytes(format!(
+                    "incomplete header of avro file {}",
+                    path
+                )));
+            }
+        }
+    }
+
+    fn exec_copy(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()> {
+        AvroFormatPipe::execute_copy_with_aligner(ctx, pipeline)
+    }
+
+    fn exec_stream(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()> {
+        AvroFormatPipe::execute_stream(ctx, pipeline)
+    }
+}
+
+pub struct AvroFormatPipe;
+
+#[async_trait::async_trait]
+impl InputFormatPipe for AvroFormatPipe {
+    type SplitMeta = ();
+    type ReadBatch = Vec<u8>;
+    type RowBatch = AvroBlock;
+    type AligningState = AvroAligningState;
+    type BlockBuilder = AvroBlockBuilder;
+}
+
+struct AvroFileHeader {
+    schema: AvroSchema,
+    codec: Codec,
+    sync_marker: [u8; SYNC_MARKER_SIZE],
+}
+
+impl AvroFileHeader {
+    // returns the header and its size, or None if the buffer doesn't contain the whole header
+    fn try_read(buf: &[u8]) -> Result<Option<(AvroFileHeader, usize)>> {
+        if buf.len() < AVRO_MAGIC.len() {
+            return Ok(None);
+        }
+        if buf[..AVRO_MAGIC.len()] != AVRO_MAGIC {
+            return Err(ErrorCode::BadBytes(
+                "invalid avro file, the magic of object container file not found",
+            ));
+        }
+
+        let mut pos = AVRO_MAGIC.len();
+        let mut metadata = HashMap::new();
+        loop {
+            let mut count = try_ready!(read_long(buf, &mut pos));
+            if count == 0 {
+                break;
+            }
+            if count < 0 {
+                // the size in bytes of the block follows a negative count
+                count = -count;
+                try_ready!(read_long(buf, &mut pos));
+            }
+            for _ in 0..count {
+                let key = try_ready!(read_bytes(buf, &mut pos));
+                let value = try_ready!(read_bytes(buf, &mut pos));
+                metadata.insert(key, value);
+            }
+        }
+        if buf.len() < pos + SYNC_MARKER_SIZE {
+            return Ok(None);
+        }
+        let mut sync_marker = [0u8; SYNC_MARKER_SIZE];
+        sync_marker.copy_from_slice(&buf[pos..pos + SYNC_MARKER_SIZE]);
+        pos += SYNC_MARKER_SIZE;
+
+        let schema = match metadata.get(b"avro.schema".as_slice()) {
+            Some(schema) => AvroSchema::parse_str(&String::from_utf8_lossy(schema))
+                .map_err(|e| ErrorCode::BadBytes(format!("invalid avro schema: {e}")))?,
+            None => {
+                return Err(ErrorCode::BadBytes(
+                    "invalid avro file, avro.schema not found in the header",
+                ));
+            }
+        };
+        let codec = match metadata.get(b"avro.codec".as_slice()) {
+            Some(codec) => {
+                let codec = String::from_utf8_lossy(codec);
+                Codec::from_str(&codec).map_err(|_| {
+                    ErrorCode::Unimplemented(format!("avro codec {} is not supported", codec))
+                })?
+            }
+            None => Codec::Null,
+        };
+
+        Ok(Some((
+            AvroFileHeader {
+                schema,
+                codec,
+                sync_marker,
+            },
+            pos,
+        )))
+    }
+}
+
+// read a zigzag encoded long
+fn read_long(buf: &[u8], pos: &mut usize) -> Result<Option<i64>> {
+    let mut value = 0u64;
+    for (i, b) in buf[*pos..].iter().take(10).enumerate() {
+        value |= ((b & 0x7f) as u64) << (7 * i);
+        if b & 0x80 == 0 {
+            *pos += i + 1;
+            return Ok(Some((value >> 1) as i64 ^ -((value & 1) as i64)));
+        }
+    }
+    if buf.len() - *pos < 10 {
+        Ok(None)
+    } else {
+        Err(ErrorCode::BadBytes("invalid avro file, long overflow"))
+    }
+}
+
+fn read_bytes<'a>(buf: &'a [u8], pos: &mut usize) -> Result<Option<&'a [u8]>> {
+    let mut start = *pos;
+    let len = try_ready!(read_long(buf, &mut start));
+    if len < 0 {
+        return Err(ErrorCode::BadBytes(format!(
+            "invalid avro file, negative length {}",
+            len
+        )));
+    }
+    let end = start.checked_add(len as usize).ok_or_else(|| {
+        ErrorCode::BadBytes(format!("invalid avro file, length {} overflows", len))
+    })?;
+    if buf.len() < end {
+        return Ok(None);
+    }
+    *pos = end;
+    Ok(Some(&buf[start..end]))
+}
+
+// the header of the file and the columns to read
+pub struct AvroFileMeta {
+    header: AvroFileHeader,
+    // indexes of the avro fields, in the order of the schema to load
+    projection: Vec<usize>,
+    data_types: Vec<DataType>,
+}
+
+impl AvroFileMeta {
+    fn try_create(header: AvroFileHeader, schema: &TableSchemaRef) -> Result<Self> {
+        let avro_schema = avro_table_schema(&header.schema)?;
+        let mut projection = Vec::with_capacity(schema.num_fields());
+        let mut data_types = Vec::with_capacity(schema.num_fields());
+        for f in schema.fields().iter() {
+            match avro_schema
+                .fields()
+                .iter()
+                .position(|c| c.name().eq_ignore_ascii_case(f.name()))
+            {
+                Some(i) => {
+                    projection.push(i);
+                    data_types.push(avro_schema.field(i).data_type().into());
+                }
+                None => {
+                    return Err(ErrorCode::TableSchemaMismatch(format!(
+                        "schema field size mismatch, expected to find column: {}",
+                        f.name()
+                    )));
+                }
+            }
+        }
+        Ok(Self {
+            header,
+            projection,
+            data_types,
+        })
+    }
+}
+
+// a data block of avro file, with the objects still encoded and compressed
+pub struct AvroBlock {
+    meta: Arc<AvroFileMeta>,
+    split_info: Arc<SplitInfo>,
+    num_rows: usize,
+    data: Vec<u8>,
+}
+
+impl Debug for AvroBlock {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "AvroBlock")
+    }
+}
+
+impl RowBatchTrait for AvroBlock {
+    fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    fn rows(&self) -> usize {
+        self.num_rows
+    }
+}
+
+impl AvroBlock {
+    // returns the block, or None if the buffer doesn't contain the whole block
+    fn try_read(
+        buf: &[u8],
+        pos: &mut usize,
+        meta: &Arc<AvroFileMeta>,
+        split_info: &Arc<SplitInfo>,
+    ) -> Result<Option<AvroBlock>> {
+        let mut start = *pos;
+        let num_rows = try_ready!(read_long(buf, &mut start));
+        let size = try_ready!(read_long(buf, &mut start));
+        if num_rows < 0 || size < 0 {
+            return Err(ErrorCode::BadBytes(format!(
+                "invalid block of avro file {}, {} objects of {} bytes",
+                split_info.file.path, num_rows, size
+            )));
+        }
+        let end = start
+            .checked_add(size as usize)
+            .filter(|end| end.checked_add(SYNC_MARKER_SIZE).is_some())
+            .ok_or_else(|| {
+                ErrorCode::BadBytes(format!(
+                    "invalid block of avro file {}, block size {} overflows",
+                    split_info.file.path, size
+                ))
+            })?;
+        if buf.len() < end + SYNC_MARKER_SIZE {
+            return Ok(None);
+        }
+        if buf[end..end + SYNC_MARKER_SIZE] != meta.header.sync_marker {
+            return Err(ErrorCode::BadBytes(format!(
+                "invalid block of avro file {}, the sync marker mismatches the header",
+                split_info.file.path
+            )));
+        }
+        *pos = end + SYNC_MARKER_SIZE;
+        Ok(Some(AvroBlock {
+            meta: meta.clone(),
+            split_info: split_info.clone(),
+            num_rows: num_rows as usize,
+            data: buf[start..end].to_vec(),
+        }))
+    }
+
+    fn deserialize(self, columns: &mut [ColumnBuilder]) -> Result<()> {
+        let header = &self.meta.header;
+        let mut data = self.data;
+        header.codec.decompress(&mut data).map_err(|e| {
+            ErrorCode::InvalidCompressionData(format!(
+                "fail to decompress block of avro file {}: {e}",
+                self.split_info.file.path
+            ))
+        })?;
+
+        let mut reader = Cursor::new(data);
+        for row in 0..self.num_rows {
+            let value = from_avro_datum(&header.schema, &mut reader, None).map_err(|e| {
+                ErrorCode::BadBytes(format!(
+                    "fail to decode object {} of block in avro file {}: {e}",
+                    row, self.split_info.file.path
+                ))
+            })?;
+            let mut fields = match value {
+                AvroValue::Record(fields) => fields,
+                other => {
+                    return Err(ErrorCode::BadBytes(format!(
+                        "expect avro record, but got {:?}",
+                        other
+                    )));
+                }
+            };
+            for ((column, index), data_type) in columns
+                .iter_mut()
+                .zip(self.meta.projection.iter())
+                .zip(self.meta.data_types.iter())
+            {
+                let (name, value) =
+                    std::mem::replace(&mut fields[*index], (String::new(), AvroValue::Null));
+                let scalar = avro_value_to_scalar(value, data_type)
+                    .map_err(|e| e.add_message(format!("column={}", name)))?;
+                column.push(scalar.as_ref());
+            }
+        }
+        Ok(())
+    }
+}
+
+pub struct AvroAligningState {
+    ctx: Arc<InputContext>,
+    split_info: Arc<SplitInfo>,
+    meta: Option<Arc<AvroFileMeta>>,
+    buf: Vec<u8>,
+}
+
+impl AligningStateTrait for AvroAligningState {
+    type Pipe = AvroFormatPipe;
+
+    fn try_create(ctx: &Arc<InputContext>, split_info: &Arc<SplitInfo>) -> Result<Self> {
+        Ok(AvroAligningState {
+            ctx: ctx.clone(),
+            split_info: split_info.clone(),
+            meta: None,
+            buf: vec![],
+        })
+    }
+
+    fn align(&mut self, read_batch: Option<Vec<u8>>) -> Result<Vec<AvroBlock>> {
+        let eof = read_batch.is_none();
+        if let Some(data) = read_batch {
+            self.buf.extend_from_slice(&data);
+        }
+
+        let mut pos = 0;
+        if self.meta.is_none() {
+            if let Some((header, size)) = AvroFileHeader::try_read(&self.buf)? {
+                self.meta = Some(Arc::new(AvroFileMeta::try_create(
+                    header,
+                    &self.ctx.schema,
+                )?));
+                pos = size;
+            }
+        }
+
+        let mut blocks = vec![];
+        if let Some(meta) = &self.meta {
+            while let Some(block) =
+                AvroBlock::try_read(&self.buf, &mut pos, meta, &self.split_info)?
+            {
+                blocks.push(block);
+            }
+        }
+        self.buf.drain(..pos);
+
+        if eof && !self.buf.is_empty() {
+            return Err(ErrorCode::BadBytes(format!(
+                "unexpected end of avro file {}, {} bytes left",
+                self.split_info.file.path,
+                self.buf.len()
+            )));
+        }
+        tracing::debug!(
+            "align avro file {} to {} blocks",
+            self.split_info.file.path,
+            blocks.len()
+        );
+        Ok(blocks)
+    }
+}
+
+pub struct AvroBlockBuilder {
+    ctx: Arc<InputContext>,
+    // the data types of the avro files may be different, blocks are flushed when it changes
+    data_types: Vec<DataType>,
+    mutable_columns: Vec<ColumnBuilder>,
+    num_rows: usize,
+}
+
+impl AvroBlockBuilder {
+    fn flush(&mut self) -> Vec<DataBlock> {
+        let columns = std::mem::take(&mut self.mutable_columns)
+            .into_iter()
+            .map(|c| c.build())
+            .collect::<Vec<_>>();
+        let num_rows = std::mem::take(&mut self.num_rows);
+        if columns.is_empty() || num_rows == 0 {
+            vec![]
+        } else {
+            vec![DataBlock::new_from_columns(columns)]
+        }
+    }
+
+    fn memory_size(&self) -> usize {
+        self.mutable_columns.iter().map(|x| x.memory_size()).sum()
+    }
+}
+
+impl BlockBuilderTrait for AvroBlockBuilder {
+    type Pipe = AvroFormatPipe;
+
+    fn create(ctx: Arc<InputContext>) -> Self {
+        AvroBlockBuilder {
+            ctx,
+            data_types: vec![],
+            mutable_columns: vec![],
+            num_rows: 0,
+        }
+    }
+
+    fn deserialize(&mut self, batch: Option<AvroBlock>) -> Result<Vec<DataBlock>> {
+        let batch = match batch {
+            Some(batch) => batch,
+            None => return Ok(self.flush()),
+        };
+
+        let mut blocks = vec![];
+        if self.mutable_columns.is_empty() || self.data_types != batch.meta.data_types {
+            blocks = self.flush();
+            self.data_types = batch.meta.data_types.clone();
+            self.mutable_columns = self
+                .data_types
+                .iter()
+                .map(|ty| {
+                    ColumnBuilder::with_capacity(
+                        ty,
+                        self.ctx.block_compact_thresholds.min_rows_per_block,
+                    )
+                })
+                .collect();
+        }
+
+        self.num_rows += batch.num_rows;
+        batch.deserialize(&mut self.mutable_columns)?;
+        if self.num_rows >= self.ctx.block_compact_thresholds.min_rows_per_block
+            || self.memory_size() > self.ctx.block_compact_thresholds.max_bytes_per_block
+        {
+            blocks.extend(self.flush());
+        }
+        Ok(blocks)
+    }
+}
+
+// top level fields of the avro record are the columns
+fn avro_table_schema(schema: &AvroSchema) -> Result<TableSchema> {
+    match schema {
+        AvroSchema::Record { fields, .. } => {
+            let mut names = HashMap::new();
+            let fields = fields
+                .iter()
+                .map(|f| {
+                    Ok(TableField::new(
+                        &f.name,
+                        avro_to_table_type(&f.schema, &mut names)?,
+                    ))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(TableSchema::new(fields))
+        }
+        _ => Err(ErrorCode::BadBytes(format!(
+            "the schema of avro file must be record, but got {}",
+            schema.canonical_form()
+        ))),
+    }
+}
+
+// named types are collected in `names`, to resolve the references to them
+fn avro_to_table_type(
+    schema: &AvroSchema,
+    names: &mut HashMap<Name, TableDataType>,
+) -> Result<TableDataType> {
+    let data_type = match schema {
+        AvroSchema::Null => TableDataType::Null,
+        AvroSchema::Boolean => TableDataType::Boolean,
+        AvroSchema::Int | AvroSchema::TimeMillis => TableDataType::Number(NumberDataType::Int32),
+        AvroSchema::Long | AvroSchema::TimeMicros => TableDataType::Number(NumberDataType::Int64),
+        AvroSchema::Float => TableDataType::Number(NumberDataType::Float32),
+        AvroSchema::Double => TableDataType::Number(NumberDataType::Float64),
+        AvroSchema::Bytes | AvroSchema::String | AvroSchema::Uuid => TableDataType::String,
+        AvroSchema::Date => TableDataType::Date,
+        AvroSchema::TimestampMillis | AvroSchema::TimestampMicros => TableDataType::Timestamp,
+        AvroSchema::Decimal {
+            precision, scale, ..
+        } => {
+            let size = DecimalSize {
+                precision: u8::try_from(*precision).unwrap_or(u8::MAX),
+                scale: u8::try_from(*scale).unwrap_or(u8::MAX),
+            };
+            TableDataType::Decimal(DecimalDataType::from_size(size)?)
+        }
+        AvroSchema::Array(item) => TableDataType::Array(Box::new(avro_to_table_type(item, names)?)),
+        AvroSchema::Map(value) => TableDataType::Map(Box::new(TableDataType::Tuple {
+            fields_name: vec!["key".to_string(), "value".to_string()],
+            fields_type: vec![TableDataType::String, avro_to_table_type(value, names)?],
+        })),
+        AvroSchema::Union(union) => {
+            let variants = union
+                .variants()
+                .iter()
+                .filter(|s| !matches!(s, AvroSchema::Null))
+                .collect::<Vec<_>>();
+            let data_type = match variants.as_slice() {
+                [] => return Ok(TableDataType::Null),
+                [variant] => avro_to_table_type(variant, names)?,
+                // values of different types are loaded as variant
+                _ => TableDataType::Variant,
+            };
+            if union.is_nullable() {
+                data_type.wrap_nullable()
+            } else {
+                data_type
+            }
+        }
+        AvroSchema::Record { name, fields, .. } => {
+            let data_type = TableDataType::Tuple {
+                fields_name: fields.iter().map(|f| f.name.clone()).collect(),
+                fields_type: fields
+                    .iter()
+                    .map(|f| avro_to_table_type(&f.schema, names))
+                    .collect::<Result<Vec<_>>>()?,
+            };
+            names.insert(name.clone(), data_type.clone());
+            data_type
+        }
+        AvroSchema::Enum { name, .. } | AvroSchema::Fixed { name, .. } => {
+            names.insert(name.clone(), TableDataType::String);
+            TableDataType::String
+        }
+        AvroSchema::Ref { name } => match names.get(name) {
+            Some(data_type) => data_type.clone(),
+            None => {
+                return Err(ErrorCode::Unimplemented(format!(
+                    "recursive avro type {} is not supported",
+                    name.fullname(None)
+                )));
+            }
+        },
+        AvroSchema::Duration => {
+            return Err(ErrorCode::Unimplemented(
+                "avro type duration is not supported",
+            ));
+        }
+    };
+    Ok(data_type)
+}
+
+fn avro_value_to_scalar(value: AvroValue, data_type: &DataType) -> Result<Scalar> {
+    let scalar = match (value, data_type) {
+        (AvroValue::Union(_, value), _) => return avro_value_to_scalar(*value, data_type),
+        (AvroValue::Null, _) => Scalar::Null,
+        (value, DataType::Nullable(inner)) => return avro_value_to_scalar(value, inner),
+        (value, DataType::Variant) => {
+            let json = serde_json::Value::try_from(value)
+                .map_err(|e| ErrorCode::BadBytes(format!("fail to convert to variant: {e}")))?;
+            let mut buf = vec![];
+            jsonb::Value::from(&json).write_to_vec(&mut buf);
+            Scalar::Variant(buf)
+        }
+        (AvroValue::Boolean(v), _) => Scalar::Boolean(v),
+        (AvroValue::Int(v), _) | (AvroValue::TimeMillis(v), _) => {
+            Scalar::Number(NumberScalar::Int32(v))
+        }
+        (AvroValue::Long(v), _) | (AvroValue::TimeMicros(v), _) => {
+            Scalar::Number(NumberScalar::Int64(v))
+        }
+        (AvroValue::Float(v), _) => Scalar::Number(NumberScalar::Float32(F32::from(v))),
+        (AvroValue::Double(v), _) => Scalar::Number(NumberScalar::Float64(F64::from(v))),
+        (AvroValue::Bytes(v), _) | (AvroValue::Fixed(_, v), _) => Scalar::String(v),
+        (AvroValue::String(v), _) | (AvroValue::Enum(_, v), _) => Scalar::String(v.into_bytes()),
+        (AvroValue::Uuid(v), _) => Scalar::String(v.to_string().into_bytes()),
+        (AvroValue::Date(v), _) => Scalar::Date(v),
+        (AvroValue::TimestampMillis(v), _) => {
+            Scalar::Timestamp(v.checked_mul(1000).ok_or_else(|| {
+                ErrorCode::BadBytes(format!("avro timestamp-millis {v} is out of range"))
+            })?)
+        }
+        (AvroValue::TimestampMicros(v), _) => Scalar::Timestamp(v),
+        (AvroValue::Decimal(v), DataType::Decimal(decimal_type)) => {
+            let bytes = Vec::<u8>::try_from(v)
+                .map_err(|e| ErrorCode::BadBytes(format!("invalid avro decimal: {e}")))?;
+            decimal_from_be_bytes(&bytes, decimal_type)?
+        }
+        (AvroValue::Array(items), DataType::Array(inner)) => {
+            let mut builder = ColumnBuilder::with_capacity(inner, items.len());
+            for item in items {
+                builder.push(avro_value_to_scalar(item, inner)?.as_ref());
+            }
+            Scalar::Array(builder.build())
+        }
+        (AvroValue::Map(items), DataType::Map(inner)) => {
+            let value_type = match inner.as_ref() {
+                DataType::Tuple(types) if types.len() == 2 => &types[1],
+                _ => unreachable!("the inner type of map must be tuple of key and value"),
+            };
+            // sort by the keys to keep the result stable
+            let mut items = items.into_iter().collect::<Vec<_>>();
+            items.sort_by(|a, b| a.0.cmp(&b.0));
+            let mut builder = ColumnBuilder::with_capacity(inner, items.len());
+            for (key, value) in items {
+                let value = avro_value_to_scalar(value, value_type)?;
+                builder.push(Scalar::Tuple(vec![Scalar::String(key.into_bytes()), value]).as_ref());
+            }
+            Scalar::Map(builder.build())
+        }
+        (AvroValue::Record(fields), DataType::Tuple(types)) => Scalar::Tuple(
+            fields
+                .into_iter()
+                .zip(types.iter())
+                .map(|((_, value), ty)| avro_value_to_scalar(value, ty))
+                .collect::<Result<Vec<_>>>()?,
+        ),
+        (value, _) => {
+            return Err(ErrorCode::BadBytes(format!(
+                "unexpected avro value {:?} for type {}",
+                value, data_type
+            )));
+        }
+    };
+    Ok(scalar)
+}
+
+// the decimal is the two's-complement big-endian representation of the unscaled integer
+fn decimal_from_be_bytes(bytes: &[u8], decimal_type: &DecimalDataType) -> Result<Scalar> {
+    let negative = bytes.first().map(|b| b & 0x80 != 0).unwrap_or(false);
+    let fill = if negative { 0xff } else { 0 };
+    let scalar = match decimal_type {
+        DecimalDataType::Decimal128(size) if bytes.len() <= 16 => {
+            let mut buf = [fill; 16];
+            buf[16 - bytes.len()..].copy_from_slice(bytes);
+            DecimalScalar::Decimal128(i128::from_be_bytes(buf), *size)
+        }
+        DecimalDataType::Decimal256(size) if bytes.len() <= 32 => {
+            let mut buf = [fill; 32];
+            buf[32 - bytes.len()..].copy_from_slice(bytes);
+            DecimalScalar::Decimal256(i256::from_be_bytes(buf), *size)
+        }
+        _ => {
+            return Err(ErrorCode::BadBytes(format!(
+                "avro decimal of {} bytes overflows {}",
+                bytes.len(),
+                DataType::Decimal(*decimal_type)
+            )));
+        }
+    };
+    Ok(Scalar::Decimal(scalar))
+}
@@ -21,7 +21,9 @@ use std::sync::Arc;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_expression::ColumnBuilder;
+use common_expression::TableField;
 use common_expression::TableSchemaRef;
+use common_expression::TableSchemaRefExt;
 use common_formats::FieldDecoder;
 use common_formats::FieldDecoderCSV;
 use common_formats::FieldDecoderRowBased;
@@ -29,11 +31,13 @@ use common_formats::FileFormatOptionsExt;
 use common_formats::RecordDelimiter;
 use common_io::cursor_ext::*;
 use common_io::format_diagnostic::verbose_char;
+use common_meta_app::principal::FileFormatOptions;
 use common_meta_app::principal::OnErrorMode;
 use common_meta_app::principal::StageFileFormatType;
 use csv_core::ReadRecordResult;
 
 use crate::input_formats::impls::input_format_tsv::format_column_error;
+use crate::input_formats::impls::text_schema_inference::InferredType;
 use crate::input_formats::AligningStateCommon;
 use crate::input_formats::AligningStateTextBased;
 use crate::input_formats::BlockBuilder;
@@ -100,6 +104,15 @@ impl InputFormatTextBase for InputFormatCSV {
         Arc::new(FieldDecoderCSV::create(options))
     }
 
+    fn infer_schema_by_sample(
+        sample: &[u8],
+        options: &FileFormatOptions,
+    ) -> Result<TableSchemaRef> {
+        let mut reader = create_csv_reader(options)?;
+        let rows = read_records(&mut reader, sample)?;
+        infer_csv_schema(&rows, options.skip_header as usize)
+    }
+
     fn deserialize(
         builder: &mut BlockBuilder<Self>,
         batch: RowBatch,
@@ -151,7 +164,6 @@ impl InputFormatTextBase for InputFormatCSV {
 
 pub struct CsvReaderState {
     common: AligningStateCommon,
-    #[allow(unused)]
     ctx: Arc<InputContext>,
     split_info: Arc<SplitInfo>,
     pub reader: csv_core::Reader,
@@ -166,11 +178,26 @@ pub struct CsvReaderState {
 
 impl CsvReaderState {
     fn read_record(&mut self, input: &[u8], output: &mut [u8]) -> Result<(bool, usize, usize)> {
-        let (result, n_in, n_out, n_end) =
+        let (mut result, mut n_in, mut n_out, n_end) =
             self.reader
                 .read_record(input, output, &mut self.field_ends[self.n_end..]);
         self.n_end += n_end;
 
+        // rows of files queried from stages may have any number of fields
+        while self.ctx.is_select && result == ReadRecordResult::OutputEndsFull {
+            let len = self.field_ends.len();
+            self.field_ends.resize(len * 2, 0);
+            let (more_result, more_in, more_out, more_end) = self.reader.read_record(
+                &input[n_in..],
+                &mut output[n_out..],
+                &mut self.field_ends[self.n_end..],
+            );
+            result = more_result;
+            n_in += more_in;
+            n_out += more_out;
+            self.n_end += more_end;
+        }
+
         match result {
             ReadRecordResult::InputEmpty => {
                 if input.is_empty() {
@@ -202,20 +229,7 @@ impl CsvReaderState {
 
 impl AligningStateTextBased for CsvReaderState {
     fn try_create(ctx: &Arc<InputContext>, split_info: &Arc<SplitInfo>) -> Result<Self> {
-        let escape = if ctx.format_options.stage.escape.is_empty() {
-            None
-        } else {
-            Some(ctx.format_options.stage.escape.as_bytes()[0])
-        };
-        let reader = csv_core::ReaderBuilder::new()
-            .delimiter(ctx.format_options.get_field_delimiter())
-            .quote(ctx.format_options.stage.quote.as_bytes()[0])
-            .escape(escape)
-            .terminator(match ctx.format_options.get_record_delimiter()? {
-                RecordDelimiter::Crlf => csv_core::Terminator::CRLF,
-                RecordDelimiter::Any(v) => csv_core::Terminator::Any(v),
-            })
-            .build();
+        let reader = create_csv_reader(&ctx.format_options.stage)?;
         Ok(Self {
             common: AligningStateCommon::create(ctx, split_info, false),
             ctx: ctx.clone(),
@@ -338,10 +352,21 @@ impl AligningStateTextBased for CsvReaderState {
 }
 
 impl CsvReaderState {
-    fn check_num_field(&self) -> Result<()> {
+    fn check_num_field(&mut self) -> Result<()> {
         let expect = self.num_fields;
         let actual = self.n_end;
-        if actual < expect {
+        if self.ctx.is_select {
+            // the missing fields are read as empty fields, and the extra fields are ignored
+            if actual < expect {
+                let last_end = if actual == 0 {
+                    0
+                } else {
+                    self.field_ends[actual - 1]
+                };
+                self.field_ends[actual..expect].fill(last_end);
+            }
+            Ok(())
+        } else if actual < expect {
             Err(self.csv_error(&format!("expect {} fields, only found {} ", expect, actual)))
         } else if actual > expect + 1
             || (actual == expect + 1 && self.field_ends[expect] != self.field_ends[expect - 1])
@@ -377,3 +402,115 @@ impl CsvReaderState {
         )
     }
 }
+
+fn create_csv_reader(options: &FileFormatOptions) -> Result<csv_core::Reader> {
+    let escape = if options.escape.is_empty() {
+        None
+    } else {
+        Some(options.escape.as_bytes()[0])
+    };
+    let reader = csv_core::ReaderBuilder::new()
+        .delimiter(options.field_delimiter.as_bytes()[0])
+        .quote(options.quote.as_bytes()[0])
+        .escape(escape)
+        .terminator(
+            match RecordDelimiter::try_from(options.record_delimiter.as_bytes())? {
+                RecordDelimiter::Crlf => csv_core::Terminator::CRLF,
+                RecordDelimiter::Any(v) => csv_core::Terminator::Any(v),
+            },
+        )
+        .build();
+    Ok(reader)
+}
+
+// split the sampled data into rows of fields
+fn read_records(reader: &mut csv_core::Reader, mut input: &[u8]) -> Result<Vec<Vec<Vec<u8>>>> {
+    let mut rows = vec![];
+    let mut output = vec![0u8; input.len() + 1];
+    let mut field_ends = vec![0usize; 64];
+    let mut n_out = 0;
+    let mut n_end = 0;
+    loop {
+        let (result, r_in, r_out, r_end) =
+            reader.read_record(input, &mut output[n_out..], &mut field_ends[n_end..]);
+        input = &input[r_in..];
+        n_out += r_out;
+        n_end += r_end;
+        match result {
+            ReadRecordResult::InputEmpty => {}
+            ReadRecordResult::OutputFull => output.resize(output.len() * 2, 0),
+            ReadRecordResult::OutputEndsFull => field_ends.resize(field_ends.len() * 2, 0),
+            ReadRecordResult::Record => {
+                let mut start = 0;
+                let row = field_ends[..n_end]
+                    .iter()
+                    .map(|end| {
+                        let field = output[start..*end].to_vec();
+                        start = *end;
+                        field
+                    })
+                    .collect();
+                rows.push(row);
+                n_out = 0;
+                n_end = 0;
+            }
+            ReadRecordResult::End => break,
+        }
+    }
+    Ok(rows)
+}
+
+fn infer_csv_types(rows: &[Vec<Vec<u8>>]) -> Vec<InferredType> {
+    let mut types = vec![];
+    for row in rows {
+        if types.len() < row.len() {
+            types.resize(row.len(), InferredType::Null);
+        }
+        for (ty, field) in types.iter_mut().zip(row.iter()) {
+            *ty = ty
+                .merge(InferredType::from_text(field))
+                .unwrap_or(InferredType::String);
+        }
+    }
+    types
+}
+
+fn infer_csv_schema(rows: &[Vec<Vec<u8>>], skip_header: usize) -> Result<TableSchemaRef> {
+    if rows.is_empty() {
+        return Err(ErrorCode::BadBytes(
+            "no rows in the file to infer the schema",
+        ));
+    }
+
+    // without the skip_header option, the first row is taken as the header if its fields
+    // are all strings, while the other rows have columns of other types.
+    let has_header = skip_header > 0 || {
+        let first_row_types = infer_csv_types(&rows[..1]);
+        let other_rows_types = infer_csv_types(&rows[1..]);
+        first_row_types.iter().all(|ty| *ty == InferredType::String)
+            && other_rows_types
+                .iter()
+                .any(|ty| !matches!(ty, InferredType::String | InferredType::Null))
+    };
+    let (names, mut types) = if has_header {
+        let data_rows = &rows[skip_header.max(1).min(rows.len())..];
+        (rows[0].as_slice(), infer_csv_types(data_rows))
+    } else {
+        (&[][..], infer_csv_types(rows))
+    };
+    if types.len() < names.len() {
+        types.resize(names.len(), InferredType::Null);
+    }
+
+    // the columns without names in the header are named as c1, c2, ...
+    let mut fields: Vec<TableField> = Vec::with_capacity(types.len());
+    for (i, ty) in types.into_iter().enumerate() {
+        let name = names
+            .get(i)
+            .map(|name| String::from_utf8_lossy(name).trim().to_string())
+            .filter(|name| !name.is_empty() && fields.iter().all(|f| f.name() != name))
+            .unwrap_or_else(|| format!("c{}", i + 1));
+        fields.push(TableField::new(&name, ty.to_table_type()));
+    }
+    Ok(TableSchemaRefExt::create(fields))
+}
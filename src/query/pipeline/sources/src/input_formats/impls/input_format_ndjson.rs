@@ -20,13 +20,18 @@ use bstr::ByteSlice;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_expression::ColumnBuilder;
+use common_expression::TableField;
 use common_expression::TableSchemaRef;
+use common_expression::TableSchemaRefExt;
 use common_formats::FieldDecoder;
 use common_formats::FieldJsonAstDecoder;
 use common_formats::FileFormatOptionsExt;
+use common_formats::RecordDelimiter;
+use common_meta_app::principal::FileFormatOptions;
 use common_meta_app::principal::OnErrorMode;
 use common_meta_app::principal::StageFileFormatType;
 
+use crate::input_formats::impls::text_schema_inference::InferredType;
 use crate::input_formats::AligningStateRowDelimiter;
 use crate::input_formats::BlockBuilder;
 use crate::input_formats::InputError;
@@ -44,8 +49,15 @@ impl InputFormatNDJson {
         buf: &[u8],
         columns: &mut [ColumnBuilder],
         schema: &TableSchemaRef,
+        is_select: bool,
     ) -> Result<()> {
         let mut json: serde_json::Value = serde_json::from_reader(buf)?;
+        // the whole row is read as the only column `$1` when queried from stages
+        if is_select {
+            return field_decoder
+                .read_field(&mut columns[0], &json)
+                .map_err(|e| ErrorCode::BadBytes(format!("{}. column=$1", e)));
+        }
         // if it's not case_sensitive, we convert to lowercase
         if !field_decoder.ident_case_sensitive {
             if let serde_json::Value::Object(x) = json {
@@ -89,6 +101,53 @@ impl InputFormatTextBase for InputFormatNDJson {
         Arc::new(FieldJsonAstDecoder::create(options))
     }
 
+    fn infer_schema_by_sample(
+        sample: &[u8],
+        options: &FileFormatOptions,
+    ) -> Result<TableSchemaRef> {
+        let record_delimiter = RecordDelimiter::try_from(options.record_delimiter.as_bytes())?;
+
+        // the columns are the union of the keys of all the rows, in the order they are first seen.
+        // the nested values are kept as variants, and so are the keys with values of conflicting types.
+        let mut columns: Vec<(String, InferredType)> = vec![];
+        for row in sample.split(|b| *b == record_delimiter.end()) {
+            let row = row.trim();
+            if row.is_empty() {
+                continue;
+            }
+            let json: serde_json::Value = serde_json::from_slice(row)?;
+            let object = match json {
+                serde_json::Value::Object(object) => object,
+                _ => {
+                    return Err(ErrorCode::BadBytes(format!(
+                        "infer_schema of NDJSON expects an object in each row, but got {}",
+                        maybe_truncated(&json.to_string(), 1024)
+                    )));
+                }
+            };
+            for (key, value) in object.iter() {
+                let ty = InferredType::from_json(value);
+                match columns.iter_mut().find(|(name, _)| name == key) {
+                    Some((_, column_type)) => {
+                        *column_type = column_type.merge(ty).unwrap_or(InferredType::Variant)
+                    }
+                    None => columns.push((key.clone(), ty)),
+                }
+            }
+        }
+        if columns.is_empty() {
+            return Err(ErrorCode::BadBytes(
+                "no rows in the file to infer the schema",
+            ));
+        }
+
+        let fields = columns
+            .into_iter()
+            .map(|(name, ty)| TableField::new(&name, ty.to_table_type()))
+            .collect();
+        Ok(TableSchemaRefExt::create(fields))
+    }
+
     fn deserialize(
         builder: &mut BlockBuilder<Self>,
         batch: RowBatch,
@@ -107,7 +166,13 @@ impl InputFormatTextBase for InputFormatNDJson {
             let buf = &batch.data[start..*end];
             let buf = buf.trim();
             if !buf.is_empty() {
-                if let Err(e) = Self::read_row(field_decoder, buf, columns, &builder.ctx.schema) {
+                if let Err(e) = Self::read_row(
+                    field_decoder,
+                    buf,
+                    columns,
+                    &builder.ctx.schema,
+                    builder.ctx.is_select,
+                ) {
                     match builder.ctx.on_error_mode {
                         OnErrorMode::Continue => {
                             Self::on_error_continue(columns, num_rows, e.clone(), &mut error_map);
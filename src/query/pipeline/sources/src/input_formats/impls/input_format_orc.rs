@@ -0,0 +1,447 @@
+//  Copyright 2023 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::any::Any;
+use std::fmt::Debug;
+use std::fmt::Formatter;
+use std::mem;
+use std::sync::Arc;
+
+use common_arrow::arrow::datatypes::Field as ArrowField;
+use common_arrow::arrow::error::Error as ArrowError;
+use common_arrow::arrow::io::orc::format::proto::r#type::Kind;
+use common_arrow::arrow::io::orc::format::proto::StripeInformation;
+use common_arrow::arrow::io::orc::format::read::read_stripe_footer;
+use common_arrow::arrow::io::orc::format::read::FileMetadata;
+use common_arrow::deserialize_orc_stripe_column;
+use common_arrow::infer_orc_data_type;
+use common_arrow::orc_tail_length;
+use common_arrow::read_orc_metadata;
+use common_arrow::OrcStripeReader;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::types::DataType;
+use common_expression::BlockEntry;
+use common_expression::Column;
+use common_expression::DataBlock;
+use common_expression::TableDataType;
+use common_expression::TableField;
+use common_expression::TableSchema;
+use common_expression::TableSchemaRef;
+use common_expression::Value;
+use common_meta_app::principal::FileFormatOptions;
+use common_meta_app::principal::StageInfo;
+use common_pipeline_core::Pipeline;
+use common_settings::Settings;
+use common_storage::StageFileInfo;
+use futures::StreamExt;
+use futures::TryStreamExt;
+use opendal::Operator;
+use serde::Deserializer;
+use serde::Serializer;
+
+use crate::input_formats::input_pipeline::AligningStateTrait;
+use crate::input_formats::input_pipeline::BlockBuilderTrait;
+use crate::input_formats::input_pipeline::InputFormatPipe;
+use crate::input_formats::input_pipeline::RowBatchTrait;
+use crate::input_formats::input_split::DynData;
+use crate::input_formats::input_split::FileInfo;
+use crate::input_formats::InputContext;
+use crate::input_formats::InputFormat;
+use crate::input_formats::SplitInfo;
+
+// the postscript and footer of most orc files are in the last 16KB
+const ORC_TAIL_SIZE_HINT: u64 = 16 * 1024;
+// the number of orc files to read metas concurrently
+const META_READ_CONCURRENCY: usize = 16;
+
+pub struct InputFormatOrc;
+
+impl InputFormatOrc {
+    // each stripe of orc files is a split
+    fn make_splits(
+        file_infos: Vec<StageFileInfo>,
+        metas: Vec<OrcFileMeta>,
+    ) -> Result<Vec<Arc<SplitInfo>>> {
+        let mut infos = vec![];
+        for (info, meta) in file_infos.into_iter().zip(metas.into_iter()) {
+            let meta = Arc::new(meta);
+            let stripes = &meta.meta.footer.stripes;
+            let file_info = Arc::new(FileInfo {
+                path: info.path.clone(),
+                size: info.size as usize,
+                num_splits: stripes.len(),
+                compress_alg: None,
+            });
+
+            for (i, stripe) in stripes.iter().enumerate() {
+                if stripe.number_of_rows() == 0 {
+                    continue;
+                }
+                infos.push(Arc::new(SplitInfo {
+                    file: file_info.clone(),
+                    seq_in_file: i,
+                    offset: stripe.offset() as usize,
+                    size: stripe_length(stripe) as usize,
+                    num_file_splits: stripes.len(),
+                    format_info: Some(Arc::new(OrcSplitMeta {
+                        file: meta.clone(),
+                        stripe: i,
+                    })),
+                }));
+            }
+        }
+        Ok(infos)
+    }
+}
+
+#[async_trait::async_trait]
+impl InputFormat for InputFormatOrc {
+    #[async_backtrace::framed]
+    async fn get_splits(
+        &self,
+        file_infos: Vec<StageFileInfo>,
+        _stage_info: &StageInfo,
+        op: &Operator,
+        _settings: &Arc<Settings>,
+    ) -> Result<Vec<Arc<SplitInfo>>> {
+        let metas = futures::stream::iter(file_infos.iter())
+            .map(|info| read_orc_file_meta(op, &info.path, info.size))
+            .buffered(META_READ_CONCURRENCY)
+            .try_collect::<Vec<_>>()
+            .await?;
+        Self::make_splits(file_infos, metas)
+    }
+
+    #[async_backtrace::framed]
+    async fn infer_schema(
+        &self,
+        path: &str,
+        op: &Operator,
+        _options: &FileFormatOptions,
+    ) -> Result<TableSchemaRef> {
+        let size = op.stat(path).await?.content_length();
+        let meta = read_orc_file_meta(op, path, size).await?;
+        let fields = meta
+            .columns
+            .iter()
+            .map(|(name, column)| {
+                let data_type = infer_orc_data_type(&meta.meta.footer.types, *column)?;
+                Ok(TableField::from(&ArrowField::new(name, data_type, true)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Arc::new(TableSchema::new(fields)))
+    }
+
+    fn exec_copy(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()> {
+        OrcFormatPipe::execute_copy_aligned(ctx, pipeline)
+    }
+
+    fn exec_stream(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()> {
+        OrcFormatPipe::execute_stream(ctx, pipeline)
+    }
+}
+
+pub struct OrcFormatPipe;
+
+#[async_trait::async_trait]
+impl InputFormatPipe for OrcFormatPipe {
+    type SplitMeta = OrcSplitMeta;
+    type ReadBatch = Vec<u8>;
+    type RowBatch = StripeInMemory;
+    type AligningState = OrcAligningState;
+    type BlockBuilder = OrcBlockBuilder;
+
+    #[async_backtrace::framed]
+    async fn read_split(
+        ctx: Arc<InputContext>,
+        split_info: Arc<SplitInfo>,
+    ) -> Result<Self::RowBatch> {
+        let meta = Self::get_split_meta(&split_info).expect("must success");
+        let op = ctx.source.get_operator()?;
+        let offset = split_info.offset as u64;
+        let data = op
+            .range_read(
+                &split_info.file.path,
+                offset..offset + split_info.size as u64,
+            )
+            .await?;
+        Ok(StripeInMemory {
+            split_info: split_info.to_string(),
+            file: meta.file.clone(),
+            stripe: meta.stripe,
+            data,
+        })
+    }
+}
+
+pub struct OrcFileMeta {
+    pub meta: FileMetadata,
+    // names and orc column ids of the top level columns
+    pub columns: Vec<(String, u32)>,
+}
+
+impl OrcFileMeta {
+    fn try_create(meta: FileMetadata, path: &str) -> Result<Self> {
+        let root = match meta.footer.types.first() {
+            Some(root) if root.kind() == Kind::Struct => root,
+            _ => {
+                return Err(ErrorCode::BadBytes(format!(
+                    "invalid orc file {}, the root type must be struct",
+                    path
+                )));
+            }
+        };
+        let columns = root
+            .field_names
+            .iter()
+            .cloned()
+            .zip(root.subtypes.iter().cloned())
+            .collect();
+        Ok(OrcFileMeta { meta, columns })
+    }
+
+    // the orc column ids of the columns in the table schema
+    fn project(&self, schema: &TableSchemaRef) -> Result<Vec<u32>> {
+        schema
+            .fields()
+            .iter()
+            .map(|f| {
+                self.columns
+                    .iter()
+                    .filter(|(name, _)| name.eq_ignore_ascii_case(f.name()))
+                    .map(|(_, column)| *column)
+                    .last()
+                    .ok_or_else(|| {
+                        ErrorCode::TableSchemaMismatch(format!(
+                            "schema field size mismatch, expected to find column: {}",
+                            f.name()
+                        ))
+                    })
+            })
+            .collect()
+    }
+}
+
+#[async_backtrace::framed]
+async fn read_orc_file_meta(op: &Operator, path: &str, size: u64) -> Result<OrcFileMeta> {
+    let hint = ORC_TAIL_SIZE_HINT.min(size);
+    let mut tail = op.range_read(path, size - hint..size).await?;
+    let tail_length = orc_tail_length(&tail)? as u64;
+    if tail_length > size {
+        return Err(ErrorCode::BadBytes(format!(
+            "invalid orc file {}, tail length {} is larger than file size {}",
+            path, tail_length, size
+        )));
+    }
+    if tail_length > hint {
+        tail = op.range_read(path, size - tail_length..size).await?;
+    }
+    let meta = read_orc_metadata(&tail)?;
+    OrcFileMeta::try_create(meta, path)
+}
+
+fn stripe_length(stripe: &StripeInformation) -> u64 {
+    stripe.index_length() + stripe.data_length() + stripe.footer_length()
+}
+
+#[derive(Clone)]
+pub struct OrcSplitMeta {
+    pub file: Arc<OrcFileMeta>,
+    pub stripe: usize,
+}
+
+impl Debug for OrcSplitMeta {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "orc split meta")
+    }
+}
+
+impl serde::Serialize for OrcSplitMeta {
+    fn serialize<S>(&self, _serializer: S) -> Result<S::Ok, S::Error>
+    where S: Serializer {
+        unimplemented!()
+    }
+}
+
+impl<'a> serde::Deserialize<'a> for OrcSplitMeta {
+    fn deserialize<D: Deserializer<'a>>(_deserializer: D) -> Result<Self, D::Error> {
+        unimplemented!()
+    }
+}
+
+#[typetag::serde(name = "orc_split")]
+impl DynData for OrcSplitMeta {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+}
+
+pub struct StripeInMemory {
+    pub split_info: String,
+    pub file: Arc<OrcFileMeta>,
+    pub stripe: usize,
+    // the whole stripe, starts at the offset of the stripe in the file
+    pub data: Vec<u8>,
+}
+
+impl RowBatchTrait for StripeInMemory {
+    fn size(&self) -> usize {
+        self.data.len()
+    }
+
+    fn rows(&self) -> usize {
+        self.file.meta.footer.stripes[self.stripe].number_of_rows() as usize
+    }
+}
+
+impl StripeInMemory {
+    // the columns are in the order of `columns`, and of the types in the orc file
+    fn deserialize(self, columns: &[u32]) -> Result<DataBlock> {
+        let meta = &self.file.meta;
+        let stripe_info = &meta.footer.stripes[self.stripe];
+        let num_rows = stripe_info.number_of_rows() as usize;
+        let mut reader = OrcStripeReader::new(stripe_info.offset(), self.data);
+        let footer = read_stripe_footer(&mut reader, meta, self.stripe, &mut vec![])
+            .map_err(ArrowError::from)?;
+
+        let mut entries = Vec::with_capacity(columns.len());
+        for column in columns {
+            let orc_type = infer_orc_data_type(&meta.footer.types, *column)?;
+            let field = ArrowField::new("", orc_type.clone(), true);
+            let data_type = DataType::from(&TableDataType::from(&field));
+            let array = deserialize_orc_stripe_column(
+                &mut reader,
+                meta,
+                self.stripe,
+                &footer,
+                *column,
+                orc_type,
+            )?;
+            entries.push(BlockEntry {
+                value: Value::Column(Column::from_arrow(array.as_ref(), &data_type)),
+                data_type,
+            });
+        }
+        Ok(DataBlock::new(entries, num_rows))
+    }
+}
+
+impl Debug for StripeInMemory {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "StripeInMemory")
+    }
+}
+
+pub struct OrcBlockBuilder {
+    ctx: Arc<InputContext>,
+}
+
+impl BlockBuilderTrait for OrcBlockBuilder {
+    type Pipe = OrcFormatPipe;
+
+    fn create(ctx: Arc<InputContext>) -> Self {
+        OrcBlockBuilder { ctx }
+    }
+
+    fn deserialize(&mut self, batch: Option<StripeInMemory>) -> Result<Vec<DataBlock>> {
+        if let Some(stripe) = batch {
+            let split_info = stripe.split_info.clone();
+            let columns = stripe.file.project(&self.ctx.schema)?;
+            let block = stripe
+                .deserialize(&columns)
+                .map_err(|e| e.add_message(format!(" when deserializing {}", split_info)))?;
+
+            let block_total_rows = block.num_rows();
+            let num_rows_per_block = self.ctx.block_compact_thresholds.max_rows_per_block;
+            let blocks: Vec<DataBlock> = (0..block_total_rows)
+                .step_by(num_rows_per_block)
+                .map(|idx| {
+                    if idx + num_rows_per_block < block_total_rows {
+                        block.slice(idx..idx + num_rows_per_block)
+                    } else {
+                        block.slice(idx..block_total_rows)
+                    }
+                })
+                .collect();
+
+            Ok(blocks)
+        } else {
+            Ok(vec![])
+        }
+    }
+}
+
+// used by streaming load, the whole file is buffered since the metadata is at the end of it
+pub struct OrcAligningState {
+    split_info: Arc<SplitInfo>,
+    buffers: Vec<Vec<u8>>,
+}
+
+impl AligningStateTrait for OrcAligningState {
+    type Pipe = OrcFormatPipe;
+
+    fn try_create(_ctx: &Arc<InputContext>, split_info: &Arc<SplitInfo>) -> Result<Self> {
+        Ok(OrcAligningState {
+            split_info: split_info.clone(),
+            buffers: vec![],
+        })
+    }
+
+    fn align(&mut self, read_batch: Option<Vec<u8>>) -> Result<Vec<StripeInMemory>> {
+        if let Some(data) = read_batch {
+            self.buffers.push(data);
+            return Ok(vec![]);
+        }
+
+        let path = &self.split_info.file.path;
+        let file_in_memory = mem::take(&mut self.buffers).concat();
+        let size = file_in_memory.len();
+        let tail_length = orc_tail_length(&file_in_memory)?;
+        if tail_length > size {
+            return Err(ErrorCode::BadBytes(format!(
+                "invalid orc file {}, tail length {} is larger than file size {}",
+                path, tail_length, size
+            )));
+        }
+        let meta = read_orc_metadata(&file_in_memory[size - tail_length..])?;
+        let file = Arc::new(OrcFileMeta::try_create(meta, path)?);
+
+        let split_info = self.split_info.to_string();
+        let mut stripes = Vec::with_capacity(file.meta.footer.stripes.len());
+        for (i, stripe) in file.meta.footer.stripes.iter().enumerate() {
+            let start = stripe.offset() as usize;
+            let end = start + stripe_length(stripe) as usize;
+            if end > size {
+                return Err(ErrorCode::BadBytes(format!(
+                    "invalid orc file {}, stripe {} ends at {} beyond the file size {}",
+                    path, i, end, size
+                )));
+            }
+            stripes.push(StripeInMemory {
+                split_info: split_info.clone(),
+                file: file.clone(),
+                stripe: i,
+                data: file_in_memory[start..end].to_vec(),
+            });
+        }
+        tracing::info!(
+            "align orc file {} of {} bytes to {} stripes",
+            path,
+            size,
+            stripes.len()
+        );
+        Ok(stripes)
+    }
+}
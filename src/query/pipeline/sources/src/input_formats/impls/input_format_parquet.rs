@@ -41,6 +41,7 @@ use common_expression::DataField;
 use common_expression::DataSchema;
 use common_expression::TableSchema;
 use common_expression::TableSchemaRef;
+use common_meta_app::principal::FileFormatOptions;
 use common_meta_app::principal::StageInfo;
 use common_pipeline_core::Pipeline;
 use common_settings::Settings;
@@ -145,7 +146,12 @@ impl InputFormat for InputFormatParquet {
     }
 
     #[async_backtrace::framed]
-    async fn infer_schema(&self, path: &str, op: &Operator) -> Result<TableSchemaRef> {
+    async fn infer_schema(
+        &self,
+        path: &str,
+        op: &Operator,
+        _options: &FileFormatOptions,
+    ) -> Result<TableSchemaRef> {
         let mut reader = op.reader(path).await?;
         let file_meta = read_metadata_async(&mut reader).await?;
         let arrow_schema = infer_schema(&file_meta)?;
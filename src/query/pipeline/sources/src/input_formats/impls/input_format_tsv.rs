@@ -47,6 +47,7 @@ impl InputFormatTSV {
         buf: &[u8],
         columns: &mut Vec<ColumnBuilder>,
         schema: &TableSchemaRef,
+        is_select: bool,
     ) -> Result<()> {
         let num_columns = columns.len();
         let mut column_index = 0;
@@ -92,7 +93,12 @@ impl InputFormatTSV {
             }
             pos += 1;
         }
-        if err_msg.is_none() {
+        if err_msg.is_none() && is_select {
+            // the missing columns are filled with default values, and the extra columns are ignored
+            for column in columns.iter_mut().skip(column_index) {
+                column.push_default();
+            }
+        } else if err_msg.is_none() {
             if column_index < num_columns {
                 err_msg = Some(format!(
                     "need {} columns, find {} only",
@@ -157,6 +163,7 @@ impl InputFormatTextBase for InputFormatTSV {
                 buf,
                 columns,
                 schema,
+                builder.ctx.is_select,
             ) {
                 match builder.ctx.on_error_mode {
                     OnErrorMode::Continue => {
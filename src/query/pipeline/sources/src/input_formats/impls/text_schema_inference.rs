@@ -0,0 +1,181 @@
+//  Copyright 2023 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use common_expression::types::NumberDataType;
+use common_expression::TableDataType;
+
+/// The type of a column inferred from the values sampled from text files.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InferredType {
+    // only NULLs are seen
+    Null,
+    Boolean,
+    Int64,
+    Float64,
+    Date,
+    Timestamp,
+    String,
+    Variant,
+}
+
+impl InferredType {
+    // the type of a field of CSV files, empty fields are NULLs
+    pub fn from_text(value: &[u8]) -> Self {
+        if value.is_empty() {
+            InferredType::Null
+        } else if value.eq_ignore_ascii_case(b"true") || value.eq_ignore_ascii_case(b"false") {
+            InferredType::Boolean
+        } else if is_integer(value) {
+            InferredType::Int64
+        } else if is_float(value) {
+            InferredType::Float64
+        } else {
+            Self::from_string(value)
+        }
+    }
+
+    // the type of a value of NDJSON files, nested values are kept as variants
+    pub fn from_json(value: &serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => InferredType::Null,
+            serde_json::Value::Bool(_) => InferredType::Boolean,
+            serde_json::Value::Number(n) if n.is_i64() => InferredType::Int64,
+            serde_json::Value::Number(_) => InferredType::Float64,
+            serde_json::Value::String(s) => Self::from_string(s.as_bytes()),
+            serde_json::Value::Array(_) | serde_json::Value::Object(_) => InferredType::Variant,
+        }
+    }
+
+    fn from_string(value: &[u8]) -> Self {
+        if is_date(value) {
+            InferredType::Date
+        } else if is_timestamp(value) {
+            InferredType::Timestamp
+        } else {
+            InferredType::String
+        }
+    }
+
+    // returns None if the two types have no common type other than String or Variant,
+    // the caller decides which one to fall back to.
+    pub fn merge(self, other: Self) -> Option<Self> {
+        use InferredType::*;
+        match (self, other) {
+            (Null, t) | (t, Null) => Some(t),
+            (a, b) if a == b => Some(a),
+            (Int64, Float64) | (Float64, Int64) => Some(Float64),
+            (Date, Timestamp) | (Timestamp, Date) => Some(Timestamp),
+            (String, Date | Timestamp) | (Date | Timestamp, String) => Some(String),
+            (Variant, _) | (_, Variant) => Some(Variant),
+            _ => None,
+        }
+    }
+
+    // all the inferred columns are nullable, since only a part of the file is sampled
+    pub fn to_table_type(self) -> TableDataType {
+        let ty = match self {
+            InferredType::Boolean => TableDataType::Boolean,
+            InferredType::Int64 => TableDataType::Number(NumberDataType::Int64),
+            InferredType::Float64 => TableDataType::Number(NumberDataType::Float64),
+            InferredType::Date => TableDataType::Date,
+            InferredType::Timestamp => TableDataType::Timestamp,
+            InferredType::Null | InferredType::String => TableDataType::String,
+            InferredType::Variant => TableDataType::Variant,
+        };
+        ty.wrap_nullable()
+    }
+}
+
+fn is_integer(value: &[u8]) -> bool {
+    let digits = match value {
+        [b'+' | b'-', rest @ ..] => rest,
+        _ => value,
+    };
+    !digits.is_empty()
+        && digits.iter().all(|b| b.is_ascii_digit())
+        && std::str::from_utf8(value)
+            .map(|s| s.parse::<i64>().is_ok())
+            .unwrap_or(false)
+}
+
+fn is_float(value: &[u8]) -> bool {
+    // `inf` and `nan` are not taken as numbers
+    value.iter().any(|b| b.is_ascii_digit())
+        && value
+            .iter()
+            .all(|b| b.is_ascii_digit() || matches!(b, b'+' | b'-' | b'.' | b'e' | b'E'))
+        && std::str::from_utf8(value)
+            .map(|s| s.parse::<f64>().is_ok())
+            .unwrap_or(false)
+}
+
+fn is_digits(value: &[u8]) -> bool {
+    value.iter().all(|b| b.is_ascii_digit())
+}
+
+fn parse_digits(value: &[u8]) -> u32 {
+    value.iter().fold(0, |acc, b| acc * 10 + (b - b'0') as u32)
+}
+
+// `YYYY-MM-DD`
+fn is_date(value: &[u8]) -> bool {
+    value.len() == 10
+        && value[4] == b'-'
+        && value[7] == b'-'
+        && is_digits(&value[0..4])
+        && is_digits(&value[5..7])
+        && is_digits(&value[8..10])
+        && (1..=12).contains(&parse_digits(&value[5..7]))
+        && (1..=31).contains(&parse_digits(&value[8..10]))
+}
+
+// `YYYY-MM-DD HH:MM:SS[.fraction][Z|+HH:MM|-HH:MM]`, `T` is also allowed as the separator
+fn is_timestamp(value: &[u8]) -> bool {
+    if value.len() < 19 || !is_date(&value[0..10]) || !matches!(value[10], b' ' | b'T') {
+        return false;
+    }
+    let time = &value[11..19];
+    if time[2] != b':'
+        || time[5] != b':'
+        || !is_digits(&time[0..2])
+        || !is_digits(&time[3..5])
+        || !is_digits(&time[6..8])
+        || parse_digits(&time[0..2]) > 23
+        || parse_digits(&time[3..5]) > 59
+        || parse_digits(&time[6..8]) > 59
+    {
+        return false;
+    }
+
+    let mut rest = &value[19..];
+    if let [b'.', fraction @ ..] = rest {
+        let n = fraction.iter().take_while(|b| b.is_ascii_digit()).count();
+        if n == 0 {
+            return false;
+        }
+        rest = &fraction[n..];
+    }
+    match rest {
+        [] | [b'Z'] => true,
+        [b'+' | b'-', offset @ ..] => {
+            let offset = offset
+                .iter()
+                .filter(|b| **b != b':')
+                .copied()
+                .collect::<Vec<_>>();
+            matches!(offset.len(), 2 | 4) && is_digits(&offset)
+        }
+        _ => false,
+    }
+}
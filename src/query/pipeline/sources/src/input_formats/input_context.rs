@@ -39,8 +39,11 @@ use common_settings::Settings;
 use dashmap::DashMap;
 use opendal::Operator;
 
+use crate::input_formats::impls::InputFormatArrowIpc;
+use crate::input_formats::impls::InputFormatAvro;
 use crate::input_formats::impls::InputFormatCSV;
 use crate::input_formats::impls::InputFormatNDJson;
+use crate::input_formats::impls::InputFormatOrc;
 use crate::input_formats::impls::InputFormatParquet;
 use crate::input_formats::impls::InputFormatTSV;
 use crate::input_formats::impls::InputFormatXML;
@@ -124,6 +127,10 @@ pub struct InputContext {
     pub on_error_mode: OnErrorMode,
     pub on_error_count: AtomicU64,
     pub on_error_map: Option<DashMap<String, HashMap<u16, InputError>>>,
+
+    // the files are queried by `SELECT ... FROM @stage`, the columns of text files
+    // are referred by positions and the number of fields in each row is not checked.
+    pub is_select: bool,
 }
 
 impl Debug for InputContext {
@@ -145,6 +152,9 @@ impl InputContext {
             StageFileFormatType::NdJson => Ok(Arc::new(InputFormatNDJson::create())),
             StageFileFormatType::Parquet => Ok(Arc::new(InputFormatParquet {})),
             StageFileFormatType::Xml => Ok(Arc::new(InputFormatXML::create())),
+            StageFileFormatType::Avro => Ok(Arc::new(InputFormatAvro::create())),
+            StageFileFormatType::Orc => Ok(Arc::new(InputFormatOrc {})),
+            StageFileFormatType::ArrowIpc => Ok(Arc::new(InputFormatArrowIpc {})),
             format => Err(ErrorCode::Internal(format!(
                 "Unsupported file format: {:?}",
                 format
@@ -160,6 +170,7 @@ impl InputContext {
         splits: Vec<Arc<SplitInfo>>,
         scan_progress: Arc<Progress>,
         block_compact_thresholds: BlockThresholds,
+        is_select: bool,
     ) -> Result<Self> {
         let on_error_mode = stage_info.copy_options.on_error.clone();
         let plan = Box::new(CopyIntoPlan { stage_info });
@@ -188,6 +199,7 @@ impl InputContext {
             on_error_mode,
             on_error_count: AtomicU64::new(0),
             on_error_map: Some(DashMap::new()),
+            is_select,
         })
     }
 
@@ -231,6 +243,7 @@ impl InputContext {
             on_error_mode: OnErrorMode::AbortNum(1),
             on_error_count: AtomicU64::new(0),
             on_error_map: None,
+            is_select: false,
         })
     }
 
@@ -271,6 +284,7 @@ impl InputContext {
             on_error_mode: OnErrorMode::AbortNum(1),
             on_error_count: AtomicU64::new(0),
             on_error_map: None,
+            is_select: false,
         })
     }
 
@@ -16,6 +16,7 @@ use std::sync::Arc;
 
 use common_exception::Result;
 use common_expression::TableSchemaRef;
+use common_meta_app::principal::FileFormatOptions;
 use common_meta_app::principal::StageInfo;
 use common_pipeline_core::Pipeline;
 use common_settings::Settings;
@@ -35,7 +36,12 @@ pub trait InputFormat: Send + Sync {
         settings: &Arc<Settings>,
     ) -> Result<Vec<Arc<SplitInfo>>>;
 
-    async fn infer_schema(&self, path: &str, op: &Operator) -> Result<TableSchemaRef>;
+    async fn infer_schema(
+        &self,
+        path: &str,
+        op: &Operator,
+        options: &FileFormatOptions,
+    ) -> Result<TableSchemaRef>;
 
     fn exec_copy(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()>;
 
@@ -27,8 +27,11 @@ use common_expression::Column;
 use common_expression::ColumnBuilder;
 use common_expression::DataBlock;
 use common_expression::TableSchemaRef;
+use common_formats::get_format_option_checker;
 use common_formats::FieldDecoder;
 use common_formats::FileFormatOptionsExt;
+use common_formats::RecordDelimiter;
+use common_meta_app::principal::FileFormatOptions;
 use common_meta_app::principal::StageFileFormatType;
 use common_meta_app::principal::StageInfo;
 use common_pipeline_core::Pipeline;
@@ -48,6 +51,9 @@ use crate::input_formats::InputError;
 use crate::input_formats::InputFormat;
 use crate::input_formats::SplitInfo;
 
+// the max bytes read from the beginning of a file to infer its schema
+const INFER_SCHEMA_SAMPLE_BYTES: usize = 1024 * 1024;
+
 pub trait AligningStateTextBased: Sync + Sized + Send {
     fn try_create(ctx: &Arc<InputContext>, split_info: &Arc<SplitInfo>) -> Result<Self>;
 
@@ -227,6 +233,17 @@ pub trait InputFormatTextBase: Sized + Send + Sync + 'static {
 
     fn create_field_decoder(options: &FileFormatOptionsExt) -> Arc<dyn FieldDecoder>;
 
+    // infer the schema from the rows sampled at the beginning of a file,
+    // the sample is ended with a complete row.
+    fn infer_schema_by_sample(
+        _sample: &[u8],
+        _options: &FileFormatOptions,
+    ) -> Result<TableSchemaRef> {
+        Err(ErrorCode::Unimplemented(
+            "infer_schema is not implemented for this format yet.",
+        ))
+    }
+
     fn deserialize(
         builder: &mut BlockBuilder<Self>,
         batch: RowBatch,
@@ -350,10 +367,38 @@ impl<T: InputFormatTextBase> InputFormat for T {
     }
 
     #[async_backtrace::framed]
-    async fn infer_schema(&self, _path: &str, _op: &Operator) -> Result<TableSchemaRef> {
-        Err(ErrorCode::Unimplemented(
-            "infer_schema is not implemented for this format yet.",
-        ))
+    async fn infer_schema(
+        &self,
+        path: &str,
+        op: &Operator,
+        options: &FileFormatOptions,
+    ) -> Result<TableSchemaRef> {
+        let mut options = options.clone();
+        get_format_option_checker(&options.format)?.check_options(&mut options)?;
+
+        let size = op.stat(path).await?.content_length() as usize;
+        let sample_size = size.min(INFER_SCHEMA_SAMPLE_BYTES);
+        let data = op.range_read(path, 0..sample_size as u64).await?;
+        let mut sample = match InputContext::get_compression_alg_copy(options.compression, path)? {
+            Some(alg) => decompress(&mut DecompressDecoder::new(alg), &data)?,
+            None => data,
+        };
+
+        // drop the last row which may be cut off by the sample
+        if sample_size < size {
+            let record_delimiter_end = if options.record_delimiter.is_empty() {
+                b'\n'
+            } else {
+                RecordDelimiter::try_from(options.record_delimiter.as_bytes())?.end()
+            };
+            let end = sample
+                .iter()
+                .rposition(|b| *b == record_delimiter_end)
+                .map(|p| p + 1)
+                .unwrap_or_default();
+            sample.truncate(end);
+        }
+        T::infer_schema_by_sample(&sample, &options)
     }
 
     fn exec_copy(&self, ctx: Arc<InputContext>, pipeline: &mut Pipeline) -> Result<()> {
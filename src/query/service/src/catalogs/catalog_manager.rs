@@ -24,9 +24,11 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_meta_app::schema::CatalogOption;
 use common_meta_app::schema::CreateCatalogReq;
+use common_meta_app::schema::DeltaCatalogOption;
 use common_meta_app::schema::DropCatalogReq;
 use common_meta_app::schema::IcebergCatalogOption;
 use common_storage::DataOperator;
+use common_storages_delta::DeltaCatalog;
 #[cfg(feature = "hive")]
 use common_storages_hive::HiveCatalog;
 use common_storages_iceberg::IcebergCatalog;
@@ -152,6 +154,20 @@ impl CatalogManagerHelper for CatalogManager {
                     data_operator,
                 )?);
 
+                let if_not_exists = req.if_not_exists;
+                self.insert_catalog(ctl_name, catalog, if_not_exists)
+            }
+            CatalogOption::Delta(opt) => {
+                let DeltaCatalogOption {
+                    storage_params: sp,
+                    flatten,
+                } = opt;
+
+                let data_operator = DataOperator::try_create(&sp).await?;
+                let ctl_name = &req.name_ident.catalog_name;
+                let catalog: Arc<dyn Catalog> =
+                    Arc::new(DeltaCatalog::try_create(ctl_name, flatten, data_operator)?);
+
                 let if_not_exists = req.if_not_exists;
                 self.insert_catalog(ctl_name, catalog, if_not_exists)
             }
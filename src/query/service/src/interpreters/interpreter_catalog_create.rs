@@ -48,8 +48,13 @@ impl Interpreter for CreateCatalogInterpreter {
     #[tracing::instrument(level = "debug", skip(self), fields(ctx.id = self.ctx.get_id().as_str()))]
     #[async_backtrace::framed]
     async fn execute2(&self) -> Result<PipelineBuildResult> {
-        if let CatalogOption::Iceberg(opt) = &self.plan.meta.catalog_option {
-            if !opt.storage_params.is_secure() && !GlobalConfig::instance().storage.allow_insecure {
+        let storage_params = match &self.plan.meta.catalog_option {
+            CatalogOption::Iceberg(opt) => Some(&opt.storage_params),
+            CatalogOption::Delta(opt) => Some(&opt.storage_params),
+            CatalogOption::Hive(_) => None,
+        };
+        if let Some(sp) = storage_params {
+            if !sp.is_secure() && !GlobalConfig::instance().storage.allow_insecure {
                 return Err(ErrorCode::CatalogNotSupported(
                     "Accessing insecure storage in not allowed by configuration",
                 ));
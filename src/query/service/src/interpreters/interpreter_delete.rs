@@ -20,6 +20,7 @@ use common_expression::DataSchemaRef;
 use common_functions::BUILTIN_FUNCTIONS;
 use common_sql::executor::cast_expr_to_non_null_boolean;
 
+use crate::interpreters::interpreter_merge_into::build_merge_rows_pipeline;
 use crate::interpreters::Interpreter;
 use crate::pipelines::PipelineBuildResult;
 use crate::sessions::QueryContext;
@@ -73,10 +74,8 @@ impl Interpreter for DeleteInterpreter {
             let col_indices = scalar.used_columns().into_iter().collect();
             (Some(filter), col_indices)
         } else {
-            if self.plan.input_expr.is_some() {
-                return Err(ErrorCode::Unimplemented(
-                    "Delete with subquery isn't supported",
-                ));
+            if let Some(input) = &self.plan.input {
+                return build_merge_rows_pipeline(&self.ctx, tbl, input, "delete from").await;
             }
             (None, vec![])
         };
@@ -233,6 +233,7 @@ impl InsertInterpreter {
                 pattern: None,
             },
             files_to_copy: None,
+            is_select: false,
         };
 
         let all_source_files = StageTable::list_files(&stage_table_info, None).await?;
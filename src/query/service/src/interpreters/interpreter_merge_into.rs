@@ -0,0 +1,107 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::table::Table;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::DataSchemaRef;
+use common_sql::executor::PhysicalPlanBuilder;
+use common_sql::plans::MergeIntoPlan;
+use common_sql::plans::Plan;
+
+use crate::interpreters::Interpreter;
+use crate::interpreters::InterpreterPtr;
+use crate::pipelines::PipelineBuildResult;
+use crate::schedulers::build_query_pipeline;
+use crate::sessions::QueryContext;
+
+/// interprets MergeIntoPlan
+pub struct MergeIntoInterpreter {
+    ctx: Arc<QueryContext>,
+    plan: MergeIntoPlan,
+}
+
+impl MergeIntoInterpreter {
+    pub fn try_create(ctx: Arc<QueryContext>, plan: MergeIntoPlan) -> Result<InterpreterPtr> {
+        Ok(Arc::new(MergeIntoInterpreter { ctx, plan }))
+    }
+}
+
+#[async_trait::async_trait]
+impl Interpreter for MergeIntoInterpreter {
+    fn name(&self) -> &str {
+        "MergeIntoInterpreter"
+    }
+
+    fn schema(&self) -> DataSchemaRef {
+        self.plan.schema()
+    }
+
+    #[tracing::instrument(level = "debug", name = "merge_into_interpreter_execute", skip(self), fields(ctx.id = self.ctx.get_id().as_str()))]
+    #[async_backtrace::framed]
+    async fn execute2(&self) -> Result<PipelineBuildResult> {
+        let plan = &self.plan;
+        let table = self
+            .ctx
+            .get_table(&plan.catalog, &plan.database, &plan.table)
+            .await?;
+
+        build_merge_rows_pipeline(&self.ctx, table, &plan.input, "merge into").await
+    }
+}
+
+/// Builds the pipeline which runs the query producing the rows to be merged,
+/// and applies the rows to the table by `Table::merge_into`.
+///
+/// `statement` names the statement in errors, e.g. `merge into`.
+#[async_backtrace::framed]
+pub(crate) async fn build_merge_rows_pipeline(
+    ctx: &Arc<QueryContext>,
+    table: Arc<dyn Table>,
+    input: &Plan,
+    statement: &str,
+) -> Result<PipelineBuildResult> {
+    // merged blocks are not clustered, the cluster statistics would be wrong.
+    // Like REPLACE INTO, tables with cluster keys are rejected until the merged
+    // rows are sorted by the cluster keys before being appended.
+    if table.get_table_info().meta.default_cluster_key_id.is_some() {
+        return Err(ErrorCode::StorageOther(format!(
+            "{statement} table with cluster key definition is not supported yet"
+        )));
+    }
+
+    let (physical_plan, result_columns) = match input {
+        Plan::Query {
+            s_expr,
+            metadata,
+            bind_context,
+            ..
+        } => {
+            let mut builder = PhysicalPlanBuilder::new(metadata.clone(), ctx.clone());
+            (builder.build(s_expr).await?, bind_context.columns.clone())
+        }
+        _ => unreachable!(),
+    };
+
+    let mut build_res =
+        build_query_pipeline(ctx, &result_columns, &physical_plan, false, false).await?;
+    table
+        .merge_into(ctx.clone(), &mut build_res.main_pipeline)
+        .await?;
+
+    Ok(build_res)
+}
@@ -28,6 +28,7 @@ use common_sql::ColumnBinding;
 use common_sql::ScalarExpr;
 use common_sql::Visibility;
 
+use crate::interpreters::interpreter_merge_into::build_merge_rows_pipeline;
 use crate::interpreters::Interpreter;
 use crate::pipelines::PipelineBuildResult;
 use crate::sessions::QueryContext;
@@ -67,6 +68,10 @@ impl Interpreter for UpdateInterpreter {
         let tbl_name = self.plan.table.as_str();
         let tbl = self.ctx.get_table(catalog_name, db_name, tbl_name).await?;
 
+        if let Some(input) = &self.plan.input {
+            return build_merge_rows_pipeline(&self.ctx, tbl, input, "update").await;
+        }
+
         let (filter, col_indices) = if let Some(scalar) = &self.plan.selection {
             let filter =
                 cast_expr_to_non_null_boolean(scalar.as_expr_with_col_name()?)?.as_remote_expr();
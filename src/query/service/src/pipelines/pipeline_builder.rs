@@ -12,6 +12,8 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
+use std::collections::VecDeque;
 use std::sync::Arc;
 
 use async_channel::Receiver;
@@ -33,9 +35,12 @@ use common_pipeline_core::pipe::Pipe;
 use common_pipeline_core::pipe::PipeItem;
 use common_pipeline_core::processors::port::InputPort;
 use common_pipeline_core::processors::processor::ProcessorPtr;
+use common_pipeline_core::processors::Processor;
+use common_pipeline_sinks::AsyncSinker;
 use common_pipeline_sinks::EmptySink;
 use common_pipeline_sinks::Sinker;
 use common_pipeline_sinks::UnionReceiveSink;
+use common_pipeline_sources::BlocksSource;
 use common_pipeline_transforms::processors::transforms::try_add_multi_sort_merge;
 use common_pipeline_transforms::processors::transforms::try_create_transform_sort_merge;
 use common_profile::ProfSpanSetRef;
@@ -45,6 +50,7 @@ use common_sql::executor::AggregateExpand;
 use common_sql::executor::AggregateFinal;
 use common_sql::executor::AggregateFunctionDesc;
 use common_sql::executor::AggregatePartial;
+use common_sql::executor::AsofJoin;
 use common_sql::executor::DistributedInsertSelect;
 use common_sql::executor::EvalScalar;
 use common_sql::executor::ExchangeSink;
@@ -55,6 +61,9 @@ use common_sql::executor::Limit;
 use common_sql::executor::PhysicalPlan;
 use common_sql::executor::Project;
 use common_sql::executor::ProjectSet;
+use common_sql::executor::RangeJoin;
+use common_sql::executor::RecursiveCteScan;
+use common_sql::executor::RecursiveUnion;
 use common_sql::executor::RuntimeFilterSource;
 use common_sql::executor::Sort;
 use common_sql::executor::TableScan;
@@ -65,6 +74,7 @@ use common_sql::ColumnBinding;
 use common_sql::IndexType;
 use common_storage::DataOperator;
 use common_storages_fuse::operations::FillInternalColumnProcessor;
+use parking_lot::Mutex;
 
 use super::processors::transforms::WindowFunctionInfo;
 use super::processors::ProfileWrapper;
@@ -72,22 +82,33 @@ use super::processors::TransformExpandGroupingSets;
 use crate::api::DefaultExchangeInjector;
 use crate::api::ExchangeInjector;
 use crate::pipelines::processors::transforms::build_partition_bucket;
+use crate::pipelines::processors::transforms::create_transform_sort_spill;
 use crate::pipelines::processors::transforms::AggregateInjector;
+use crate::pipelines::processors::transforms::AsofJoinState;
 use crate::pipelines::processors::transforms::FinalSingleStateAggregator;
 use crate::pipelines::processors::transforms::HashJoinDesc;
 use crate::pipelines::processors::transforms::PartialSingleStateAggregator;
+use crate::pipelines::processors::transforms::RangeJoinState;
 use crate::pipelines::processors::transforms::RightSemiAntiJoinCompactor;
 use crate::pipelines::processors::transforms::RuntimeFilterState;
+use crate::pipelines::processors::transforms::SinkAsofJoinRight;
+use crate::pipelines::processors::transforms::SinkRangeJoinRight;
+use crate::pipelines::processors::transforms::SortSpillParams;
 use crate::pipelines::processors::transforms::TransformAggregateSpillWriter;
+use crate::pipelines::processors::transforms::TransformAsofJoinLeft;
 use crate::pipelines::processors::transforms::TransformGroupBySpillWriter;
 use crate::pipelines::processors::transforms::TransformLeftJoin;
 use crate::pipelines::processors::transforms::TransformMarkJoin;
 use crate::pipelines::processors::transforms::TransformMergeBlock;
 use crate::pipelines::processors::transforms::TransformPartialAggregate;
 use crate::pipelines::processors::transforms::TransformPartialGroupBy;
+use crate::pipelines::processors::transforms::TransformRangeJoinLeft;
+use crate::pipelines::processors::transforms::TransformRecursiveUnion;
 use crate::pipelines::processors::transforms::TransformRightJoin;
 use crate::pipelines::processors::transforms::TransformRightSemiAntiJoin;
 use crate::pipelines::processors::transforms::TransformWindow;
+use crate::pipelines::processors::transforms::WindowSpillParams;
+use crate::pipelines::processors::transforms::WorkingTable;
 use crate::pipelines::processors::AggregatorParams;
 use crate::pipelines::processors::JoinHashTable;
 use crate::pipelines::processors::LeftJoinCompactor;
@@ -95,6 +116,7 @@ use crate::pipelines::processors::MarkJoinCompactor;
 use crate::pipelines::processors::RightJoinCompactor;
 use crate::pipelines::processors::SinkBuildHashTable;
 use crate::pipelines::processors::SinkRuntimeFilterSource;
+use crate::pipelines::processors::SinkSpillBuildHashTable;
 use crate::pipelines::processors::TransformCastSchema;
 use crate::pipelines::processors::TransformHashJoinProbe;
 use crate::pipelines::processors::TransformLimit;
@@ -116,6 +138,8 @@ pub struct PipelineBuilder {
     pub join_state: Option<Arc<JoinHashTable>>,
     // record the index of join build side pipeline in `pipelines`
     pub index: Option<usize>,
+    // Working tables of the recursive common table expressions being evaluated
+    pub working_tables: HashMap<String, WorkingTable>,
 
     enable_profiling: bool,
     prof_span_set: ProfSpanSetRef,
@@ -137,6 +161,7 @@ impl PipelineBuilder {
             prof_span_set,
             exchange_injector: DefaultExchangeInjector::create(),
             index: None,
+            working_tables: HashMap::new(),
         }
     }
 
@@ -172,9 +197,15 @@ impl PipelineBuilder {
             PhysicalPlan::Sort(sort) => self.build_sort(sort),
             PhysicalPlan::Limit(limit) => self.build_limit(limit),
             PhysicalPlan::HashJoin(join) => self.build_join(join),
+            PhysicalPlan::RangeJoin(range_join) => self.build_range_join(range_join),
+            PhysicalPlan::AsofJoin(asof_join) => self.build_asof_join(asof_join),
             PhysicalPlan::ExchangeSink(sink) => self.build_exchange_sink(sink),
             PhysicalPlan::ExchangeSource(source) => self.build_exchange_source(source),
             PhysicalPlan::UnionAll(union_all) => self.build_union_all(union_all),
+            PhysicalPlan::RecursiveUnion(recursive_union) => {
+                self.build_recursive_union(recursive_union)
+            }
+            PhysicalPlan::RecursiveCteScan(scan) => self.build_recursive_cte_scan(scan),
             PhysicalPlan::DistributedInsertSelect(insert_select) => {
                 self.build_distributed_insert_select(insert_select)
             }
@@ -204,6 +235,23 @@ impl PipelineBuilder {
         )
     }
 
+    // The build side is written to storage by an async sink if the hash join can spill.
+    fn create_build_sink(
+        input: Arc<InputPort>,
+        join_state: Arc<JoinHashTable>,
+    ) -> Result<Box<dyn Processor>> {
+        match join_state.spiller.clone() {
+            Some(spiller) => Ok(AsyncSinker::create(
+                input,
+                SinkSpillBuildHashTable::try_create(join_state, spiller)?,
+            )),
+            None => Ok(Sinker::<SinkBuildHashTable>::create(
+                input,
+                SinkBuildHashTable::try_create(join_state)?,
+            )),
+        }
+    }
+
     fn expand_build_side_pipeline(
         &mut self,
         build: &PhysicalPlan,
@@ -211,20 +259,18 @@ impl PipelineBuilder {
         join_state: Arc<JoinHashTable>,
     ) -> Result<()> {
         let build_side_context = QueryContext::create_from(self.ctx.clone());
-        let build_side_builder = PipelineBuilder::create(
+        let mut build_side_builder = PipelineBuilder::create(
             build_side_context,
             self.enable_profiling,
             self.prof_span_set.clone(),
         );
+        build_side_builder.working_tables = self.working_tables.clone();
         let mut build_res = build_side_builder.finalize(build)?;
 
         assert!(build_res.main_pipeline.is_pulling_pipeline()?);
 
         let create_sink_processor = |input| {
-            let transform = Sinker::<SinkBuildHashTable>::create(
-                input,
-                SinkBuildHashTable::try_create(join_state.clone())?,
-            );
+            let transform = Self::create_build_sink(input, join_state.clone())?;
 
             if self.enable_profiling {
                 Ok(ProcessorPtr::create(ProfileWrapper::create(
@@ -250,6 +296,106 @@ impl PipelineBuilder {
         Ok(())
     }
 
+    fn build_range_join(&mut self, range_join: &RangeJoin) -> Result<()> {
+        let state = RangeJoinState::try_create(self.ctx.clone(), range_join)?;
+
+        // The right side is collected by a separated pipeline.
+        let right_side_context = QueryContext::create_from(self.ctx.clone());
+        let mut right_side_builder = PipelineBuilder::create(
+            right_side_context,
+            self.enable_profiling,
+            self.prof_span_set.clone(),
+        );
+        right_side_builder.working_tables = self.working_tables.clone();
+        let mut right_res = right_side_builder.finalize(&range_join.right)?;
+
+        assert!(right_res.main_pipeline.is_pulling_pipeline()?);
+        right_res.main_pipeline.add_sink(|input| {
+            let transform = Sinker::<SinkRangeJoinRight>::create(
+                input,
+                SinkRangeJoinRight::create(state.clone()),
+            );
+
+            if self.enable_profiling {
+                Ok(ProcessorPtr::create(ProfileWrapper::create(
+                    transform,
+                    range_join.plan_id,
+                    self.prof_span_set.clone(),
+                )))
+            } else {
+                Ok(ProcessorPtr::create(transform))
+            }
+        })?;
+        self.pipelines.push(right_res.main_pipeline);
+        self.pipelines
+            .extend(right_res.sources_pipelines.into_iter());
+
+        self.build_pipeline(&range_join.left)?;
+        self.main_pipeline.add_transform(|input, output| {
+            let transform = TransformRangeJoinLeft::create(input, output, state.clone());
+
+            if self.enable_profiling {
+                Ok(ProcessorPtr::create(ProfileWrapper::create(
+                    transform,
+                    range_join.plan_id,
+                    self.prof_span_set.clone(),
+                )))
+            } else {
+                Ok(ProcessorPtr::create(transform))
+            }
+        })
+    }
+
+    fn build_asof_join(&mut self, asof_join: &AsofJoin) -> Result<()> {
+        let state = AsofJoinState::try_create(self.ctx.clone(), asof_join)?;
+
+        // The right side is collected and sorted by a separated pipeline.
+        let right_side_context = QueryContext::create_from(self.ctx.clone());
+        let mut right_side_builder = PipelineBuilder::create(
+            right_side_context,
+            self.enable_profiling,
+            self.prof_span_set.clone(),
+        );
+        right_side_builder.working_tables = self.working_tables.clone();
+        let mut right_res = right_side_builder.finalize(&asof_join.right)?;
+
+        assert!(right_res.main_pipeline.is_pulling_pipeline()?);
+        right_res.main_pipeline.add_sink(|input| {
+            let transform = Sinker::<SinkAsofJoinRight>::create(
+                input,
+                SinkAsofJoinRight::create(state.clone()),
+            );
+
+            if self.enable_profiling {
+                Ok(ProcessorPtr::create(ProfileWrapper::create(
+                    transform,
+                    asof_join.plan_id,
+                    self.prof_span_set.clone(),
+                )))
+            } else {
+                Ok(ProcessorPtr::create(transform))
+            }
+        })?;
+        self.pipelines.push(right_res.main_pipeline);
+        self.pipelines
+            .extend(right_res.sources_pipelines.into_iter());
+
+        self.build_pipeline(&asof_join.left)?;
+        self.main_pipeline.add_transform(|input, output| {
+            let transform = TransformAsofJoinLeft::create(input, output, state.clone());
+
+            if self.enable_profiling {
+                Ok(ProcessorPtr::create(ProfileWrapper::create(
+                    transform,
+                    asof_join.plan_id,
+                    self.prof_span_set.clone(),
+                )))
+            } else {
+                Ok(ProcessorPtr::create(transform))
+            }
+        })
+    }
+
     pub fn render_result_set(
         func_ctx: &FunctionContext,
         input_schema: DataSchemaRef,
@@ -731,7 +877,11 @@ impl PipelineBuilder {
             .iter()
             .map(|o| {
                 let offset = input_schema.index_of(&o.order_by.to_string())?;
-                Ok(offset)
+                Ok(SortColumnDescription {
+                    offset,
+                    asc: o.asc,
+                    nulls_first: o.nulls_first,
+                })
             })
             .collect::<Result<Vec<_>>>()?;
 
@@ -747,21 +897,28 @@ impl PipelineBuilder {
                 })
             }
 
-            for (order_desc, offset) in window.order_by.iter().zip(order_by.iter()) {
-                sort_desc.push(SortColumnDescription {
-                    offset: *offset,
-                    asc: order_desc.asc,
-                    nulls_first: order_desc.nulls_first,
-                })
-            }
+            sort_desc.extend(order_by.iter().cloned());
 
             self.build_sort_pipeline(input_schema.clone(), sort_desc, window.plan_id, None)?;
         }
         // `TransformWindow` is a pipeline breaker.
         self.main_pipeline.resize(1)?;
         let func = WindowFunctionInfo::try_create(&window.func, &input_schema)?;
-        // Window
+        let spilling_threshold = self
+            .ctx
+            .get_settings()
+            .get_spilling_bytes_threshold_per_proc()?;
+        let location_prefix = format!("_window_spill/{}", self.ctx.get_tenant());
+        // Window, the blocks of large partitions are spilled to storage if they exceed the threshold.
         self.main_pipeline.add_transform(|input, output| {
+            let spill_params = match spilling_threshold {
+                0 => None,
+                _ => Some(WindowSpillParams {
+                    operator: DataOperator::instance().operator(),
+                    location_prefix: location_prefix.clone(),
+                    threshold: spilling_threshold,
+                }),
+            };
             let transform = TransformWindow::try_create(
                 input,
                 output,
@@ -769,6 +926,7 @@ impl PipelineBuilder {
                 partition_by.clone(),
                 order_by.clone(),
                 window.window_frame.clone(),
+                spill_params,
             )?;
             Ok(ProcessorPtr::create(transform))
         })?;
@@ -806,6 +964,11 @@ impl PipelineBuilder {
     ) -> Result<()> {
         let block_size = self.ctx.get_settings().get_max_block_size()? as usize;
         let max_threads = self.ctx.get_settings().get_max_threads()? as usize;
+        let spilling_threshold = self
+            .ctx
+            .get_settings()
+            .get_spilling_bytes_threshold_per_proc()?;
+        let location_prefix = format!("_sort_spill/{}", self.ctx.get_tenant());
 
         // TODO(Winter): the query will hang in MultiSortMergeProcessor when max_threads == 1 and output_len != 1
         if self.main_pipeline.output_len() == 1 || max_threads == 1 {
@@ -827,16 +990,31 @@ impl PipelineBuilder {
             }
         })?;
 
-        // Merge
+        // Merge, the sorted runs are spilled to storage if the input exceeds the threshold.
         self.main_pipeline.add_transform(|input, output| {
-            let transform = try_create_transform_sort_merge(
-                input,
-                output,
-                input_schema.clone(),
-                block_size,
-                limit,
-                sort_desc.clone(),
-            )?;
+            let transform = match spilling_threshold {
+                0 => try_create_transform_sort_merge(
+                    input,
+                    output,
+                    input_schema.clone(),
+                    block_size,
+                    limit,
+                    sort_desc.clone(),
+                )?,
+                _ => create_transform_sort_spill(
+                    input,
+                    output,
+                    input_schema.clone(),
+                    block_size,
+                    limit,
+                    sort_desc.clone(),
+                    SortSpillParams {
+                        operator: DataOperator::instance().operator(),
+                        location_prefix: location_prefix.clone(),
+                        threshold: spilling_threshold,
+                    },
+                )?,
+            };
 
             if self.enable_profiling {
                 Ok(ProcessorPtr::create(ProfileWrapper::create(
@@ -1019,8 +1197,9 @@ impl PipelineBuilder {
         union_plan: &UnionAll,
     ) -> Result<Receiver<DataBlock>> {
         let union_ctx = QueryContext::create_from(self.ctx.clone());
-        let pipeline_builder =
+        let mut pipeline_builder =
             PipelineBuilder::create(union_ctx, self.enable_profiling, self.prof_span_set.clone());
+        pipeline_builder.working_tables = self.working_tables.clone();
         let mut build_res = pipeline_builder.finalize(input)?;
 
         assert!(build_res.main_pipeline.is_pulling_pipeline()?);
@@ -1074,6 +1253,48 @@ impl PipelineBuilder {
         Ok(())
     }
 
+    pub fn build_recursive_union(&mut self, recursive_union: &RecursiveUnion) -> Result<()> {
+        let mut working_tables = self.working_tables.clone();
+        working_tables.insert(
+            recursive_union.cte_name.clone(),
+            Arc::new(Mutex::new(vec![])),
+        );
+
+        // The members are executed by the source one iteration after another.
+        self.main_pipeline.add_source(
+            |output| {
+                TransformRecursiveUnion::try_create(
+                    self.ctx.clone(),
+                    output,
+                    recursive_union.clone(),
+                    working_tables.clone(),
+                )
+            },
+            1,
+        )
+    }
+
+    fn build_recursive_cte_scan(&mut self, scan: &RecursiveCteScan) -> Result<()> {
+        let working_table = self.working_tables.get(&scan.cte_name).ok_or_else(|| {
+            ErrorCode::Internal(format!(
+                "Working table of recursive cte '{}' is not found",
+                scan.cte_name
+            ))
+        })?;
+        let blocks = Arc::new(Mutex::new(
+            working_table
+                .lock()
+                .iter()
+                .cloned()
+                .collect::<VecDeque<_>>(),
+        ));
+
+        self.main_pipeline.add_source(
+            |output| BlocksSource::create(self.ctx.clone(), output, blocks.clone()),
+            1,
+        )
+    }
+
     pub fn build_distributed_insert_select(
         &mut self,
         insert_select: &DistributedInsertSelect,
@@ -1167,10 +1388,10 @@ impl PipelineBuilder {
         for _ in 0..output_size / 2 {
             let input = InputPort::create();
             items.push(PipeItem::create(
-                ProcessorPtr::create(Sinker::<SinkBuildHashTable>::create(
+                ProcessorPtr::create(Self::create_build_sink(
                     input.clone(),
-                    SinkBuildHashTable::try_create(self.join_state.as_ref().unwrap().clone())?,
-                )),
+                    self.join_state.as_ref().unwrap().clone(),
+                )?),
                 vec![input],
                 vec![],
             ));
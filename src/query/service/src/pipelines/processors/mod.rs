@@ -28,6 +28,7 @@ pub use transforms::RightJoinCompactor;
 pub use transforms::SerializerHashTable;
 pub use transforms::SinkBuildHashTable;
 pub use transforms::SinkRuntimeFilterSource;
+pub use transforms::SinkSpillBuildHashTable;
 pub use transforms::SortMergeCompactor;
 pub use transforms::TransformBlockCompact;
 pub use transforms::TransformCastSchema;
@@ -0,0 +1,348 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::cmp::Ordering;
+use std::sync::atomic::AtomicBool;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use common_arrow::arrow::array::ord::build_compare;
+use common_arrow::arrow::array::ord::DynComparator;
+use common_arrow::arrow::bitmap::Bitmap;
+use common_base::base::tokio::sync::Notify;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::BlockEntry;
+use common_expression::Column;
+use common_expression::DataBlock;
+use common_expression::DataSchemaRef;
+use common_expression::Evaluator;
+use common_expression::Expr;
+use common_expression::Scalar;
+use common_expression::Value;
+use common_functions::BUILTIN_FUNCTIONS;
+use common_sql::executor::AsofJoin;
+use parking_lot::RwLock;
+
+use crate::sessions::QueryContext;
+
+/// The right side of asof join.
+struct AsofJoinRight {
+    // All of the right rows with nullable columns, followed by a NULL row for the unmatched left rows.
+    block: DataBlock,
+    // The equi keys followed by the match key.
+    keys: Vec<Column>,
+    // The rows with non-NULL keys, sorted by the keys.
+    rows: Vec<u32>,
+}
+
+pub struct AsofJoinState {
+    ctx: Arc<QueryContext>,
+    // The equi keys followed by the match key.
+    left_keys: Vec<Expr>,
+    right_keys: Vec<Expr>,
+    operator: String,
+    right_schema: DataSchemaRef,
+
+    right_blocks: RwLock<Vec<DataBlock>>,
+    right_side: RwLock<Option<Arc<AsofJoinRight>>>,
+
+    ref_count: Mutex<usize>,
+    is_finished: Mutex<bool>,
+    finished_notify: Arc<Notify>,
+    interrupt: AtomicBool,
+}
+
+impl AsofJoinState {
+    pub fn try_create(ctx: Arc<QueryContext>, join: &AsofJoin) -> Result<Arc<Self>> {
+        let left_keys = join
+            .left_keys
+            .iter()
+            .chain(std::iter::once(&join.match_condition.left_expr))
+            .map(|expr| expr.as_expr(&BUILTIN_FUNCTIONS))
+            .collect();
+        let right_keys = join
+            .right_keys
+            .iter()
+            .chain(std::iter::once(&join.match_condition.right_expr))
+            .map(|expr| expr.as_expr(&BUILTIN_FUNCTIONS))
+            .collect();
+
+        Ok(Arc::new(AsofJoinState {
+            ctx,
+            left_keys,
+            right_keys,
+            operator: join.match_condition.operator.clone(),
+            right_schema: join.right.output_schema()?,
+            right_blocks: RwLock::new(vec![]),
+            right_side: RwLock::new(None),
+            ref_count: Mutex::new(0),
+            is_finished: Mutex::new(false),
+            finished_notify: Arc::new(Notify::new()),
+            interrupt: AtomicBool::new(false),
+        }))
+    }
+
+    pub fn attach(&self) {
+        let mut count = self.ref_count.lock().unwrap();
+        *count += 1;
+    }
+
+    /// Detach a right side sink, the right side is sorted once all of the sinks are detached.
+    pub fn detach(&self) -> Result<()> {
+        let mut count = self.ref_count.lock().unwrap();
+        *count -= 1;
+        if *count == 0 {
+            let right_blocks = std::mem::take(&mut *self.right_blocks.write());
+            let right_side = self.create_right_side(&right_blocks)?;
+            *self.right_side.write() = Some(Arc::new(right_side));
+
+            let mut is_finished = self.is_finished.lock().unwrap();
+            *is_finished = true;
+            self.finished_notify.notify_waiters();
+        }
+        Ok(())
+    }
+
+    pub fn interrupt(&self) {
+        self.interrupt
+            .store(true, std::sync::atomic::Ordering::Release);
+    }
+
+    pub fn sink_right(&self, block: DataBlock) -> Result<()> {
+        if !block.is_empty() {
+            self.right_blocks.write().push(block.convert_to_full());
+        }
+        Ok(())
+    }
+
+    #[async_backtrace::framed]
+    pub async fn wait_finish(&self) -> Result<()> {
+        let notified = {
+            let finished_guard = self.is_finished.lock().unwrap();
+
+            match *finished_guard {
+                true => None,
+                false => Some(self.finished_notify.notified()),
+            }
+        };
+
+        if let Some(notified) = notified {
+            notified.await;
+        }
+
+        Ok(())
+    }
+
+    fn create_right_side(&self, right_blocks: &[DataBlock]) -> Result<AsofJoinRight> {
+        let null_row = DataBlock::new(
+            self.right_schema
+                .fields()
+                .iter()
+                .map(|field| BlockEntry {
+                    data_type: field.data_type().wrap_nullable(),
+                    value: Value::Scalar(Scalar::Null),
+                })
+                .collect(),
+            1,
+        );
+        if right_blocks.is_empty() {
+            return Ok(AsofJoinRight {
+                block: null_row,
+                keys: vec![],
+                rows: vec![],
+            });
+        }
+
+        // The right columns are nullable in the output, the same as left outer join.
+        let block = DataBlock::concat(right_blocks)?;
+        let num_rows = block.num_rows();
+        let block = DataBlock::new(
+            block
+                .columns()
+                .iter()
+                .map(|entry| BlockEntry {
+                    data_type: entry.data_type.wrap_nullable(),
+                    value: entry.value.clone().wrap_nullable(),
+                })
+                .collect(),
+            num_rows,
+        );
+
+        let (keys, rows) = self.sort_by_keys(&block, &self.right_keys)?;
+        Ok(AsofJoinRight {
+            block: DataBlock::concat(&[block, null_row])?,
+            keys,
+            rows,
+        })
+    }
+
+    /// Evaluate the keys of the block, returns the keys without validity and the rows
+    /// with non-NULL keys sorted by the keys. Rows with NULL keys never match.
+    fn sort_by_keys(&self, block: &DataBlock, keys: &[Expr]) -> Result<(Vec<Column>, Vec<u32>)> {
+        let func_ctx = self.ctx.get_function_context()?;
+        let evaluator = Evaluator::new(block, func_ctx, &BUILTIN_FUNCTIONS);
+
+        let mut validity: Option<Bitmap> = None;
+        let mut columns = Vec::with_capacity(keys.len());
+        for key in keys {
+            let column = evaluator
+                .run(key)?
+                .convert_to_full_column(key.data_type(), block.num_rows());
+            match column {
+                Column::Nullable(column) => {
+                    validity = match validity {
+                        None => Some(column.validity.clone()),
+                        Some(validity) => Some(&validity & &column.validity),
+                    };
+                    columns.push(column.column);
+                }
+                column => columns.push(column),
+            }
+        }
+
+        let mut rows = match validity {
+            Some(validity) => (0..block.num_rows() as u32)
+                .filter(|row| validity.get_bit(*row as usize))
+                .collect::<Vec<_>>(),
+            None => (0..block.num_rows() as u32).collect::<Vec<_>>(),
+        };
+        let comparator = RowComparator::try_create(&columns, &columns)?;
+        rows.sort_unstable_by(|a, b| comparator.compare(*a, *b));
+        Ok((columns, rows))
+    }
+
+    /// Join a left block with the right side. Both sides are sorted by the keys and merged,
+    /// for each left row the nearest right row with equal equi keys that satisfies the match
+    /// condition is picked. The left rows without a match are joined with a NULL row.
+    pub fn join(&self, block: DataBlock) -> Result<DataBlock> {
+        if self.interrupt.load(std::sync::atomic::Ordering::Relaxed) {
+            return Err(ErrorCode::AbortedQuery(
+                "Aborted query, because the server is shutting down or the query was killed.",
+            ));
+        }
+
+        let right = match self.right_side.read().clone() {
+            Some(right) => right,
+            None => {
+                return Err(ErrorCode::Internal(
+                    "Right side of asof join is not finished",
+                ));
+            }
+        };
+        let null_row = (right.block.num_rows() - 1) as u32;
+        let mut indices = vec![null_row; block.num_rows()];
+
+        if !right.rows.is_empty() && !block.is_empty() {
+            let (left_keys, left_rows) = self.sort_by_keys(&block, &self.left_keys)?;
+            // Compare a right row with a left row by the equi keys and the match key.
+            let comparator = RowComparator::try_create(&right.keys, &left_keys)?;
+            let compare = |right_row: u32, left_row: u32| comparator.compare(right_row, left_row);
+            let equi_keys = left_keys.len() - 1;
+            let equal_keys = |right_row: u32, left_row: u32| {
+                comparator.compare_prefix(equi_keys, right_row, left_row) == Ordering::Equal
+            };
+
+            match self.operator.as_str() {
+                // `left >= right` or `left > right`: the nearest right row is the last one
+                // before the left row, merge both sides in ascending order.
+                "gte" | "gt" => {
+                    let strict = self.operator == "gt";
+                    let mut cursor = 0;
+                    for left_row in left_rows {
+                        while cursor < right.rows.len() {
+                            let ordering = compare(right.rows[cursor], left_row);
+                            if ordering == Ordering::Greater
+                                || (strict && ordering == Ordering::Equal)
+                            {
+                                break;
+                            }
+                            cursor += 1;
+                        }
+                        if cursor > 0 && equal_keys(right.rows[cursor - 1], left_row) {
+                            indices[left_row as usize] = right.rows[cursor - 1];
+                        }
+                    }
+                }
+                // `left <= right` or `left < right`: the nearest right row is the first one
+                // after the left row, merge both sides in descending order.
+                _ => {
+                    let strict = self.operator == "lt";
+                    let mut cursor = right.rows.len();
+                    for left_row in left_rows.into_iter().rev() {
+                        while cursor > 0 {
+                            let ordering = compare(right.rows[cursor - 1], left_row);
+                            if ordering == Ordering::Less || (strict && ordering == Ordering::Equal)
+                            {
+                                break;
+                            }
+                            cursor -= 1;
+                        }
+                        if cursor < right.rows.len() && equal_keys(right.rows[cursor], left_row) {
+                            indices[left_row as usize] = right.rows[cursor];
+                        }
+                    }
+                }
+            }
+        }
+
+        let mut block = block;
+        for column in right.block.take(&indices)?.columns() {
+            block.add_column(column.clone());
+        }
+        Ok(block)
+    }
+}
+
+/// Compare rows of two lists of key columns column by column,
+/// the columns are compared directly without building scalars.
+struct RowComparator {
+    comparators: Vec<DynComparator>,
+}
+
+impl RowComparator {
+    fn try_create(left: &[Column], right: &[Column]) -> Result<Self> {
+        let comparators = left
+            .iter()
+            .zip(right.iter())
+            .map(|(left, right)| {
+                build_compare(left.as_arrow().as_ref(), right.as_arrow().as_ref()).map_err(|e| {
+                    ErrorCode::Unimplemented(format!(
+                        "Unsupported keys of asof join, {:?} and {:?}: {e}",
+                        left.data_type(),
+                        right.data_type()
+                    ))
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(RowComparator { comparators })
+    }
+
+    /// Compare the row of the left columns with the row of the right columns.
+    fn compare(&self, left_row: u32, right_row: u32) -> Ordering {
+        self.compare_prefix(self.comparators.len(), left_row, right_row)
+    }
+
+    /// Compare the rows by the first `n` columns.
+    fn compare_prefix(&self, n: usize, left_row: u32, right_row: u32) -> Ordering {
+        for comparator in &self.comparators[..n] {
+            let ordering = comparator(left_row as usize, right_row as usize);
+            if ordering != Ordering::Equal {
+                return ordering;
+            }
+        }
+        Ordering::Equal
+    }
+}
@@ -12,9 +12,12 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::sync::Arc;
+
 use common_exception::Result;
 use common_expression::DataBlock;
 
+use super::HashJoinSpiller;
 use super::ProbeState;
 use crate::pipelines::processors::transforms::hash_join::desc::JoinState;
 
@@ -58,4 +61,16 @@ pub trait HashJoinState: Send + Sync {
 
     /// Get left join results
     fn left_join_blocks(&self, blocks: &[DataBlock]) -> Result<Vec<DataBlock>>;
+
+    /// Get the spiller if the build side is allowed to spill to storage
+    fn spiller(&self) -> Option<Arc<HashJoinSpiller>>;
+
+    /// Scatter the build block into the partitions of the spiller at the re-partitioning level
+    fn partition_build_block(&self, input: &DataBlock, level: u32) -> Result<Vec<DataBlock>>;
+
+    /// Scatter the probe block into the partitions of the spiller at the re-partitioning level
+    fn partition_probe_block(&self, input: &DataBlock, level: u32) -> Result<Vec<DataBlock>>;
+
+    /// Create a standalone state to join a restored spilled partition
+    fn create_partition_state(&self) -> Result<Arc<dyn HashJoinState>>;
 }
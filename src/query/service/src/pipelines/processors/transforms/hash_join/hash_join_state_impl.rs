@@ -14,6 +14,7 @@
 
 use std::borrow::BorrowMut;
 use std::sync::atomic::Ordering;
+use std::sync::Arc;
 
 use common_arrow::arrow::bitmap::Bitmap;
 use common_arrow::arrow::bitmap::MutableBitmap;
@@ -23,6 +24,7 @@ use common_expression::DataBlock;
 use common_expression::HashMethod;
 use common_hashtable::HashtableLike;
 
+use super::HashJoinSpiller;
 use super::ProbeState;
 use crate::pipelines::processors::transforms::hash_join::desc::JoinState;
 use crate::pipelines::processors::transforms::hash_join::desc::MarkerKind;
@@ -36,6 +38,12 @@ use crate::sql::planner::plans::JoinType;
 #[async_trait::async_trait]
 impl HashJoinState for JoinHashTable {
     fn build(&self, input: DataBlock) -> Result<()> {
+        if let Some(spiller) = &self.spiller {
+            let partitions =
+                self.partition_block(spiller, &input, &self.hash_join_desc.build_keys, 0)?;
+            return spiller.add_build_blocks(partitions);
+        }
+
         let data_block_size_limit = self.ctx.get_settings().get_max_block_size()? * 16;
         let mut buffer = self.row_space.buffer.write().unwrap();
         buffer.push(input);
@@ -64,6 +72,9 @@ impl HashJoinState for JoinHashTable {
             | JoinType::Right
             | JoinType::Full => self.probe_join(input, probe_state),
             JoinType::Cross => self.probe_cross_join(input, probe_state),
+            JoinType::Asof => Err(ErrorCode::Internal(
+                "Asof join should be executed by AsofJoin processor",
+            )),
         }
     }
 
@@ -154,6 +165,15 @@ impl HashJoinState for JoinHashTable {
             }};
         }
 
+        if let Some(spiller) = &self.spiller {
+            // Only the partitions kept in memory are inserted into the hash table,
+            // the spilled partitions will be restored after probing.
+            let blocks = spiller.finish_build();
+            if !blocks.is_empty() {
+                self.add_build_block(DataBlock::concat(&blocks)?)?;
+            }
+        }
+
         {
             let buffer = self.row_space.buffer.write().unwrap();
             if !buffer.is_empty() {
@@ -426,6 +446,33 @@ impl HashJoinState for JoinHashTable {
         input_blocks.push(rest_block);
         Ok(input_blocks)
     }
+
+    fn spiller(&self) -> Option<Arc<HashJoinSpiller>> {
+        self.spiller.clone()
+    }
+
+    fn partition_build_block(&self, input: &DataBlock, level: u32) -> Result<Vec<DataBlock>> {
+        match &self.spiller {
+            Some(spiller) => {
+                self.partition_block(spiller, input, &self.hash_join_desc.build_keys, level)
+            }
+            None => Ok(vec![input.clone()]),
+        }
+    }
+
+    fn partition_probe_block(&self, input: &DataBlock, level: u32) -> Result<Vec<DataBlock>> {
+        match &self.spiller {
+            Some(spiller) => {
+                self.partition_block(spiller, input, &self.hash_join_desc.probe_keys, level)
+            }
+            None => Ok(vec![input.clone()]),
+        }
+    }
+
+    fn create_partition_state(&self) -> Result<Arc<dyn HashJoinState>> {
+        let partition_state: Arc<dyn HashJoinState> = self.create_partition_hash_table()?;
+        Ok(partition_state)
+    }
 }
 
 impl JoinHashTable {
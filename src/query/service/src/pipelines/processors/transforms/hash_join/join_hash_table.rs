@@ -19,12 +19,15 @@ use std::sync::Mutex;
 use common_arrow::arrow::bitmap::Bitmap;
 use common_arrow::arrow::bitmap::MutableBitmap;
 use common_base::base::tokio::sync::Notify;
+use common_base::base::GlobalUniqName;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_expression::arrow::and_validities;
 use common_expression::with_hash_method;
 use common_expression::DataBlock;
 use common_expression::DataSchemaRef;
 use common_expression::Evaluator;
+use common_expression::Expr;
 use common_expression::HashMethod;
 use common_expression::HashMethodFixedKeys;
 use common_expression::HashMethodKind;
@@ -37,6 +40,7 @@ use common_hashtable::HashtableKeyable;
 use common_hashtable::ShortStringHashMap;
 use common_hashtable::StringHashMap;
 use common_sql::plans::JoinType;
+use common_storage::DataOperator;
 use ethnum::U256;
 use parking_lot::RwLock;
 
@@ -44,6 +48,7 @@ use super::ProbeState;
 use crate::pipelines::processors::transforms::hash_join::desc::HashJoinDesc;
 use crate::pipelines::processors::transforms::hash_join::row::RowPtr;
 use crate::pipelines::processors::transforms::hash_join::row::RowSpace;
+use crate::pipelines::processors::transforms::hash_join::spill::HashJoinSpiller;
 use crate::pipelines::processors::transforms::hash_join::util::build_schema_wrap_nullable;
 use crate::pipelines::processors::transforms::hash_join::util::probe_schema_wrap_nullable;
 use crate::sessions::QueryContext;
@@ -88,6 +93,8 @@ pub struct JoinHashTable {
     pub(crate) probe_schema: DataSchemaRef,
     pub(crate) interrupt: Arc<AtomicBool>,
     pub(crate) finished_notify: Arc<Notify>,
+    /// Spill the build side to storage if it exceeds `join_spilling_threshold`
+    pub(crate) spiller: Option<Arc<HashJoinSpiller>>,
 }
 
 impl JoinHashTable {
@@ -103,88 +110,126 @@ impl JoinHashTable {
             .map(|expr| expr.as_expr(&BUILTIN_FUNCTIONS).data_type().clone())
             .collect::<Vec<_>>();
         let method = DataBlock::choose_hash_method_with_types(&hash_key_types)?;
-        Ok(match method {
-            HashMethodKind::Serializer(_) => Arc::new(JoinHashTable::try_create(
-                ctx,
-                HashTable::Serializer(SerializerHashTable {
-                    hash_table: StringHashMap::<[u8], Vec<RowPtr>>::new(),
-                    hash_method: HashMethodSerializer::default(),
-                }),
-                build_schema,
-                probe_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::SingleString(_) => Arc::new(JoinHashTable::try_create(
-                ctx,
-                HashTable::SingleString(SingleStringHashTable {
-                    hash_table: ShortStringHashMap::<[u8], Vec<RowPtr>>::new(),
-                    hash_method: HashMethodSingleString::default(),
-                }),
-                build_schema,
-                probe_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU8(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
-                HashTable::KeysU8(FixedKeyHashTable {
-                    hash_table: HashMap::<u8, Vec<RowPtr>>::new(),
-                    hash_method,
-                }),
-                build_schema,
-                probe_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU16(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
-                HashTable::KeysU16(FixedKeyHashTable {
-                    hash_table: HashMap::<u16, Vec<RowPtr>>::new(),
-                    hash_method,
-                }),
-                build_schema,
-                probe_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU32(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
-                HashTable::KeysU32(FixedKeyHashTable {
-                    hash_table: HashMap::<u32, Vec<RowPtr>>::new(),
-                    hash_method,
-                }),
-                build_schema,
-                probe_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU64(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
-                HashTable::KeysU64(FixedKeyHashTable {
-                    hash_table: HashMap::<u64, Vec<RowPtr>>::new(),
-                    hash_method,
-                }),
-                build_schema,
-                probe_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU128(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
-                HashTable::KeysU128(FixedKeyHashTable {
-                    hash_table: HashMap::<u128, Vec<RowPtr>>::new(),
-                    hash_method,
-                }),
-                build_schema,
-                probe_schema,
-                hash_join_desc,
-            )?),
-            HashMethodKind::KeysU256(hash_method) => Arc::new(JoinHashTable::try_create(
-                ctx,
-                HashTable::KeysU256(FixedKeyHashTable {
-                    hash_table: HashMap::<U256, Vec<RowPtr>>::new(),
-                    hash_method,
-                }),
-                build_schema,
-                probe_schema,
-                hash_join_desc,
-            )?),
-        })
+        let spiller = Self::create_spiller(&ctx, &hash_join_desc, &method)?;
+        let mut join_state = JoinHashTable::try_create(
+            ctx,
+            Self::create_hash_table(method),
+            build_schema,
+            probe_schema,
+            hash_join_desc,
+        )?;
+        join_state.spiller = spiller;
+        Ok(Arc::new(join_state))
+    }
+
+    fn create_hash_table(method: HashMethodKind) -> HashTable {
+        match method {
+            HashMethodKind::Serializer(_) => HashTable::Serializer(SerializerHashTable {
+                hash_table: StringHashMap::<[u8], Vec<RowPtr>>::new(),
+                hash_method: HashMethodSerializer::default(),
+            }),
+            HashMethodKind::SingleString(_) => HashTable::SingleString(SingleStringHashTable {
+                hash_table: ShortStringHashMap::<[u8], Vec<RowPtr>>::new(),
+                hash_method: HashMethodSingleString::default(),
+            }),
+            HashMethodKind::KeysU8(hash_method) => HashTable::KeysU8(FixedKeyHashTable {
+                hash_table: HashMap::<u8, Vec<RowPtr>>::new(),
+                hash_method,
+            }),
+            HashMethodKind::KeysU16(hash_method) => HashTable::KeysU16(FixedKeyHashTable {
+                hash_table: HashMap::<u16, Vec<RowPtr>>::new(),
+                hash_method,
+            }),
+            HashMethodKind::KeysU32(hash_method) => HashTable::KeysU32(FixedKeyHashTable {
+                hash_table: HashMap::<u32, Vec<RowPtr>>::new(),
+                hash_method,
+            }),
+            HashMethodKind::KeysU64(hash_method) => HashTable::KeysU64(FixedKeyHashTable {
+                hash_table: HashMap::<u64, Vec<RowPtr>>::new(),
+                hash_method,
+            }),
+            HashMethodKind::KeysU128(hash_method) => HashTable::KeysU128(FixedKeyHashTable {
+                hash_table: HashMap::<u128, Vec<RowPtr>>::new(),
+                hash_method,
+            }),
+            HashMethodKind::KeysU256(hash_method) => HashTable::KeysU256(FixedKeyHashTable {
+                hash_table: HashMap::<U256, Vec<RowPtr>>::new(),
+                hash_method,
+            }),
+        }
+    }
+
+    // Only the join types that probe each row independently can be spilled,
+    // the others rely on states shared by all of the probed rows.
+    fn create_spiller(
+        ctx: &Arc<QueryContext>,
+        hash_join_desc: &HashJoinDesc,
+        method: &HashMethodKind,
+    ) -> Result<Option<Arc<HashJoinSpiller>>> {
+        let threshold = ctx.get_settings().get_join_spilling_threshold()?;
+        if threshold == 0
+            || hash_join_desc.build_keys.is_empty()
+            || !matches!(
+                hash_join_desc.join_type,
+                JoinType::Inner | JoinType::LeftSemi | JoinType::LeftAnti
+            )
+        {
+            return Ok(None);
+        }
+
+        Ok(Some(Arc::new(HashJoinSpiller::create(
+            DataOperator::instance().operator(),
+            format!(
+                "_hash_join_spill/{}/{}",
+                ctx.get_tenant(),
+                GlobalUniqName::unique()
+            ),
+            threshold,
+            method.clone(),
+        ))))
+    }
+
+    /// Create a state to join a restored spilled partition, which shares nothing with `self`.
+    pub(crate) fn create_partition_hash_table(&self) -> Result<Arc<JoinHashTable>> {
+        let method = match &self.spiller {
+            Some(spiller) => spiller.hash_method.clone(),
+            None => {
+                return Err(ErrorCode::Internal(
+                    "Cannot create a partition state for an unspillable hash join",
+                ));
+            }
+        };
+        Ok(Arc::new(JoinHashTable::try_create(
+            self.ctx.clone(),
+            Self::create_hash_table(method),
+            self.row_space.data_schema.clone(),
+            self.probe_schema.clone(),
+            self.hash_join_desc.create_partition_desc()?,
+        )?))
+    }
+
+    pub(crate) fn partition_block(
+        &self,
+        spiller: &HashJoinSpiller,
+        input: &DataBlock,
+        keys: &[Expr],
+        level: u32,
+    ) -> Result<Vec<DataBlock>> {
+        let func_ctx = self.ctx.get_function_context()?;
+        let evaluator = Evaluator::new(input, func_ctx, &BUILTIN_FUNCTIONS);
+        let keys = keys
+            .iter()
+            .map(|expr| {
+                let return_type = expr.data_type();
+                Ok((
+                    evaluator
+                        .run(expr)?
+                        .convert_to_full_column(return_type, input.num_rows()),
+                    return_type.clone(),
+                ))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        spiller.partition_block(input, &keys, level)
     }
 
     pub fn try_create(
@@ -217,6 +262,7 @@ impl JoinHashTable {
             probe_schema: probe_data_schema,
             finished_notify: Arc::new(Notify::new()),
             interrupt: Arc::new(AtomicBool::new(false)),
+            spiller: None,
         })
     }
 
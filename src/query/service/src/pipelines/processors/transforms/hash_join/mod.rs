@@ -21,6 +21,7 @@ mod probe_join;
 mod probe_state;
 mod result_blocks;
 pub(crate) mod row;
+mod spill;
 mod util;
 
 pub use desc::HashJoinDesc;
@@ -31,3 +32,5 @@ pub use join_hash_table::JoinHashTable;
 pub use join_hash_table::SerializerHashTable;
 pub use probe_state::ProbeState;
 pub use result_blocks::*;
+pub use spill::HashJoinSpiller;
+pub use spill::SPILL_PARTITIONS;
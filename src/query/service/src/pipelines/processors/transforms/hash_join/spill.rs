@@ -0,0 +1,458 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use common_base::base::tokio::sync::Notify;
+use common_base::runtime::GlobalIORuntime;
+use common_base::runtime::TrySpawn;
+use common_config::GlobalConfig;
+use common_exception::Result;
+use common_expression::types::DataType;
+use common_expression::with_hash_method;
+use common_expression::Column;
+use common_expression::DataBlock;
+use common_expression::HashMethod;
+use common_expression::HashMethodKind;
+use common_hashtable::hash2bucket;
+use common_hashtable::FastHash;
+use common_metrics::label_counter;
+use opendal::Operator;
+use tracing::error;
+use tracing::info;
+
+use super::HashJoinState;
+use crate::pipelines::processors::transforms::spill_file::delete_spilled_file;
+use crate::pipelines::processors::transforms::spill_file::restore_spilled_file;
+use crate::pipelines::processors::transforms::spill_file::spill_blocks;
+use crate::pipelines::processors::transforms::spill_file::SpilledFile;
+
+static METRIC_HASH_JOIN_SPILLED_PARTITIONS: &str = "hash_join_spilled_partitions";
+
+/// The build side and the probe side are both scattered into `1 << SPILL_PARTITION_BITS` partitions.
+const SPILL_PARTITION_BITS: u32 = 3;
+pub const SPILL_PARTITIONS: usize = 1 << SPILL_PARTITION_BITS;
+/// A restored partition still exceeding the threshold is re-partitioned by the next bits
+/// of the hash, at most this many times, in case the rows share the same keys.
+const MAX_SPILL_LEVEL: u32 = 4;
+/// Spilled blocks of a partition are buffered until they reach this size, then written as one file.
+const SPILL_WRITE_BUFFER_SIZE: usize = 4 * 1024 * 1024;
+
+#[derive(Default)]
+struct BuildPartition {
+    /// Blocks kept in memory, or waiting to be written if the partition is spilled.
+    blocks: Vec<DataBlock>,
+    memory_size: usize,
+    spilled: bool,
+}
+
+/// A spilled partition to be joined, `level` is the number of times it's re-partitioned.
+struct SpilledPartition {
+    level: u32,
+    build_files: Vec<SpilledFile>,
+    probe_files: Vec<SpilledFile>,
+}
+
+#[derive(Default)]
+struct SpillerState {
+    partitions: Vec<BuildPartition>,
+    /// Memory size of the partitions that are not spilled.
+    memory_size: usize,
+    build_files: Vec<Vec<SpilledFile>>,
+    probe_files: Vec<Vec<SpilledFile>>,
+    /// Spilled partitions waiting to be joined after all probe processors finished.
+    restore_queue: VecDeque<usize>,
+    /// Re-partitioned partitions waiting to be joined, they are joined before `restore_queue`.
+    repartitioned: VecDeque<SpilledPartition>,
+    probe_ref_count: usize,
+    probe_finished: bool,
+}
+
+/// Grace hash join support for `JoinHashTable`.
+///
+/// Both sides are partitioned by the hash of the join keys. When the in-memory
+/// partitions of the build side exceed the threshold, the largest one is spilled
+/// to storage, and the probe rows that fall into the spilled partitions are spilled
+/// as well. Once the probe input is exhausted, the spilled partitions are restored
+/// and joined one by one, the ones still too large are re-partitioned and spilled again.
+///
+/// The files are written under a location unique to the spiller, which is removed
+/// when the spiller is dropped, so that nothing is left if the query fails.
+pub struct HashJoinSpiller {
+    operator: Operator,
+    location_prefix: String,
+    threshold: usize,
+    write_buffer_size: usize,
+    pub(crate) hash_method: HashMethodKind,
+    state: Mutex<SpillerState>,
+    probe_finished_notify: Arc<Notify>,
+}
+
+impl HashJoinSpiller {
+    pub fn create(
+        operator: Operator,
+        location_prefix: String,
+        threshold: usize,
+        hash_method: HashMethodKind,
+    ) -> Self {
+        let mut state = SpillerState::default();
+        for _ in 0..SPILL_PARTITIONS {
+            state.partitions.push(BuildPartition::default());
+            state.build_files.push(vec![]);
+            state.probe_files.push(vec![]);
+        }
+
+        HashJoinSpiller {
+            operator,
+            location_prefix,
+            threshold,
+            write_buffer_size: std::cmp::min(threshold, SPILL_WRITE_BUFFER_SIZE),
+            hash_method,
+            state: Mutex::new(state),
+            probe_finished_notify: Arc::new(Notify::new()),
+        }
+    }
+
+    /// Scatter the block into partitions by the hash of the join keys,
+    /// each re-partitioning `level` takes the next bits of the hash.
+    pub fn partition_block(
+        &self,
+        block: &DataBlock,
+        keys: &[(Column, DataType)],
+        level: u32,
+    ) -> Result<Vec<DataBlock>> {
+        let shift = level * SPILL_PARTITION_BITS;
+        let mut indices = Vec::with_capacity(block.num_rows());
+        with_hash_method!(|T| match &self.hash_method {
+            HashMethodKind::T(method) => {
+                let keys_state = method.build_keys_state(keys, block.num_rows())?;
+                for key in method.build_keys_iter(&keys_state)? {
+                    let hash = key.fast_hash() as usize >> shift;
+                    indices.push(hash2bucket::<SPILL_PARTITION_BITS, true>(hash) as u16);
+                }
+            }
+        });
+
+        DataBlock::scatter(block, &indices, SPILL_PARTITIONS)
+    }
+
+    /// Buffer the partitioned blocks of the build side, spilling the largest
+    /// in-memory partitions while the memory usage exceeds the threshold.
+    pub fn add_build_blocks(&self, blocks: Vec<DataBlock>) -> Result<()> {
+        let mut guard = self.state.lock().unwrap();
+        let state = &mut *guard;
+        for (partition, block) in blocks.into_iter().enumerate() {
+            if block.is_empty() {
+                continue;
+            }
+
+            let memory_size = block.memory_size();
+            let build_partition = &mut state.partitions[partition];
+            build_partition.blocks.push(block);
+            build_partition.memory_size += memory_size;
+            if !build_partition.spilled {
+                state.memory_size += memory_size;
+            }
+        }
+
+        while state.memory_size > self.threshold {
+            let largest = state
+                .partitions
+                .iter()
+                .enumerate()
+                .filter(|(_, partition)| !partition.spilled && partition.memory_size > 0)
+                .max_by_key(|(_, partition)| partition.memory_size)
+                .map(|(index, _)| index);
+
+            match largest {
+                None => break,
+                Some(index) => {
+                    let partition = &mut state.partitions[index];
+                    partition.spilled = true;
+                    let memory_size = partition.memory_size;
+                    state.memory_size -= memory_size;
+                    info!(
+                        "Hash join build side exceeds {} bytes, spill partition {} ({} bytes)",
+                        self.threshold, index, memory_size
+                    );
+
+                    let config = GlobalConfig::instance();
+                    label_counter(
+                        METRIC_HASH_JOIN_SPILLED_PARTITIONS,
+                        &config.query.tenant_id,
+                        &config.query.cluster_id,
+                    );
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Write the buffered blocks of the spilled build partitions. If `force` is false,
+    /// only the partitions whose buffer reaches the write buffer size are written.
+    #[async_backtrace::framed]
+    pub async fn spill_build_blocks(&self, force: bool) -> Result<()> {
+        let mut spilling = Vec::new();
+        {
+            let mut state = self.state.lock().unwrap();
+            for (index, partition) in state.partitions.iter_mut().enumerate() {
+                if partition.spilled
+                    && !partition.blocks.is_empty()
+                    && (force || partition.memory_size >= self.write_buffer_size)
+                {
+                    partition.memory_size = 0;
+                    spilling.push((index, std::mem::take(&mut partition.blocks)));
+                }
+            }
+        }
+
+        for (partition, blocks) in spilling.into_iter() {
+            let file = self.spill_blocks(&blocks).await?;
+            self.state.lock().unwrap().build_files[partition].push(file);
+        }
+
+        Ok(())
+    }
+
+    /// Take the blocks of the partitions that are kept in memory, and prepare the
+    /// spilled partitions to be restored after probing.
+    pub fn finish_build(&self) -> Vec<DataBlock> {
+        let mut guard = self.state.lock().unwrap();
+        let state = &mut *guard;
+        let mut blocks = Vec::new();
+        let mut restore_queue = VecDeque::new();
+        for (index, partition) in state.partitions.iter_mut().enumerate() {
+            match partition.spilled {
+                true => restore_queue.push_back(index),
+                false => {
+                    partition.memory_size = 0;
+                    blocks.append(&mut partition.blocks);
+                }
+            }
+        }
+        state.memory_size = 0;
+        state.restore_queue = restore_queue;
+        blocks
+    }
+
+    /// The spilled flag of each partition, it's stable once the build side is finished.
+    pub fn spilled_partitions(&self) -> Vec<bool> {
+        let state = self.state.lock().unwrap();
+        state.partitions.iter().map(|p| p.spilled).collect()
+    }
+
+    pub fn write_buffer_size(&self) -> usize {
+        self.write_buffer_size
+    }
+
+    #[async_backtrace::framed]
+    pub async fn spill_probe_blocks(&self, partition: usize, blocks: &[DataBlock]) -> Result<()> {
+        let file = self.spill_blocks(blocks).await?;
+        self.state.lock().unwrap().probe_files[partition].push(file);
+        Ok(())
+    }
+
+    pub fn attach_probe(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.probe_ref_count += 1;
+    }
+
+    /// Detach a probe processor, the spilled partitions can be restored once all
+    /// of the probe processors are detached.
+    pub fn detach_probe(&self) {
+        let mut state = self.state.lock().unwrap();
+        state.probe_ref_count -= 1;
+        if state.probe_ref_count == 0 {
+            state.probe_finished = true;
+            self.probe_finished_notify.notify_waiters();
+        }
+    }
+
+    #[async_backtrace::framed]
+    pub async fn wait_probe_finish(&self) -> Result<()> {
+        let notified = {
+            let state = self.state.lock().unwrap();
+
+            match state.probe_finished {
+                true => None,
+                false => Some(self.probe_finished_notify.notified()),
+            }
+        };
+
+        if let Some(notified) = notified {
+            notified.await;
+        }
+
+        Ok(())
+    }
+
+    /// Pop a spilled partition, returns its restored build blocks and its spilled probe
+    /// files. The partitions without probe rows are skipped, they can't produce any results.
+    ///
+    /// The partition whose build side still exceeds the threshold is re-partitioned
+    /// with the join keys evaluated by `join_state`, instead of being restored.
+    #[async_backtrace::framed]
+    pub async fn restore_partition(
+        &self,
+        join_state: &dyn HashJoinState,
+    ) -> Result<Option<(Vec<DataBlock>, VecDeque<SpilledFile>)>> {
+        loop {
+            let partition = {
+                let mut state = self.state.lock().unwrap();
+                match state.repartitioned.pop_front() {
+                    Some(partition) => partition,
+                    None => match state.restore_queue.pop_front() {
+                        None => return Ok(None),
+                        Some(partition) => SpilledPartition {
+                            level: 0,
+                            build_files: std::mem::take(&mut state.build_files[partition]),
+                            probe_files: std::mem::take(&mut state.probe_files[partition]),
+                        },
+                    },
+                }
+            };
+
+            if partition.probe_files.is_empty() {
+                for file in partition.build_files.iter() {
+                    delete_spilled_file(&self.operator, file).await;
+                }
+                continue;
+            }
+
+            let memory_size = partition
+                .build_files
+                .iter()
+                .map(|file| file.memory_size())
+                .sum::<usize>();
+            if memory_size > self.threshold && partition.level < MAX_SPILL_LEVEL {
+                info!(
+                    "Restored hash join partition exceeds {} bytes ({} bytes), re-partition it at level {}",
+                    self.threshold,
+                    memory_size,
+                    partition.level + 1
+                );
+                let partitions = self.repartition(join_state, partition).await?;
+                let mut state = self.state.lock().unwrap();
+                for partition in partitions.into_iter().rev() {
+                    state.repartitioned.push_front(partition);
+                }
+                continue;
+            }
+
+            let mut build_blocks = Vec::with_capacity(partition.build_files.len());
+            for file in partition.build_files.iter() {
+                build_blocks.push(self.restore_file(file).await?);
+            }
+
+            return Ok(Some((build_blocks, partition.probe_files.into())));
+        }
+    }
+
+    /// Scatter both sides of the partition by the next bits of the hash.
+    #[async_backtrace::framed]
+    async fn repartition(
+        &self,
+        join_state: &dyn HashJoinState,
+        partition: SpilledPartition,
+    ) -> Result<Vec<SpilledPartition>> {
+        let level = partition.level + 1;
+        let build_files = self
+            .repartition_files(&partition.build_files, |block| {
+                join_state.partition_build_block(block, level)
+            })
+            .await?;
+        let probe_files = self
+            .repartition_files(&partition.probe_files, |block| {
+                join_state.partition_probe_block(block, level)
+            })
+            .await?;
+
+        Ok(build_files
+            .into_iter()
+            .zip(probe_files)
+            .map(|(build_files, probe_files)| SpilledPartition {
+                level,
+                build_files,
+                probe_files,
+            })
+            .collect())
+    }
+
+    #[async_backtrace::framed]
+    async fn repartition_files(
+        &self,
+        files: &[SpilledFile],
+        scatter: impl Fn(&DataBlock) -> Result<Vec<DataBlock>> + Send + Sync,
+    ) -> Result<Vec<Vec<SpilledFile>>> {
+        let mut outputs = (0..SPILL_PARTITIONS).map(|_| vec![]).collect::<Vec<_>>();
+        let mut buffers = vec![vec![]; SPILL_PARTITIONS];
+        let mut buffer_sizes = vec![0; SPILL_PARTITIONS];
+        for file in files {
+            let block = self.restore_file(file).await?;
+            for (index, block) in scatter(&block)?.into_iter().enumerate() {
+                if block.is_empty() {
+                    continue;
+                }
+                buffer_sizes[index] += block.memory_size();
+                buffers[index].push(block);
+                if buffer_sizes[index] >= self.write_buffer_size {
+                    let blocks = std::mem::take(&mut buffers[index]);
+                    buffer_sizes[index] = 0;
+                    outputs[index].push(self.spill_blocks(&blocks).await?);
+                }
+            }
+        }
+
+        for (index, blocks) in buffers.into_iter().enumerate() {
+            if !blocks.is_empty() {
+                outputs[index].push(self.spill_blocks(&blocks).await?);
+            }
+        }
+        Ok(outputs)
+    }
+
+    #[async_backtrace::framed]
+    pub async fn restore_file(&self, file: &SpilledFile) -> Result<DataBlock> {
+        restore_spilled_file(&self.operator, file).await
+    }
+
+    #[async_backtrace::framed]
+    async fn spill_blocks(&self, blocks: &[DataBlock]) -> Result<SpilledFile> {
+        spill_blocks(&self.operator, &self.location_prefix, blocks).await
+    }
+}
+
+impl Drop for HashJoinSpiller {
+    fn drop(&mut self) {
+        let state = self.state.get_mut().unwrap_or_else(|e| e.into_inner());
+        if !state.partitions.iter().any(|partition| partition.spilled) {
+            return;
+        }
+
+        // The files are deleted once they are read, the remaining ones are left by
+        // a cancelled or failed query.
+        let operator = self.operator.clone();
+        let location = format!("{}/", self.location_prefix);
+        GlobalIORuntime::instance().spawn(async move {
+            if let Err(cause) = operator.remove_all(&location).await {
+                error!(
+                    "Cannot remove spill files in {}, cause: {:?}",
+                    location, cause
+                );
+            }
+        });
+    }
+}
@@ -13,25 +13,32 @@
 // limitations under the License.
 
 mod aggregator;
+mod asof_join;
 pub mod group_by;
 pub(crate) mod hash_join;
+mod transform_asof_join;
 mod transform_cast_schema;
 mod transform_create_sets;
 mod transform_hash_join;
 mod transform_left_join;
 mod transform_limit;
 mod transform_mark_join;
+mod transform_range_join;
 mod window;
 
 mod profile_wrapper;
+mod range_join;
 mod runtime_filter;
+mod spill_file;
 mod transform_add_const_columns;
 mod transform_merge_block;
+mod transform_recursive_union;
 mod transform_resort_addon;
 mod transform_right_join;
 mod transform_right_semi_anti_join;
 mod transform_runtime_cast_schema;
 mod transform_runtime_filter;
+mod transform_sort_spill;
 
 pub use aggregator::build_partition_bucket;
 pub use aggregator::AggregateInjector;
@@ -52,6 +59,7 @@ pub use aggregator::TransformGroupBySpillReader;
 pub use aggregator::TransformGroupBySpillWriter;
 pub use aggregator::TransformPartialAggregate;
 pub use aggregator::TransformPartialGroupBy;
+pub use asof_join::AsofJoinState;
 use common_pipeline_transforms::processors::transforms::transform;
 use common_pipeline_transforms::processors::transforms::transform_block_compact;
 use common_pipeline_transforms::processors::transforms::transform_compact;
@@ -64,8 +72,11 @@ pub use hash_join::HashTable;
 pub use hash_join::JoinHashTable;
 pub use hash_join::SerializerHashTable;
 pub use profile_wrapper::ProfileWrapper;
+pub use range_join::RangeJoinState;
 pub use runtime_filter::RuntimeFilterState;
 pub use transform_add_const_columns::TransformAddConstColumns;
+pub use transform_asof_join::SinkAsofJoinRight;
+pub use transform_asof_join::TransformAsofJoinLeft;
 pub use transform_block_compact::BlockCompactor;
 pub use transform_block_compact::TransformBlockCompact;
 pub use transform_cast_schema::TransformCastSchema;
@@ -74,6 +85,7 @@ pub use transform_compact::TransformCompact;
 pub use transform_create_sets::SubqueryReceiver;
 pub use transform_create_sets::TransformCreateSets;
 pub use transform_hash_join::SinkBuildHashTable;
+pub use transform_hash_join::SinkSpillBuildHashTable;
 pub use transform_hash_join::TransformHashJoinProbe;
 pub use transform_left_join::LeftJoinCompactor;
 pub use transform_left_join::TransformLeftJoin;
@@ -81,6 +93,10 @@ pub use transform_limit::TransformLimit;
 pub use transform_mark_join::MarkJoinCompactor;
 pub use transform_mark_join::TransformMarkJoin;
 pub use transform_merge_block::TransformMergeBlock;
+pub use transform_range_join::SinkRangeJoinRight;
+pub use transform_range_join::TransformRangeJoinLeft;
+pub use transform_recursive_union::TransformRecursiveUnion;
+pub use transform_recursive_union::WorkingTable;
 pub use transform_resort_addon::TransformResortAddOn;
 pub use transform_right_join::RightJoinCompactor;
 pub use transform_right_join::TransformRightJoin;
@@ -91,5 +107,9 @@ pub use transform_runtime_filter::SinkRuntimeFilterSource;
 pub use transform_runtime_filter::TransformRuntimeFilter;
 pub use transform_sort_merge::SortMergeCompactor;
 pub use transform_sort_partial::TransformSortPartial;
+pub use transform_sort_spill::create_transform_sort_spill;
+pub use transform_sort_spill::SortSpillParams;
+pub use transform_sort_spill::TransformSortSpill;
 pub use window::TransformWindow;
 pub use window::WindowFunctionInfo;
+pub use window::WindowSpillParams;
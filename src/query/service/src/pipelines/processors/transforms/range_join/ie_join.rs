@@ -0,0 +1,271 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_expression::types::UInt32Type;
+use common_expression::types::UInt8Type;
+use common_expression::types::ValueType;
+use common_expression::Column;
+use common_expression::DataBlock;
+use common_expression::FromData;
+use common_expression::SortColumnDescription;
+
+use super::range_join_state::RangeJoinSide;
+
+/// IEJoin (https://vldb.org/pvldb/vol8/p2074-khayyat.pdf) of the left and right rows
+/// for one or two inequality conditions.
+///
+/// The rows of both sides are sorted by the key of the first condition into `L1`, so that
+/// the right rows satisfying the first condition for a left row are all on one side of it.
+/// Then the rows are visited in the order of the key of the second condition (`L2`), such
+/// that only the right rows satisfying the second condition are marked in a bitmap of `L1`
+/// positions when a left row is visited. The marked positions on the valid side of the left
+/// row are the matches.
+pub struct IEJoin {
+    pub left: RangeJoinSide,
+    pub right: Arc<RangeJoinSide>,
+
+    // Rows less than `num_left` are left rows, the others are right rows.
+    num_left: usize,
+    l1_rows: Vec<u32>,
+    l1_positions: Vec<u32>,
+    l2_rows: Vec<u32>,
+    // The left rows are matched with the right rows after (or before) them in `L1`.
+    match_after: bool,
+    bitmap: Vec<u64>,
+
+    // The next row of `L2` to visit, and the next `L1` position to check for it.
+    l2_cursor: usize,
+    l1_cursor: Option<usize>,
+}
+
+impl IEJoin {
+    pub fn try_create(
+        left: RangeJoinSide,
+        right: Arc<RangeJoinSide>,
+        operators: &[String],
+    ) -> Result<Self> {
+        let num_left = left.block.num_rows();
+        let num_rows = num_left + right.block.num_rows();
+
+        let l1_rows = Self::sort_rows(
+            &left.keys[0],
+            &right.keys[0],
+            true,
+            matches!(operators[0].as_str(), "lt" | "gte"),
+        )?;
+        let mut l1_positions = vec![0; num_rows];
+        for (position, row) in l1_rows.iter().enumerate() {
+            l1_positions[*row as usize] = position as u32;
+        }
+
+        let l2_rows = match operators.get(1) {
+            Some(operator) => Self::sort_rows(
+                &left.keys[1],
+                &right.keys[1],
+                matches!(operator.as_str(), "gt" | "gte"),
+                matches!(operator.as_str(), "lte" | "gte"),
+            )?,
+            // All of the right rows satisfy the missing second condition.
+            None => (num_left as u32..num_rows as u32)
+                .chain(0..num_left as u32)
+                .collect(),
+        };
+
+        Ok(IEJoin {
+            left,
+            right,
+            num_left,
+            l1_rows,
+            l1_positions,
+            l2_rows,
+            match_after: matches!(operators[0].as_str(), "lt" | "lte"),
+            bitmap: vec![0; (num_rows + 63) / 64],
+            l2_cursor: 0,
+            l1_cursor: None,
+        })
+    }
+
+    // Sort the left and right rows by the key, the equal keys are ordered by side.
+    fn sort_rows(
+        left_key: &Column,
+        right_key: &Column,
+        asc: bool,
+        right_first: bool,
+    ) -> Result<Vec<u32>> {
+        let num_left = left_key.len();
+        let num_rows = num_left + right_key.len();
+
+        let key = Column::concat(&[left_key.clone(), right_key.clone()]);
+        let sides = (0..num_rows)
+            .map(|row| ((row < num_left) == right_first) as u8)
+            .collect::<Vec<_>>();
+        let rows = (0..num_rows as u32).collect::<Vec<_>>();
+        let block = DataBlock::new_from_columns(vec![
+            key,
+            UInt8Type::from_data(sides),
+            UInt32Type::from_data(rows),
+        ]);
+
+        let sorted = DataBlock::sort(
+            &block,
+            &[
+                SortColumnDescription {
+                    offset: 0,
+                    asc,
+                    nulls_first: false,
+                },
+                SortColumnDescription {
+                    offset: 1,
+                    asc: true,
+                    nulls_first: false,
+                },
+            ],
+            None,
+        )?;
+        let rows = sorted.get_by_offset(2).value.as_column().unwrap();
+        Ok(UInt32Type::try_downcast_column(rows).unwrap().to_vec())
+    }
+
+    /// Returns the next matched pairs of the left and right rows, at most `max_rows` pairs.
+    pub fn next_pairs(&mut self, max_rows: usize) -> Option<(Vec<u32>, Vec<u32>)> {
+        let mut left_rows = Vec::with_capacity(max_rows);
+        let mut right_rows = Vec::with_capacity(max_rows);
+
+        while self.l2_cursor < self.l2_rows.len() {
+            let row = self.l2_rows[self.l2_cursor] as usize;
+            let position = self.l1_positions[row] as usize;
+            if row >= self.num_left {
+                self.bitmap[position / 64] |= 1 << (position % 64);
+                self.l2_cursor += 1;
+                continue;
+            }
+
+            let (start, end) = match self.match_after {
+                true => (position + 1, self.l1_rows.len()),
+                false => (0, position),
+            };
+            let mut start = self.l1_cursor.unwrap_or(start);
+            while start < end {
+                let word_index = start / 64;
+                let word_end = end.min((word_index + 1) * 64);
+                let mut word = self.bitmap[word_index] & (u64::MAX << (start % 64));
+                if word_end % 64 != 0 {
+                    word &= (1 << (word_end % 64)) - 1;
+                }
+
+                while word != 0 {
+                    let matched = word_index * 64 + word.trailing_zeros() as usize;
+                    left_rows.push(row as u32);
+                    right_rows.push(self.l1_rows[matched] - self.num_left as u32);
+                    word &= word - 1;
+
+                    if left_rows.len() >= max_rows {
+                        self.l1_cursor = Some(matched + 1);
+                        return Some((left_rows, right_rows));
+                    }
+                }
+                start = word_end;
+            }
+
+            self.l1_cursor = None;
+            self.l2_cursor += 1;
+        }
+
+        match left_rows.is_empty() {
+            true => None,
+            false => Some((left_rows, right_rows)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use common_expression::types::Int32Type;
+    use common_expression::DataBlock;
+    use common_expression::FromData;
+
+    use super::IEJoin;
+    use crate::pipelines::processors::transforms::range_join::range_join_state::RangeJoinSide;
+
+    fn create_side(keys: &[Vec<i32>]) -> RangeJoinSide {
+        let keys = keys
+            .iter()
+            .map(|key| Int32Type::from_data(key.clone()))
+            .collect::<Vec<_>>();
+        RangeJoinSide {
+            block: DataBlock::new_from_columns(vec![keys[0].clone()]),
+            keys,
+        }
+    }
+
+    fn compare(lhs: i32, operator: &str, rhs: i32) -> bool {
+        match operator {
+            "lt" => lhs < rhs,
+            "lte" => lhs <= rhs,
+            "gt" => lhs > rhs,
+            _ => lhs >= rhs,
+        }
+    }
+
+    #[test]
+    fn test_ie_join() {
+        let left = vec![vec![1, 3, 3, 5, 7, 2, 9], vec![4, 2, 6, 6, 1, 8, 3]];
+        let right = vec![vec![3, 1, 6, 2, 8, 3], vec![2, 6, 6, 9, 1, 4]];
+        let operators = ["lt", "lte", "gt", "gte"];
+
+        for op1 in operators {
+            for op2 in operators {
+                for conditions in [vec![op1], vec![op1, op2]] {
+                    let conditions = conditions
+                        .into_iter()
+                        .map(|op| op.to_string())
+                        .collect::<Vec<_>>();
+                    let mut expected = vec![];
+                    for i in 0..left[0].len() {
+                        for j in 0..right[0].len() {
+                            if conditions
+                                .iter()
+                                .enumerate()
+                                .all(|(k, op)| compare(left[k][i], op, right[k][j]))
+                            {
+                                expected.push((i as u32, j as u32));
+                            }
+                        }
+                    }
+
+                    let mut ie_join = IEJoin::try_create(
+                        create_side(&left),
+                        Arc::new(create_side(&right)),
+                        &conditions,
+                    )
+                    .unwrap();
+                    let mut actual = vec![];
+                    while let Some((left_rows, right_rows)) = ie_join.next_pairs(3) {
+                        assert!(left_rows.len() <= 3);
+                        actual.extend(left_rows.into_iter().zip(right_rows));
+                    }
+
+                    expected.sort();
+                    actual.sort();
+                    assert_eq!(expected, actual, "{:?}", conditions);
+                }
+            }
+        }
+    }
+}
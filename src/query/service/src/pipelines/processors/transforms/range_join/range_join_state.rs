@@ -0,0 +1,263 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use common_base::base::tokio::sync::Notify;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::type_check::check_function;
+use common_expression::types::BooleanType;
+use common_expression::Column;
+use common_expression::DataBlock;
+use common_expression::Evaluator;
+use common_expression::Expr;
+use common_functions::BUILTIN_FUNCTIONS;
+use common_sql::executor::cast_expr_to_non_null_boolean;
+use common_sql::executor::RangeJoin;
+use parking_lot::RwLock;
+
+use super::ie_join::IEJoin;
+use crate::sessions::QueryContext;
+
+/// The rows of one side with non-NULL join keys, and the evaluated join keys.
+pub struct RangeJoinSide {
+    pub block: DataBlock,
+    pub keys: Vec<Column>,
+}
+
+impl RangeJoinSide {
+    fn try_create(ctx: &QueryContext, blocks: &[DataBlock], keys: &[Expr]) -> Result<Option<Self>> {
+        let blocks = blocks
+            .iter()
+            .filter(|block| !block.is_empty())
+            .cloned()
+            .collect::<Vec<_>>();
+        if blocks.is_empty() {
+            return Ok(None);
+        }
+
+        let block = DataBlock::concat(&blocks)?;
+        let func_ctx = ctx.get_function_context()?;
+        let evaluator = Evaluator::new(&block, func_ctx, &BUILTIN_FUNCTIONS);
+
+        let mut validity = None;
+        let mut columns = Vec::with_capacity(keys.len());
+        for key in keys {
+            let column = evaluator
+                .run(key)?
+                .convert_to_full_column(key.data_type(), block.num_rows());
+            match column {
+                Column::Nullable(column) => {
+                    validity = match validity {
+                        None => Some(column.validity.clone()),
+                        Some(validity) => Some(&validity & &column.validity),
+                    };
+                    columns.push(column.column);
+                }
+                column => columns.push(column),
+            }
+        }
+
+        // Rows with NULL keys never satisfy the conditions.
+        match validity {
+            Some(validity) if validity.unset_bits() > 0 => {
+                if validity.unset_bits() == block.num_rows() {
+                    return Ok(None);
+                }
+                let keys = columns
+                    .iter()
+                    .map(|column| column.filter(&validity))
+                    .collect();
+                Ok(Some(RangeJoinSide {
+                    block: block.filter_with_bitmap(&validity)?,
+                    keys,
+                }))
+            }
+            _ => Ok(Some(RangeJoinSide {
+                block,
+                keys: columns,
+            })),
+        }
+    }
+}
+
+pub struct RangeJoinState {
+    ctx: Arc<QueryContext>,
+    left_keys: Vec<Expr>,
+    right_keys: Vec<Expr>,
+    operators: Vec<String>,
+    other_predicate: Option<Expr>,
+    max_block_size: usize,
+
+    right_blocks: RwLock<Vec<DataBlock>>,
+    right_side: RwLock<Option<Arc<RangeJoinSide>>>,
+
+    ref_count: Mutex<usize>,
+    is_finished: Mutex<bool>,
+    finished_notify: Arc<Notify>,
+    interrupt: AtomicBool,
+}
+
+impl RangeJoinState {
+    pub fn try_create(ctx: Arc<QueryContext>, join: &RangeJoin) -> Result<Arc<Self>> {
+        let other_predicate = join
+            .other_conditions
+            .iter()
+            .map(|expr| expr.as_expr(&BUILTIN_FUNCTIONS))
+            .try_reduce(|lhs, rhs| {
+                check_function(None, "and_filters", &[], &[lhs, rhs], &BUILTIN_FUNCTIONS)
+            })?
+            .map(cast_expr_to_non_null_boolean)
+            .transpose()?;
+        let max_block_size = ctx.get_settings().get_max_block_size()? as usize;
+
+        Ok(Arc::new(RangeJoinState {
+            ctx,
+            left_keys: join
+                .conditions
+                .iter()
+                .map(|condition| condition.left_expr.as_expr(&BUILTIN_FUNCTIONS))
+                .collect(),
+            right_keys: join
+                .conditions
+                .iter()
+                .map(|condition| condition.right_expr.as_expr(&BUILTIN_FUNCTIONS))
+                .collect(),
+            operators: join
+                .conditions
+                .iter()
+                .map(|condition| condition.operator.clone())
+                .collect(),
+            other_predicate,
+            max_block_size,
+            right_blocks: RwLock::new(vec![]),
+            right_side: RwLock::new(None),
+            ref_count: Mutex::new(0),
+            is_finished: Mutex::new(false),
+            finished_notify: Arc::new(Notify::new()),
+            interrupt: AtomicBool::new(false),
+        }))
+    }
+
+    pub fn attach(&self) {
+        let mut count = self.ref_count.lock().unwrap();
+        *count += 1;
+    }
+
+    /// Detach a right side sink, the right side is finished once all of the sinks are detached.
+    pub fn detach(&self) -> Result<()> {
+        let mut count = self.ref_count.lock().unwrap();
+        *count -= 1;
+        if *count == 0 {
+            let right_blocks = std::mem::take(&mut *self.right_blocks.write());
+            let right_side = RangeJoinSide::try_create(&self.ctx, &right_blocks, &self.right_keys)?;
+            *self.right_side.write() = right_side.map(Arc::new);
+
+            let mut is_finished = self.is_finished.lock().unwrap();
+            *is_finished = true;
+            self.finished_notify.notify_waiters();
+        }
+        Ok(())
+    }
+
+    pub fn interrupt(&self) {
+        self.interrupt.store(true, Ordering::Release);
+    }
+
+    pub fn sink_right(&self, block: DataBlock) -> Result<()> {
+        self.right_blocks.write().push(block.convert_to_full());
+        Ok(())
+    }
+
+    #[async_backtrace::framed]
+    pub async fn wait_finish(&self) -> Result<()> {
+        let notified = {
+            let finished_guard = self.is_finished.lock().unwrap();
+
+            match *finished_guard {
+                true => None,
+                false => Some(self.finished_notify.notified()),
+            }
+        };
+
+        if let Some(notified) = notified {
+            notified.await;
+        }
+
+        Ok(())
+    }
+
+    /// Whether the right side is finished without any rows to join.
+    pub fn right_is_empty(&self) -> bool {
+        self.right_side.read().is_none()
+    }
+
+    /// Create the IEJoin of the left blocks of a processor and the right side.
+    pub fn create_ie_join(&self, left_blocks: &[DataBlock]) -> Result<Option<IEJoin>> {
+        let right = match self.right_side.read().clone() {
+            Some(right) => right,
+            None => return Ok(None),
+        };
+        let left_blocks = left_blocks
+            .iter()
+            .map(|block| block.convert_to_full())
+            .collect::<Vec<_>>();
+        match RangeJoinSide::try_create(&self.ctx, &left_blocks, &self.left_keys)? {
+            Some(left) => Ok(Some(IEJoin::try_create(left, right, &self.operators)?)),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns the next joined block, or `None` if all of the pairs are joined.
+    pub fn next_block(&self, ie_join: &mut IEJoin) -> Result<Option<DataBlock>> {
+        while let Some((left_rows, right_rows)) = ie_join.next_pairs(self.max_block_size) {
+            if self.interrupt.load(Ordering::Relaxed) {
+                return Err(ErrorCode::AbortedQuery(
+                    "Aborted query, because the server is shutting down or the query was killed.",
+                ));
+            }
+
+            let mut block = DataBlock::take(&ie_join.left.block, &left_rows)?;
+            for column in DataBlock::take(&ie_join.right.block, &right_rows)?.columns() {
+                block.add_column(column.clone());
+            }
+
+            let block = match &self.other_predicate {
+                None => block,
+                Some(other_predicate) => {
+                    let func_ctx = self.ctx.get_function_context()?;
+                    let evaluator = Evaluator::new(&block, func_ctx, &BUILTIN_FUNCTIONS);
+                    let predicate = evaluator
+                        .run(other_predicate)?
+                        .try_downcast::<BooleanType>()
+                        .ok_or_else(|| {
+                            ErrorCode::Internal(format!(
+                                "Residual predicate of range join must be boolean: {other_predicate}"
+                            ))
+                        })?;
+                    block.filter_boolean_value(&predicate)?
+                }
+            };
+            if !block.is_empty() {
+                return Ok(Some(block));
+            }
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,121 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::time::Instant;
+
+use common_base::base::GlobalUniqName;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::arrow::deserialize_column;
+use common_expression::arrow::serialize_column;
+use common_expression::DataBlock;
+use opendal::Operator;
+use tracing::error;
+use tracing::info;
+
+/// A file holding a data block spilled to storage.
+pub struct SpilledFile {
+    location: String,
+    columns_layout: Vec<usize>,
+    /// The memory size of the block once it is read back.
+    memory_size: usize,
+}
+
+impl SpilledFile {
+    pub fn memory_size(&self) -> usize {
+        self.memory_size
+    }
+}
+
+/// Concatenate the blocks and write them into a new spill file under the location prefix.
+#[async_backtrace::framed]
+pub async fn spill_blocks(
+    operator: &Operator,
+    location_prefix: &str,
+    blocks: &[DataBlock],
+) -> Result<SpilledFile> {
+    let instant = Instant::now();
+    let data_block = DataBlock::concat(blocks)?.convert_to_full();
+
+    let mut columns_layout = Vec::with_capacity(data_block.num_columns());
+    let mut write_data = Vec::with_capacity(data_block.memory_size());
+    for entry in data_block.columns() {
+        let column_data = serialize_column(entry.value.as_column().unwrap());
+        columns_layout.push(column_data.len());
+        write_data.extend(column_data);
+    }
+
+    let location = format!("{}/{}", location_prefix, GlobalUniqName::unique());
+    operator.write(&location, write_data).await?;
+
+    info!(
+        "Write spill {} successfully, elapsed: {:?}",
+        location,
+        instant.elapsed()
+    );
+
+    Ok(SpilledFile {
+        location,
+        columns_layout,
+        memory_size: data_block.memory_size(),
+    })
+}
+
+/// Read the data block back from the spill file, the file is deleted after reading.
+#[async_backtrace::framed]
+pub async fn restore_spilled_file(operator: &Operator, file: &SpilledFile) -> Result<DataBlock> {
+    let data_block = read_spilled_file(operator, file).await?;
+    delete_spilled_file(operator, file).await;
+    Ok(data_block)
+}
+
+/// Read the data block back from the spill file, the file is kept for reading again.
+#[async_backtrace::framed]
+pub async fn read_spilled_file(operator: &Operator, file: &SpilledFile) -> Result<DataBlock> {
+    let instant = Instant::now();
+    let data = operator.read(&file.location).await?;
+
+    let mut begin = 0;
+    let mut columns = Vec::with_capacity(file.columns_layout.len());
+    for column_layout in file.columns_layout.iter() {
+        let column = data
+            .get(begin..begin + column_layout)
+            .and_then(deserialize_column)
+            .ok_or_else(|| {
+                ErrorCode::StorageOther(format!(
+                    "Failed to deserialize column from spill file {}",
+                    &file.location
+                ))
+            })?;
+        columns.push(column);
+        begin += column_layout;
+    }
+
+    info!(
+        "Read spill {} successfully, elapsed: {:?}",
+        &file.location,
+        instant.elapsed()
+    );
+
+    Ok(DataBlock::new_from_columns(columns))
+}
+
+#[async_backtrace::framed]
+pub async fn delete_spilled_file(operator: &Operator, file: &SpilledFile) {
+    if let Err(cause) = operator.delete(&file.location).await {
+        error!(
+            "Cannot delete spill file {}, cause: {:?}",
+            &file.location, cause
+        );
+    }
+}
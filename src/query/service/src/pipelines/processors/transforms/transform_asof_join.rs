@@ -0,0 +1,156 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_expression::DataBlock;
+use common_pipeline_sinks::Sink;
+
+use super::asof_join::AsofJoinState;
+use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::Event;
+use crate::pipelines::processors::Processor;
+
+pub struct SinkAsofJoinRight {
+    state: Arc<AsofJoinState>,
+}
+
+impl SinkAsofJoinRight {
+    pub fn create(state: Arc<AsofJoinState>) -> Self {
+        state.attach();
+        Self { state }
+    }
+}
+
+impl Sink for SinkAsofJoinRight {
+    const NAME: &'static str = "AsofJoinRight";
+
+    fn on_finish(&mut self) -> Result<()> {
+        self.state.detach()
+    }
+
+    fn interrupt(&self) {
+        self.state.interrupt()
+    }
+
+    fn consume(&mut self, data_block: DataBlock) -> Result<()> {
+        self.state.sink_right(data_block)
+    }
+}
+
+enum AsofJoinStep {
+    WaitRight,
+    Join,
+}
+
+/// Wait for the right side to be sorted, then join the left blocks with it one by one.
+pub struct TransformAsofJoinLeft {
+    input_port: Arc<InputPort>,
+    output_port: Arc<OutputPort>,
+    state: Arc<AsofJoinState>,
+    step: AsofJoinStep,
+
+    input_data: Option<DataBlock>,
+    output_data: Option<DataBlock>,
+}
+
+impl TransformAsofJoinLeft {
+    pub fn create(
+        input_port: Arc<InputPort>,
+        output_port: Arc<OutputPort>,
+        state: Arc<AsofJoinState>,
+    ) -> Box<dyn Processor> {
+        Box::new(TransformAsofJoinLeft {
+            input_port,
+            output_port,
+            state,
+            step: AsofJoinStep::WaitRight,
+            input_data: None,
+            output_data: None,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for TransformAsofJoinLeft {
+    fn name(&self) -> String {
+        "AsofJoin".to_string()
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        match self.step {
+            AsofJoinStep::WaitRight => Ok(Event::Async),
+            AsofJoinStep::Join => {
+                if self.output_port.is_finished() {
+                    self.input_port.finish();
+                    return Ok(Event::Finished);
+                }
+
+                if !self.output_port.can_push() {
+                    self.input_port.set_not_need_data();
+                    return Ok(Event::NeedConsume);
+                }
+
+                if let Some(data) = self.output_data.take() {
+                    self.output_port.push_data(Ok(data));
+                    return Ok(Event::NeedConsume);
+                }
+
+                if self.input_data.is_some() {
+                    return Ok(Event::Sync);
+                }
+
+                if self.input_port.has_data() {
+                    self.input_data = Some(self.input_port.pull_data().unwrap()?);
+                    return Ok(Event::Sync);
+                }
+
+                if self.input_port.is_finished() {
+                    self.output_port.finish();
+                    return Ok(Event::Finished);
+                }
+
+                self.input_port.set_need_data();
+                Ok(Event::NeedData)
+            }
+        }
+    }
+
+    fn interrupt(&self) {
+        self.state.interrupt()
+    }
+
+    fn process(&mut self) -> Result<()> {
+        if let Some(data) = self.input_data.take() {
+            self.output_data = Some(self.state.join(data)?);
+        }
+        Ok(())
+    }
+
+    #[async_backtrace::framed]
+    async fn async_process(&mut self) -> Result<()> {
+        if let AsofJoinStep::WaitRight = self.step {
+            self.state.wait_finish().await?;
+            self.step = AsofJoinStep::Join;
+        }
+        Ok(())
+    }
+}
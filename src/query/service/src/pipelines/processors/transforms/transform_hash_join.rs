@@ -16,12 +16,18 @@ use std::any::Any;
 use std::collections::VecDeque;
 use std::sync::Arc;
 
+use async_trait::async_trait;
+use async_trait::unboxed_simple;
 use common_exception::Result;
 use common_expression::DataBlock;
 use common_expression::DataSchemaRef;
+use common_pipeline_sinks::AsyncSink;
 use common_pipeline_sinks::Sink;
 
+use super::hash_join::HashJoinSpiller;
 use super::hash_join::ProbeState;
+use super::hash_join::SPILL_PARTITIONS;
+use super::spill_file::SpilledFile;
 use crate::pipelines::processors::port::InputPort;
 use crate::pipelines::processors::port::OutputPort;
 use crate::pipelines::processors::processor::Event;
@@ -57,9 +63,56 @@ impl Sink for SinkBuildHashTable {
     }
 }
 
+/// Build the hash table and write the spilled partitions to storage,
+/// used when the hash join is allowed to spill.
+pub struct SinkSpillBuildHashTable {
+    join_state: Arc<dyn HashJoinState>,
+    spiller: Arc<HashJoinSpiller>,
+}
+
+impl SinkSpillBuildHashTable {
+    pub fn try_create(
+        join_state: Arc<dyn HashJoinState>,
+        spiller: Arc<HashJoinSpiller>,
+    ) -> Result<Self> {
+        join_state.attach()?;
+        Ok(Self {
+            join_state,
+            spiller,
+        })
+    }
+}
+
+#[async_trait]
+impl AsyncSink for SinkSpillBuildHashTable {
+    const NAME: &'static str = "SpillBuildHashTable";
+
+    #[async_backtrace::framed]
+    async fn on_finish(&mut self) -> Result<()> {
+        // All the spilled blocks must be written before the hash table is finished.
+        self.spiller.spill_build_blocks(true).await?;
+        self.join_state.detach()
+    }
+
+    fn interrupt(&self) {
+        self.join_state.interrupt()
+    }
+
+    #[unboxed_simple]
+    #[async_backtrace::framed]
+    async fn consume(&mut self, data_block: DataBlock) -> Result<bool> {
+        self.join_state.build(data_block)?;
+        self.spiller.spill_build_blocks(false).await?;
+        Ok(false)
+    }
+}
+
 enum HashJoinStep {
     Build,
     Probe,
+    /// Join the spilled partitions one by one after all probe processors finished.
+    Restore,
+    Finished,
 }
 
 pub struct TransformHashJoinProbe {
@@ -71,6 +124,17 @@ pub struct TransformHashJoinProbe {
     step: HashJoinStep,
     join_state: Arc<dyn HashJoinState>,
     probe_state: ProbeState,
+
+    spiller: Option<Arc<HashJoinSpiller>>,
+    probe_attached: bool,
+    /// Whether each partition of the build side is spilled, empty if nothing is spilled.
+    spilled_partitions: Vec<bool>,
+    /// Probe rows of the spilled partitions waiting to be written.
+    spilling_blocks: Vec<Vec<DataBlock>>,
+    spilling_sizes: Vec<usize>,
+    /// The state and the remaining probe files of the partition being restored.
+    partition_state: Option<Arc<dyn HashJoinState>>,
+    restore_files: VecDeque<SpilledFile>,
 }
 
 impl TransformHashJoinProbe {
@@ -82,6 +146,10 @@ impl TransformHashJoinProbe {
         _output_schema: DataSchemaRef,
     ) -> Result<Box<dyn Processor>> {
         let default_block_size = ctx.get_settings().get_max_block_size()?;
+        let spiller = join_state.spiller();
+        if let Some(spiller) = &spiller {
+            spiller.attach_probe();
+        }
         Ok(Box::new(TransformHashJoinProbe {
             input_data: None,
             output_data_blocks: VecDeque::new(),
@@ -90,6 +158,13 @@ impl TransformHashJoinProbe {
             step: HashJoinStep::Build,
             join_state,
             probe_state: ProbeState::with_capacity(default_block_size as usize),
+            probe_attached: spiller.is_some(),
+            spiller,
+            spilled_partitions: vec![],
+            spilling_blocks: vec![],
+            spilling_sizes: vec![],
+            partition_state: None,
+            restore_files: VecDeque::new(),
         }))
     }
 
@@ -99,6 +174,98 @@ impl TransformHashJoinProbe {
             .extend(self.join_state.probe(block, &mut self.probe_state)?);
         Ok(())
     }
+
+    // Probe the rows of the in-memory partitions, and buffer the others to be spilled.
+    fn probe_or_spill(&mut self, block: &DataBlock) -> Result<()> {
+        let partitions = self.join_state.partition_probe_block(block, 0)?;
+        for (partition, block) in partitions.into_iter().enumerate() {
+            if block.is_empty() {
+                continue;
+            }
+
+            if self.spilled_partitions[partition] {
+                self.spilling_sizes[partition] += block.memory_size();
+                self.spilling_blocks[partition].push(block);
+            } else {
+                self.probe(&block)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn need_spill(&self) -> bool {
+        match &self.spiller {
+            Some(spiller) => self
+                .spilling_sizes
+                .iter()
+                .any(|size| *size >= spiller.write_buffer_size()),
+            None => false,
+        }
+    }
+
+    fn detach_probe(&mut self) {
+        if self.probe_attached {
+            self.probe_attached = false;
+            if let Some(spiller) = &self.spiller {
+                spiller.detach_probe();
+            }
+        }
+    }
+
+    #[async_backtrace::framed]
+    async fn spill_probe_blocks(&mut self, force: bool) -> Result<()> {
+        let spiller = match &self.spiller {
+            Some(spiller) => spiller.clone(),
+            None => return Ok(()),
+        };
+
+        for partition in 0..self.spilling_blocks.len() {
+            if !self.spilling_blocks[partition].is_empty()
+                && (force || self.spilling_sizes[partition] >= spiller.write_buffer_size())
+            {
+                let blocks = std::mem::take(&mut self.spilling_blocks[partition]);
+                self.spilling_sizes[partition] = 0;
+                spiller.spill_probe_blocks(partition, &blocks).await?;
+            }
+        }
+        Ok(())
+    }
+
+    // Read the next spilled probe block, or build the hash table of the next spilled partition.
+    #[async_backtrace::framed]
+    async fn restore(&mut self) -> Result<()> {
+        let spiller = match &self.spiller {
+            Some(spiller) => spiller.clone(),
+            None => {
+                self.step = HashJoinStep::Finished;
+                return Ok(());
+            }
+        };
+
+        if self.restore_files.is_empty() {
+            self.partition_state = None;
+            match spiller.restore_partition(self.join_state.as_ref()).await? {
+                None => {
+                    self.step = HashJoinStep::Finished;
+                    return Ok(());
+                }
+                Some((build_blocks, probe_files)) => {
+                    let partition_state = self.join_state.create_partition_state()?;
+                    for block in build_blocks.into_iter() {
+                        partition_state.build(block)?;
+                    }
+                    partition_state.finish()?;
+                    self.partition_state = Some(partition_state);
+                    self.restore_files = probe_files;
+                }
+            }
+        }
+
+        if let Some(file) = self.restore_files.pop_front() {
+            self.input_data = Some(spiller.restore_file(&file).await?);
+        }
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -114,8 +281,9 @@ impl Processor for TransformHashJoinProbe {
     fn event(&mut self) -> Result<Event> {
         match self.step {
             HashJoinStep::Build => Ok(Event::Async),
-            HashJoinStep::Probe => {
+            HashJoinStep::Probe | HashJoinStep::Restore => {
                 if self.output_port.is_finished() {
+                    self.detach_probe();
                     self.input_port.finish();
                     return Ok(Event::Finished);
                 }
@@ -135,6 +303,14 @@ impl Processor for TransformHashJoinProbe {
                     return Ok(Event::Sync);
                 }
 
+                if let HashJoinStep::Restore = self.step {
+                    return Ok(Event::Async);
+                }
+
+                if self.need_spill() {
+                    return Ok(Event::Async);
+                }
+
                 if self.input_port.has_data() {
                     let data = self.input_port.pull_data().unwrap()?;
                     self.input_data = Some(data);
@@ -142,6 +318,10 @@ impl Processor for TransformHashJoinProbe {
                 }
 
                 if self.input_port.is_finished() {
+                    if !self.spilled_partitions.is_empty() {
+                        self.step = HashJoinStep::Restore;
+                        return Ok(Event::Async);
+                    }
                     self.output_port.finish();
                     return Ok(Event::Finished);
                 }
@@ -149,6 +329,11 @@ impl Processor for TransformHashJoinProbe {
                 self.input_port.set_need_data();
                 Ok(Event::NeedData)
             }
+            HashJoinStep::Finished => {
+                self.input_port.finish();
+                self.output_port.finish();
+                Ok(Event::Finished)
+            }
         }
     }
 
@@ -158,11 +343,24 @@ impl Processor for TransformHashJoinProbe {
 
     fn process(&mut self) -> Result<()> {
         match self.step {
-            HashJoinStep::Build => Ok(()),
+            HashJoinStep::Build | HashJoinStep::Finished => Ok(()),
             HashJoinStep::Probe => {
                 if let Some(data) = self.input_data.take() {
                     let data = data.convert_to_full();
-                    self.probe(&data)?;
+                    match self.spilled_partitions.is_empty() {
+                        true => self.probe(&data)?,
+                        false => self.probe_or_spill(&data)?,
+                    }
+                }
+                Ok(())
+            }
+            HashJoinStep::Restore => {
+                if let (Some(data), Some(partition_state)) =
+                    (self.input_data.take(), &self.partition_state)
+                {
+                    self.probe_state.clear();
+                    self.output_data_blocks
+                        .extend(partition_state.probe(&data, &mut self.probe_state)?);
                 }
                 Ok(())
             }
@@ -171,9 +369,32 @@ impl Processor for TransformHashJoinProbe {
 
     #[async_backtrace::framed]
     async fn async_process(&mut self) -> Result<()> {
-        if let HashJoinStep::Build = &self.step {
-            self.join_state.wait_finish().await?;
-            self.step = HashJoinStep::Probe;
+        match self.step {
+            HashJoinStep::Build => {
+                self.join_state.wait_finish().await?;
+                if let Some(spiller) = &self.spiller {
+                    let spilled_partitions = spiller.spilled_partitions();
+                    if spilled_partitions.iter().any(|spilled| *spilled) {
+                        self.spilled_partitions = spilled_partitions;
+                        self.spilling_blocks = vec![vec![]; SPILL_PARTITIONS];
+                        self.spilling_sizes = vec![0; SPILL_PARTITIONS];
+                    }
+                }
+                self.step = HashJoinStep::Probe;
+            }
+            HashJoinStep::Probe => self.spill_probe_blocks(false).await?,
+            HashJoinStep::Restore => {
+                if self.probe_attached {
+                    // The spilled partitions are restored only if all of the probe rows are spilled.
+                    self.spill_probe_blocks(true).await?;
+                    self.detach_probe();
+                    if let Some(spiller) = &self.spiller {
+                        spiller.wait_probe_finish().await?;
+                    }
+                }
+                self.restore().await?;
+            }
+            HashJoinStep::Finished => {}
         }
 
         Ok(())
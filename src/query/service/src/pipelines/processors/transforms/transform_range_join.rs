@@ -0,0 +1,184 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use common_exception::Result;
+use common_expression::DataBlock;
+use common_pipeline_sinks::Sink;
+
+use super::range_join::IEJoin;
+use super::range_join::RangeJoinState;
+use crate::pipelines::processors::port::InputPort;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::Event;
+use crate::pipelines::processors::Processor;
+
+pub struct SinkRangeJoinRight {
+    state: Arc<RangeJoinState>,
+}
+
+impl SinkRangeJoinRight {
+    pub fn create(state: Arc<RangeJoinState>) -> Self {
+        state.attach();
+        Self { state }
+    }
+}
+
+impl Sink for SinkRangeJoinRight {
+    const NAME: &'static str = "RangeJoinRight";
+
+    fn on_finish(&mut self) -> Result<()> {
+        self.state.detach()
+    }
+
+    fn interrupt(&self) {
+        self.state.interrupt()
+    }
+
+    fn consume(&mut self, data_block: DataBlock) -> Result<()> {
+        self.state.sink_right(data_block)
+    }
+}
+
+enum RangeJoinStep {
+    WaitRight,
+    CollectLeft,
+    Join,
+    Finished,
+}
+
+/// Collect the left blocks of the processor, and join them with all of the right
+/// blocks once both sides are finished.
+pub struct TransformRangeJoinLeft {
+    input_port: Arc<InputPort>,
+    output_port: Arc<OutputPort>,
+    state: Arc<RangeJoinState>,
+    step: RangeJoinStep,
+
+    left_blocks: Vec<DataBlock>,
+    ie_join: Option<IEJoin>,
+    output_data: Option<DataBlock>,
+}
+
+impl TransformRangeJoinLeft {
+    pub fn create(
+        input_port: Arc<InputPort>,
+        output_port: Arc<OutputPort>,
+        state: Arc<RangeJoinState>,
+    ) -> Box<dyn Processor> {
+        Box::new(TransformRangeJoinLeft {
+            input_port,
+            output_port,
+            state,
+            step: RangeJoinStep::WaitRight,
+            left_blocks: vec![],
+            ie_join: None,
+            output_data: None,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl Processor for TransformRangeJoinLeft {
+    fn name(&self) -> String {
+        "RangeJoin".to_string()
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        match self.step {
+            RangeJoinStep::WaitRight => Ok(Event::Async),
+            RangeJoinStep::CollectLeft => {
+                if self.output_port.is_finished() {
+                    self.input_port.finish();
+                    return Ok(Event::Finished);
+                }
+
+                if self.input_port.has_data() {
+                    let data = self.input_port.pull_data().unwrap()?;
+                    // Nothing can be joined with an empty right side.
+                    if !self.state.right_is_empty() {
+                        self.left_blocks.push(data);
+                    }
+                }
+
+                if self.input_port.is_finished() {
+                    self.step = RangeJoinStep::Join;
+                    return Ok(Event::Sync);
+                }
+
+                self.input_port.set_need_data();
+                Ok(Event::NeedData)
+            }
+            RangeJoinStep::Join => {
+                if self.output_port.is_finished() {
+                    return Ok(Event::Finished);
+                }
+
+                if !self.output_port.can_push() {
+                    return Ok(Event::NeedConsume);
+                }
+
+                if let Some(data) = self.output_data.take() {
+                    self.output_port.push_data(Ok(data));
+                    return Ok(Event::NeedConsume);
+                }
+
+                Ok(Event::Sync)
+            }
+            RangeJoinStep::Finished => {
+                self.input_port.finish();
+                self.output_port.finish();
+                Ok(Event::Finished)
+            }
+        }
+    }
+
+    fn interrupt(&self) {
+        self.state.interrupt()
+    }
+
+    fn process(&mut self) -> Result<()> {
+        if let RangeJoinStep::Join = self.step {
+            if !self.left_blocks.is_empty() {
+                let left_blocks = std::mem::take(&mut self.left_blocks);
+                self.ie_join = self.state.create_ie_join(&left_blocks)?;
+            }
+
+            self.output_data = match &mut self.ie_join {
+                Some(ie_join) => self.state.next_block(ie_join)?,
+                None => None,
+            };
+            if self.output_data.is_none() {
+                self.ie_join = None;
+                self.step = RangeJoinStep::Finished;
+            }
+        }
+        Ok(())
+    }
+
+    #[async_backtrace::framed]
+    async fn async_process(&mut self) -> Result<()> {
+        if let RangeJoinStep::WaitRight = self.step {
+            self.state.wait_finish().await?;
+            self.step = RangeJoinStep::CollectLeft;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,188 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::DataBlock;
+use common_expression::Scalar;
+use common_pipeline_sources::SyncSource;
+use common_pipeline_sources::SyncSourcer;
+use common_profile::ProfSpanSetRef;
+use common_sql::executor::PhysicalPlan;
+use common_sql::executor::RecursiveUnion;
+use parking_lot::Mutex;
+
+use crate::pipelines::executor::ExecutorSettings;
+use crate::pipelines::executor::PipelinePullingExecutor;
+use crate::pipelines::processors::port::OutputPort;
+use crate::pipelines::processors::processor::ProcessorPtr;
+use crate::pipelines::PipelineBuilder;
+use crate::sessions::QueryContext;
+use crate::sessions::TableContext;
+
+/// The rows produced by the last iteration of a recursive common table expression.
+pub type WorkingTable = Arc<Mutex<Vec<DataBlock>>>;
+
+/// Evaluates a recursive common table expression.
+///
+/// The anchor member is executed first, then the recursive member is executed
+/// repeatedly on the rows produced by the previous iteration (the working table),
+/// until an iteration doesn't produce any rows. Each iteration is executed by a
+/// separate pipeline, and its result is emitted as one block.
+pub struct TransformRecursiveUnion {
+    ctx: Arc<QueryContext>,
+    plan: RecursiveUnion,
+    // Working tables of this cte and of the ctes it is nested in
+    working_tables: HashMap<String, WorkingTable>,
+    max_depth: u64,
+    depth: u64,
+    finished: bool,
+
+    // Used by `UNION` to remove the rows produced by the previous iterations
+    seen_rows: HashSet<Vec<Scalar>>,
+}
+
+impl TransformRecursiveUnion {
+    pub fn try_create(
+        ctx: Arc<QueryContext>,
+        output: Arc<OutputPort>,
+        plan: RecursiveUnion,
+        working_tables: HashMap<String, WorkingTable>,
+    ) -> Result<ProcessorPtr> {
+        let max_depth = ctx.get_settings().get_max_cte_recursive_depth()?;
+        SyncSourcer::create(ctx.clone(), output, TransformRecursiveUnion {
+            ctx,
+            plan,
+            working_tables,
+            max_depth,
+            depth: 0,
+            finished: false,
+            seen_rows: HashSet::new(),
+        })
+    }
+
+    fn execute(&self, plan: &PhysicalPlan) -> Result<Vec<DataBlock>> {
+        let ctx = QueryContext::create_from(self.ctx.clone());
+        let mut pipeline_builder =
+            PipelineBuilder::create(ctx.clone(), false, ProfSpanSetRef::default());
+        pipeline_builder.working_tables = self.working_tables.clone();
+        let mut build_res = pipeline_builder.finalize(plan)?;
+
+        let settings = ctx.get_settings();
+        build_res.set_max_threads(settings.get_max_threads()? as usize);
+        let settings = ExecutorSettings::try_create(&settings, ctx.get_id())?;
+
+        let mut executor = PipelinePullingExecutor::from_pipelines(build_res, settings)?;
+        executor.start();
+        let mut blocks = vec![];
+        while let Some(block) = executor.pull_data()? {
+            blocks.push(block);
+        }
+        Ok(blocks)
+    }
+
+    // Reorder the columns of the output of a member to the output schema of the recursive union.
+    fn project(
+        &self,
+        plan: &PhysicalPlan,
+        left: bool,
+        blocks: Vec<DataBlock>,
+    ) -> Result<DataBlock> {
+        let schema = plan.output_schema()?;
+        let projection = self
+            .plan
+            .pairs
+            .iter()
+            .map(|(l, r)| schema.index_of(if left { l } else { r }))
+            .collect::<Result<Vec<_>>>()?;
+
+        let blocks = blocks
+            .into_iter()
+            .filter(|block| !block.is_empty())
+            .map(|block| {
+                let columns = projection
+                    .iter()
+                    .map(|index| block.get_by_offset(*index).clone())
+                    .collect();
+                DataBlock::new(columns, block.num_rows())
+            })
+            .collect::<Vec<_>>();
+
+        if blocks.is_empty() {
+            return Ok(DataBlock::empty_with_schema(self.plan.schema.clone()));
+        }
+        DataBlock::concat(&blocks)
+    }
+
+    fn remove_seen_rows(&mut self, block: DataBlock) -> Result<DataBlock> {
+        let mut indices = Vec::with_capacity(block.num_rows());
+        for row in 0..block.num_rows() {
+            let values = block
+                .columns()
+                .iter()
+                .map(|entry| entry.value.index(row).unwrap().to_owned())
+                .collect::<Vec<_>>();
+            if self.seen_rows.insert(values) {
+                indices.push(row as u32);
+            }
+        }
+        if indices.len() == block.num_rows() {
+            return Ok(block);
+        }
+        block.take(&indices)
+    }
+}
+
+impl SyncSource for TransformRecursiveUnion {
+    const NAME: &'static str = "RecursiveUnion";
+
+    fn generate(&mut self) -> Result<Option<DataBlock>> {
+        if self.finished {
+            return Ok(None);
+        }
+
+        let block = if self.depth == 0 {
+            let blocks = self.execute(&self.plan.left)?;
+            self.project(&self.plan.left, true, blocks)?
+        } else {
+            let blocks = self.execute(&self.plan.right)?;
+            self.project(&self.plan.right, false, blocks)?
+        };
+        let block = if self.plan.distinct {
+            self.remove_seen_rows(block)?
+        } else {
+            block
+        };
+
+        if block.is_empty() {
+            self.finished = true;
+            return Ok(None);
+        }
+        if self.depth > self.max_depth {
+            return Err(ErrorCode::Overflow(format!(
+                "Recursive cte '{}' exceeds the maximum recursion depth {}, it can be changed by the setting `max_cte_recursive_depth`",
+                self.plan.cte_name, self.max_depth
+            )));
+        }
+
+        self.depth += 1;
+        let working_table = &self.working_tables[&self.plan.cte_name];
+        *working_table.lock() = vec![block.clone()];
+        Ok(Some(block))
+    }
+}
@@ -0,0 +1,475 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+use std::any::Any;
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use common_arrow::arrow::compute::sort::row::RowConverter as ArrowRowConverter;
+use common_arrow::arrow::compute::sort::row::Rows as ArrowRows;
+use common_exception::Result;
+use common_expression::types::DataType;
+use common_expression::types::DateType;
+use common_expression::types::NumberDataType;
+use common_expression::types::NumberType;
+use common_expression::types::StringType;
+use common_expression::types::TimestampType;
+use common_expression::with_number_mapped_type;
+use common_expression::DataBlock;
+use common_expression::DataSchemaRef;
+use common_expression::SortColumnDescription;
+use common_pipeline_core::processors::port::InputPort;
+use common_pipeline_core::processors::port::OutputPort;
+use common_pipeline_core::processors::processor::Event;
+use common_pipeline_core::processors::Processor;
+use common_pipeline_transforms::processors::transforms::sort::Cursor;
+use common_pipeline_transforms::processors::transforms::sort::RowConverter;
+use common_pipeline_transforms::processors::transforms::sort::Rows;
+use common_pipeline_transforms::processors::transforms::sort::SimpleRowConverter;
+use common_pipeline_transforms::processors::transforms::sort::SimpleRows;
+use opendal::Operator;
+
+use crate::pipelines::processors::transforms::spill_file::restore_spilled_file;
+use crate::pipelines::processors::transforms::spill_file::spill_blocks;
+use crate::pipelines::processors::transforms::spill_file::SpilledFile;
+use crate::pipelines::processors::transforms::Compactor;
+use crate::pipelines::processors::transforms::SortMergeCompactor;
+
+pub struct SortSpillParams {
+    pub operator: Operator,
+    pub location_prefix: String,
+    /// Buffered blocks are sorted and spilled as a run once they exceed this size.
+    pub threshold: usize,
+}
+
+/// A sorted run, the blocks are either kept in memory or spilled to storage.
+#[derive(Default)]
+struct SortedRun {
+    blocks: VecDeque<DataBlock>,
+    files: VecDeque<SpilledFile>,
+}
+
+enum SortSpillState {
+    Consume,
+    /// K-way merge over all of the sorted runs.
+    Merge,
+    Finished,
+}
+
+/// External merge sort. It works the same as `TransformSortMerge` if the input fits
+/// in memory, otherwise the buffered blocks are merged into sorted runs and spilled
+/// to storage, then the runs are merged when the input is finished.
+pub struct TransformSortSpill<R, Converter>
+where
+    R: Rows,
+    Converter: RowConverter<R>,
+{
+    input: Arc<InputPort>,
+    output: Arc<OutputPort>,
+    input_data: Option<DataBlock>,
+    output_data: VecDeque<DataBlock>,
+    input_finished: bool,
+
+    compactor: SortMergeCompactor<R, Converter>,
+    params: SortSpillParams,
+    block_size: usize,
+    limit: Option<usize>,
+    /// Sort fields' indices in the output schema
+    sort_field_indices: Vec<usize>,
+    row_converter: Converter,
+
+    buffered_blocks: Vec<DataBlock>,
+    buffered_size: usize,
+    /// The sorted run waiting to be spilled.
+    spilling_blocks: Vec<DataBlock>,
+    runs: Vec<SortedRun>,
+
+    /// The current blocks of each run, only the last one is not drained.
+    merging_blocks: Vec<VecDeque<DataBlock>>,
+    heap: BinaryHeap<Reverse<Cursor<R>>>,
+    /// Data format: (run_index, block_index, row_index)
+    in_progress_rows: Vec<(usize, usize, usize)>,
+    state: SortSpillState,
+}
+
+impl<R, Converter> TransformSortSpill<R, Converter>
+where
+    R: Rows + Send + 'static,
+    Converter: RowConverter<R> + Send + 'static,
+{
+    pub fn try_create(
+        input: Arc<InputPort>,
+        output: Arc<OutputPort>,
+        output_schema: DataSchemaRef,
+        block_size: usize,
+        limit: Option<usize>,
+        sort_columns_descriptions: Vec<SortColumnDescription>,
+        params: SortSpillParams,
+    ) -> Result<Box<dyn Processor>> {
+        let sort_field_indices = sort_columns_descriptions
+            .iter()
+            .map(|d| d.offset)
+            .collect::<Vec<_>>();
+        let row_converter =
+            Converter::create(sort_columns_descriptions.clone(), output_schema.clone())?;
+        let compactor = SortMergeCompactor::try_create(
+            output_schema,
+            block_size,
+            limit,
+            sort_columns_descriptions,
+        )?;
+        Ok(Box::new(TransformSortSpill {
+            input,
+            output,
+            input_data: None,
+            output_data: VecDeque::new(),
+            input_finished: false,
+            compactor,
+            params,
+            block_size,
+            limit,
+            sort_field_indices,
+            row_converter,
+            buffered_blocks: vec![],
+            buffered_size: 0,
+            spilling_blocks: vec![],
+            runs: vec![],
+            merging_blocks: vec![],
+            heap: BinaryHeap::new(),
+            in_progress_rows: vec![],
+            state: SortSpillState::Consume,
+        }))
+    }
+
+    // Push the next block of the run into the heap, if any.
+    #[async_backtrace::framed]
+    async fn load_block(&mut self, run_index: usize) -> Result<()> {
+        let run = &mut self.runs[run_index];
+        let block = match run.blocks.pop_front() {
+            Some(block) => block,
+            None => match run.files.pop_front() {
+                Some(file) => restore_spilled_file(&self.params.operator, &file).await?,
+                None => return Ok(()),
+            },
+        };
+
+        let columns = self
+            .sort_field_indices
+            .iter()
+            .map(|i| block.get_by_offset(*i).clone())
+            .collect::<Vec<_>>();
+        let rows = self.row_converter.convert(&columns, block.num_rows())?;
+        self.heap.push(Reverse(Cursor::try_create(run_index, rows)));
+        self.merging_blocks[run_index].push_back(block);
+        Ok(())
+    }
+
+    /// Merge the runs into the next output block, returns None if all the runs are drained.
+    #[async_backtrace::framed]
+    async fn merge_next_block(&mut self) -> Result<Option<DataBlock>> {
+        let output_size = match self.limit {
+            Some(limit) => limit.min(self.block_size),
+            None => self.block_size,
+        };
+
+        while self.in_progress_rows.len() < output_size {
+            let mut cursor = match self.heap.pop() {
+                Some(Reverse(cursor)) => cursor,
+                None => break,
+            };
+
+            let run_index = cursor.input_index;
+            let block_index = self.merging_blocks[run_index].len() - 1;
+            while self.in_progress_rows.len() < output_size
+                && !cursor.is_finished()
+                && self.heap.peek().map_or(true, |next| cursor.le(&next.0))
+            {
+                self.in_progress_rows
+                    .push((run_index, block_index, cursor.advance()));
+            }
+
+            if cursor.is_finished() {
+                // The previous blocks are kept until the output block is built.
+                self.load_block(run_index).await?;
+            } else {
+                self.heap.push(Reverse(cursor));
+            }
+        }
+
+        if self.in_progress_rows.is_empty() {
+            return Ok(None);
+        }
+
+        let block = self.build_block();
+        self.limit = self.limit.map(|limit| limit - block.num_rows());
+        Ok(Some(block))
+    }
+
+    /// Drain `self.in_progress_rows` to build an output data block.
+    fn build_block(&mut self) -> DataBlock {
+        let mut blocks_num_pre_sum = Vec::with_capacity(self.merging_blocks.len());
+        let mut len = 0;
+        for blocks in self.merging_blocks.iter() {
+            blocks_num_pre_sum.push(len);
+            len += blocks.len();
+        }
+
+        let mut merge_slices: Vec<(usize, usize, usize)> = Vec::new();
+        for (run_index, block_index, row_index) in self.in_progress_rows.iter() {
+            let index = blocks_num_pre_sum[*run_index] + block_index;
+            match merge_slices.last_mut() {
+                Some(last) if last.0 == index && last.1 + last.2 == *row_index => last.2 += 1,
+                _ => merge_slices.push((index, *row_index, 1)),
+            }
+        }
+
+        let blocks = self
+            .merging_blocks
+            .iter()
+            .flatten()
+            .cloned()
+            .collect::<Vec<_>>();
+        let block = DataBlock::take_by_slices_limit_from_blocks(&blocks, &merge_slices, None);
+
+        self.in_progress_rows.clear();
+        // A new block of a run is loaded only if the previous block is finished,
+        // so all the blocks except the last one of each run are drained.
+        for blocks in self.merging_blocks.iter_mut() {
+            if blocks.len() > 1 {
+                blocks.drain(0..(blocks.len() - 1));
+            }
+        }
+        block
+    }
+}
+
+#[async_trait::async_trait]
+impl<R, Converter> Processor for TransformSortSpill<R, Converter>
+where
+    R: Rows + Send + 'static,
+    Converter: RowConverter<R> + Send + 'static,
+{
+    fn name(&self) -> String {
+        "SortSpillTransform".to_string()
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if self.output.is_finished() {
+            self.input.finish();
+            return Ok(Event::Finished);
+        }
+
+        if !self.output.can_push() {
+            self.input.set_not_need_data();
+            return Ok(Event::NeedConsume);
+        }
+
+        if let Some(data_block) = self.output_data.pop_front() {
+            self.output.push_data(Ok(data_block));
+            return Ok(Event::NeedConsume);
+        }
+
+        match self.state {
+            SortSpillState::Consume => {
+                if self.input_data.is_some() || self.input_finished {
+                    return Ok(Event::Sync);
+                }
+
+                if !self.spilling_blocks.is_empty() {
+                    return Ok(Event::Async);
+                }
+
+                if self.input.has_data() {
+                    self.input_data = Some(self.input.pull_data().unwrap()?);
+                    return Ok(Event::Sync);
+                }
+
+                if self.input.is_finished() {
+                    self.input_finished = true;
+                    return Ok(Event::Sync);
+                }
+
+                self.input.set_need_data();
+                Ok(Event::NeedData)
+            }
+            SortSpillState::Merge => Ok(Event::Async),
+            SortSpillState::Finished => {
+                self.output.finish();
+                Ok(Event::Finished)
+            }
+        }
+    }
+
+    fn interrupt(&self) {
+        self.compactor.interrupt()
+    }
+
+    fn process(&mut self) -> Result<()> {
+        if let Some(data_block) = self.input_data.take() {
+            self.buffered_size += data_block.memory_size();
+            self.buffered_blocks.push(data_block);
+            if self.buffered_size >= self.params.threshold {
+                self.spilling_blocks = self.compactor.compact_final(&self.buffered_blocks)?;
+                self.buffered_blocks.clear();
+                self.buffered_size = 0;
+            }
+            return Ok(());
+        }
+
+        if self.input_finished {
+            let blocks = self.compactor.compact_final(&self.buffered_blocks)?;
+            self.buffered_blocks.clear();
+            self.buffered_size = 0;
+
+            if self.runs.is_empty() {
+                // Nothing is spilled, the blocks are already sorted.
+                self.output_data.extend(blocks);
+                self.state = SortSpillState::Finished;
+            } else {
+                self.runs.push(SortedRun {
+                    blocks: blocks.into(),
+                    files: VecDeque::new(),
+                });
+                self.merging_blocks = vec![VecDeque::new(); self.runs.len()];
+                self.state = SortSpillState::Merge;
+            }
+            self.input_finished = false;
+        }
+        Ok(())
+    }
+
+    #[async_backtrace::framed]
+    async fn async_process(&mut self) -> Result<()> {
+        match self.state {
+            SortSpillState::Consume => {
+                let mut run = SortedRun::default();
+                for block in std::mem::take(&mut self.spilling_blocks) {
+                    let file =
+                        spill_blocks(&self.params.operator, &self.params.location_prefix, &[
+                            block,
+                        ])
+                        .await?;
+                    run.files.push_back(file);
+                }
+                self.runs.push(run);
+            }
+            SortSpillState::Merge => {
+                if self.heap.is_empty() && self.in_progress_rows.is_empty() {
+                    // Push the first block of each run into the heap.
+                    for run_index in 0..self.runs.len() {
+                        self.load_block(run_index).await?;
+                    }
+                }
+
+                match self.merge_next_block().await? {
+                    Some(data_block) => self.output_data.push_back(data_block),
+                    None => self.state = SortSpillState::Finished,
+                }
+            }
+            SortSpillState::Finished => {}
+        }
+        Ok(())
+    }
+}
+
+pub fn create_transform_sort_spill(
+    input: Arc<InputPort>,
+    output: Arc<OutputPort>,
+    output_schema: DataSchemaRef,
+    block_size: usize,
+    limit: Option<usize>,
+    sort_columns_descriptions: Vec<SortColumnDescription>,
+    params: SortSpillParams,
+) -> Result<Box<dyn Processor>> {
+    if sort_columns_descriptions.len() == 1 {
+        let sort_type = output_schema
+            .field(sort_columns_descriptions[0].offset)
+            .data_type();
+        match sort_type {
+            DataType::Number(num_ty) => with_number_mapped_type!(|NUM_TYPE| match num_ty {
+                NumberDataType::NUM_TYPE => TransformSortSpill::<
+                    SimpleRows<NumberType<NUM_TYPE>>,
+                    SimpleRowConverter<NumberType<NUM_TYPE>>,
+                >::try_create(
+                    input,
+                    output,
+                    output_schema,
+                    block_size,
+                    limit,
+                    sort_columns_descriptions,
+                    params,
+                ),
+            }),
+            DataType::Date => {
+                TransformSortSpill::<SimpleRows<DateType>, SimpleRowConverter<DateType>>::try_create(
+                    input,
+                    output,
+                    output_schema,
+                    block_size,
+                    limit,
+                    sort_columns_descriptions,
+                    params,
+                )
+            }
+            DataType::Timestamp => TransformSortSpill::<
+                SimpleRows<TimestampType>,
+                SimpleRowConverter<TimestampType>,
+            >::try_create(
+                input,
+                output,
+                output_schema,
+                block_size,
+                limit,
+                sort_columns_descriptions,
+                params,
+            ),
+            DataType::String => TransformSortSpill::<
+                SimpleRows<StringType>,
+                SimpleRowConverter<StringType>,
+            >::try_create(
+                input,
+                output,
+                output_schema,
+                block_size,
+                limit,
+                sort_columns_descriptions,
+                params,
+            ),
+            _ => TransformSortSpill::<ArrowRows, ArrowRowConverter>::try_create(
+                input,
+                output,
+                output_schema,
+                block_size,
+                limit,
+                sort_columns_descriptions,
+                params,
+            ),
+        }
+    } else {
+        TransformSortSpill::<ArrowRows, ArrowRowConverter>::try_create(
+            input,
+            output,
+            output_schema,
+            block_size,
+            limit,
+            sort_columns_descriptions,
+            params,
+        )
+    }
+}
@@ -16,4 +16,5 @@ mod transform_window;
 mod window_function;
 
 pub use transform_window::TransformWindow;
+pub use transform_window::WindowSpillParams;
 pub use window_function::WindowFunctionInfo;
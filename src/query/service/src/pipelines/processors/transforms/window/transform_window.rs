@@ -21,13 +21,17 @@ use std::cmp::Ordering;
 use std::collections::VecDeque;
 use std::sync::Arc;
 
+use common_exception::ErrorCode;
 use common_exception::Result;
+use common_expression::types::number::F64;
 use common_expression::types::NumberScalar;
 use common_expression::BlockEntry;
 use common_expression::Column;
 use common_expression::ColumnBuilder;
 use common_expression::DataBlock;
+use common_expression::Scalar;
 use common_expression::ScalarRef;
+use common_expression::SortColumnDescription;
 use common_expression::Value;
 use common_pipeline_core::processors::port::InputPort;
 use common_pipeline_core::processors::port::OutputPort;
@@ -35,10 +39,18 @@ use common_pipeline_core::processors::processor::Event;
 use common_pipeline_core::processors::Processor;
 use common_sql::plans::WindowFuncFrame;
 use common_sql::plans::WindowFuncFrameBound;
+use common_sql::plans::WindowFuncFrameUnits;
+use opendal::Operator;
 
 use super::window_function::WindowFuncAggImpl;
+use super::window_function::WindowFuncLagLeadImpl;
+use super::window_function::WindowFuncNthValueImpl;
 use super::window_function::WindowFunctionImpl;
 use super::WindowFunctionInfo;
+use crate::pipelines::processors::transforms::spill_file::read_spilled_file;
+use crate::pipelines::processors::transforms::spill_file::restore_spilled_file;
+use crate::pipelines::processors::transforms::spill_file::spill_blocks;
+use crate::pipelines::processors::transforms::spill_file::SpilledFile;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Default)]
 struct RowPtr {
@@ -53,10 +65,155 @@ impl RowPtr {
     }
 }
 
-#[derive(Clone)]
+/// The bound of a frame, the offset is the number of rows for ROWS frames,
+/// and the distance from the ORDER BY key of the current row for RANGE frames.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum FrameBound<T> {
+    CurrentRow,
+    Preceding(Option<T>),
+    Following(Option<T>),
+}
+
+impl<T> FrameBound<T> {
+    fn try_create(
+        bound: &WindowFuncFrameBound,
+        offset: impl Fn(&Scalar) -> Option<T>,
+    ) -> Result<Self> {
+        let resolve = |n: &Option<Scalar>| match n {
+            None => Ok(None),
+            Some(n) => offset(n)
+                .map(Some)
+                .ok_or_else(|| ErrorCode::Internal(format!("Invalid offset of window frame: {n}"))),
+        };
+        Ok(match bound {
+            WindowFuncFrameBound::CurrentRow => FrameBound::CurrentRow,
+            WindowFuncFrameBound::Preceding(n) => FrameBound::Preceding(resolve(n)?),
+            WindowFuncFrameBound::Following(n) => FrameBound::Following(resolve(n)?),
+        })
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum FrameKind {
+    Rows(FrameBound<usize>, FrameBound<usize>),
+    Range(FrameBound<RangeValue>, FrameBound<RangeValue>),
+}
+
+impl FrameKind {
+    fn try_create(frame: &WindowFuncFrame) -> Result<Self> {
+        Ok(match frame.units {
+            WindowFuncFrameUnits::Rows => {
+                let rows = |n: &Scalar| match n {
+                    Scalar::Number(NumberScalar::UInt64(n)) => Some(*n as usize),
+                    _ => None,
+                };
+                FrameKind::Rows(
+                    FrameBound::try_create(&frame.start_bound, rows)?,
+                    FrameBound::try_create(&frame.end_bound, rows)?,
+                )
+            }
+            WindowFuncFrameUnits::Range => {
+                let range = |n: &Scalar| RangeValue::from_scalar(n.as_ref());
+                FrameKind::Range(
+                    FrameBound::try_create(&frame.start_bound, range)?,
+                    FrameBound::try_create(&frame.end_bound, range)?,
+                )
+            }
+        })
+    }
+
+    /// Whether the bounds are found without reading the rows, the bounds of ROWS frames
+    /// are unbounded or the current row, and RANGE frames are unbounded on both sides.
+    fn is_unbounded_or_current_row(&self) -> bool {
+        let is_positional = |bound: &FrameBound<usize>| {
+            matches!(
+                bound,
+                FrameBound::CurrentRow | FrameBound::Preceding(None) | FrameBound::Following(None)
+            )
+        };
+        match self {
+            FrameKind::Rows(start, end) => is_positional(start) && is_positional(end),
+            FrameKind::Range(_, _) => self.unbounded() == (true, true),
+        }
+    }
+
+    /// Whether the frame starts from the start of the partition,
+    /// and whether it ends at the end of the partition.
+    fn unbounded(&self) -> (bool, bool) {
+        match self {
+            FrameKind::Rows(start, end) => (
+                matches!(start, FrameBound::Preceding(None)),
+                matches!(end, FrameBound::Following(None)),
+            ),
+            FrameKind::Range(start, end) => (
+                matches!(start, FrameBound::Preceding(None)),
+                matches!(end, FrameBound::Following(None)),
+            ),
+        }
+    }
+}
+
+/// A value of the ORDER BY key of RANGE frames, or the offset from it.
+///
+/// Dates are the number of days, and timestamps are the number of microseconds.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+enum RangeValue {
+    Int(i128),
+    Float(f64),
+}
+
+impl RangeValue {
+    /// `None` if the value is NULL.
+    fn from_scalar(value: ScalarRef) -> Option<Self> {
+        Some(match value {
+            ScalarRef::Number(number) => match number {
+                NumberScalar::UInt8(v) => RangeValue::Int(v as i128),
+                NumberScalar::UInt16(v) => RangeValue::Int(v as i128),
+                NumberScalar::UInt32(v) => RangeValue::Int(v as i128),
+                NumberScalar::UInt64(v) => RangeValue::Int(v as i128),
+                NumberScalar::Int8(v) => RangeValue::Int(v as i128),
+                NumberScalar::Int16(v) => RangeValue::Int(v as i128),
+                NumberScalar::Int32(v) => RangeValue::Int(v as i128),
+                NumberScalar::Int64(v) => RangeValue::Int(v as i128),
+                NumberScalar::Float32(v) => RangeValue::Float(v.0 as f64),
+                NumberScalar::Float64(v) => RangeValue::Float(v.0),
+            },
+            ScalarRef::Date(v) => RangeValue::Int(v as i128),
+            ScalarRef::Timestamp(v) => RangeValue::Int(v as i128),
+            _ => return None,
+        })
+    }
+
+    // The planner makes sure that the offset is a float if and only if the key is a float.
+    fn add_offset(self, offset: RangeValue) -> RangeValue {
+        match (self, offset) {
+            (RangeValue::Int(v), RangeValue::Int(n)) => RangeValue::Int(v + n),
+            (RangeValue::Float(v), RangeValue::Float(n)) => RangeValue::Float(v + n),
+            _ => unreachable!(),
+        }
+    }
+
+    fn sub_offset(self, offset: RangeValue) -> RangeValue {
+        match (self, offset) {
+            (RangeValue::Int(v), RangeValue::Int(n)) => RangeValue::Int(v - n),
+            (RangeValue::Float(v), RangeValue::Float(n)) => RangeValue::Float(v - n),
+            _ => unreachable!(),
+        }
+    }
+}
+
 struct WindowBlock {
     block: DataBlock,
     builder: ColumnBuilder,
+    /// The block is replaced by an empty block with the same number of rows once spilled.
+    spilled: Option<SpilledFile>,
+}
+
+pub struct WindowSpillParams {
+    pub operator: Operator,
+    pub location_prefix: String,
+    /// The blocks of the current partition are spilled once the buffered blocks exceed this size.
+    pub threshold: usize,
 }
 
 /// The input [`DataBlock`] of [`TransformWindow`] should be sorted by partition and order by columns.
@@ -71,7 +228,7 @@ pub struct TransformWindow {
     func: WindowFunctionImpl,
 
     partition_indices: Vec<usize>,
-    order_by_indices: Vec<usize>,
+    order_by: Vec<SortColumnDescription>,
 
     /// A queue of data blocks that we need to process.
     /// If partition is ended, we may free the data block from front of the queue.
@@ -86,9 +243,12 @@ pub struct TransformWindow {
     partition_start: RowPtr,
     partition_end: RowPtr,
     partition_ended: bool,
+    // The values of the PARTITION BY columns of the current partition,
+    // the block of the partition start may have been output or spilled.
+    partition_keys: Vec<Scalar>,
 
     // Frame: [`frame_start`, `frame_end`). `frame_end` is excluded.
-    frame_kind: WindowFuncFrame,
+    frame_kind: FrameKind,
     frame_start: RowPtr,
     frame_end: RowPtr,
     frame_started: bool,
@@ -106,6 +266,26 @@ pub struct TransformWindow {
     current_rank: usize,
     current_rank_count: usize,
     current_dense_rank: usize,
+
+    // Used for ntile, percent_rank and cume_dist, which need the whole partition.
+    partition_rows: usize,
+    // Used for cume_dist, the position of the last peer of the current row in the partition.
+    current_peer_end: usize,
+
+    // Used for the navigation functions with IGNORE NULLS, the rows of the current frame
+    // whose values are not NULL, and the row where the scan for them continues.
+    frame_non_null_rows: VecDeque<RowPtr>,
+    non_null_scan_end: RowPtr,
+
+    /// Spill the blocks of large partitions to storage, `None` if spilling is disabled.
+    spill_params: Option<WindowSpillParams>,
+    /// The memory size of the blocks in the queue that are not spilled.
+    buffered_size: usize,
+    spilled_blocks: usize,
+    /// The spilled block that the current row needs to read.
+    restore_block: Option<usize>,
+    // Whether the aggregation over the whole current partition has been computed from the spilled blocks.
+    partition_aggregated: bool,
 }
 
 impl TransformWindow {
@@ -115,17 +295,16 @@ impl TransformWindow {
         output: Arc<OutputPort>,
         func: WindowFunctionInfo,
         partition_indices: Vec<usize>,
-        order_by_indices: Vec<usize>,
+        order_by: Vec<SortColumnDescription>,
         frame_kind: WindowFuncFrame,
+        spill_params: Option<WindowSpillParams>,
     ) -> Result<Box<dyn Processor>> {
-        let transform = Self::create(
-            input,
-            output,
-            func,
-            partition_indices,
-            order_by_indices,
-            frame_kind,
-        )?;
+        let mut transform =
+            Self::create(input, output, func, partition_indices, order_by, frame_kind)?;
+        // Only the partitions whose rows are read from the current row on can be spilled.
+        if transform.reads_from_current_row() {
+            transform.spill_params = spill_params;
+        }
         Ok(Box::new(transform))
     }
 
@@ -134,23 +313,25 @@ impl TransformWindow {
         output: Arc<OutputPort>,
         func: WindowFunctionInfo,
         partition_indices: Vec<usize>,
-        order_by_indices: Vec<usize>,
+        order_by: Vec<SortColumnDescription>,
         frame_kind: WindowFuncFrame,
     ) -> Result<Self> {
         let func = WindowFunctionImpl::try_create(func)?;
+        let frame_kind = FrameKind::try_create(&frame_kind)?;
         Ok(Self {
             input,
             output,
             state: ProcessorState::Consume,
             func,
             partition_indices,
-            order_by_indices,
+            order_by,
             blocks: VecDeque::new(),
             outputs: VecDeque::new(),
             first_block: 0,
             partition_start: RowPtr::default(),
             partition_end: RowPtr::default(),
             partition_ended: false,
+            partition_keys: vec![],
             frame_kind,
             frame_start: RowPtr::default(),
             frame_end: RowPtr::default(),
@@ -163,7 +344,16 @@ impl TransformWindow {
             current_rank: 1,
             current_rank_count: 1,
             current_dense_rank: 1,
+            partition_rows: 0,
+            current_peer_end: 0,
+            frame_non_null_rows: VecDeque::new(),
+            non_null_scan_end: RowPtr::default(),
             input_is_finished: false,
+            spill_params: None,
+            buffered_size: 0,
+            spilled_blocks: 0,
+            restore_block: None,
+            partition_aggregated: false,
         })
     }
 
@@ -191,6 +381,16 @@ impl TransformWindow {
             .unwrap()
     }
 
+    #[inline(always)]
+    fn is_spilled(&self, block: usize) -> bool {
+        self.spilled_blocks > 0
+            && block >= self.first_block
+            && self
+                .blocks
+                .get(block - self.first_block)
+                .map_or(false, |window_block| window_block.spilled.is_some())
+    }
+
     fn add_rows_within_partition(&self, mut cur: RowPtr, mut n: usize) -> RowPtr {
         debug_assert!(cur.ge(&self.partition_start) && cur.le(&self.partition_end));
 
@@ -233,16 +433,23 @@ impl TransformWindow {
             return;
         }
 
+        if self.partition_keys.is_empty() {
+            self.partition_keys = self
+                .partition_indices
+                .iter()
+                .map(|index| self.value_at(self.partition_start, *index))
+                .collect();
+        }
+
         let block_rows = self.block_rows(self.partition_end);
 
         while self.partition_end.row < block_rows {
             let mut i = 0;
             while i < partition_by_columns {
-                let start_column = self.column_at(self.partition_start, self.partition_indices[i]);
                 let compare_column = self.column_at(self.partition_end, self.partition_indices[i]);
 
-                if start_column.index(self.partition_start.row)
-                    != compare_column.index(self.partition_end.row)
+                if compare_column.index(self.partition_end.row)
+                    != Some(self.partition_keys[i].as_ref())
                 {
                     break;
                 }
@@ -267,48 +474,62 @@ impl TransformWindow {
         if self.frame_started {
             return;
         }
-        match &self.frame_kind.start_bound {
-            WindowFuncFrameBound::CurrentRow => {
+        match self.frame_kind {
+            FrameKind::Rows(start, _) => self.advance_rows_frame_start(start),
+            FrameKind::Range(start, _) => self.advance_range_frame_start(start),
+        }
+    }
+
+    fn advance_frame_end(&mut self) {
+        match self.frame_kind {
+            FrameKind::Rows(_, end) => self.advance_rows_frame_end(end),
+            FrameKind::Range(_, end) => self.advance_range_frame_end(end),
+        }
+    }
+
+    fn advance_rows_frame_start(&mut self, bound: FrameBound<usize>) {
+        match bound {
+            FrameBound::CurrentRow => {
                 self.frame_started = true;
                 self.frame_start = self.current_row;
             }
-            WindowFuncFrameBound::Preceding(Some(n)) => {
+            FrameBound::Preceding(Some(n)) => {
                 self.frame_started = true;
-                if self.current_row_in_partition - 1 <= *n {
+                if self.current_row_in_partition - 1 <= n {
                     self.frame_start = self.partition_start;
                 } else {
                     self.frame_start = self.advance_row(self.prev_frame_start);
                 }
             }
-            WindowFuncFrameBound::Preceding(_) => {
+            FrameBound::Preceding(_) => {
                 self.frame_started = true;
                 self.frame_start = self.partition_start;
             }
-            WindowFuncFrameBound::Following(Some(n)) => {
+            FrameBound::Following(Some(n)) => {
                 self.frame_start = if self.current_row_in_partition == 1 {
-                    self.add_rows_within_partition(self.current_row, *n)
+                    self.add_rows_within_partition(self.current_row, n)
                 } else {
                     self.advance_row(self.prev_frame_start)
                         .min(self.partition_end)
                 };
                 self.frame_started = self.partition_ended || self.frame_start < self.partition_end;
             }
-            WindowFuncFrameBound::Following(_) => {
+            FrameBound::Following(_) => {
                 unreachable!()
             }
         }
     }
 
-    fn advance_frame_end(&mut self) {
-        match &self.frame_kind.end_bound {
-            WindowFuncFrameBound::CurrentRow => {
+    fn advance_rows_frame_end(&mut self, bound: FrameBound<usize>) {
+        match bound {
+            FrameBound::CurrentRow => {
                 self.frame_ended = true;
                 // `self.frame_end` is excluded.
                 self.frame_end = self.advance_row(self.current_row);
             }
-            WindowFuncFrameBound::Preceding(Some(n)) => {
+            FrameBound::Preceding(Some(n)) => {
                 self.frame_ended = true;
-                match (self.current_row_in_partition - 1).cmp(n) {
+                match (self.current_row_in_partition - 1).cmp(&n) {
                     Ordering::Less => {
                         self.frame_end = self.partition_start;
                     }
@@ -320,12 +541,12 @@ impl TransformWindow {
                     }
                 }
             }
-            WindowFuncFrameBound::Preceding(_) => {
+            FrameBound::Preceding(_) => {
                 unreachable!()
             }
-            WindowFuncFrameBound::Following(Some(n)) => {
+            FrameBound::Following(Some(n)) => {
                 self.frame_end = if self.current_row_in_partition == 1 {
-                    let next_end = self.add_rows_within_partition(self.current_row, *n);
+                    let next_end = self.add_rows_within_partition(self.current_row, n);
                     self.frame_ended = self.partition_ended || next_end < self.partition_end;
                     // `self.frame_end` is excluded.
                     self.advance_row(next_end)
@@ -336,16 +557,131 @@ impl TransformWindow {
                 }
                 .min(self.partition_end);
             }
-            WindowFuncFrameBound::Following(_) => {
+            FrameBound::Following(_) => {
                 self.frame_ended = self.partition_ended;
                 self.frame_end = self.partition_end;
             }
         }
     }
 
+    // Where the search of a frame bound starts: the bound of the previous row,
+    // or the start of the partition for the first row of it.
+    #[inline]
+    fn search_start(&self, prev: RowPtr) -> RowPtr {
+        if self.current_row_in_partition == 1 {
+            self.partition_start
+        } else {
+            prev
+        }
+    }
+
+    fn advance_range_frame_start(&mut self, bound: FrameBound<RangeValue>) {
+        // The frame start never moves backward in a partition.
+        let mut row = self.search_start(self.prev_frame_start);
+        match bound {
+            FrameBound::CurrentRow => {
+                // The first peer of the current row.
+                while row < self.current_row && !self.is_order_by_keys_equal(row, self.current_row)
+                {
+                    row = self.advance_row(row);
+                }
+            }
+            FrameBound::Preceding(None) => {
+                row = self.partition_start;
+            }
+            FrameBound::Preceding(Some(_)) | FrameBound::Following(Some(_)) => {
+                // The first row whose key is not before the bound value.
+                let value = self.range_bound_value(bound);
+                while row < self.partition_end
+                    && self.compare_range_value(row, value) == Ordering::Less
+                {
+                    row = self.advance_row(row);
+                }
+            }
+            FrameBound::Following(None) => {
+                unreachable!()
+            }
+        }
+        self.frame_start = row;
+        self.frame_started = self.partition_ended || row < self.partition_end;
+    }
+
+    fn advance_range_frame_end(&mut self, bound: FrameBound<RangeValue>) {
+        // The frame end never moves backward in a partition.
+        let mut row = self.search_start(self.prev_frame_end);
+        match bound {
+            FrameBound::CurrentRow => {
+                // The row after the last peer of the current row.
+                row = row.max(self.current_row);
+                while row < self.partition_end && self.is_order_by_keys_equal(row, self.current_row)
+                {
+                    row = self.advance_row(row);
+                }
+            }
+            FrameBound::Preceding(Some(_)) | FrameBound::Following(Some(_)) => {
+                // The first row whose key is after the bound value.
+                let value = self.range_bound_value(bound);
+                while row < self.partition_end
+                    && self.compare_range_value(row, value) != Ordering::Greater
+                {
+                    row = self.advance_row(row);
+                }
+            }
+            FrameBound::Following(None) => {
+                row = self.partition_end;
+            }
+            FrameBound::Preceding(None) => {
+                unreachable!()
+            }
+        }
+        // `self.frame_end` is excluded.
+        self.frame_end = row;
+        self.frame_ended = self.partition_ended || row < self.partition_end;
+    }
+
+    /// The value of the ORDER BY key at `row`, `None` if it is NULL.
+    #[inline]
+    fn range_value(&self, row: RowPtr) -> Option<RangeValue> {
+        let column = self.column_at(row, self.order_by[0].offset);
+        RangeValue::from_scalar(column.index(row.row).unwrap())
+    }
+
+    /// The value of the ORDER BY key at the offset bound of the current row, `None` if it is NULL.
+    fn range_bound_value(&self, bound: FrameBound<RangeValue>) -> Option<RangeValue> {
+        let value = self.range_value(self.current_row)?;
+        // `PRECEDING` means the smaller keys if ascending, and the larger keys if descending.
+        Some(match (bound, self.order_by[0].asc) {
+            (FrameBound::Preceding(Some(offset)), true)
+            | (FrameBound::Following(Some(offset)), false) => value.sub_offset(offset),
+            (FrameBound::Preceding(Some(offset)), false)
+            | (FrameBound::Following(Some(offset)), true) => value.add_offset(offset),
+            _ => value,
+        })
+    }
+
+    /// Compare the ORDER BY key at `row` with `value` in the sort order.
+    fn compare_range_value(&self, row: RowPtr, value: Option<RangeValue>) -> Ordering {
+        let desc = &self.order_by[0];
+        match (self.range_value(row), value) {
+            (None, None) => Ordering::Equal,
+            (None, Some(_)) if desc.nulls_first => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (Some(_), None) if desc.nulls_first => Ordering::Greater,
+            (Some(_), None) => Ordering::Less,
+            (Some(lhs), Some(rhs)) => {
+                let ordering = lhs.partial_cmp(&rhs).unwrap_or(Ordering::Equal);
+                if desc.asc {
+                    ordering
+                } else {
+                    ordering.reverse()
+                }
+            }
+        }
+    }
+
     // Advance the current row to the next row
     // if the current row is the last row of the current block, advance the current block and row = 0
-    fn advance_row(&mut self, mut row: RowPtr) -> RowPtr {
+    fn advance_row(&self, mut row: RowPtr) -> RowPtr {
         if row == self.blocks_end() {
             return row;
         }
@@ -358,15 +694,143 @@ impl TransformWindow {
         row
     }
 
+    // The row before `row`, `row` must be after the start of the partition.
+    fn prev_row(&self, mut row: RowPtr) -> RowPtr {
+        if row.row > 0 {
+            row.row -= 1;
+            return row;
+        }
+        loop {
+            row.block -= 1;
+            let rows = self.block_rows(row);
+            if rows > 0 {
+                row.row = rows - 1;
+                return row;
+            }
+        }
+    }
+
+    /// Find the `n`-th (starts from 1) row in the current frame,
+    /// counting from the end of the frame if `from_end`.
+    ///
+    /// If `ignore_nulls`, the rows whose value of `column` is NULL are not counted.
+    fn nth_row_in_frame(
+        &self,
+        column: usize,
+        n: usize,
+        from_end: bool,
+        ignore_nulls: bool,
+    ) -> Option<RowPtr> {
+        if ignore_nulls {
+            debug_assert_eq!(self.ignore_nulls_arg(), Some(column));
+            let rows = &self.frame_non_null_rows;
+            let index = if from_end {
+                rows.len().checked_sub(n)?
+            } else {
+                n.checked_sub(1)?
+            };
+            return rows.get(index).copied();
+        }
+
+        let mut count = 0;
+        if from_end {
+            let mut row = self.frame_end;
+            while row > self.frame_start {
+                row = self.prev_row(row);
+                count += 1;
+                if count == n {
+                    return Some(row);
+                }
+            }
+        } else {
+            let mut row = self.frame_start;
+            while row < self.frame_end {
+                count += 1;
+                if count == n {
+                    return Some(row);
+                }
+                row = self.advance_row(row);
+            }
+        }
+        None
+    }
+
+    /// The argument of the navigation function whose NULL values are ignored.
+    fn ignore_nulls_arg(&self) -> Option<usize> {
+        match &self.func {
+            WindowFunctionImpl::LagLead(lag_lead)
+                if lag_lead.ignore_nulls && lag_lead.offset > 0 =>
+            {
+                Some(lag_lead.arg)
+            }
+            WindowFunctionImpl::NthValue(nth) if nth.ignore_nulls => Some(nth.arg),
+            _ => None,
+        }
+    }
+
+    /// Slide the non-NULL rows to the current frame. Both bounds of the frame never move backward
+    /// in a partition, so every row is scanned once.
+    fn update_frame_non_null_rows(&mut self, column: usize) {
+        let mut row = self.non_null_scan_end.max(self.frame_start);
+        while row < self.frame_end {
+            if !matches!(
+                self.column_at(row, column).index(row.row),
+                Some(ScalarRef::Null)
+            ) {
+                self.frame_non_null_rows.push_back(row);
+            }
+            row = self.advance_row(row);
+        }
+        self.non_null_scan_end = row;
+        while matches!(self.frame_non_null_rows.front(), Some(row) if *row < self.frame_start) {
+            self.frame_non_null_rows.pop_front();
+        }
+    }
+
+    #[inline]
+    fn value_at(&self, row: RowPtr, column: usize) -> Scalar {
+        self.column_at(row, column)
+            .index(row.row)
+            .unwrap()
+            .to_owned()
+    }
+
+    fn lag_lead_value(&self, lag_lead: &WindowFuncLagLeadImpl) -> Scalar {
+        // Without `IGNORE NULLS`, the frame contains only the row at the offset.
+        // Otherwise the frame contains all the rows before (lag) or after (lead) the current row,
+        // and the row at the offset is counted from the current row.
+        let row = if lag_lead.ignore_nulls && lag_lead.offset > 0 {
+            self.nth_row_in_frame(lag_lead.arg, lag_lead.offset, lag_lead.is_lag, true)
+        } else {
+            self.nth_row_in_frame(lag_lead.arg, 1, false, false)
+        };
+        match (row, lag_lead.default) {
+            (Some(row), _) => self.value_at(row, lag_lead.arg),
+            (None, Some(default)) => self.value_at(self.current_row, default),
+            (None, None) => Scalar::Null,
+        }
+    }
+
+    fn nth_value(&self, nth: &WindowFuncNthValueImpl) -> Scalar {
+        let row = match nth.n {
+            Some(n) => self.nth_row_in_frame(nth.arg, n, false, nth.ignore_nulls),
+            None => self.nth_row_in_frame(nth.arg, 1, true, nth.ignore_nulls),
+        };
+        match row {
+            Some(row) => self.value_at(row, nth.arg),
+            None => Scalar::Null,
+        }
+    }
+
     #[inline]
     fn is_order_by_keys_equal(&self, lhs: RowPtr, rhs: RowPtr) -> bool {
         debug_assert!({
             let end = self.blocks_end();
             lhs < end && rhs < end
         });
-        for offset in &self.order_by_indices {
-            let lhs_column = self.column_at(lhs, *offset);
-            let rhs_column = self.column_at(rhs, *offset);
+        for desc in &self.order_by {
+            let lhs_column = self.column_at(lhs, desc.offset);
+            let rhs_column = self.column_at(rhs, desc.offset);
             if lhs_column.index(lhs.row) != rhs_column.index(rhs.row) {
                 return false;
             }
@@ -378,10 +842,79 @@ impl TransformWindow {
     fn needs_rank(&self) -> bool {
         matches!(
             self.func,
-            WindowFunctionImpl::Rank | WindowFunctionImpl::DenseRank
+            WindowFunctionImpl::Rank
+                | WindowFunctionImpl::DenseRank
+                | WindowFunctionImpl::PercentRank
         )
     }
 
+    #[inline(always)]
+    fn needs_partition_rows(&self) -> bool {
+        matches!(
+            self.func,
+            WindowFunctionImpl::Ntile(_)
+                | WindowFunctionImpl::PercentRank
+                | WindowFunctionImpl::CumeDist
+        )
+    }
+
+    // The number of rows in [`start`, `end`).
+    fn rows_between(&self, start: RowPtr, end: RowPtr) -> usize {
+        let mut rows = 0;
+        let mut cur = start;
+        while cur.block < end.block {
+            rows += self.block_rows(cur) - cur.row;
+            cur = RowPtr::new(cur.block + 1, 0);
+        }
+        rows + end.row - cur.row
+    }
+
+    /// Compute the information of the partition for the current row,
+    /// the frame of these functions is the whole partition, so the partition has ended here.
+    ///
+    /// Returns `false` if the peers of the current row are in a spilled block.
+    fn update_partition_info(&mut self) -> bool {
+        if self.current_row_in_partition == 1 {
+            self.partition_rows = self.rows_between(self.partition_start, self.partition_end);
+            self.current_peer_end = 0;
+        }
+
+        if matches!(self.func, WindowFunctionImpl::CumeDist)
+            && self.current_row_in_partition > self.current_peer_end
+        {
+            // The current row starts a new group of peers, find the end of the group.
+            let mut peers = 1;
+            let mut row = self.advance_row(self.current_row);
+            while row < self.partition_end {
+                if self.is_spilled(row.block) {
+                    self.restore_block = Some(row.block);
+                    return false;
+                }
+                if !self.is_order_by_keys_equal(self.current_row, row) {
+                    break;
+                }
+                peers += 1;
+                row = self.advance_row(row);
+            }
+            self.current_peer_end = self.current_row_in_partition + peers - 1;
+        }
+        true
+    }
+
+    // The bucket (starts from 1) of the current row when the partition is divided into `n` buckets,
+    // the sizes of buckets differ by at most 1, and the larger buckets come first.
+    fn ntile_bucket(&self, n: usize) -> usize {
+        let row = self.current_row_in_partition - 1;
+        let size = self.partition_rows / n;
+        let remainder = self.partition_rows % n;
+        let large_rows = remainder * (size + 1);
+        if row < large_rows {
+            row / (size + 1) + 1
+        } else {
+            remainder + (row - large_rows) / size + 1
+        }
+    }
+
     /// When adding a [`DataBlock`], we compute the aggregations to the end.
     ///
     /// For each row in the input block,
@@ -393,9 +926,12 @@ impl TransformWindow {
     fn add_block(&mut self, data: Option<DataBlock>) -> Result<()> {
         if let Some(data) = data {
             let num_rows = data.num_rows();
+            let block = data.convert_to_full();
+            self.buffered_size += block.memory_size();
             self.blocks.push_back(WindowBlock {
-                block: data.convert_to_full(),
+                block,
                 builder: ColumnBuilder::with_capacity(&self.func.return_type()?, num_rows),
+                spilled: None,
             });
         }
 
@@ -411,6 +947,16 @@ impl TransformWindow {
             self.advance_partition();
 
             while self.current_row < self.partition_end {
+                // The row after the current row is read for the ranking.
+                let next_block = self.current_row.block + 1;
+                if self.is_spilled(next_block) {
+                    self.restore_block = Some(next_block);
+                    break;
+                }
+                if self.needs_aggregate_spilled_partition() {
+                    break;
+                }
+
                 // 2.
                 self.advance_frame_start();
                 if !self.frame_started {
@@ -425,6 +971,12 @@ impl TransformWindow {
                 if let WindowFunctionImpl::Aggregate(agg) = &self.func {
                     self.apply_aggregate(agg)?;
                 }
+                if let Some(column) = self.ignore_nulls_arg() {
+                    self.update_frame_non_null_rows(column);
+                }
+                if self.needs_partition_rows() && !self.update_partition_info() {
+                    break;
+                }
 
                 self.merge_result_of_current_row()?;
 
@@ -456,7 +1008,8 @@ impl TransformWindow {
             }
 
             // 4.
-            if !self.partition_ended {
+            // The remaining rows of an ended partition may wait for the spilled blocks.
+            if !self.partition_ended || self.current_row < self.partition_end {
                 break;
             }
 
@@ -464,10 +1017,16 @@ impl TransformWindow {
             self.partition_start = self.partition_end;
             self.partition_end = self.advance_row(self.partition_end);
             self.partition_ended = false;
+            self.partition_keys.clear();
+            self.partition_aggregated = false;
 
             // reset frames
             self.frame_start = self.partition_start;
             self.frame_end = self.partition_start;
+            self.prev_frame_start = self.partition_start;
+            self.prev_frame_end = self.partition_start;
+            self.frame_non_null_rows.clear();
+            self.non_null_scan_end = self.partition_start;
 
             self.current_row_in_partition = 1;
             self.current_rank = 1;
@@ -478,10 +1037,35 @@ impl TransformWindow {
         Ok(())
     }
 
+    /// The first block that may be used by the frames of the following rows,
+    /// the blocks before it can be output once the results of all their rows are computed.
+    fn first_used_block(&self) -> usize {
+        if self.input_is_finished {
+            return usize::MAX;
+        }
+        if self.reads_from_current_row() {
+            return self.current_row.block;
+        }
+        let mut first_used = self.frame_end.min(self.prev_frame_end);
+        // The aggregation of the frames starting from the partition start is computed incrementally,
+        // so the rows before the frame end are not used again.
+        if !matches!(
+            (&self.func, self.frame_kind.unbounded()),
+            (WindowFunctionImpl::Aggregate(_), (true, false))
+        ) {
+            first_used = first_used.min(self.frame_start).min(self.prev_frame_start);
+        }
+        first_used.block
+    }
+
     fn check_outputs(&mut self) {
-        while let Some(WindowBlock { block, builder }) = self.blocks.front() {
-            if block.num_rows() == builder.len() {
-                let WindowBlock { mut block, builder } = self.blocks.pop_front().unwrap();
+        let first_used_block = self.first_used_block();
+        while let Some(WindowBlock { block, builder, .. }) = self.blocks.front() {
+            if block.num_rows() == builder.len() && self.first_block < first_used_block {
+                let WindowBlock {
+                    mut block, builder, ..
+                } = self.blocks.pop_front().unwrap();
+                self.buffered_size -= block.memory_size();
                 let new_column = builder.build();
                 block.add_column(BlockEntry {
                     data_type: new_column.data_type(),
@@ -495,25 +1079,183 @@ impl TransformWindow {
         }
     }
 
-    fn apply_aggregate(&self, agg: &WindowFuncAggImpl) -> Result<()> {
-        let WindowFuncFrame {
-            start_bound,
-            end_bound,
-            ..
-        } = &self.frame_kind;
-        match (start_bound, end_bound) {
-            (WindowFuncFrameBound::Preceding(None), WindowFuncFrameBound::Following(None)) => {
-                self.apply_aggregate_for_unbounded_frame(agg)
+    /// Whether the rows before the current row are not read again, and the rows after it are only read
+    /// for the ranking and the aggregation over the whole partition at the first row of it.
+    ///
+    /// The blocks of such partitions can be spilled and read back when the current row reaches them.
+    fn reads_from_current_row(&self) -> bool {
+        if !self.frame_kind.is_unbounded_or_current_row() {
+            return false;
+        }
+        match &self.func {
+            WindowFunctionImpl::RowNumber
+            | WindowFunctionImpl::Rank
+            | WindowFunctionImpl::DenseRank
+            | WindowFunctionImpl::Ntile(_)
+            | WindowFunctionImpl::PercentRank
+            | WindowFunctionImpl::CumeDist => true,
+            WindowFunctionImpl::Aggregate(_) => self.frame_kind.unbounded() == (true, true),
+            WindowFunctionImpl::LagLead(_) | WindowFunctionImpl::NthValue(_) => false,
+        }
+    }
+
+    /// Whether the aggregation over the whole partition should be computed before the first row,
+    /// some blocks of the partition are spilled.
+    fn needs_aggregate_spilled_partition(&self) -> bool {
+        self.spilled_blocks > 0
+            && self.partition_ended
+            && self.current_row_in_partition == 1
+            && !self.partition_aggregated
+            && matches!(self.func, WindowFunctionImpl::Aggregate(_))
+    }
+
+    /// The block to spill next: the last one not spilled in the current partition,
+    /// the blocks of the current row and the row after it are kept in memory.
+    fn spill_candidate(&self) -> Option<usize> {
+        (self.current_row.block + 2..self.partition_end.block)
+            .rev()
+            .find(|block| !self.is_spilled(*block))
+    }
+
+    fn next_state(&self) -> ProcessorState {
+        if !self.outputs.is_empty() {
+            return ProcessorState::Output;
+        }
+        if self.restore_block.is_some() {
+            return ProcessorState::Restore;
+        }
+        if self.needs_aggregate_spilled_partition() {
+            return ProcessorState::AggregateSpilled;
+        }
+        match &self.spill_params {
+            Some(params)
+                if self.buffered_size > params.threshold && self.spill_candidate().is_some() =>
+            {
+                ProcessorState::Spill
             }
-            (WindowFuncFrameBound::Preceding(None), _) => {
-                self.apply_aggregate_for_unbounded_preceding(agg)
+            _ => ProcessorState::Consume,
+        }
+    }
+
+    #[async_backtrace::framed]
+    async fn spill(&mut self) -> Result<()> {
+        let params = self.spill_params.as_ref().unwrap();
+        while self.buffered_size > params.threshold {
+            let index = match self.spill_candidate() {
+                Some(index) => index,
+                None => break,
+            };
+            let window_block = &mut self.blocks[index - self.first_block];
+            let num_rows = window_block.block.num_rows();
+            let block =
+                std::mem::replace(&mut window_block.block, DataBlock::new(vec![], num_rows));
+            // No results of the rows after the current row have been computed.
+            window_block.builder = ColumnBuilder::with_capacity(&self.func.return_type()?, 0);
+            self.buffered_size -= block.memory_size();
+
+            let file = spill_blocks(&params.operator, &params.location_prefix, &[block]).await?;
+            self.blocks[index - self.first_block].spilled = Some(file);
+            self.spilled_blocks += 1;
+        }
+        Ok(())
+    }
+
+    #[async_backtrace::framed]
+    async fn restore(&mut self) -> Result<()> {
+        let index = self.restore_block.take().unwrap();
+        let params = self.spill_params.as_ref().unwrap();
+        let window_block = &mut self.blocks[index - self.first_block];
+        let file = window_block.spilled.take().unwrap();
+        let block = restore_spilled_file(&params.operator, &file).await?;
+
+        let window_block = &mut self.blocks[index - self.first_block];
+        self.buffered_size += block.memory_size();
+        window_block.builder =
+            ColumnBuilder::with_capacity(&self.func.return_type()?, block.num_rows());
+        window_block.block = block;
+        self.spilled_blocks -= 1;
+        Ok(())
+    }
+
+    /// Compute the aggregation over the whole partition block by block,
+    /// the spilled blocks are read without being restored into memory.
+    #[async_backtrace::framed]
+    async fn aggregate_spilled_partition(&mut self) -> Result<()> {
+        let operator = &self.spill_params.as_ref().unwrap().operator;
+        self.reset_aggregate();
+
+        let mut start = self.partition_start;
+        while start < self.partition_end {
+            let window_block = &self.blocks[start.block - self.first_block];
+            let end = if start.block == self.partition_end.block {
+                self.partition_end.row
+            } else {
+                window_block.block.num_rows()
+            };
+            match &window_block.spilled {
+                Some(file) => {
+                    let data = read_spilled_file(operator, file).await?;
+                    self.accumulate_rows(&data, start.row, end)?;
+                }
+                None => self.accumulate_rows(&window_block.block, start.row, end)?,
             }
-            (_, _) => self.apply_aggregate_common(agg),
+            start = RowPtr::new(start.block + 1, 0);
+        }
+
+        self.partition_aggregated = true;
+        Ok(())
+    }
+
+    fn reset_aggregate(&self) {
+        if let WindowFunctionImpl::Aggregate(agg) = &self.func {
+            agg.reset();
+        }
+    }
+
+    fn accumulate_rows(&self, data: &DataBlock, start: usize, end: usize) -> Result<()> {
+        if let WindowFunctionImpl::Aggregate(agg) = &self.func {
+            let columns = agg.arg_columns(data);
+            for row in start..end {
+                agg.accumulate_row(&columns, row)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn apply_aggregate(&self, agg: &WindowFuncAggImpl) -> Result<()> {
+        match self.frame_kind.unbounded() {
+            (true, true) => self.apply_aggregate_for_unbounded_frame(agg),
+            (true, false) => self.apply_aggregate_for_unbounded_preceding(agg),
+            _ => self.apply_aggregate_common(agg),
         }
     }
 
     #[inline]
     fn merge_result_of_current_row(&mut self) -> Result<()> {
+        // The values are read from the blocks before the builder is borrowed.
+        let value = match &self.func {
+            WindowFunctionImpl::LagLead(lag_lead) => Some(self.lag_lead_value(lag_lead)),
+            WindowFunctionImpl::NthValue(nth) => Some(self.nth_value(nth)),
+            WindowFunctionImpl::Ntile(n) => Some(Scalar::Number(NumberScalar::UInt64(
+                self.ntile_bucket(*n) as u64,
+            ))),
+            WindowFunctionImpl::PercentRank => {
+                let percent_rank = if self.partition_rows > 1 {
+                    (self.current_rank - 1) as f64 / (self.partition_rows - 1) as f64
+                } else {
+                    0.0
+                };
+                Some(Scalar::Number(NumberScalar::Float64(F64::from(
+                    percent_rank,
+                ))))
+            }
+            WindowFunctionImpl::CumeDist => {
+                let cume_dist = self.current_peer_end as f64 / self.partition_rows as f64;
+                Some(Scalar::Number(NumberScalar::Float64(F64::from(cume_dist))))
+            }
+            _ => None,
+        };
+
         let builder = &mut self.blocks[self.current_row.block - self.first_block].builder;
 
         match &self.func {
@@ -535,6 +1277,13 @@ impl TransformWindow {
                     self.current_dense_rank as u64,
                 )));
             }
+            WindowFunctionImpl::LagLead(_)
+            | WindowFunctionImpl::NthValue(_)
+            | WindowFunctionImpl::Ntile(_)
+            | WindowFunctionImpl::PercentRank
+            | WindowFunctionImpl::CumeDist => {
+                builder.push(value.unwrap().as_ref());
+            }
         };
 
         Ok(())
@@ -542,7 +1291,7 @@ impl TransformWindow {
 
     #[inline]
     fn apply_aggregate_for_unbounded_frame(&self, agg: &WindowFuncAggImpl) -> Result<()> {
-        if self.current_row_in_partition == 1 {
+        if self.current_row_in_partition == 1 && !self.partition_aggregated {
             self.apply_aggregate_common(agg)
         } else {
             // Else do nothing
@@ -554,13 +1303,15 @@ impl TransformWindow {
     fn apply_aggregate_for_unbounded_preceding(&self, agg: &WindowFuncAggImpl) -> Result<()> {
         if self.current_row_in_partition == 1 {
             self.apply_aggregate_common(agg)
-        } else if self.frame_end != self.prev_frame_end {
-            let row = self.prev_frame_end.row;
-            let data = self.block_at(self.prev_frame_end);
-            let columns = agg.arg_columns(data);
-            agg.accumulate_row(&columns, row)
         } else {
-            // Else do nothing
+            // The frame end of RANGE frames may move more than one row.
+            let mut row = self.prev_frame_end;
+            while row < self.frame_end {
+                let data = self.block_at(row);
+                let columns = agg.arg_columns(data);
+                agg.accumulate_row(&columns, row.row)?;
+                row = self.advance_row(row);
+            }
             Ok(())
         }
     }
@@ -601,6 +1352,9 @@ enum ProcessorState {
     Consume,
     AddBlock(Option<DataBlock>),
     Output,
+    Spill,
+    Restore,
+    AggregateSpilled,
 }
 
 #[async_trait::async_trait]
@@ -652,11 +1406,14 @@ impl Processor for TransformWindow {
                 let output = self.outputs.pop_front().unwrap();
                 self.output.push_data(Ok(output));
                 if self.outputs.is_empty() {
-                    self.state = ProcessorState::Consume;
+                    self.state = self.next_state();
                 }
                 Ok(Event::NeedConsume)
             }
-            _ => unreachable!(),
+            ProcessorState::AddBlock(_) => Ok(Event::Sync),
+            ProcessorState::Spill | ProcessorState::Restore | ProcessorState::AggregateSpilled => {
+                Ok(Event::Async)
+            }
         }
     }
 
@@ -666,16 +1423,32 @@ impl Processor for TransformWindow {
         {
             self.add_block(data)?;
             self.check_outputs();
-            self.state = if self.outputs.is_empty() {
-                ProcessorState::Consume
-            } else {
-                ProcessorState::Output
-            };
+            self.state = self.next_state();
         } else {
             unreachable!()
         }
         Ok(())
     }
+
+    #[async_backtrace::framed]
+    async fn async_process(&mut self) -> Result<()> {
+        match std::mem::replace(&mut self.state, ProcessorState::Consume) {
+            ProcessorState::Spill => {
+                self.spill().await?;
+            }
+            // Continue computing the current row.
+            ProcessorState::Restore => {
+                self.restore().await?;
+                self.state = ProcessorState::AddBlock(None);
+            }
+            ProcessorState::AggregateSpilled => {
+                self.aggregate_spilled_partition().await?;
+                self.state = ProcessorState::AddBlock(None);
+            }
+            _ => unreachable!(),
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -688,10 +1461,14 @@ mod tests {
     use common_expression::types::DataType;
     use common_expression::types::Int32Type;
     use common_expression::types::NumberDataType;
+    use common_expression::types::NumberScalar;
     use common_expression::Column;
     use common_expression::ColumnBuilder;
     use common_expression::DataBlock;
     use common_expression::FromData;
+    use common_expression::FromOptData;
+    use common_expression::Scalar;
+    use common_expression::SortColumnDescription;
     use common_functions::aggregates::AggregateFunctionFactory;
     use common_pipeline_core::processors::connect;
     use common_pipeline_core::processors::port::InputPort;
@@ -701,9 +1478,15 @@ mod tests {
     use common_sql::plans::WindowFuncFrame;
     use common_sql::plans::WindowFuncFrameBound;
     use common_sql::plans::WindowFuncFrameUnits;
+    use opendal::services::Memory;
+    use opendal::Operator;
 
+    use super::ProcessorState;
     use super::TransformWindow;
     use super::WindowBlock;
+    use super::WindowFuncLagLeadImpl;
+    use super::WindowFuncNthValueImpl;
+    use super::WindowSpillParams;
     use crate::pipelines::processors::transforms::window::transform_window::RowPtr;
     use crate::pipelines::processors::transforms::window::WindowFunctionInfo;
 
@@ -730,9 +1513,12 @@ mod tests {
         let data_type = column.data_type();
         let num_rows = column.len();
         let mut transform = get_transform_window(window_frame, data_type.clone())?;
+        let block = DataBlock::new_from_columns(vec![column]);
+        transform.buffered_size += block.memory_size();
         transform.blocks.push_back(WindowBlock {
-            block: DataBlock::new_from_columns(vec![column]),
+            block,
             builder: ColumnBuilder::with_capacity(&data_type, num_rows),
+            spilled: None,
         });
         Ok(transform)
     }
@@ -779,8 +1565,8 @@ mod tests {
             let mut transform = get_transform_window_with_data(
                 WindowFuncFrame {
                     units: WindowFuncFrameUnits::Rows,
-                    start_bound: WindowFuncFrameBound::Following(Some(4)),
-                    end_bound: WindowFuncFrameBound::Following(Some(5)),
+                    start_bound: WindowFuncFrameBound::following_rows(4),
+                    end_bound: WindowFuncFrameBound::following_rows(5),
                 },
                 Int32Type::from_data(vec![1, 1, 1]),
             )?;
@@ -798,8 +1584,8 @@ mod tests {
             let mut transform = get_transform_window_with_data(
                 WindowFuncFrame {
                     units: WindowFuncFrameUnits::Rows,
-                    start_bound: WindowFuncFrameBound::Preceding(Some(2)),
-                    end_bound: WindowFuncFrameBound::Following(Some(5)),
+                    start_bound: WindowFuncFrameBound::preceding_rows(2),
+                    end_bound: WindowFuncFrameBound::following_rows(5),
                 },
                 Int32Type::from_data(vec![1, 1, 1]),
             )?;
@@ -822,8 +1608,8 @@ mod tests {
             let mut transform = get_transform_window_with_data(
                 WindowFuncFrame {
                     units: WindowFuncFrameUnits::Rows,
-                    start_bound: WindowFuncFrameBound::Preceding(Some(2)),
-                    end_bound: WindowFuncFrameBound::Following(Some(1)),
+                    start_bound: WindowFuncFrameBound::preceding_rows(2),
+                    end_bound: WindowFuncFrameBound::following_rows(1),
                 },
                 Int32Type::from_data(vec![1, 1, 1]),
             )?;
@@ -1104,7 +1890,7 @@ mod tests {
                 WindowFuncFrame {
                     units: WindowFuncFrameUnits::Rows,
                     start_bound: WindowFuncFrameBound::Preceding(None),
-                    end_bound: WindowFuncFrameBound::Following(Some(1)),
+                    end_bound: WindowFuncFrameBound::following_rows(1),
                 },
                 DataType::Number(NumberDataType::Int32),
             )?;
@@ -1165,8 +1951,8 @@ mod tests {
             let mut transform = get_transform_window(
                 WindowFuncFrame {
                     units: WindowFuncFrameUnits::Rows,
-                    start_bound: WindowFuncFrameBound::Preceding(Some(1)),
-                    end_bound: WindowFuncFrameBound::Following(Some(1)),
+                    start_bound: WindowFuncFrameBound::preceding_rows(1),
+                    end_bound: WindowFuncFrameBound::following_rows(1),
                 },
                 DataType::Number(NumberDataType::Int32),
             )?;
@@ -1289,7 +2075,7 @@ mod tests {
             let mut transform = get_transform_window(
                 WindowFuncFrame {
                     units: WindowFuncFrameUnits::Rows,
-                    start_bound: WindowFuncFrameBound::Preceding(Some(1)),
+                    start_bound: WindowFuncFrameBound::preceding_rows(1),
                     end_bound: WindowFuncFrameBound::CurrentRow,
                 },
                 DataType::Number(NumberDataType::Int32),
@@ -1350,6 +2136,203 @@ mod tests {
         Ok(())
     }
 
+    // Apply the function to the partitions `[10, NULL, 30, NULL]` and `[50, 60]`.
+    fn get_navigation_result(
+        func: WindowFunctionInfo,
+        window_frame: WindowFuncFrame,
+    ) -> Result<DataBlock> {
+        let mut transform = TransformWindow::create(
+            InputPort::create(),
+            OutputPort::create(),
+            func,
+            vec![0],
+            vec![],
+            window_frame,
+        )?;
+        transform.add_block(Some(DataBlock::new_from_columns(vec![
+            Int32Type::from_data(vec![1, 1, 1, 1, 2, 2]),
+            Int32Type::from_opt_data(vec![Some(10), None, Some(30), None, Some(50), Some(60)]),
+        ])))?;
+        transform.input_is_finished = true;
+        transform.add_block(None)?;
+        transform.check_outputs();
+        Ok(transform.outputs.pop_front().unwrap())
+    }
+
+    #[test]
+    fn test_navigation_functions() -> Result<()> {
+        let return_type = DataType::Number(NumberDataType::Int32).wrap_nullable();
+        let lag_lead = |is_lag, ignore_nulls| {
+            WindowFunctionInfo::LagLead(WindowFuncLagLeadImpl {
+                is_lag,
+                offset: 1,
+                arg: 1,
+                default: None,
+                ignore_nulls,
+                return_type: return_type.clone(),
+            })
+        };
+        let nth_value = |n, ignore_nulls| {
+            WindowFunctionInfo::NthValue(WindowFuncNthValueImpl {
+                n,
+                arg: 1,
+                ignore_nulls,
+                return_type: return_type.clone(),
+            })
+        };
+        let rows_frame = |start_bound, end_bound| WindowFuncFrame {
+            units: WindowFuncFrameUnits::Rows,
+            start_bound,
+            end_bound,
+        };
+
+        // lag(c1, 1)
+        let output = get_navigation_result(
+            lag_lead(true, false),
+            rows_frame(
+                WindowFuncFrameBound::preceding_rows(1),
+                WindowFuncFrameBound::preceding_rows(1),
+            ),
+        )?;
+        assert_blocks_eq(
+            vec![
+                "+----------+----------+----------+",
+                "| Column 0 | Column 1 | Column 2 |",
+                "+----------+----------+----------+",
+                "| 1        | 10       | NULL     |",
+                "| 1        | NULL     | 10       |",
+                "| 1        | 30       | NULL     |",
+                "| 1        | NULL     | 30       |",
+                "| 2        | 50       | NULL     |",
+                "| 2        | 60       | 50       |",
+                "+----------+----------+----------+",
+            ],
+            &[output],
+        );
+
+        // lag(c1, 1) ignore nulls
+        let output = get_navigation_result(
+            lag_lead(true, true),
+            rows_frame(
+                WindowFuncFrameBound::Preceding(None),
+                WindowFuncFrameBound::preceding_rows(1),
+            ),
+        )?;
+        assert_blocks_eq(
+            vec![
+                "+----------+----------+----------+",
+                "| Column 0 | Column 1 | Column 2 |",
+                "+----------+----------+----------+",
+                "| 1        | 10       | NULL     |",
+                "| 1        | NULL     | 10       |",
+                "| 1        | 30       | 10       |",
+                "| 1        | NULL     | 30       |",
+                "| 2        | 50       | NULL     |",
+                "| 2        | 60       | 50       |",
+                "+----------+----------+----------+",
+            ],
+            &[output],
+        );
+
+        // lead(c1, 1) ignore nulls
+        let output = get_navigation_result(
+            lag_lead(false, true),
+            rows_frame(
+                WindowFuncFrameBound::following_rows(1),
+                WindowFuncFrameBound::Following(None),
+            ),
+        )?;
+        assert_blocks_eq(
+            vec![
+                "+----------+----------+----------+",
+                "| Column 0 | Column 1 | Column 2 |",
+                "+----------+----------+----------+",
+                "| 1        | 10       | 30       |",
+                "| 1        | NULL     | 30       |",
+                "| 1        | 30       | NULL     |",
+                "| 1        | NULL     | NULL     |",
+                "| 2        | 50       | 60       |",
+                "| 2        | 60       | NULL     |",
+                "+----------+----------+----------+",
+            ],
+            &[output],
+        );
+
+        // last_value(c1) ignore nulls over (rows between unbounded preceding and current row)
+        let output = get_navigation_result(
+            nth_value(None, true),
+            rows_frame(
+                WindowFuncFrameBound::Preceding(None),
+                WindowFuncFrameBound::CurrentRow,
+            ),
+        )?;
+        assert_blocks_eq(
+            vec![
+                "+----------+----------+----------+",
+                "| Column 0 | Column 1 | Column 2 |",
+                "+----------+----------+----------+",
+                "| 1        | 10       | 10       |",
+                "| 1        | NULL     | 10       |",
+                "| 1        | 30       | 30       |",
+                "| 1        | NULL     | 30       |",
+                "| 2        | 50       | 50       |",
+                "| 2        | 60       | 60       |",
+                "+----------+----------+----------+",
+            ],
+            &[output],
+        );
+
+        // nth_value(c1, 2) over (rows between unbounded preceding and unbounded following)
+        let output = get_navigation_result(
+            nth_value(Some(2), false),
+            rows_frame(
+                WindowFuncFrameBound::Preceding(None),
+                WindowFuncFrameBound::Following(None),
+            ),
+        )?;
+        assert_blocks_eq(
+            vec![
+                "+----------+----------+----------+",
+                "| Column 0 | Column 1 | Column 2 |",
+                "+----------+----------+----------+",
+                "| 1        | 10       | NULL     |",
+                "| 1        | NULL     | NULL     |",
+                "| 1        | 30       | NULL     |",
+                "| 1        | NULL     | NULL     |",
+                "| 2        | 50       | 60       |",
+                "| 2        | 60       | 60       |",
+                "+----------+----------+----------+",
+            ],
+            &[output],
+        );
+
+        // first_value(c1) ignore nulls over (rows between current row and 1 following)
+        let output = get_navigation_result(
+            nth_value(Some(1), true),
+            rows_frame(
+                WindowFuncFrameBound::CurrentRow,
+                WindowFuncFrameBound::following_rows(1),
+            ),
+        )?;
+        assert_blocks_eq(
+            vec![
+                "+----------+----------+----------+",
+                "| Column 0 | Column 1 | Column 2 |",
+                "+----------+----------+----------+",
+                "| 1        | 10       | 10       |",
+                "| 1        | NULL     | 30       |",
+                "| 1        | 30       | 30       |",
+                "| 1        | NULL     | NULL     |",
+                "| 2        | 50       | 50       |",
+                "| 2        | 60       | 60       |",
+                "+----------+----------+----------+",
+            ],
+            &[output],
+        );
+
+        Ok(())
+    }
+
     #[allow(clippy::type_complexity)]
     fn get_transform_window_and_ports(
         window_frame: WindowFuncFrame,
@@ -1378,8 +2361,8 @@ mod tests {
             let downstream_input = InputPort::create();
             let (mut transform, input, output) = get_transform_window_and_ports(WindowFuncFrame {
                 units: WindowFuncFrameUnits::Rows,
-                start_bound: WindowFuncFrameBound::Preceding(Some(1)),
-                end_bound: WindowFuncFrameBound::Following(Some(1)),
+                start_bound: WindowFuncFrameBound::preceding_rows(1),
+                end_bound: WindowFuncFrameBound::following_rows(1),
             })?;
 
             unsafe {
@@ -1546,4 +2529,336 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_distribution_functions() -> Result<()> {
+        let get_result = |func| -> Result<DataBlock> {
+            let mut transform = TransformWindow::create(
+                InputPort::create(),
+                OutputPort::create(),
+                func,
+                vec![0],
+                vec![SortColumnDescription {
+                    offset: 1,
+                    asc: true,
+                    nulls_first: false,
+                }],
+                WindowFuncFrame {
+                    units: WindowFuncFrameUnits::Rows,
+                    start_bound: WindowFuncFrameBound::Preceding(None),
+                    end_bound: WindowFuncFrameBound::Following(None),
+                },
+            )?;
+            transform.add_block(Some(DataBlock::new_from_columns(vec![
+                Int32Type::from_data(vec![1, 1, 1]),
+                Int32Type::from_data(vec![10, 20, 20]),
+            ])))?;
+            transform.add_block(Some(DataBlock::new_from_columns(vec![
+                Int32Type::from_data(vec![1, 1, 2, 2]),
+                Int32Type::from_data(vec![30, 40, 50, 60]),
+            ])))?;
+            transform.input_is_finished = true;
+            transform.add_block(None)?;
+            transform.check_outputs();
+            let outputs = transform.outputs.drain(..).collect::<Vec<_>>();
+            DataBlock::concat(&outputs)
+        };
+
+        // ntile(3)
+        let output = get_result(WindowFunctionInfo::Ntile(3))?;
+        assert_blocks_eq(
+            vec![
+                "+----------+----------+----------+",
+                "| Column 0 | Column 1 | Column 2 |",
+                "+----------+----------+----------+",
+                "| 1        | 10       | 1        |",
+                "| 1        | 20       | 1        |",
+                "| 1        | 20       | 2        |",
+                "| 1        | 30       | 2        |",
+                "| 1        | 40       | 3        |",
+                "| 2        | 50       | 1        |",
+                "| 2        | 60       | 2        |",
+                "+----------+----------+----------+",
+            ],
+            &[output],
+        );
+
+        // percent_rank()
+        let output = get_result(WindowFunctionInfo::PercentRank)?;
+        assert_blocks_eq(
+            vec![
+                "+----------+----------+----------+",
+                "| Column 0 | Column 1 | Column 2 |",
+                "+----------+----------+----------+",
+                "| 1        | 10       | 0        |",
+                "| 1        | 20       | 0.25     |",
+                "| 1        | 20       | 0.25     |",
+                "| 1        | 30       | 0.75     |",
+                "| 1        | 40       | 1        |",
+                "| 2        | 50       | 0        |",
+                "| 2        | 60       | 1        |",
+                "+----------+----------+----------+",
+            ],
+            &[output],
+        );
+
+        // cume_dist()
+        let output = get_result(WindowFunctionInfo::CumeDist)?;
+        assert_blocks_eq(
+            vec![
+                "+----------+----------+----------+",
+                "| Column 0 | Column 1 | Column 2 |",
+                "+----------+----------+----------+",
+                "| 1        | 10       | 0.2      |",
+                "| 1        | 20       | 0.6      |",
+                "| 1        | 20       | 0.6      |",
+                "| 1        | 30       | 0.8      |",
+                "| 1        | 40       | 1        |",
+                "| 2        | 50       | 0.5      |",
+                "| 2        | 60       | 1        |",
+                "+----------+----------+----------+",
+            ],
+            &[output],
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_range_frame() -> Result<()> {
+        let get_result = |start_bound, end_bound| -> Result<DataBlock> {
+            let agg = AggregateFunctionFactory::instance()
+                .get("sum", vec![], vec![DataType::Number(NumberDataType::Int32)])?;
+            let mut transform = TransformWindow::create(
+                InputPort::create(),
+                OutputPort::create(),
+                WindowFunctionInfo::Aggregate(agg, vec![1]),
+                vec![0],
+                vec![SortColumnDescription {
+                    offset: 1,
+                    asc: true,
+                    nulls_first: false,
+                }],
+                WindowFuncFrame {
+                    units: WindowFuncFrameUnits::Range,
+                    start_bound,
+                    end_bound,
+                },
+            )?;
+            transform.add_block(Some(DataBlock::new_from_columns(vec![
+                Int32Type::from_data(vec![1, 1, 1]),
+                Int32Type::from_data(vec![1, 2, 2]),
+            ])))?;
+            transform.check_outputs();
+            // The frames of the peers `2` are not ended.
+            assert!(transform.outputs.is_empty());
+
+            transform.add_block(Some(DataBlock::new_from_columns(vec![
+                Int32Type::from_data(vec![1, 1, 2]),
+                Int32Type::from_data(vec![4, 5, 3]),
+            ])))?;
+            transform.check_outputs();
+            transform.input_is_finished = true;
+            transform.add_block(None)?;
+            transform.check_outputs();
+            let outputs = transform.outputs.drain(..).collect::<Vec<_>>();
+            DataBlock::concat(&outputs)
+        };
+        let offset = |n: u64| Some(Scalar::Number(NumberScalar::UInt64(n)));
+
+        // sum(c1) over (partition by c0 order by c1 range between 1 preceding and current row)
+        let output = get_result(
+            WindowFuncFrameBound::Preceding(offset(1)),
+            WindowFuncFrameBound::CurrentRow,
+        )?;
+        assert_blocks_eq(
+            vec![
+                "+----------+----------+----------+",
+                "| Column 0 | Column 1 | Column 2 |",
+                "+----------+----------+----------+",
+                "| 1        | 1        | 1        |",
+                "| 1        | 2        | 5        |",
+                "| 1        | 2        | 5        |",
+                "| 1        | 4        | 4        |",
+                "| 1        | 5        | 9        |",
+                "| 2        | 3        | 3        |",
+                "+----------+----------+----------+",
+            ],
+            &[output],
+        );
+
+        // sum(c1) over (partition by c0 order by c1 range between unbounded preceding and current row)
+        let output = get_result(
+            WindowFuncFrameBound::Preceding(None),
+            WindowFuncFrameBound::CurrentRow,
+        )?;
+        assert_blocks_eq(
+            vec![
+                "+----------+----------+----------+",
+                "| Column 0 | Column 1 | Column 2 |",
+                "+----------+----------+----------+",
+                "| 1        | 1        | 1        |",
+                "| 1        | 2        | 5        |",
+                "| 1        | 2        | 5        |",
+                "| 1        | 4        | 9        |",
+                "| 1        | 5        | 14       |",
+                "| 2        | 3        | 3        |",
+                "+----------+----------+----------+",
+            ],
+            &[output],
+        );
+
+        // sum(c1) over (partition by c0 order by c1 range between 1 following and 3 following)
+        let output = get_result(
+            WindowFuncFrameBound::Following(offset(1)),
+            WindowFuncFrameBound::Following(offset(3)),
+        )?;
+        assert_blocks_eq(
+            vec![
+                "+----------+----------+----------+",
+                "| Column 0 | Column 1 | Column 2 |",
+                "+----------+----------+----------+",
+                "| 1        | 1        | 8        |",
+                "| 1        | 2        | 9        |",
+                "| 1        | 2        | 9        |",
+                "| 1        | 4        | 5        |",
+                "| 1        | 5        | NULL     |",
+                "| 2        | 3        | NULL     |",
+                "+----------+----------+----------+",
+            ],
+            &[output],
+        );
+
+        Ok(())
+    }
+
+    // Apply the function to the partitions `[10, 20, 20, 20, 30, 40, 40, 50]` and `[60, 70]`,
+    // the blocks are spilled whenever possible.
+    async fn get_spilled_result(
+        func: WindowFunctionInfo,
+        window_frame: WindowFuncFrame,
+    ) -> Result<(DataBlock, usize)> {
+        let mut transform = TransformWindow::create(
+            InputPort::create(),
+            OutputPort::create(),
+            func,
+            vec![0],
+            vec![SortColumnDescription {
+                offset: 1,
+                asc: true,
+                nulls_first: false,
+            }],
+            window_frame,
+        )?;
+        transform.spill_params = Some(WindowSpillParams {
+            operator: Operator::new(Memory::default())?.finish(),
+            location_prefix: "_window_spill".to_string(),
+            threshold: 1,
+        });
+
+        let mut inputs = vec![
+            (vec![1, 1], vec![10, 20]),
+            (vec![1, 1], vec![20, 20]),
+            (vec![1, 1], vec![30, 40]),
+            (vec![1, 1], vec![40, 50]),
+            (vec![2, 2], vec![60, 70]),
+        ]
+        .into_iter()
+        .map(|(c0, c1)| {
+            DataBlock::new_from_columns(vec![Int32Type::from_data(c0), Int32Type::from_data(c1)])
+        });
+
+        let mut outputs = vec![];
+        let mut max_spilled_blocks = 0;
+        loop {
+            max_spilled_blocks = max_spilled_blocks.max(transform.spilled_blocks);
+            match std::mem::replace(&mut transform.state, ProcessorState::Consume) {
+                ProcessorState::Consume => match inputs.next() {
+                    Some(block) => transform.state = ProcessorState::AddBlock(Some(block)),
+                    None if transform.input_is_finished && transform.blocks.is_empty() => break,
+                    None => {
+                        transform.input_is_finished = true;
+                        transform.state = ProcessorState::AddBlock(None);
+                    }
+                },
+                ProcessorState::Output => {
+                    outputs.extend(transform.outputs.drain(..));
+                    transform.state = transform.next_state();
+                }
+                state @ ProcessorState::AddBlock(_) => {
+                    transform.state = state;
+                    transform.process()?;
+                }
+                state => {
+                    transform.state = state;
+                    transform.async_process().await?;
+                }
+            }
+        }
+        Ok((DataBlock::concat(&outputs)?, max_spilled_blocks))
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 1)]
+    async fn test_window_spill() -> Result<()> {
+        let unbounded_frame = WindowFuncFrame {
+            units: WindowFuncFrameUnits::Rows,
+            start_bound: WindowFuncFrameBound::Preceding(None),
+            end_bound: WindowFuncFrameBound::Following(None),
+        };
+
+        // sum(c1) over (partition by c0 order by c1 rows between unbounded preceding and unbounded following)
+        let agg = AggregateFunctionFactory::instance()
+            .get("sum", vec![], vec![DataType::Number(NumberDataType::Int32)])?;
+        let (output, spilled_blocks) = get_spilled_result(
+            WindowFunctionInfo::Aggregate(agg, vec![1]),
+            unbounded_frame.clone(),
+        )
+        .await?;
+        assert!(spilled_blocks > 0);
+        assert_blocks_eq(
+            vec![
+                "+----------+----------+----------+",
+                "| Column 0 | Column 1 | Column 2 |",
+                "+----------+----------+----------+",
+                "| 1        | 10       | 230      |",
+                "| 1        | 20       | 230      |",
+                "| 1        | 20       | 230      |",
+                "| 1        | 20       | 230      |",
+                "| 1        | 30       | 230      |",
+                "| 1        | 40       | 230      |",
+                "| 1        | 40       | 230      |",
+                "| 1        | 50       | 230      |",
+                "| 2        | 60       | 130      |",
+                "| 2        | 70       | 130      |",
+                "+----------+----------+----------+",
+            ],
+            &[output],
+        );
+
+        // cume_dist() over (partition by c0 order by c1)
+        let (output, spilled_blocks) =
+            get_spilled_result(WindowFunctionInfo::CumeDist, unbounded_frame).await?;
+        assert!(spilled_blocks > 0);
+        assert_blocks_eq(
+            vec![
+                "+----------+----------+----------+",
+                "| Column 0 | Column 1 | Column 2 |",
+                "+----------+----------+----------+",
+                "| 1        | 10       | 0.125    |",
+                "| 1        | 20       | 0.5      |",
+                "| 1        | 20       | 0.5      |",
+                "| 1        | 20       | 0.5      |",
+                "| 1        | 30       | 0.625    |",
+                "| 1        | 40       | 0.875    |",
+                "| 1        | 40       | 0.875    |",
+                "| 1        | 50       | 1        |",
+                "| 2        | 60       | 0.5      |",
+                "| 2        | 70       | 1        |",
+                "+----------+----------+----------+",
+            ],
+            &[output],
+        );
+
+        Ok(())
+    }
 }
@@ -35,6 +35,11 @@ pub enum WindowFunctionInfo {
     RowNumber,
     Rank,
     DenseRank,
+    LagLead(WindowFuncLagLeadImpl),
+    NthValue(WindowFuncNthValueImpl),
+    Ntile(usize),
+    PercentRank,
+    CumeDist,
 }
 
 pub struct WindowFuncAggImpl {
@@ -86,11 +91,40 @@ impl Drop for WindowFuncAggImpl {
     }
 }
 
+/// `lag` and `lead` take the value from the frame rewritten by the planner,
+/// the default value is taken from the current row if the frame has no such row.
+#[derive(Clone)]
+pub struct WindowFuncLagLeadImpl {
+    pub is_lag: bool,
+    pub offset: usize,
+    // argument offsets
+    pub arg: usize,
+    pub default: Option<usize>,
+    pub ignore_nulls: bool,
+    pub return_type: DataType,
+}
+
+/// `first_value`, `last_value` and `nth_value` take the value from the frame.
+#[derive(Clone)]
+pub struct WindowFuncNthValueImpl {
+    // starts from 1, `None` means the last row of the frame
+    pub n: Option<usize>,
+    // argument offset
+    pub arg: usize,
+    pub ignore_nulls: bool,
+    pub return_type: DataType,
+}
+
 pub enum WindowFunctionImpl {
     Aggregate(WindowFuncAggImpl),
     RowNumber,
     Rank,
     DenseRank,
+    LagLead(WindowFuncLagLeadImpl),
+    NthValue(WindowFuncNthValueImpl),
+    Ntile(usize),
+    PercentRank,
+    CumeDist,
 }
 
 impl WindowFunctionInfo {
@@ -115,6 +149,26 @@ impl WindowFunctionInfo {
             WindowFunction::RowNumber => Self::RowNumber,
             WindowFunction::Rank => Self::Rank,
             WindowFunction::DenseRank => Self::DenseRank,
+            WindowFunction::LagLead(lag_lead) => Self::LagLead(WindowFuncLagLeadImpl {
+                is_lag: lag_lead.is_lag,
+                offset: lag_lead.offset as usize,
+                arg: schema.index_of(&lag_lead.arg.to_string())?,
+                default: lag_lead
+                    .default
+                    .map(|default| schema.index_of(&default.to_string()))
+                    .transpose()?,
+                ignore_nulls: lag_lead.ignore_nulls,
+                return_type: lag_lead.return_type.clone(),
+            }),
+            WindowFunction::NthValue(nth) => Self::NthValue(WindowFuncNthValueImpl {
+                n: nth.n.map(|n| n as usize),
+                arg: schema.index_of(&nth.arg.to_string())?,
+                ignore_nulls: nth.ignore_nulls,
+                return_type: nth.return_type.clone(),
+            }),
+            WindowFunction::Ntile(n) => Self::Ntile(*n as usize),
+            WindowFunction::PercentRank => Self::PercentRank,
+            WindowFunction::CumeDist => Self::CumeDist,
         })
     }
 }
@@ -138,15 +192,23 @@ impl WindowFunctionImpl {
             WindowFunctionInfo::RowNumber => Self::RowNumber,
             WindowFunctionInfo::Rank => Self::Rank,
             WindowFunctionInfo::DenseRank => Self::DenseRank,
+            WindowFunctionInfo::LagLead(lag_lead) => Self::LagLead(lag_lead),
+            WindowFunctionInfo::NthValue(nth) => Self::NthValue(nth),
+            WindowFunctionInfo::Ntile(n) => Self::Ntile(n),
+            WindowFunctionInfo::PercentRank => Self::PercentRank,
+            WindowFunctionInfo::CumeDist => Self::CumeDist,
         })
     }
 
     pub fn return_type(&self) -> Result<DataType> {
         Ok(match self {
             Self::Aggregate(agg) => agg.agg.return_type()?,
-            Self::RowNumber | Self::Rank | Self::DenseRank => {
+            Self::RowNumber | Self::Rank | Self::DenseRank | Self::Ntile(_) => {
                 DataType::Number(NumberDataType::UInt64)
             }
+            Self::PercentRank | Self::CumeDist => DataType::Number(NumberDataType::Float64),
+            Self::LagLead(lag_lead) => lag_lead.return_type.clone(),
+            Self::NthValue(nth) => nth.return_type.clone(),
         })
     }
 }
@@ -26,12 +26,14 @@ use crate::clusters::ClusterHelper;
 use crate::schedulers::fragments::plan_fragment::FragmentType;
 use crate::schedulers::PlanFragment;
 use crate::sessions::QueryContext;
+use crate::sql::executor::AsofJoin;
 use crate::sql::executor::Exchange;
 use crate::sql::executor::ExchangeSink;
 use crate::sql::executor::ExchangeSource;
 use crate::sql::executor::HashJoin;
 use crate::sql::executor::PhysicalPlan;
 use crate::sql::executor::PhysicalPlanReplacer;
+use crate::sql::executor::RangeJoin;
 use crate::sql::executor::TableScan;
 
 /// Visitor to split a `PhysicalPlan` into fragments.
@@ -155,6 +157,50 @@ impl PhysicalPlanReplacer for Fragmenter {
         }))
     }
 
+    fn replace_range_join(&mut self, plan: &RangeJoin) -> Result<PhysicalPlan> {
+        let mut fragments = vec![];
+        let left_input = self.replace(plan.left.as_ref())?;
+
+        // Consume current fragments to prevent them being consumed by `right_input`.
+        fragments.append(&mut self.fragments);
+        let right_input = self.replace(plan.right.as_ref())?;
+
+        fragments.append(&mut self.fragments);
+        self.fragments = fragments;
+
+        Ok(PhysicalPlan::RangeJoin(RangeJoin {
+            plan_id: plan.plan_id,
+            left: Box::new(left_input),
+            right: Box::new(right_input),
+            conditions: plan.conditions.clone(),
+            other_conditions: plan.other_conditions.clone(),
+            join_type: plan.join_type.clone(),
+            stat_info: plan.stat_info.clone(),
+        }))
+    }
+
+    fn replace_asof_join(&mut self, plan: &AsofJoin) -> Result<PhysicalPlan> {
+        let mut fragments = vec![];
+        let left_input = self.replace(plan.left.as_ref())?;
+
+        // Consume current fragments to prevent them being consumed by `right_input`.
+        fragments.append(&mut self.fragments);
+        let right_input = self.replace(plan.right.as_ref())?;
+
+        fragments.append(&mut self.fragments);
+        self.fragments = fragments;
+
+        Ok(PhysicalPlan::AsofJoin(AsofJoin {
+            plan_id: plan.plan_id,
+            left: Box::new(left_input),
+            right: Box::new(right_input),
+            left_keys: plan.left_keys.clone(),
+            right_keys: plan.right_keys.clone(),
+            match_condition: plan.match_condition.clone(),
+            stat_info: plan.stat_info.clone(),
+        }))
+    }
+
     fn replace_exchange(&mut self, plan: &Exchange) -> Result<PhysicalPlan> {
         // Recursively rewrite input
         let input = self.replace(plan.input.as_ref())?;
@@ -38,6 +38,7 @@ use common_meta_app::schema::TableIdent;
 use common_meta_app::schema::TableInfo;
 use common_meta_app::schema::TableMeta;
 use common_pipeline_core::processors::processor::ProcessorPtr;
+use common_pipeline_sources::input_formats::InputContext;
 use common_pipeline_sources::AsyncSource;
 use common_pipeline_sources::AsyncSourcer;
 use common_sql::binder::parse_stage_location;
@@ -194,9 +195,20 @@ impl AsyncSource for InferSchemaSource {
                 let arrow_schema = read_parquet_schema_async(&operator, &first_file.path).await?;
                 TableSchema::from(&arrow_schema)
             }
+            StageFileFormatType::Avro
+            | StageFileFormatType::Orc
+            | StageFileFormatType::ArrowIpc
+            | StageFileFormatType::Csv
+            | StageFileFormatType::NdJson => {
+                let input_format = InputContext::get_input_format(&file_format_options.format)?;
+                let schema = input_format
+                    .infer_schema(&first_file.path, &operator, &file_format_options)
+                    .await?;
+                schema.as_ref().clone()
+            }
             _ => {
                 return Err(ErrorCode::BadArguments(
-                    "infer_schema is currently limited to format Parquet",
+                    "infer_schema is currently limited to format Parquet, Avro, Orc, ArrowIpc, CSV and NDJSON",
                 ));
             }
         };
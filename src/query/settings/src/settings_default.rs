@@ -155,6 +155,11 @@ impl DefaultSettings {
                     desc: "Sets the maximum number of rows that can be returned in a query result when no specific row count is specified. Setting it to 0 means no limit.",
                     possible_values: None,
                 }),
+                ("max_cte_recursive_depth", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(1000),
+                    desc: "Sets the maximum number of iterations of a recursive common table expression.",
+                    possible_values: None,
+                }),
                 ("enable_distributed_eval_index", DefaultSettingValue {
                     value: UserSettingValue::UInt64(1),
                     desc: "Enables evaluated indexes to be created and maintained across multiple nodes.",
@@ -230,7 +235,12 @@ impl DefaultSettings {
                 }),
                 ("spilling_bytes_threshold_per_proc", DefaultSettingValue {
                     value: UserSettingValue::UInt64(0),
-                    desc: "Sets the maximum amount of memory in bytes that an aggregator can use before spilling data to storage during query execution.",
+                    desc: "Sets the maximum amount of memory in bytes that an aggregator, a sort or a window function can use before spilling data to storage during query execution.",
+                    possible_values: None,
+                }),
+                ("join_spilling_threshold", DefaultSettingValue {
+                    value: UserSettingValue::UInt64(0),
+                    desc: "Sets the maximum amount of memory in bytes that a hash join can use for the build side before spilling data to storage during query execution. Setting it to 0 disables spilling.",
                     possible_values: None,
                 }),
                 ("group_by_shuffle_mode", DefaultSettingValue {
@@ -213,6 +213,10 @@ impl Settings {
         self.try_get_u64("max_result_rows")
     }
 
+    pub fn get_max_cte_recursive_depth(&self) -> Result<u64> {
+        self.try_get_u64("max_cte_recursive_depth")
+    }
+
     pub fn set_enable_distributed_eval_index(&self, val: bool) -> Result<()> {
         self.try_set_u64("enable_distributed_eval_index", u64::from(val))
     }
@@ -312,6 +316,10 @@ impl Settings {
         self.try_set_u64("spilling_bytes_threshold_per_proc", value as u64)
     }
 
+    pub fn get_join_spilling_threshold(&self) -> Result<usize> {
+        Ok(self.try_get_u64("join_spilling_threshold")? as usize)
+    }
+
     pub fn get_group_by_shuffle_mode(&self) -> Result<String> {
         self.try_get_string("group_by_shuffle_mode")
     }
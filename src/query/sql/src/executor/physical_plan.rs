@@ -290,19 +290,49 @@ pub enum WindowFunction {
     RowNumber,
     Rank,
     DenseRank,
+    LagLead(LagLeadFunctionDesc),
+    NthValue(NthValueFunctionDesc),
+    Ntile(u64),
+    PercentRank,
+    CumeDist,
 }
 
 impl WindowFunction {
     fn data_type(&self) -> DataType {
         match self {
             WindowFunction::Aggregate(agg) => agg.sig.return_type.clone(),
-            WindowFunction::RowNumber | WindowFunction::Rank | WindowFunction::DenseRank => {
-                DataType::Number(NumberDataType::UInt64)
+            WindowFunction::RowNumber
+            | WindowFunction::Rank
+            | WindowFunction::DenseRank
+            | WindowFunction::Ntile(_) => DataType::Number(NumberDataType::UInt64),
+            WindowFunction::PercentRank | WindowFunction::CumeDist => {
+                DataType::Number(NumberDataType::Float64)
             }
+            WindowFunction::LagLead(lag_lead) => lag_lead.return_type.clone(),
+            WindowFunction::NthValue(nth) => nth.return_type.clone(),
         }
     }
 }
 
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct LagLeadFunctionDesc {
+    pub is_lag: bool,
+    pub offset: u64,
+    pub arg: IndexType,
+    pub default: Option<IndexType>,
+    pub ignore_nulls: bool,
+    pub return_type: DataType,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct NthValueFunctionDesc {
+    /// `None` means the last row of the frame.
+    pub n: Option<u64>,
+    pub arg: IndexType,
+    pub ignore_nulls: bool,
+    pub return_type: DataType,
+}
+
 impl Display for WindowFunction {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
@@ -310,6 +340,16 @@ impl Display for WindowFunction {
             WindowFunction::RowNumber => write!(f, "row_number"),
             WindowFunction::Rank => write!(f, "rank"),
             WindowFunction::DenseRank => write!(f, "dense_rank"),
+            WindowFunction::LagLead(lag_lead) if lag_lead.is_lag => write!(f, "lag"),
+            WindowFunction::LagLead(_) => write!(f, "lead"),
+            WindowFunction::NthValue(nth) => match nth.n {
+                Some(1) => write!(f, "first_value"),
+                None => write!(f, "last_value"),
+                Some(_) => write!(f, "nth_value"),
+            },
+            WindowFunction::Ntile(_) => write!(f, "ntile"),
+            WindowFunction::PercentRank => write!(f, "percent_rank"),
+            WindowFunction::CumeDist => write!(f, "cume_dist"),
         }
     }
 }
@@ -482,6 +522,70 @@ impl HashJoin {
     }
 }
 
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct RangeJoin {
+    /// A unique id of operator in a `PhysicalPlan` tree.
+    /// Only used for display.
+    pub plan_id: u32,
+
+    pub left: Box<PhysicalPlan>,
+    pub right: Box<PhysicalPlan>,
+    // The first two conditions are used by IEJoin, the first one alone by a band join.
+    pub conditions: Vec<RangeJoinCondition>,
+    // Remaining conditions, evaluated on the joined block.
+    pub other_conditions: Vec<RemoteExpr>,
+    pub join_type: JoinType,
+
+    /// Only used for explain
+    pub stat_info: Option<PlanStatsInfo>,
+}
+
+impl RangeJoin {
+    pub fn output_schema(&self) -> Result<DataSchemaRef> {
+        let mut fields = self.left.output_schema()?.fields().clone();
+        fields.extend(self.right.output_schema()?.fields().clone());
+        Ok(DataSchemaRefExt::create(fields))
+    }
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct RangeJoinCondition {
+    pub left_expr: RemoteExpr,
+    pub right_expr: RemoteExpr,
+    // "gt" | "lt" | "gte" | "lte"
+    pub operator: String,
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct AsofJoin {
+    /// A unique id of operator in a `PhysicalPlan` tree.
+    /// Only used for display.
+    pub plan_id: u32,
+
+    pub left: Box<PhysicalPlan>,
+    pub right: Box<PhysicalPlan>,
+    // Equi conditions, the nearest match is only picked among the rows with equal keys.
+    pub left_keys: Vec<RemoteExpr>,
+    pub right_keys: Vec<RemoteExpr>,
+    pub match_condition: RangeJoinCondition,
+
+    /// Only used for explain
+    pub stat_info: Option<PlanStatsInfo>,
+}
+
+impl AsofJoin {
+    pub fn output_schema(&self) -> Result<DataSchemaRef> {
+        let mut fields = self.left.output_schema()?.fields().clone();
+        for field in self.right.output_schema()?.fields() {
+            fields.push(DataField::new(
+                field.name().as_str(),
+                field.data_type().wrap_nullable(),
+            ));
+        }
+        Ok(DataSchemaRefExt::create(fields))
+    }
+}
+
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct Exchange {
     pub input: Box<PhysicalPlan>,
@@ -564,6 +668,50 @@ impl UnionAll {
     }
 }
 
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct RecursiveUnion {
+    /// A unique id of operator in a `PhysicalPlan` tree.
+    /// Only used for display.
+    pub plan_id: u32,
+
+    pub cte_name: String,
+    /// The anchor member, evaluated once.
+    pub left: Box<PhysicalPlan>,
+    /// The recursive member, evaluated repeatedly on the rows of the previous iteration.
+    pub right: Box<PhysicalPlan>,
+    pub pairs: Vec<(String, String)>,
+    pub schema: DataSchemaRef,
+    pub distinct: bool,
+
+    /// Only used for explain
+    pub stat_info: Option<PlanStatsInfo>,
+}
+
+impl RecursiveUnion {
+    pub fn output_schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+}
+
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct RecursiveCteScan {
+    /// A unique id of operator in a `PhysicalPlan` tree.
+    /// Only used for display.
+    pub plan_id: u32,
+
+    pub cte_name: String,
+    pub schema: DataSchemaRef,
+
+    /// Only used for explain
+    pub stat_info: Option<PlanStatsInfo>,
+}
+
+impl RecursiveCteScan {
+    pub fn output_schema(&self) -> Result<DataSchemaRef> {
+        Ok(self.schema.clone())
+    }
+}
+
 #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct DistributedInsertSelect {
     pub input: Box<PhysicalPlan>,
@@ -619,8 +767,12 @@ pub enum PhysicalPlan {
     Sort(Sort),
     Limit(Limit),
     HashJoin(HashJoin),
+    RangeJoin(RangeJoin),
+    AsofJoin(AsofJoin),
     Exchange(Exchange),
     UnionAll(UnionAll),
+    RecursiveUnion(RecursiveUnion),
+    RecursiveCteScan(RecursiveCteScan),
     RuntimeFilterSource(RuntimeFilterSource),
 
     /// For insert into ... select ... in cluster
@@ -653,10 +805,14 @@ impl PhysicalPlan {
             PhysicalPlan::Sort(plan) => plan.output_schema(),
             PhysicalPlan::Limit(plan) => plan.output_schema(),
             PhysicalPlan::HashJoin(plan) => plan.output_schema(),
+            PhysicalPlan::RangeJoin(plan) => plan.output_schema(),
+            PhysicalPlan::AsofJoin(plan) => plan.output_schema(),
             PhysicalPlan::Exchange(plan) => plan.output_schema(),
             PhysicalPlan::ExchangeSource(plan) => plan.output_schema(),
             PhysicalPlan::ExchangeSink(plan) => plan.output_schema(),
             PhysicalPlan::UnionAll(plan) => plan.output_schema(),
+            PhysicalPlan::RecursiveUnion(plan) => plan.output_schema(),
+            PhysicalPlan::RecursiveCteScan(plan) => plan.output_schema(),
             PhysicalPlan::DistributedInsertSelect(plan) => plan.output_schema(),
             PhysicalPlan::ProjectSet(plan) => plan.output_schema(),
             PhysicalPlan::RuntimeFilterSource(plan) => plan.output_schema(),
@@ -676,8 +832,12 @@ impl PhysicalPlan {
             PhysicalPlan::Sort(_) => "Sort".to_string(),
             PhysicalPlan::Limit(_) => "Limit".to_string(),
             PhysicalPlan::HashJoin(_) => "HashJoin".to_string(),
+            PhysicalPlan::RangeJoin(_) => "RangeJoin".to_string(),
+            PhysicalPlan::AsofJoin(_) => "AsofJoin".to_string(),
             PhysicalPlan::Exchange(_) => "Exchange".to_string(),
             PhysicalPlan::UnionAll(_) => "UnionAll".to_string(),
+            PhysicalPlan::RecursiveUnion(_) => "RecursiveUnion".to_string(),
+            PhysicalPlan::RecursiveCteScan(_) => "RecursiveCteScan".to_string(),
             PhysicalPlan::DistributedInsertSelect(_) => "DistributedInsertSelect".to_string(),
             PhysicalPlan::ExchangeSource(_) => "Exchange Source".to_string(),
             PhysicalPlan::ExchangeSink(_) => "Exchange Sink".to_string(),
@@ -701,12 +861,22 @@ impl PhysicalPlan {
             PhysicalPlan::HashJoin(plan) => Box::new(
                 std::iter::once(plan.probe.as_ref()).chain(std::iter::once(plan.build.as_ref())),
             ),
+            PhysicalPlan::RangeJoin(plan) => Box::new(
+                std::iter::once(plan.left.as_ref()).chain(std::iter::once(plan.right.as_ref())),
+            ),
+            PhysicalPlan::AsofJoin(plan) => Box::new(
+                std::iter::once(plan.left.as_ref()).chain(std::iter::once(plan.right.as_ref())),
+            ),
             PhysicalPlan::Exchange(plan) => Box::new(std::iter::once(plan.input.as_ref())),
             PhysicalPlan::ExchangeSource(_) => Box::new(std::iter::empty()),
             PhysicalPlan::ExchangeSink(plan) => Box::new(std::iter::once(plan.input.as_ref())),
             PhysicalPlan::UnionAll(plan) => Box::new(
                 std::iter::once(plan.left.as_ref()).chain(std::iter::once(plan.right.as_ref())),
             ),
+            PhysicalPlan::RecursiveUnion(plan) => Box::new(
+                std::iter::once(plan.left.as_ref()).chain(std::iter::once(plan.right.as_ref())),
+            ),
+            PhysicalPlan::RecursiveCteScan(_) => Box::new(std::iter::empty()),
             PhysicalPlan::DistributedInsertSelect(plan) => {
                 Box::new(std::iter::once(plan.input.as_ref()))
             }
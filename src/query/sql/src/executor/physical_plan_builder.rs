@@ -25,10 +25,12 @@ use common_catalog::table_context::TableContext;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_expression::type_check::check_function;
+use common_expression::type_check::common_super_type;
 use common_expression::types::DataType;
 use common_expression::ConstantFolder;
 use common_expression::DataBlock;
 use common_expression::DataField;
+use common_expression::DataSchemaRef;
 use common_expression::DataSchemaRefExt;
 use common_expression::Expr;
 use common_expression::RemoteExpr;
@@ -41,14 +43,24 @@ use super::AggregateFinal;
 use super::AggregateFunctionDesc;
 use super::AggregateFunctionSignature;
 use super::AggregatePartial;
+use super::AsofJoin;
 use super::Exchange as PhysicalExchange;
 use super::Filter;
 use super::HashJoin;
+use super::LagLeadFunctionDesc;
 use super::Limit;
+use super::NthValueFunctionDesc;
 use super::ProjectSet;
+use super::RangeJoin;
+use super::RangeJoinCondition;
+use super::RecursiveCteScan;
+use super::RecursiveUnion;
 use super::Sort;
 use super::TableScan;
 use super::WindowFunction;
+use crate::binder::satisfied_by;
+use crate::binder::split_conjunctions;
+use crate::binder::wrap_cast;
 use crate::executor::explain::PlanStatsInfo;
 use crate::executor::table_read_plan::ToReadDataSourcePlan;
 use crate::executor::EvalScalar;
@@ -63,7 +75,10 @@ use crate::optimizer::RelExpr;
 use crate::optimizer::SExpr;
 use crate::plans::AggregateMode;
 use crate::plans::AndExpr;
+use crate::plans::ComparisonExpr;
+use crate::plans::ComparisonOp;
 use crate::plans::Exchange;
+use crate::plans::Join;
 use crate::plans::JoinType;
 use crate::plans::RelOperator;
 use crate::plans::ScalarExpr;
@@ -285,6 +300,22 @@ impl PhysicalPlanBuilder {
                 let build_side = self.build(s_expr.child(1)?).await?;
                 let probe_side = self.build(s_expr.child(0)?).await?;
 
+                if join.join_type == JoinType::Asof {
+                    return self.build_asof_join(probe_side, build_side, join, stat_info);
+                }
+
+                if let Some((conditions, other_conditions)) =
+                    Self::range_join_conditions(s_expr, join)?
+                {
+                    return self.build_range_join(
+                        probe_side,
+                        build_side,
+                        conditions,
+                        other_conditions,
+                        stat_info,
+                    );
+                }
+
                 let build_schema = match join.join_type {
                     JoinType::Left | JoinType::Full => {
                         let build_schema = build_side.output_schema()?;
@@ -700,6 +731,16 @@ impl PhysicalPlanBuilder {
                     })
                     .collect::<Vec<_>>();
 
+                let column_index = |arg: &ScalarExpr| {
+                    if let ScalarExpr::BoundColumnRef(col) = arg {
+                        Ok(col.column.index)
+                    } else {
+                        Err(ErrorCode::Internal(
+                            "Window function argument must be a BoundColumnRef".to_string(),
+                        ))
+                    }
+                };
+
                 let func = match &w.function {
                     WindowFuncType::Aggregate(agg) => {
                         WindowFunction::Aggregate(AggregateFunctionDesc {
@@ -731,6 +772,31 @@ impl PhysicalPlanBuilder {
                     WindowFuncType::RowNumber => WindowFunction::RowNumber,
                     WindowFuncType::Rank => WindowFunction::Rank,
                     WindowFuncType::DenseRank => WindowFunction::DenseRank,
+                    WindowFuncType::Ntile(n) => WindowFunction::Ntile(*n),
+                    WindowFuncType::PercentRank => WindowFunction::PercentRank,
+                    WindowFuncType::CumeDist => WindowFunction::CumeDist,
+                    WindowFuncType::LagLead(lag_lead) => {
+                        WindowFunction::LagLead(LagLeadFunctionDesc {
+                            is_lag: lag_lead.is_lag,
+                            offset: lag_lead.offset,
+                            arg: column_index(&lag_lead.arg)?,
+                            default: lag_lead
+                                .default
+                                .as_ref()
+                                .map(|default| column_index(default))
+                                .transpose()?,
+                            ignore_nulls: lag_lead.ignore_nulls,
+                            return_type: *lag_lead.return_type.clone(),
+                        })
+                    }
+                    WindowFuncType::NthValue(nth) => {
+                        WindowFunction::NthValue(NthValueFunctionDesc {
+                            n: nth.n,
+                            arg: column_index(&nth.arg)?,
+                            ignore_nulls: nth.ignore_nulls,
+                            return_type: *nth.return_type.clone(),
+                        })
+                    }
                 };
 
                 Ok(PhysicalPlan::Window(Window {
@@ -824,6 +890,54 @@ impl PhysicalPlanBuilder {
                 }))
             }
 
+            RelOperator::RecursiveUnion(op) => {
+                let left = self.build(s_expr.child(0)?).await?;
+                let left_schema = left.output_schema()?;
+                let pairs = op
+                    .pairs
+                    .iter()
+                    .map(|(l, r)| (l.to_string(), r.to_string()))
+                    .collect::<Vec<_>>();
+                let fields = pairs
+                    .iter()
+                    .map(|(left, _)| Ok(left_schema.field_with_name(left)?.clone()))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(PhysicalPlan::RecursiveUnion(RecursiveUnion {
+                    plan_id: self.next_plan_id(),
+                    cte_name: op.cte_name.clone(),
+                    left: Box::new(left),
+                    right: Box::new(self.build(s_expr.child(1)?).await?),
+                    pairs,
+                    schema: DataSchemaRefExt::create(fields),
+                    distinct: op.distinct,
+
+                    stat_info: Some(stat_info),
+                }))
+            }
+
+            RelOperator::RecursiveCteScan(op) => {
+                let metadata = self.metadata.read().clone();
+                let fields = op
+                    .columns
+                    .iter()
+                    .map(|index| match metadata.column(*index) {
+                        ColumnEntry::DerivedColumn(DerivedColumn { data_type, .. }) => {
+                            Ok(DataField::new(&index.to_string(), data_type.clone()))
+                        }
+                        _ => Err(ErrorCode::Internal(
+                            "Columns of a recursive cte scan must be derived columns",
+                        )),
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(PhysicalPlan::RecursiveCteScan(RecursiveCteScan {
+                    plan_id: self.next_plan_id(),
+                    cte_name: op.cte_name.clone(),
+                    schema: DataSchemaRefExt::create(fields),
+
+                    stat_info: Some(stat_info),
+                }))
+            }
+
             RelOperator::RuntimeFilterSource(op) => {
                 let left_side = Box::new(self.build(s_expr.child(0)?).await?);
                 let left_schema = left_side.output_schema()?;
@@ -901,6 +1015,206 @@ impl PhysicalPlanBuilder {
         }
     }
 
+    /// Check whether the join can be executed by `RangeJoin`, that is an inner join without
+    /// equi conditions but with at least one inequality between the two sides.
+    /// Returns the inequalities (with the left side expression first) and the remaining conditions.
+    #[allow(clippy::type_complexity)]
+    fn range_join_conditions(
+        s_expr: &SExpr,
+        join: &Join,
+    ) -> Result<Option<(Vec<(ScalarExpr, ScalarExpr, ComparisonOp)>, Vec<ScalarExpr>)>> {
+        if !matches!(join.join_type, JoinType::Inner | JoinType::Cross)
+            || !join.left_conditions.is_empty()
+            || join.non_equi_conditions.is_empty()
+        {
+            return Ok(None);
+        }
+
+        let rel_expr = RelExpr::with_s_expr(s_expr);
+        let left_prop = rel_expr.derive_relational_prop_child(0)?;
+        let right_prop = rel_expr.derive_relational_prop_child(1)?;
+
+        let mut conditions = vec![];
+        let mut other_conditions = vec![];
+        for condition in join.non_equi_conditions.iter().flat_map(split_conjunctions) {
+            let (op, left, right) = match &condition {
+                // IEJoin handles at most two inequalities, the rest are evaluated as a filter.
+                ScalarExpr::ComparisonExpr(ComparisonExpr { op, left, right })
+                    if conditions.len() < 2
+                        && !matches!(op, ComparisonOp::Equal | ComparisonOp::NotEqual)
+                        && !left.used_columns().is_empty()
+                        && !right.used_columns().is_empty() =>
+                {
+                    (op, left, right)
+                }
+                _ => {
+                    other_conditions.push(condition);
+                    continue;
+                }
+            };
+
+            let (op, left, right) =
+                if satisfied_by(left, &left_prop) && satisfied_by(right, &right_prop) {
+                    (op.clone(), left, right)
+                } else if satisfied_by(left, &right_prop) && satisfied_by(right, &left_prop) {
+                    let op = match op {
+                        ComparisonOp::GT => ComparisonOp::LT,
+                        ComparisonOp::LT => ComparisonOp::GT,
+                        ComparisonOp::GTE => ComparisonOp::LTE,
+                        _ => ComparisonOp::GTE,
+                    };
+                    (op, right, left)
+                } else {
+                    other_conditions.push(condition);
+                    continue;
+                };
+
+            let left_type = left.data_type()?;
+            let right_type = right.data_type()?;
+            match common_super_type(
+                left_type.clone(),
+                right_type.clone(),
+                &BUILTIN_FUNCTIONS.default_cast_rules,
+            ) {
+                Some(common_type) => {
+                    let left = match left_type == common_type {
+                        true => *left.clone(),
+                        false => wrap_cast(left, &common_type),
+                    };
+                    let right = match right_type == common_type {
+                        true => *right.clone(),
+                        false => wrap_cast(right, &common_type),
+                    };
+                    conditions.push((left, right, op));
+                }
+                None => other_conditions.push(condition),
+            }
+        }
+
+        if conditions.is_empty() {
+            return Ok(None);
+        }
+        Ok(Some((conditions, other_conditions)))
+    }
+
+    fn build_range_join(
+        &mut self,
+        left_side: PhysicalPlan,
+        right_side: PhysicalPlan,
+        conditions: Vec<(ScalarExpr, ScalarExpr, ComparisonOp)>,
+        other_conditions: Vec<ScalarExpr>,
+        stat_info: PlanStatsInfo,
+    ) -> Result<PhysicalPlan> {
+        let left_schema = left_side.output_schema()?;
+        let right_schema = right_side.output_schema()?;
+        let merged_schema = DataSchemaRefExt::create(
+            left_schema
+                .fields()
+                .iter()
+                .chain(right_schema.fields())
+                .cloned()
+                .collect::<Vec<_>>(),
+        );
+
+        let func_ctx = self.ctx.get_function_context()?;
+        let build_expr = |scalar: &ScalarExpr, schema: &DataSchemaRef| -> Result<RemoteExpr> {
+            let expr = scalar
+                .resolve_and_check(schema.as_ref())?
+                .project_column_ref(|index| schema.index_of(&index.to_string()).unwrap());
+            let (expr, _) = ConstantFolder::fold(&expr, func_ctx, &BUILTIN_FUNCTIONS);
+            Ok(expr.as_remote_expr())
+        };
+
+        let conditions = conditions
+            .iter()
+            .map(|(left, right, op)| {
+                Ok(RangeJoinCondition {
+                    left_expr: build_expr(left, &left_schema)?,
+                    right_expr: build_expr(right, &right_schema)?,
+                    operator: op.to_func_name().to_string(),
+                })
+            })
+            .collect::<Result<_>>()?;
+        let other_conditions = other_conditions
+            .iter()
+            .map(|scalar| build_expr(scalar, &merged_schema))
+            .collect::<Result<_>>()?;
+
+        Ok(PhysicalPlan::RangeJoin(RangeJoin {
+            plan_id: self.next_plan_id(),
+            left: Box::new(left_side),
+            right: Box::new(right_side),
+            conditions,
+            other_conditions,
+            join_type: JoinType::Inner,
+            stat_info: Some(stat_info),
+        }))
+    }
+
+    fn build_asof_join(
+        &mut self,
+        left_side: PhysicalPlan,
+        right_side: PhysicalPlan,
+        join: &Join,
+        stat_info: PlanStatsInfo,
+    ) -> Result<PhysicalPlan> {
+        // The binder has normalized the match condition into `left op right`.
+        let (op, left, right) = match join.non_equi_conditions.as_slice() {
+            [ScalarExpr::ComparisonExpr(ComparisonExpr { op, left, right })] => (op, left, right),
+            _ => {
+                return Err(ErrorCode::Internal(
+                    "Asof join should contain exactly one match condition",
+                ));
+            }
+        };
+
+        let left_schema = left_side.output_schema()?;
+        // Wrap nullable type for columns in right side, the same as left outer join.
+        let right_schema = DataSchemaRefExt::create(
+            right_side
+                .output_schema()?
+                .fields()
+                .iter()
+                .map(|field| DataField::new(field.name(), field.data_type().wrap_nullable()))
+                .collect::<Vec<_>>(),
+        );
+
+        let func_ctx = self.ctx.get_function_context()?;
+        let build_expr = |scalar: &ScalarExpr, schema: &DataSchemaRef| -> Result<RemoteExpr> {
+            let expr = scalar
+                .resolve_and_check(schema.as_ref())?
+                .project_column_ref(|index| schema.index_of(&index.to_string()).unwrap());
+            let (expr, _) = ConstantFolder::fold(&expr, func_ctx, &BUILTIN_FUNCTIONS);
+            Ok(expr.as_remote_expr())
+        };
+
+        let left_keys = join
+            .left_conditions
+            .iter()
+            .map(|scalar| build_expr(scalar, &left_schema))
+            .collect::<Result<_>>()?;
+        let right_keys = join
+            .right_conditions
+            .iter()
+            .map(|scalar| build_expr(scalar, &right_schema))
+            .collect::<Result<_>>()?;
+        let match_condition = RangeJoinCondition {
+            left_expr: build_expr(left, &left_schema)?,
+            right_expr: build_expr(right, &right_schema)?,
+            operator: op.to_func_name().to_string(),
+        };
+
+        Ok(PhysicalPlan::AsofJoin(AsofJoin {
+            plan_id: self.next_plan_id(),
+            left: Box::new(left_side),
+            right: Box::new(right_side),
+            left_keys,
+            right_keys,
+            match_condition,
+            stat_info: Some(stat_info),
+        }))
+    }
+
     fn push_downs(
         &self,
         scan: &Scan,
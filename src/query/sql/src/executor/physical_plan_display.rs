@@ -23,6 +23,7 @@ use super::DistributedInsertSelect;
 use super::ProjectSet;
 use crate::executor::AggregateFinal;
 use crate::executor::AggregatePartial;
+use crate::executor::AsofJoin;
 use crate::executor::EvalScalar;
 use crate::executor::Exchange;
 use crate::executor::ExchangeSink;
@@ -32,6 +33,10 @@ use crate::executor::HashJoin;
 use crate::executor::Limit;
 use crate::executor::PhysicalPlan;
 use crate::executor::Project;
+use crate::executor::RangeJoin;
+use crate::executor::RangeJoinCondition;
+use crate::executor::RecursiveCteScan;
+use crate::executor::RecursiveUnion;
 use crate::executor::RuntimeFilterSource;
 use crate::executor::Sort;
 use crate::executor::TableScan;
@@ -66,10 +71,14 @@ impl<'a> Display for PhysicalPlanIndentFormatDisplay<'a> {
             PhysicalPlan::Sort(sort) => write!(f, "{}", sort)?,
             PhysicalPlan::Limit(limit) => write!(f, "{}", limit)?,
             PhysicalPlan::HashJoin(join) => write!(f, "{}", join)?,
+            PhysicalPlan::RangeJoin(join) => write!(f, "{}", join)?,
+            PhysicalPlan::AsofJoin(join) => write!(f, "{}", join)?,
             PhysicalPlan::Exchange(exchange) => write!(f, "{}", exchange)?,
             PhysicalPlan::ExchangeSource(source) => write!(f, "{}", source)?,
             PhysicalPlan::ExchangeSink(sink) => write!(f, "{}", sink)?,
             PhysicalPlan::UnionAll(union_all) => write!(f, "{}", union_all)?,
+            PhysicalPlan::RecursiveUnion(recursive_union) => write!(f, "{}", recursive_union)?,
+            PhysicalPlan::RecursiveCteScan(scan) => write!(f, "{}", scan)?,
             PhysicalPlan::DistributedInsertSelect(insert_select) => write!(f, "{}", insert_select)?,
             PhysicalPlan::ProjectSet(unnest) => write!(f, "{}", unnest)?,
             PhysicalPlan::RuntimeFilterSource(plan) => write!(f, "{}", plan)?,
@@ -286,6 +295,73 @@ impl Display for HashJoin {
     }
 }
 
+impl Display for RangeJoin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let conditions = self
+            .conditions
+            .iter()
+            .map(|condition| condition.to_string())
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let other_conditions = self
+            .other_conditions
+            .iter()
+            .map(|scalar| scalar.as_expr(&BUILTIN_FUNCTIONS).sql_display())
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        write!(
+            f,
+            "RangeJoin: {}, range join conditions: [{}], other conditions: [{}]",
+            &self.join_type, conditions, other_conditions,
+        )
+    }
+}
+
+impl Display for AsofJoin {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let left_keys = self
+            .left_keys
+            .iter()
+            .map(|scalar| scalar.as_expr(&BUILTIN_FUNCTIONS).sql_display())
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        let right_keys = self
+            .right_keys
+            .iter()
+            .map(|scalar| scalar.as_expr(&BUILTIN_FUNCTIONS).sql_display())
+            .collect::<Vec<String>>()
+            .join(", ");
+
+        write!(
+            f,
+            "AsofJoin: left keys: [{}], right keys: [{}], match condition: [{}]",
+            left_keys, right_keys, self.match_condition,
+        )
+    }
+}
+
+impl Display for RangeJoinCondition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let operator = match self.operator.as_str() {
+            "gt" => ">",
+            "gte" => ">=",
+            "lt" => "<",
+            "lte" => "<=",
+            operator => operator,
+        };
+        write!(
+            f,
+            "{} {} {}",
+            self.left_expr.as_expr(&BUILTIN_FUNCTIONS).sql_display(),
+            operator,
+            self.right_expr.as_expr(&BUILTIN_FUNCTIONS).sql_display()
+        )
+    }
+}
+
 impl Display for Exchange {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         let keys = self
@@ -324,6 +400,18 @@ impl Display for UnionAll {
     }
 }
 
+impl Display for RecursiveUnion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RecursiveUnion: {}", self.cte_name)
+    }
+}
+
+impl Display for RecursiveCteScan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "RecursiveCteScan: {}", self.cte_name)
+    }
+}
+
 impl Display for DistributedInsertSelect {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(f, "DistributedInsertSelect")
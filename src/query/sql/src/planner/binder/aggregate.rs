@@ -42,12 +42,12 @@ use crate::plans::CastExpr;
 use crate::plans::ComparisonExpr;
 use crate::plans::EvalScalar;
 use crate::plans::FunctionCall;
+use crate::plans::LambdaFunc;
 use crate::plans::NotExpr;
 use crate::plans::OrExpr;
 use crate::plans::ScalarExpr;
 use crate::plans::ScalarItem;
 use crate::plans::WindowFunc;
-use crate::plans::WindowFuncType;
 use crate::plans::WindowOrderBy;
 use crate::BindContext;
 use crate::IndexType;
@@ -143,6 +143,22 @@ impl<'a> AggregateRewriter<'a> {
                 }
                 .into())
             }
+            ScalarExpr::LambdaFunction(lambda_func) => {
+                let new_args = lambda_func
+                    .args
+                    .iter()
+                    .map(|arg| self.visit(arg))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(LambdaFunc {
+                    span: lambda_func.span,
+                    func_name: lambda_func.func_name.clone(),
+                    args: new_args,
+                    lambda_expr: lambda_func.lambda_expr.clone(),
+                    lambda_display: lambda_func.lambda_display.clone(),
+                    return_type: lambda_func.return_type.clone(),
+                }
+                .into())
+            }
             ScalarExpr::CastExpr(cast) => Ok(CastExpr {
                 span: cast.span,
                 is_try: cast.is_try,
@@ -175,24 +191,7 @@ impl<'a> AggregateRewriter<'a> {
                         })
                     })
                     .collect::<Result<Vec<_>>>()?;
-                let func = match &window.func {
-                    WindowFuncType::Aggregate(agg) => {
-                        let new_args = agg
-                            .args
-                            .iter()
-                            .map(|arg| self.visit(arg))
-                            .collect::<Result<Vec<_>>>()?;
-                        WindowFuncType::Aggregate(AggregateFunction {
-                            func_name: agg.func_name.clone(),
-                            args: new_args,
-                            display_name: agg.display_name.clone(),
-                            distinct: agg.distinct,
-                            params: agg.params.clone(),
-                            return_type: agg.return_type.clone(),
-                        })
-                    }
-                    func => func.clone(),
-                };
+                let func = window.func.replace_arguments(|arg| self.visit(arg))?;
 
                 self.in_window = false;
 
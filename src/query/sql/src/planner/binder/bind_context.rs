@@ -46,6 +46,7 @@ pub enum ExprContext {
     SelectClause,
     WhereClause,
     HavingClause,
+    QualifyClause,
     OrderByClause,
     LimitClause,
 
@@ -168,6 +169,8 @@ pub struct BindContext {
 pub struct CteInfo {
     pub columns_alias: Vec<String>,
     pub query: Query,
+    // Declared by `WITH RECURSIVE`
+    pub recursive: bool,
 }
 
 impl BindContext {
@@ -12,6 +12,7 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use common_ast::ast::format_statement;
@@ -63,6 +64,12 @@ pub struct Binder {
     pub catalogs: Arc<CatalogManager>,
     pub name_resolution_ctx: NameResolutionContext,
     pub metadata: MetadataRef,
+    // the max position of `$1, $2, ...` in the select being bound, which decides
+    // the number of columns of the text files queried from stages.
+    pub max_column_position: usize,
+    // Columns of the working tables of the recursive ctes being bound, it's `None`
+    // while binding the anchor member, in which the cte can't be referenced.
+    pub recursive_ctes: HashMap<String, Option<Vec<ColumnBinding>>>,
 }
 
 impl<'a> Binder {
@@ -77,6 +84,8 @@ impl<'a> Binder {
             catalogs,
             name_resolution_ctx,
             metadata,
+            max_column_position: 0,
+            recursive_ctes: HashMap::new(),
         }
     }
 
@@ -236,11 +245,13 @@ impl<'a> Binder {
             }
             Statement::Insert(stmt) => self.bind_insert(bind_context, stmt).await?,
             Statement::Replace(stmt) => self.bind_replace(bind_context, stmt).await?,
+            Statement::MergeInto(stmt) => self.bind_merge_into(bind_context, stmt).await?,
             Statement::Delete {
                 table_reference,
+                using,
                 selection,
             } => {
-                self.bind_delete(bind_context, table_reference, selection)
+                self.bind_delete(bind_context, table_reference, using, selection)
                     .await?
             }
             Statement::Update(stmt) => self.bind_update(bind_context, stmt).await?,
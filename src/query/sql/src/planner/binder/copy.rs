@@ -30,6 +30,7 @@ use common_ast::ast::UriLocation;
 use common_ast::parser::parse_sql;
 use common_ast::parser::tokenize_sql;
 use common_ast::Dialect;
+use common_ast::Visitor;
 use common_catalog::plan::DataSourceInfo;
 use common_catalog::plan::DataSourcePlan;
 use common_catalog::plan::Partitions;
@@ -48,6 +49,7 @@ use tracing::debug;
 use tracing::info;
 
 use crate::binder::location::parse_uri_location;
+use crate::binder::table::MaxColumnPosition;
 use crate::binder::Binder;
 use crate::plans::CopyPlan;
 use crate::plans::Plan;
@@ -250,6 +252,7 @@ impl<'a> Binder {
                 stage_info,
                 files_info,
                 files_to_copy: None,
+                is_select: false,
             }),
             output_schema: table.schema(),
             parts: Partitions::default(),
@@ -315,6 +318,7 @@ impl<'a> Binder {
                 stage_info,
                 files_info,
                 files_to_copy: None,
+                is_select: false,
             }),
             output_schema: table.schema(),
             parts: Partitions::default(),
@@ -595,6 +599,11 @@ impl<'a> Binder {
             return Err(ErrorCode::EmptyData("no file need to copy"));
         }
 
+        let mut max_column_position = MaxColumnPosition::default();
+        for target in select_list {
+            max_column_position.visit_select_target(target);
+        }
+        self.max_column_position = max_column_position.max_pos;
         let (s_expr, mut from_context) = self
             .bind_stage_table(
                 bind_context,
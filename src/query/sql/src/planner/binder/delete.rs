@@ -13,19 +13,30 @@
 // limitations under the License.
 
 use common_ast::ast::Expr;
+use common_ast::ast::Join;
+use common_ast::ast::JoinCondition;
+use common_ast::ast::JoinOperator;
+use common_ast::ast::Literal;
 use common_ast::ast::TableReference;
+use common_catalog::plan::BLOCK_NAME;
+use common_catalog::plan::ROW_ID;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_expression::types::DataType;
 
+use crate::binder::merge_into::constant_bool;
+use crate::binder::merge_into::target_column;
+use crate::binder::merge_into::target_table_reference;
 use crate::binder::Binder;
 use crate::binder::ScalarBinder;
+use crate::normalize_identifier;
 use crate::optimizer::SExpr;
-use crate::optimizer::SubqueryRewriter;
 use crate::plans::DeletePlan;
+use crate::plans::EvalScalar;
 use crate::plans::Filter;
 use crate::plans::Plan;
+use crate::plans::ScalarItem;
 use crate::BindContext;
-use crate::ScalarExpr;
 
 impl<'a> Binder {
     #[async_backtrace::framed]
@@ -33,6 +44,7 @@ impl<'a> Binder {
         &mut self,
         bind_context: &mut BindContext,
         table_reference: &'a TableReference,
+        using: &'a Option<TableReference>,
         filter: &'a Option<Expr>,
     ) -> Result<Plan> {
         let (catalog_name, database_name, table_name) = if let TableReference::Table {
@@ -44,49 +56,151 @@ impl<'a> Binder {
         {
             self.normalize_object_identifier_triple(catalog, database, table)
         } else {
-            // we do not support USING clause yet
             return Err(ErrorCode::Internal(
                 "should not happen, parser should have report error already",
             ));
         };
 
-        let (table_expr, mut context) = self
-            .bind_table_reference(bind_context, table_reference)
-            .await?;
-
-        let mut scalar_binder = ScalarBinder::new(
-            &mut context,
-            self.ctx.clone(),
-            &self.name_resolution_ctx,
-            self.metadata.clone(),
-            &[],
-        );
+        if using.is_none() {
+            let (table_expr, mut context) = self
+                .bind_table_reference(bind_context, table_reference)
+                .await?;
 
-        let (selection, input_expr) = if let Some(expr) = filter {
-            let (scalar, _) = scalar_binder.bind(expr).await?;
-            if let ScalarExpr::SubqueryExpr(_) = scalar {
-                let filter = Filter {
-                    predicates: vec![scalar],
-                    is_having: false,
-                };
-                let filter_expr = SExpr::create_unary(filter.into(), table_expr);
-                let mut rewriter = SubqueryRewriter::new(self.metadata.clone());
-                let filter_expr = rewriter.rewrite(&filter_expr)?;
-                (None, Some(filter_expr))
-            } else {
-                (Some(scalar), None)
+            let mut scalar_binder = ScalarBinder::new(
+                &mut context,
+                self.ctx.clone(),
+                &self.name_resolution_ctx,
+                self.metadata.clone(),
+                &[],
+            );
+            let selection = match filter {
+                Some(expr) => Some(scalar_binder.bind(expr).await?.0),
+                None => None,
+            };
+            let contain_subquery = selection.as_ref().map_or(false, |scalar| {
+                SExpr::create_unary(
+                    Filter {
+                        predicates: vec![scalar.clone()],
+                        is_having: false,
+                    }
+                    .into(),
+                    table_expr,
+                )
+                .contain_subquery()
+            });
+            if !contain_subquery {
+                return Ok(Plan::Delete(Box::new(DeletePlan {
+                    catalog_name,
+                    database_name,
+                    table_name,
+                    selection,
+                    input: None,
+                })));
             }
-        } else {
-            (None, None)
-        };
+        }
 
+        let input = self
+            .bind_delete_rows(bind_context, table_reference, using, filter)
+            .await?;
         let plan = DeletePlan {
             catalog_name,
             database_name,
             table_name,
-            selection,
-            input_expr,
+            selection: None,
+            input: Some(Box::new(input)),
         };
         Ok(Plan::Delete(Box::new(plan)))
     }
+
+    /// Binds the query producing the rows to be deleted, which are located by the internal
+    /// columns of the target table. The target table is semi joined with the table of USING
+    /// clause, and the subqueries of the condition are decorrelated into semi joins as well.
+    #[async_backtrace::framed]
+    async fn bind_delete_rows(
+        &mut self,
+        bind_context: &mut BindContext,
+        table_reference: &'a TableReference,
+        using: &'a Option<TableReference>,
+        filter: &'a Option<Expr>,
+    ) -> Result<Plan> {
+        let (catalog, database, table, alias) = match table_reference {
+            TableReference::Table {
+                catalog,
+                database,
+                table,
+                alias,
+                ..
+            } => (catalog, database, table, alias),
+            _ => unreachable!(),
+        };
+        let target_name = normalize_identifier(
+            alias.as_ref().map_or(table, |alias| &alias.name),
+            &self.name_resolution_ctx,
+        )
+        .name;
+        let target_reference = target_table_reference(catalog, database, table, alias);
+
+        let (s_expr, context) = match using {
+            Some(using) => {
+                let condition = filter.clone().unwrap_or(Expr::Literal {
+                    span: None,
+                    lit: Literal::Boolean(true),
+                });
+                let join_reference = TableReference::Join {
+                    span: None,
+                    join: Join {
+                        op: JoinOperator::LeftSemi,
+                        condition: JoinCondition::On(Box::new(condition)),
+                        match_condition: None,
+                        left: Box::new(target_reference),
+                        right: Box::new(using.clone()),
+                    },
+                };
+                self.bind_table_reference(bind_context, &join_reference)
+                    .await?
+            }
+            None => {
+                let (table_expr, mut context) = self
+                    .bind_table_reference(bind_context, &target_reference)
+                    .await?;
+                let mut s_expr = table_expr;
+                if let Some(expr) = filter {
+                    let mut scalar_binder = ScalarBinder::new(
+                        &mut context,
+                        self.ctx.clone(),
+                        &self.name_resolution_ctx,
+                        self.metadata.clone(),
+                        &[],
+                    );
+                    let (scalar, _) = scalar_binder.bind(expr).await?;
+                    s_expr = SExpr::create_unary(
+                        Filter {
+                            predicates: vec![scalar],
+                            is_having: false,
+                        }
+                        .into(),
+                        s_expr,
+                    );
+                }
+                (s_expr, context)
+            }
+        };
+
+        let block_name = target_column(&context, &target_name, BLOCK_NAME)?;
+        let row_id = target_column(&context, &target_name, ROW_ID)?;
+        let deleted =
+            self.create_column_binding(None, None, "_deleted".to_string(), DataType::Boolean);
+        let s_expr = SExpr::create_unary(
+            EvalScalar {
+                items: vec![ScalarItem {
+                    scalar: constant_bool(true),
+                    index: deleted.index,
+                }],
+            }
+            .into(),
+            s_expr,
+        );
+
+        self.bind_merge_rows_query(s_expr, vec![block_name, row_id, deleted])
+    }
 }
@@ -38,6 +38,8 @@ use crate::planner::binder::wrap_cast;
 use crate::planner::binder::Binder;
 use crate::planner::semantic::NameResolutionContext;
 use crate::plans::BoundColumnRef;
+use crate::plans::ComparisonExpr;
+use crate::plans::ComparisonOp;
 use crate::plans::Filter;
 use crate::plans::Join;
 use crate::plans::JoinType;
@@ -81,6 +83,11 @@ impl Binder {
                     "cross join should not contain join conditions".to_string(),
                 ));
             }
+            JoinOperator::AsofJoin if join.match_condition.is_none() => {
+                return Err(ErrorCode::SemanticError(
+                    "asof join should contain a match condition".to_string(),
+                ));
+            }
             _ => (),
         };
 
@@ -107,6 +114,17 @@ impl Binder {
                 &join.op,
             )
             .await?;
+        if let Some(match_condition) = &join.match_condition {
+            if !non_equi_conditions.is_empty() || !other_conditions.is_empty() {
+                return Err(ErrorCode::SemanticError(
+                    "asof join only supports equi conditions besides the match condition"
+                        .to_string(),
+                ));
+            }
+            join_condition_resolver
+                .resolve_match_condition(match_condition, &mut non_equi_conditions)
+                .await?;
+        }
 
         let join_conditions = JoinConditions {
             left_conditions: left_join_conditions,
@@ -130,6 +148,9 @@ impl Binder {
             JoinOperator::CrossJoin => {
                 self.bind_join_with_type(JoinType::Cross, join_conditions, left_child, right_child)
             }
+            JoinOperator::AsofJoin => {
+                self.bind_join_with_type(JoinType::Asof, join_conditions, left_child, right_child)
+            }
             JoinOperator::LeftSemi => {
                 bind_context = left_context;
                 self.bind_join_with_type(
@@ -282,7 +303,7 @@ fn wrap_nullable_for_column(
     bind_context: &mut BindContext,
 ) {
     match join_type {
-        JoinOperator::LeftOuter => {
+        JoinOperator::LeftOuter | JoinOperator::AsofJoin => {
             for column in left_context.all_column_bindings() {
                 bind_context.add_column_binding(column.clone());
             }
@@ -610,6 +631,97 @@ impl<'a> JoinConditionResolver<'a> {
         Ok(())
     }
 
+    /// Resolve the match condition of asof join into `left op right`, where `left` only uses
+    /// columns of the left table and `right` only uses columns of the right table.
+    /// It should be called after `resolve`, which adds the columns into the join context.
+    #[async_backtrace::framed]
+    async fn resolve_match_condition(
+        &self,
+        match_condition: &Expr,
+        non_equi_conditions: &mut Vec<ScalarExpr>,
+    ) -> Result<()> {
+        let mut join_context = (*self.join_context).clone();
+        let mut scalar_binder = ScalarBinder::new(
+            &mut join_context,
+            self.ctx.clone(),
+            self.name_resolution_ctx,
+            self.metadata.clone(),
+            &[],
+        );
+        let (predicate, _) = scalar_binder.bind(match_condition).await?;
+        let (op, left, right) = match predicate {
+            ScalarExpr::ComparisonExpr(ComparisonExpr { op, left, right })
+                if !matches!(op, ComparisonOp::Equal | ComparisonOp::NotEqual) =>
+            {
+                (op, left, right)
+            }
+            _ => {
+                return Err(ErrorCode::SemanticError(
+                    "match condition of asof join should be a comparison with >, >=, < or <="
+                        .to_string(),
+                )
+                .set_span(match_condition.span()));
+            }
+        };
+
+        let (left_columns, right_columns) = self.left_right_columns()?;
+        let left_used_columns = left.used_columns();
+        let right_used_columns = right.used_columns();
+        let (op, left, right) = if !left_used_columns.is_empty()
+            && !right_used_columns.is_empty()
+            && left_used_columns.is_subset(&left_columns)
+            && right_used_columns.is_subset(&right_columns)
+        {
+            (op, left, right)
+        } else if !left_used_columns.is_empty()
+            && !right_used_columns.is_empty()
+            && left_used_columns.is_subset(&right_columns)
+            && right_used_columns.is_subset(&left_columns)
+        {
+            let op = match op {
+                ComparisonOp::GT => ComparisonOp::LT,
+                ComparisonOp::LT => ComparisonOp::GT,
+                ComparisonOp::GTE => ComparisonOp::LTE,
+                _ => ComparisonOp::GTE,
+            };
+            (op, right, left)
+        } else {
+            return Err(ErrorCode::SemanticError(
+                "match condition of asof join should compare a column of the left table with a column of the right table"
+                    .to_string(),
+            )
+            .set_span(match_condition.span()));
+        };
+
+        let left_type = left.data_type()?;
+        let right_type = right.data_type()?;
+        let (left, right) = if left_type.ne(&right_type) {
+            let least_super_type = common_super_type(
+                left_type.clone(),
+                right_type.clone(),
+                &BUILTIN_FUNCTIONS.default_cast_rules,
+            )
+            .ok_or_else(|| {
+                ErrorCode::SemanticError(format!(
+                    "Left type {left_type} and right type {right_type} of match condition cannot be matched"
+                ))
+                .set_span(match_condition.span())
+            })?;
+            (
+                wrap_cast(&left, &least_super_type),
+                wrap_cast(&right, &least_super_type),
+            )
+        } else {
+            (*left, *right)
+        };
+        non_equi_conditions.push(ScalarExpr::ComparisonExpr(ComparisonExpr {
+            op,
+            left: Box::new(left),
+            right: Box::new(right),
+        }));
+        Ok(())
+    }
+
     fn add_equi_conditions(
         &self,
         mut left: ScalarExpr,
@@ -0,0 +1,469 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use common_ast::ast::Expr;
+use common_ast::ast::Identifier;
+use common_ast::ast::Indirection;
+use common_ast::ast::Join;
+use common_ast::ast::JoinCondition;
+use common_ast::ast::JoinOperator;
+use common_ast::ast::MatchOperation;
+use common_ast::ast::MergeClause;
+use common_ast::ast::MergeIntoStmt;
+use common_ast::ast::Query;
+use common_ast::ast::SelectStmt;
+use common_ast::ast::SelectTarget;
+use common_ast::ast::SetExpr;
+use common_ast::ast::TableAlias;
+use common_ast::ast::TableReference;
+use common_catalog::plan::BLOCK_NAME;
+use common_catalog::plan::ROW_ID;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::types::DataType;
+use common_expression::FieldIndex;
+use common_expression::Scalar;
+
+use crate::binder::scalar_common::wrap_cast;
+use crate::binder::Binder;
+use crate::binder::ScalarBinder;
+use crate::field_default_value;
+use crate::normalize_identifier;
+use crate::optimizer::optimize;
+use crate::optimizer::OptimizerConfig;
+use crate::optimizer::OptimizerContext;
+use crate::optimizer::SExpr;
+use crate::plans::BoundColumnRef;
+use crate::plans::ConstantExpr;
+use crate::plans::EvalScalar;
+use crate::plans::Filter;
+use crate::plans::FunctionCall;
+use crate::plans::MergeIntoPlan;
+use crate::plans::Plan;
+use crate::plans::ScalarExpr;
+use crate::plans::ScalarItem;
+use crate::BindContext;
+use crate::ColumnBinding;
+use crate::ColumnSet;
+
+/// A `WHEN [NOT] MATCHED` clause of which the expressions are bound.
+struct BoundMergeClause {
+    condition: ScalarExpr,
+    /// The new values of the target columns, `None` for deletion.
+    values: Option<HashMap<FieldIndex, ScalarExpr>>,
+}
+
+impl Binder {
+    #[async_backtrace::framed]
+    pub(in crate::planner::binder) async fn bind_merge_into(
+        &mut self,
+        bind_context: &mut BindContext,
+        stmt: &MergeIntoStmt,
+    ) -> Result<Plan> {
+        let MergeIntoStmt {
+            catalog,
+            database,
+            table,
+            alias,
+            source,
+            join_expr,
+            merge_clauses,
+        } = stmt;
+
+        let (catalog_name, database_name, table_name) =
+            self.normalize_object_identifier_triple(catalog, database, table);
+        let schema = self
+            .ctx
+            .get_table(&catalog_name, &database_name, &table_name)
+            .await?
+            .schema();
+
+        // The internal columns of the target table locate the matched rows,
+        // they are NULL for the source rows without matched target rows.
+        let target_name = normalize_identifier(
+            alias.as_ref().map_or(table, |alias| &alias.name),
+            &self.name_resolution_ctx,
+        )
+        .name;
+        let target_reference = target_table_reference(catalog, database, table, alias);
+        let join_reference = TableReference::Join {
+            span: None,
+            join: Join {
+                op: JoinOperator::LeftOuter,
+                condition: JoinCondition::On(Box::new(join_expr.clone())),
+                match_condition: None,
+                left: Box::new(source.clone()),
+                right: Box::new(target_reference),
+            },
+        };
+        let (join_expr, mut context) = self
+            .bind_table_reference(bind_context, &join_reference)
+            .await?;
+
+        let block_name = target_column(&context, &target_name, BLOCK_NAME)?;
+        let row_id = target_column(&context, &target_name, ROW_ID)?;
+        let mut target_columns = Vec::with_capacity(schema.num_fields());
+        for field in schema.fields() {
+            target_columns.push(target_column(&context, &target_name, field.name())?);
+        }
+        let target_column_set: ColumnSet = target_columns.iter().map(|c| c.index).collect();
+
+        let mut matched_clauses = vec![];
+        let mut unmatched_clauses = vec![];
+        for clause in merge_clauses {
+            let mut scalar_binder = ScalarBinder::new(
+                &mut context,
+                self.ctx.clone(),
+                &self.name_resolution_ctx,
+                self.metadata.clone(),
+                &[],
+            );
+            let condition = match clause {
+                MergeClause::Matched(clause) => &clause.selection,
+                MergeClause::Unmatched(clause) => &clause.selection,
+            };
+            let condition = match condition {
+                Some(expr) => scalar_binder.bind(expr).await?.0,
+                None => ConstantExpr {
+                    span: None,
+                    value: Scalar::Boolean(true),
+                }
+                .into(),
+            };
+            match clause {
+                MergeClause::Matched(clause) => {
+                    let values = match &clause.operation {
+                        MatchOperation::Update { update_list } => {
+                            let mut values = HashMap::with_capacity(update_list.len());
+                            for update_expr in update_list {
+                                let column_name = normalize_identifier(
+                                    &update_expr.name,
+                                    &self.name_resolution_ctx,
+                                )
+                                .name;
+                                let index = schema.index_of(&column_name)?;
+                                if values.contains_key(&index) {
+                                    return Err(ErrorCode::BadArguments(format!(
+                                        "Multiple assignments in the single statement to column `{}`",
+                                        column_name
+                                    )));
+                                }
+                                let (scalar, _) = scalar_binder.bind(&update_expr.expr).await?;
+                                values.insert(index, scalar);
+                            }
+                            Some(values)
+                        }
+                        MatchOperation::Delete => None,
+                    };
+                    matched_clauses.push(BoundMergeClause { condition, values });
+                }
+                MergeClause::Unmatched(clause) => {
+                    let columns = if clause.columns.is_empty() {
+                        (0..schema.num_fields()).collect::<Vec<_>>()
+                    } else {
+                        clause
+                            .columns
+                            .iter()
+                            .map(|column| {
+                                let column_name =
+                                    normalize_identifier(column, &self.name_resolution_ctx).name;
+                                schema.index_of(&column_name)
+                            })
+                            .collect::<Result<Vec<_>>>()?
+                    };
+                    if columns.len() != clause.values.len() {
+                        return Err(ErrorCode::SemanticError(format!(
+                            "WHEN NOT MATCHED clause has {} columns but {} values",
+                            columns.len(),
+                            clause.values.len()
+                        )));
+                    }
+                    let mut values = HashMap::with_capacity(columns.len());
+                    for (index, expr) in columns.into_iter().zip(clause.values.iter()) {
+                        let (scalar, _) = scalar_binder.bind(expr).await?;
+                        values.insert(index, scalar);
+                    }
+                    let used_columns = values.values().chain(Some(&condition)).fold(
+                        ColumnSet::new(),
+                        |mut acc, scalar| {
+                            acc.extend(scalar.used_columns());
+                            acc
+                        },
+                    );
+                    if !used_columns.is_disjoint(&target_column_set) {
+                        return Err(ErrorCode::SemanticError(
+                            "WHEN NOT MATCHED clause cannot refer to the columns of the target table",
+                        ));
+                    }
+                    unmatched_clauses.push(BoundMergeClause {
+                        condition,
+                        values: Some(values),
+                    });
+                }
+            }
+        }
+
+        let column_ref = |column: &ColumnBinding| -> ScalarExpr {
+            BoundColumnRef {
+                span: None,
+                column: column.clone(),
+            }
+            .into()
+        };
+        let matched: ScalarExpr = FunctionCall {
+            span: None,
+            func_name: "is_not_null".to_string(),
+            params: vec![],
+            arguments: vec![column_ref(&row_id)],
+        }
+        .into();
+
+        // Keep the source rows of which a clause is applied.
+        let predicate = if_then_else(
+            matched.clone(),
+            any_of(&matched_clauses),
+            any_of(&unmatched_clauses),
+        );
+        let mut s_expr = SExpr::create_unary(
+            Filter {
+                predicates: vec![predicate],
+                is_having: false,
+            }
+            .into(),
+            join_expr,
+        );
+
+        // The first clause of which the condition is true is applied.
+        let deleted = if_then_else(
+            matched.clone(),
+            first_of(
+                &matched_clauses,
+                |clause| constant_bool(clause.values.is_none()),
+                constant_bool(false),
+            ),
+            constant_bool(false),
+        );
+        let mut items = Vec::with_capacity(schema.num_fields() + 1);
+        let mut output_columns = vec![block_name, row_id];
+        let deleted_column =
+            self.create_column_binding(None, None, "_deleted".to_string(), DataType::Boolean);
+        items.push(ScalarItem {
+            scalar: wrap_cast(&deleted, &DataType::Boolean),
+            index: deleted_column.index,
+        });
+        output_columns.push(deleted_column);
+        for (index, field) in schema.fields().iter().enumerate() {
+            let data_type = DataType::from(field.data_type());
+            let target_value = wrap_cast(&column_ref(&target_columns[index]), &data_type);
+            let updated = first_of(
+                &matched_clauses,
+                |clause| match clause.values.as_ref().and_then(|v| v.get(&index)) {
+                    Some(value) => wrap_cast(value, &data_type),
+                    None => target_value.clone(),
+                },
+                target_value.clone(),
+            );
+            let default_value: ScalarExpr = ConstantExpr {
+                span: None,
+                value: field_default_value(self.ctx.clone(), field)?,
+            }
+            .into();
+            let inserted = first_of(
+                &unmatched_clauses,
+                |clause| match clause.values.as_ref().and_then(|v| v.get(&index)) {
+                    Some(value) => wrap_cast(value, &data_type),
+                    None => wrap_cast(&default_value, &data_type),
+                },
+                wrap_cast(&default_value, &data_type),
+            );
+            let column =
+                self.create_column_binding(None, None, field.name().clone(), data_type.clone());
+            items.push(ScalarItem {
+                scalar: wrap_cast(
+                    &if_then_else(matched.clone(), updated, inserted),
+                    &data_type,
+                ),
+                index: column.index,
+            });
+            output_columns.push(column);
+        }
+        s_expr = SExpr::create_unary(EvalScalar { items }.into(), s_expr);
+
+        let input = self.bind_merge_rows_query(s_expr, output_columns)?;
+
+        Ok(Plan::MergeInto(Box::new(MergeIntoPlan {
+            catalog: catalog_name,
+            database: database_name,
+            table: table_name,
+            input: Box::new(input),
+        })))
+    }
+
+    /// Builds the query producing the rows to be merged into the target table,
+    /// see `Table::merge_into` for the layout of `output_columns`.
+    pub(in crate::planner::binder) fn bind_merge_rows_query(
+        &self,
+        s_expr: SExpr,
+        output_columns: Vec<ColumnBinding>,
+    ) -> Result<Plan> {
+        let mut output_context = BindContext::new();
+        output_context.columns = output_columns;
+        let query_plan = Plan::Query {
+            s_expr: Box::new(s_expr),
+            metadata: self.metadata.clone(),
+            bind_context: Box::new(output_context),
+            rewrite_kind: None,
+            formatted_ast: None,
+            ignore_result: false,
+        };
+        let opt_ctx = Arc::new(OptimizerContext::new(OptimizerConfig {
+            enable_distributed_optimization: !self.ctx.get_cluster().is_empty(),
+        }));
+        optimize(self.ctx.clone(), opt_ctx, query_plan)
+    }
+}
+
+/// `(SELECT *, _row_id, _block_name FROM <table>) AS <alias>`, the alias defaults to the table name.
+pub(in crate::planner::binder) fn target_table_reference(
+    catalog: &Option<Identifier>,
+    database: &Option<Identifier>,
+    table: &Identifier,
+    alias: &Option<TableAlias>,
+) -> TableReference {
+    let internal_column = |name: &str| SelectTarget::AliasedExpr {
+        expr: Box::new(Expr::ColumnRef {
+            span: None,
+            database: None,
+            table: None,
+            column: Identifier {
+                name: name.to_string(),
+                quote: None,
+                span: None,
+            },
+        }),
+        alias: None,
+    };
+    let select = SelectStmt {
+        span: None,
+        distinct: false,
+        select_list: vec![
+            SelectTarget::QualifiedName {
+                qualified: vec![Indirection::Star(None)],
+                exclude: None,
+            },
+            internal_column(ROW_ID),
+            internal_column(BLOCK_NAME),
+        ],
+        from: vec![TableReference::Table {
+            span: None,
+            catalog: catalog.clone(),
+            database: database.clone(),
+            table: table.clone(),
+            alias: None,
+            travel_point: None,
+            pivot: None,
+            unpivot: None,
+        }],
+        selection: None,
+        group_by: None,
+        having: None,
+        qualify: None,
+    };
+    let query = Query {
+        span: None,
+        with: None,
+        body: SetExpr::Select(Box::new(select)),
+        order_by: vec![],
+        limit: vec![],
+        offset: None,
+        ignore_result: false,
+    };
+    TableReference::Subquery {
+        span: None,
+        subquery: Box::new(query),
+        alias: Some(TableAlias {
+            name: alias.as_ref().map_or(table, |alias| &alias.name).clone(),
+            columns: vec![],
+        }),
+    }
+}
+
+/// Finds the column of the target table built by [target_table_reference].
+pub(in crate::planner::binder) fn target_column(
+    context: &BindContext,
+    target_name: &str,
+    name: &str,
+) -> Result<ColumnBinding> {
+    context
+        .columns
+        .iter()
+        .find(|column| BindContext::match_column_binding(None, Some(target_name), name, column))
+        .cloned()
+        .ok_or_else(|| {
+            ErrorCode::Internal(format!(
+                "column {} of the target table {} is not found",
+                name, target_name
+            ))
+        })
+}
+
+pub(in crate::planner::binder) fn constant_bool(value: bool) -> ScalarExpr {
+    ConstantExpr {
+        span: None,
+        value: Scalar::Boolean(value),
+    }
+    .into()
+}
+
+fn if_then_else(condition: ScalarExpr, then: ScalarExpr, otherwise: ScalarExpr) -> ScalarExpr {
+    FunctionCall {
+        span: None,
+        func_name: "if".to_string(),
+        params: vec![],
+        arguments: vec![condition, then, otherwise],
+    }
+    .into()
+}
+
+/// `if(<condition1>, <value1>, <condition2>, <value2>, ..., <otherwise>)`
+fn first_of(
+    clauses: &[BoundMergeClause],
+    value: impl Fn(&BoundMergeClause) -> ScalarExpr,
+    otherwise: ScalarExpr,
+) -> ScalarExpr {
+    if clauses.is_empty() {
+        return otherwise;
+    }
+    let mut arguments = Vec::with_capacity(clauses.len() * 2 + 1);
+    for clause in clauses {
+        arguments.push(clause.condition.clone());
+        arguments.push(value(clause));
+    }
+    arguments.push(otherwise);
+    FunctionCall {
+        span: None,
+        func_name: "if".to_string(),
+        params: vec![],
+        arguments,
+    }
+    .into()
+}
+
+/// Whether the condition of any clause is true.
+fn any_of(clauses: &[BoundMergeClause]) -> ScalarExpr {
+    first_of(clauses, |_| constant_bool(true), constant_bool(false))
+}
@@ -26,10 +26,10 @@ use crate::plans::CastExpr;
 use crate::plans::ComparisonExpr;
 use crate::plans::ComparisonOp;
 use crate::plans::FunctionCall;
+use crate::plans::LambdaFunc;
 use crate::plans::NotExpr;
 use crate::plans::OrExpr;
 use crate::plans::ScalarExpr;
-use crate::plans::WindowFuncType;
 
 // Visitor that find Expressions that match a particular predicate
 struct Finder<'a, F>
@@ -171,6 +171,7 @@ pub fn contain_subquery(scalar: &ScalarExpr) -> bool {
         ScalarExpr::FunctionCall(FunctionCall { arguments, .. }) => {
             arguments.iter().any(contain_subquery)
         }
+        ScalarExpr::LambdaFunction(LambdaFunc { args, .. }) => args.iter().any(contain_subquery),
         ScalarExpr::CastExpr(CastExpr { argument, .. }) => contain_subquery(argument),
         _ => false,
     }
@@ -197,12 +198,8 @@ pub fn prune_by_children(scalar: &ScalarExpr, columns: &HashSet<ScalarExpr>) ->
             prune_by_children(&scalar.left, columns) && prune_by_children(&scalar.right, columns)
         }
         ScalarExpr::WindowFunction(scalar) => {
-            let flag = match &scalar.func {
-                WindowFuncType::Aggregate(agg) => {
-                    agg.args.iter().all(|arg| prune_by_children(arg, columns))
-                }
-                _ => false,
-            };
+            let args = scalar.func.arguments();
+            let flag = !args.is_empty() && args.iter().all(|arg| prune_by_children(arg, columns));
             flag || scalar
                 .partition_by
                 .iter()
@@ -220,6 +217,10 @@ pub fn prune_by_children(scalar: &ScalarExpr, columns: &HashSet<ScalarExpr>) ->
             .arguments
             .iter()
             .all(|arg| prune_by_children(arg, columns)),
+        ScalarExpr::LambdaFunction(scalar) => scalar
+            .args
+            .iter()
+            .all(|arg| prune_by_children(arg, columns)),
         ScalarExpr::CastExpr(expr) => prune_by_children(expr.argument.as_ref(), columns),
         ScalarExpr::SubqueryExpr(_) => false,
     }
@@ -19,11 +19,11 @@ use crate::plans::AndExpr;
 use crate::plans::CastExpr;
 use crate::plans::ComparisonExpr;
 use crate::plans::FunctionCall;
+use crate::plans::LambdaFunc;
 use crate::plans::NotExpr;
 use crate::plans::OrExpr;
 use crate::plans::ScalarExpr;
 use crate::plans::WindowFunc;
-use crate::plans::WindowFuncType;
 
 /// Controls how the visitor recursion should proceed.
 pub enum Recursion<V: ScalarVisitor> {
@@ -68,10 +68,8 @@ pub trait ScalarVisitor: Sized {
                                     order_by,
                                     ..
                                 }) => {
-                                    if let WindowFuncType::Aggregate(agg) = func {
-                                        for arg in &agg.args {
-                                            stack.push(RecursionProcessing::Call(arg));
-                                        }
+                                    for arg in func.arguments() {
+                                        stack.push(RecursionProcessing::Call(arg));
                                     }
                                     for arg in partition_by.iter() {
                                         stack.push(RecursionProcessing::Call(arg));
@@ -102,6 +100,11 @@ pub trait ScalarVisitor: Sized {
                                         stack.push(RecursionProcessing::Call(arg));
                                     }
                                 }
+                                ScalarExpr::LambdaFunction(LambdaFunc { args, .. }) => {
+                                    for arg in args.iter() {
+                                        stack.push(RecursionProcessing::Call(arg));
+                                    }
+                                }
                                 ScalarExpr::BoundColumnRef(_)
                                 | ScalarExpr::BoundInternalColumnRef(_)
                                 | ScalarExpr::ConstantExpr(_) => {}
@@ -31,16 +31,19 @@ use common_ast::ast::SelectTarget;
 use common_ast::ast::SetExpr;
 use common_ast::ast::SetOperator;
 use common_ast::ast::TableReference;
+use common_ast::Visitor;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_exception::Span;
 use common_expression::type_check::common_super_type;
 use common_expression::types::DataType;
+use common_expression::types::NumberDataType;
 use common_functions::BUILTIN_FUNCTIONS;
 
 use crate::binder::join::JoinConditions;
 use crate::binder::project_set::SrfCollector;
 use crate::binder::scalar_common::split_conjunctions;
+use crate::binder::table::MaxColumnPosition;
 use crate::binder::CteInfo;
 use crate::binder::ExprContext;
 use crate::binder::Visibility;
@@ -56,6 +59,11 @@ use crate::plans::JoinType;
 use crate::plans::ScalarExpr;
 use crate::plans::ScalarItem;
 use crate::plans::UnionAll;
+use crate::plans::Window;
+use crate::plans::WindowFuncFrame;
+use crate::plans::WindowFuncFrameBound;
+use crate::plans::WindowFuncFrameUnits;
+use crate::plans::WindowFuncType;
 use crate::ColumnBinding;
 use crate::IndexType;
 
@@ -83,6 +91,12 @@ impl Binder {
         let (mut s_expr, mut from_context) = if stmt.from.is_empty() {
             self.bind_one_table(bind_context, stmt).await?
         } else {
+            // `$1, $2, ...` in the select refer to the columns of text files in stages
+            let mut max_column_position = MaxColumnPosition::default();
+            max_column_position.visit_select_stmt(stmt);
+            let outer_max_column_position =
+                std::mem::replace(&mut self.max_column_position, max_column_position.max_pos);
+
             let cross_joins = stmt
                 .from
                 .iter()
@@ -92,13 +106,15 @@ impl Binder {
                     join: Join {
                         op: JoinOperator::CrossJoin,
                         condition: JoinCondition::None,
+                        match_condition: None,
                         left: Box::new(left),
                         right: Box::new(right),
                     },
                 })
                 .unwrap();
-            self.bind_table_reference(bind_context, &cross_joins)
-                .await?
+            let res = self.bind_table_reference(bind_context, &cross_joins).await;
+            self.max_column_position = outer_max_column_position;
+            res?
         };
 
         let mut rewriter = SelectRewriter::new(
@@ -157,6 +173,15 @@ impl Binder {
             None
         };
 
+        let qualify = if let Some(qualify) = &stmt.qualify {
+            Some(
+                self.analyze_window_qualify(&mut from_context, &select_list, qualify)
+                    .await?,
+            )
+        } else {
+            None
+        };
+
         let order_items = self
             .analyze_order_items(
                 &from_context,
@@ -185,6 +210,14 @@ impl Binder {
             s_expr = self.bind_window_function(window_info, s_expr).await?;
         }
 
+        // bind qualify
+        // qualify filters the results of window functions, so it runs right after them.
+        if let Some((qualify, span)) = qualify {
+            s_expr = self
+                .bind_qualify(&mut from_context, qualify, span, s_expr)
+                .await?;
+        }
+
         if stmt.distinct {
             s_expr = self.bind_distinct(
                 stmt.span,
@@ -262,6 +295,7 @@ impl Binder {
                 let cte_info = CteInfo {
                     columns_alias: cte.alias.columns.iter().map(|c| c.name.clone()).collect(),
                     query: cte.query.clone(),
+                    recursive: with.recursive,
                 };
                 bind_context.ctes_map.insert(table_name, cte_info);
             }
@@ -382,7 +416,7 @@ impl Binder {
             }
         }
         match (op, all) {
-            (SetOperator::Intersect, false) => {
+            (SetOperator::Intersect, all) => {
                 // Transfer Intersect to Semi join
                 self.bind_intersect(
                     left.span(),
@@ -391,9 +425,10 @@ impl Binder {
                     right_bind_context,
                     left_expr,
                     right_expr,
+                    *all,
                 )
             }
-            (SetOperator::Except, false) => {
+            (SetOperator::Except, all) => {
                 // Transfer Except to Anti join
                 self.bind_except(
                     left.span(),
@@ -402,6 +437,7 @@ impl Binder {
                     right_bind_context,
                     left_expr,
                     right_expr,
+                    *all,
                 )
             }
             (SetOperator::Union, true) => self.bind_union(
@@ -424,9 +460,6 @@ impl Binder {
                 right_expr,
                 true,
             ),
-            _ => Err(ErrorCode::Unimplemented(
-                "Unsupported query type, currently, databend only support intersect distinct and except distinct",
-            )),
         }
     }
 
@@ -467,6 +500,7 @@ impl Binder {
         Ok((new_expr, new_bind_context))
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn bind_intersect(
         &mut self,
         left_span: Span,
@@ -475,6 +509,7 @@ impl Binder {
         right_context: BindContext,
         left_expr: SExpr,
         right_expr: SExpr,
+        all: bool,
     ) -> Result<(SExpr, BindContext)> {
         self.bind_intersect_or_except(
             left_span,
@@ -484,9 +519,11 @@ impl Binder {
             left_expr,
             right_expr,
             JoinType::LeftSemi,
+            all,
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn bind_except(
         &mut self,
         left_span: Span,
@@ -495,6 +532,7 @@ impl Binder {
         right_context: BindContext,
         left_expr: SExpr,
         right_expr: SExpr,
+        all: bool,
     ) -> Result<(SExpr, BindContext)> {
         self.bind_intersect_or_except(
             left_span,
@@ -504,6 +542,7 @@ impl Binder {
             left_expr,
             right_expr,
             JoinType::LeftAnti,
+            all,
         )
     }
 
@@ -517,22 +556,36 @@ impl Binder {
         left_expr: SExpr,
         right_expr: SExpr,
         join_type: JoinType,
+        all: bool,
     ) -> Result<(SExpr, BindContext)> {
-        let left_expr = self.bind_distinct(
-            left_span,
-            &left_context,
-            left_context.all_column_bindings(),
-            &mut HashMap::new(),
-            left_expr,
-        )?;
-        let mut left_conditions = Vec::with_capacity(left_context.columns.len());
-        let mut right_conditions = Vec::with_capacity(right_context.columns.len());
         assert_eq!(left_context.columns.len(), right_context.columns.len());
-        for (left_column, right_column) in left_context
-            .columns
-            .iter()
-            .zip(right_context.columns.iter())
-        {
+        let mut left_columns = left_context.columns.clone();
+        let mut right_columns = right_context.columns.clone();
+        let (left_expr, right_expr) = if all {
+            // Bag semantics: number the duplicated rows of each side, then the n-th
+            // copy of a row on the left side only matches the n-th copy on the right side.
+            // So `INTERSECT ALL` keeps `min(m, n)` copies and `EXCEPT ALL` keeps `max(m - n, 0)`
+            // copies of a row that appears `m` times on the left side and `n` times on the right side.
+            let (left_expr, left_row_number) =
+                self.bind_duplicate_row_number(left_span, &left_columns, left_expr);
+            let (right_expr, right_row_number) =
+                self.bind_duplicate_row_number(right_span, &right_columns, right_expr);
+            left_columns.push(left_row_number);
+            right_columns.push(right_row_number);
+            (left_expr, right_expr)
+        } else {
+            let left_expr = self.bind_distinct(
+                left_span,
+                &left_context,
+                left_context.all_column_bindings(),
+                &mut HashMap::new(),
+                left_expr,
+            )?;
+            (left_expr, right_expr)
+        };
+        let mut left_conditions = Vec::with_capacity(left_columns.len());
+        let mut right_conditions = Vec::with_capacity(right_columns.len());
+        for (left_column, right_column) in left_columns.iter().zip(right_columns.iter()) {
             left_conditions.push(
                 BoundColumnRef {
                     span: left_span,
@@ -558,9 +611,55 @@ impl Binder {
         Ok((s_expr, left_context))
     }
 
+    /// Add `row_number() OVER (PARTITION BY <all columns>)` on top of `child`,
+    /// which numbers the copies of each distinct row.
+    fn bind_duplicate_row_number(
+        &mut self,
+        span: Span,
+        columns: &[ColumnBinding],
+        child: SExpr,
+    ) -> (SExpr, ColumnBinding) {
+        let data_type = DataType::Number(NumberDataType::UInt64);
+        let index = self
+            .metadata
+            .write()
+            .add_derived_column("row_number".to_string(), data_type.clone());
+        let partition_by = columns
+            .iter()
+            .map(|column| ScalarItem {
+                scalar: BoundColumnRef {
+                    span,
+                    column: column.clone(),
+                }
+                .into(),
+                index: column.index,
+            })
+            .collect();
+        let window = Window {
+            index,
+            function: WindowFuncType::RowNumber,
+            partition_by,
+            order_by: vec![],
+            frame: WindowFuncFrame {
+                units: WindowFuncFrameUnits::Rows,
+                start_bound: WindowFuncFrameBound::Preceding(None),
+                end_bound: WindowFuncFrameBound::CurrentRow,
+            },
+        };
+        let column = ColumnBinding {
+            database_name: None,
+            table_name: None,
+            column_name: "row_number".to_string(),
+            index,
+            data_type: Box::new(data_type),
+            visibility: Visibility::InVisible,
+        };
+        (SExpr::create_unary(window.into(), child), column)
+    }
+
     #[allow(clippy::type_complexity)]
     #[allow(clippy::too_many_arguments)]
-    fn coercion_union_type(
+    pub(super) fn coercion_union_type(
         &self,
         left_span: Span,
         right_span: Span,
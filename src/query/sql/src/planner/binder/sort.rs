@@ -36,6 +36,7 @@ use crate::plans::CastExpr;
 use crate::plans::ComparisonExpr;
 use crate::plans::EvalScalar;
 use crate::plans::FunctionCall;
+use crate::plans::LambdaFunc;
 use crate::plans::NotExpr;
 use crate::plans::OrExpr;
 use crate::plans::ScalarExpr;
@@ -454,6 +455,27 @@ impl Binder {
                         func_name: func_name.clone(),
                     }))
                 }
+                ScalarExpr::LambdaFunction(LambdaFunc {
+                    span,
+                    func_name,
+                    args,
+                    lambda_expr,
+                    lambda_display,
+                    return_type,
+                }) => {
+                    let args = args
+                        .iter()
+                        .map(|arg| self.rewrite_scalar_with_replacement(arg, replacement_fn))
+                        .collect::<Result<Vec<_>>>()?;
+                    Ok(ScalarExpr::LambdaFunction(LambdaFunc {
+                        span: *span,
+                        func_name: func_name.clone(),
+                        args,
+                        lambda_expr: lambda_expr.clone(),
+                        lambda_display: lambda_display.clone(),
+                        return_type: return_type.clone(),
+                    }))
+                }
                 ScalarExpr::CastExpr(CastExpr {
                     span,
                     is_try,
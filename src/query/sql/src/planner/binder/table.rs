@@ -20,10 +20,13 @@ use std::sync::Arc;
 use async_recursion::async_recursion;
 use chrono::TimeZone;
 use chrono::Utc;
+use common_ast::ast::Identifier;
 use common_ast::ast::Indirection;
 use common_ast::ast::Join;
 use common_ast::ast::SelectStmt;
 use common_ast::ast::SelectTarget;
+use common_ast::ast::SetExpr;
+use common_ast::ast::SetOperator;
 use common_ast::ast::Statement;
 use common_ast::ast::TableAlias;
 use common_ast::ast::TableReference;
@@ -31,8 +34,10 @@ use common_ast::ast::TimeTravelPoint;
 use common_ast::parser::parse_sql;
 use common_ast::parser::tokenize_sql;
 use common_ast::Dialect;
+use common_ast::Visitor;
 use common_catalog::catalog_kind::CATALOG_DEFAULT;
 use common_catalog::plan::ParquetReadOptions;
+use common_catalog::plan::StageTableInfo;
 use common_catalog::table::ColumnStatistics;
 use common_catalog::table::NavigationPoint;
 use common_catalog::table::Table;
@@ -41,14 +46,20 @@ use common_catalog::table_function::TableFunction;
 use common_exception::ErrorCode;
 use common_exception::Result;
 use common_exception::Span;
+use common_expression::type_check::common_super_type;
 use common_expression::types::DataType;
 use common_expression::ColumnId;
 use common_expression::ConstantFolder;
 use common_expression::FunctionKind;
 use common_expression::Scalar;
+use common_expression::TableDataType;
+use common_expression::TableField;
+use common_expression::TableSchema;
 use common_functions::BUILTIN_FUNCTIONS;
 use common_meta_app::principal::StageFileFormatType;
 use common_meta_app::principal::StageInfo;
+use common_pipeline_sources::input_formats::InputContext;
+use common_storage::init_stage_operator;
 use common_storage::DataOperator;
 use common_storage::StageFileInfo;
 use common_storage::StageFilesInfo;
@@ -56,6 +67,7 @@ use common_storages_parquet::ParquetTable;
 use common_storages_result_cache::ResultCacheMetaManager;
 use common_storages_result_cache::ResultCacheReader;
 use common_storages_result_cache::ResultScan;
+use common_storages_stage::StageTable;
 use common_storages_view::view_table::QUERY;
 use common_users::UserApiProvider;
 use dashmap::DashMap;
@@ -71,6 +83,9 @@ use crate::binder::Visibility;
 use crate::optimizer::SExpr;
 use crate::planner::semantic::normalize_identifier;
 use crate::planner::semantic::TypeChecker;
+use crate::plans::RecursiveCteScan;
+use crate::plans::RecursiveUnion;
+use crate::plans::RelOperator;
 use crate::plans::Scan;
 use crate::plans::Statistics;
 use crate::BaseTableColumn;
@@ -80,6 +95,31 @@ use crate::DerivedColumn;
 use crate::IndexType;
 use crate::TableInternalColumn;
 
+/// Collects the max position of `$1, $2, ...` in a query,
+/// which refer to the columns of text files in stages.
+#[derive(Default)]
+pub struct MaxColumnPosition {
+    pub max_pos: usize,
+}
+
+impl<'a> Visitor<'a> for MaxColumnPosition {
+    fn visit_column_ref(
+        &mut self,
+        _span: Span,
+        _database: &'a Option<Identifier>,
+        _table: &'a Option<Identifier>,
+        column: &'a Identifier,
+    ) {
+        if let Some(pos) = column
+            .name
+            .strip_prefix('$')
+            .and_then(|pos| pos.parse::<usize>().ok())
+        {
+            self.max_pos = self.max_pos.max(pos);
+        }
+    }
+}
+
 impl Binder {
     #[async_backtrace::framed]
     pub(super) async fn bind_one_table(
@@ -386,6 +426,7 @@ impl Binder {
                         selection: None,
                         group_by: None,
                         having: None,
+                        qualify: None,
                     };
                     self.bind_select_stmt(&mut bind_context, &stmt, &[]).await
                 } else {
@@ -465,42 +506,92 @@ impl Binder {
         alias: &Option<TableAlias>,
         files_to_copy: Option<Vec<StageFileInfo>>,
     ) -> Result<(SExpr, BindContext)> {
-        if matches!(
-            stage_info.file_format_options.format,
-            StageFileFormatType::Parquet
-        ) {
-            let read_options = ParquetReadOptions::default();
-
-            let table =
+        let table = match stage_info.file_format_options.format.clone() {
+            StageFileFormatType::Parquet => {
+                let read_options = ParquetReadOptions::default();
                 ParquetTable::create(stage_info.clone(), files_info, read_options, files_to_copy)
-                    .await?;
-
-            let table_alias_name = if let Some(table_alias) = alias {
-                Some(normalize_identifier(&table_alias.name, &self.name_resolution_ctx).name)
-            } else {
-                None
-            };
-
-            let table_index = self.metadata.write().add_table(
-                CATALOG_DEFAULT.to_string(),
-                "system".to_string(),
-                table.clone(),
-                table_alias_name,
-                false,
-            );
-
-            let (s_expr, mut bind_context) = self
-                .bind_base_table(bind_context, "system", table_index)
-                .await?;
-            if let Some(alias) = alias {
-                bind_context.apply_table_alias(alias, &self.name_resolution_ctx)?;
+                    .await?
             }
-            Ok((s_expr, bind_context))
+            format => {
+                let schema = match format {
+                    StageFileFormatType::Orc | StageFileFormatType::ArrowIpc => {
+                        // the schema is inferred from the first file
+                        let operator = init_stage_operator(&stage_info)?;
+                        let first_file =
+                            match files_to_copy.as_ref().and_then(|files| files.first()) {
+                                Some(file) => file.clone(),
+                                None => files_info.first_file(&operator).await?,
+                            };
+                        InputContext::get_input_format(&format)?
+                            .infer_schema(
+                                &first_file.path,
+                                &operator,
+                                &stage_info.file_format_options,
+                            )
+                            .await?
+                    }
+                    StageFileFormatType::Csv | StageFileFormatType::Tsv => {
+                        // the columns of text files are referred by positions `$1, $2, ...`
+                        if self.max_column_position == 0 {
+                            return Err(ErrorCode::SemanticError(format!(
+                                "Query from {:?} file lacks column positions, refer to the columns by $1, $2 and so on",
+                                format
+                            )));
+                        }
+                        let fields = (1..=self.max_column_position)
+                            .map(|i| {
+                                TableField::new(
+                                    &format!("${i}"),
+                                    TableDataType::String.wrap_nullable(),
+                                )
+                            })
+                            .collect();
+                        Arc::new(TableSchema::new(fields))
+                    }
+                    StageFileFormatType::NdJson => {
+                        // each row is read as a whole into `$1`, use `$1:key` to get the fields
+                        Arc::new(TableSchema::new(vec![TableField::new(
+                            "$1",
+                            TableDataType::Variant.wrap_nullable(),
+                        )]))
+                    }
+                    _ => {
+                        return Err(ErrorCode::Unimplemented(
+                            "stage table function only support parquet, orc, arrow, csv, tsv and ndjson format for now",
+                        ));
+                    }
+                };
+                StageTable::try_create(StageTableInfo {
+                    schema,
+                    stage_info,
+                    files_info,
+                    files_to_copy,
+                    is_select: true,
+                })?
+            }
+        };
+
+        let table_alias_name = if let Some(table_alias) = alias {
+            Some(normalize_identifier(&table_alias.name, &self.name_resolution_ctx).name)
         } else {
-            Err(ErrorCode::Unimplemented(
-                "stage table function only support parquet format for now",
-            ))
+            None
+        };
+
+        let table_index = self.metadata.write().add_table(
+            CATALOG_DEFAULT.to_string(),
+            "system".to_string(),
+            table.clone(),
+            table_alias_name,
+            false,
+        );
+
+        let (s_expr, mut bind_context) = self
+            .bind_base_table(bind_context, "system", table_index)
+            .await?;
+        if let Some(alias) = alias {
+            bind_context.apply_table_alias(alias, &self.name_resolution_ctx)?;
         }
+        Ok((s_expr, bind_context))
     }
 
     #[async_backtrace::framed]
@@ -598,9 +689,13 @@ impl Binder {
             srfs: Default::default(),
             expr_context: ExprContext::default(),
         };
-        let (s_expr, mut new_bind_context) = self
-            .bind_query(&mut new_bind_context, &cte_info.query)
-            .await?;
+        let (s_expr, mut new_bind_context) = if cte_info.recursive {
+            self.bind_recursive_cte(span, &mut new_bind_context, table_name, cte_info)
+                .await?
+        } else {
+            self.bind_query(&mut new_bind_context, &cte_info.query)
+                .await?
+        };
         let mut cols_alias = cte_info.columns_alias.clone();
         if let Some(alias) = alias {
             for (idx, col_alias) in alias.columns.iter().enumerate() {
@@ -634,6 +729,177 @@ impl Binder {
         Ok((s_expr, new_bind_context))
     }
 
+    /// Bind a common table expression declared by `WITH RECURSIVE`.
+    ///
+    /// A recursive cte is a `UNION [ALL]` of an anchor member and a recursive member,
+    /// only the recursive member can reference the cte itself, which reads the rows
+    /// produced by the previous iteration. The column types of the cte are the common
+    /// super types of both members, like `UNION`. Since the types of the recursive member
+    /// depend on the types of the cte, it's re-bound until the types no longer change.
+    #[async_backtrace::framed]
+    async fn bind_recursive_cte(
+        &mut self,
+        span: Span,
+        bind_context: &mut BindContext,
+        table_name: &str,
+        cte_info: &CteInfo,
+    ) -> Result<(SExpr, BindContext)> {
+        match self.recursive_ctes.get(table_name) {
+            Some(Some(columns)) => {
+                let columns = columns.clone();
+                return self.bind_recursive_cte_scan(bind_context, table_name, columns);
+            }
+            Some(None) => {
+                return Err(ErrorCode::SemanticError(format!(
+                    "recursive cte '{table_name}' can only be referenced in the recursive member of UNION [ALL]"
+                ))
+                .set_span(span));
+            }
+            None => {}
+        }
+
+        let query = &cte_info.query;
+        let set_operation = match &query.body {
+            SetExpr::SetOperation(set_operation)
+                if set_operation.op == SetOperator::Union
+                    && query.with.is_none()
+                    && query.order_by.is_empty()
+                    && query.limit.is_empty()
+                    && query.offset.is_none() =>
+            {
+                set_operation
+            }
+            _ => {
+                // Not a recursive query, but the cte still can't reference itself.
+                self.recursive_ctes.insert(table_name.to_string(), None);
+                let result = self.bind_query(bind_context, query).await;
+                self.recursive_ctes.remove(table_name);
+                return result;
+            }
+        };
+
+        self.recursive_ctes.insert(table_name.to_string(), None);
+        let (left_expr, left_bind_context) = self
+            .bind_set_expr(bind_context, &set_operation.left, &[])
+            .await?;
+        let mut coercion_types: Vec<DataType> = left_bind_context
+            .columns
+            .iter()
+            .map(|column| *column.data_type.clone())
+            .collect();
+        let (right_expr, right_bind_context) = loop {
+            let columns = left_bind_context
+                .columns
+                .iter()
+                .zip(coercion_types.iter())
+                .map(|(column, data_type)| ColumnBinding {
+                    data_type: Box::new(data_type.clone()),
+                    ..column.clone()
+                })
+                .collect();
+            self.recursive_ctes
+                .insert(table_name.to_string(), Some(columns));
+            let result = self
+                .bind_set_expr(bind_context, &set_operation.right, &[])
+                .await;
+            self.recursive_ctes.remove(table_name);
+            let (right_expr, right_bind_context) = result?;
+
+            if !Self::contains_recursive_cte_scan(&right_expr, table_name) {
+                // The cte doesn't reference itself, bind it as a normal query.
+                return self.bind_query(bind_context, query).await;
+            }
+            if left_bind_context.columns.len() != right_bind_context.columns.len() {
+                return Err(ErrorCode::SemanticError(
+                    "SetOperation must have the same number of columns",
+                )
+                .set_span(span));
+            }
+
+            let mut new_types = Vec::with_capacity(coercion_types.len());
+            for (data_type, right_col) in
+                coercion_types.iter().zip(right_bind_context.columns.iter())
+            {
+                match common_super_type(
+                    data_type.clone(),
+                    *right_col.data_type.clone(),
+                    &BUILTIN_FUNCTIONS.default_cast_rules,
+                ) {
+                    Some(data_type) => new_types.push(data_type),
+                    None => {
+                        return Err(ErrorCode::SemanticError(format!(
+                            "SetOperation's types cannot be matched, left column type: {:?}, right column {:?}, type: {:?}",
+                            data_type, right_col.column_name, right_col.data_type
+                        ))
+                        .set_span(span));
+                    }
+                }
+            }
+            if new_types == coercion_types {
+                break (right_expr, right_bind_context);
+            }
+            // The working table is wider than the recursive member was bound with.
+            coercion_types = new_types;
+        };
+
+        // Both members are casted to the column types of the cte.
+        let (new_bind_context, pairs, left_expr, right_expr) = self.coercion_union_type(
+            set_operation.left.span(),
+            set_operation.right.span(),
+            left_bind_context,
+            right_bind_context,
+            left_expr,
+            right_expr,
+            coercion_types,
+        )?;
+        let recursive_union = RecursiveUnion {
+            cte_name: table_name.to_string(),
+            pairs,
+            distinct: !set_operation.all,
+        };
+        let s_expr = SExpr::create_binary(recursive_union.into(), left_expr, right_expr);
+        Ok((s_expr, new_bind_context))
+    }
+
+    /// Bind the reference to a recursive cte in its recursive member.
+    fn bind_recursive_cte_scan(
+        &mut self,
+        bind_context: &BindContext,
+        table_name: &str,
+        columns: Vec<ColumnBinding>,
+    ) -> Result<(SExpr, BindContext)> {
+        let mut new_bind_context = BindContext::with_parent(Box::new(bind_context.clone()));
+        let mut scan_columns = Vec::with_capacity(columns.len());
+        for column in columns {
+            let index = self
+                .metadata
+                .write()
+                .add_derived_column(column.column_name.clone(), *column.data_type.clone());
+            new_bind_context.add_column_binding(ColumnBinding {
+                database_name: None,
+                table_name: Some(table_name.to_string()),
+                column_name: column.column_name,
+                index,
+                data_type: column.data_type,
+                visibility: Visibility::Visible,
+            });
+            scan_columns.push(index);
+        }
+        let scan = RecursiveCteScan {
+            cte_name: table_name.to_string(),
+            columns: scan_columns,
+        };
+        Ok((SExpr::create_leaf(scan.into()), new_bind_context))
+    }
+
+    fn contains_recursive_cte_scan(s_expr: &SExpr, cte_name: &str) -> bool {
+        matches!(s_expr.plan(), RelOperator::RecursiveCteScan(scan) if scan.cte_name == cte_name)
+            || s_expr
+                .children()
+                .iter()
+                .any(|child| Self::contains_recursive_cte_scan(child, cte_name))
+    }
+
     #[async_backtrace::framed]
     async fn bind_base_table(
         &mut self,
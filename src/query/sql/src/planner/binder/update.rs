@@ -14,16 +14,37 @@
 
 use std::collections::HashMap;
 
+use common_ast::ast::Expr;
+use common_ast::ast::Join;
+use common_ast::ast::JoinCondition;
+use common_ast::ast::JoinOperator;
+use common_ast::ast::Literal;
 use common_ast::ast::TableReference;
+use common_ast::ast::UpdateExpr;
 use common_ast::ast::UpdateStmt;
+use common_catalog::plan::BLOCK_NAME;
+use common_catalog::plan::ROW_ID;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_expression::types::DataType;
+use common_expression::FieldIndex;
+use common_expression::TableSchemaRef;
 
+use crate::binder::merge_into::constant_bool;
+use crate::binder::merge_into::target_column;
+use crate::binder::merge_into::target_table_reference;
+use crate::binder::scalar_common::wrap_cast;
 use crate::binder::Binder;
 use crate::binder::ScalarBinder;
 use crate::normalize_identifier;
+use crate::optimizer::find_subquery_in_expr;
+use crate::optimizer::SExpr;
+use crate::plans::BoundColumnRef;
+use crate::plans::EvalScalar;
+use crate::plans::Filter;
 use crate::plans::Plan;
 use crate::plans::ScalarExpr;
+use crate::plans::ScalarItem;
 use crate::plans::UpdatePlan;
 use crate::BindContext;
 
@@ -37,6 +58,7 @@ impl Binder {
         let UpdateStmt {
             table,
             update_list,
+            from,
             selection,
         } = stmt;
 
@@ -57,68 +79,228 @@ impl Binder {
                 table.name.clone(),
             )
         } else {
-            // we do not support USING clause yet
             return Err(ErrorCode::Internal(
                 "should not happen, parser should have report error already",
             ));
         };
 
-        let (_, mut context) = self.bind_table_reference(bind_context, table).await?;
+        if from.is_none() {
+            let (_, mut context) = self.bind_table_reference(bind_context, table).await?;
 
-        let table = self
-            .ctx
-            .get_table(&catalog_name, &database_name, &table_name)
-            .await?;
-
-        let mut scalar_binder = ScalarBinder::new(
-            &mut context,
-            self.ctx.clone(),
-            &self.name_resolution_ctx,
-            self.metadata.clone(),
-            &[],
-        );
-        let schema = table.schema();
-        let mut update_columns = HashMap::with_capacity(update_list.len());
-        for update_expr in update_list {
-            let col_name = normalize_identifier(&update_expr.name, &self.name_resolution_ctx).name;
-            let index = schema.index_of(&col_name)?;
-            if update_columns.contains_key(&index) {
-                return Err(ErrorCode::BadArguments(format!(
-                    "Multiple assignments in the single statement to column `{}`",
-                    col_name
-                )));
+            let mut scalar_binder = ScalarBinder::new(
+                &mut context,
+                self.ctx.clone(),
+                &self.name_resolution_ctx,
+                self.metadata.clone(),
+                &[],
+            );
+            let mut update_columns = Vec::with_capacity(update_list.len());
+            for update_expr in update_list {
+                let (scalar, _) = scalar_binder.bind(&update_expr.expr).await?;
+                update_columns.push((update_expr, scalar));
             }
+            let push_downs = if let Some(expr) = selection {
+                let (scalar, _) = scalar_binder.bind(expr).await?;
+                Some(scalar)
+            } else {
+                None
+            };
 
-            // TODO(zhyass): selection and update_list support subquery.
-            let (scalar, _) = scalar_binder.bind(&update_expr.expr).await?;
-            if matches!(scalar, ScalarExpr::SubqueryExpr(_)) {
-                return Err(ErrorCode::Internal(
-                    "Update does not support subquery temporarily",
-                ));
+            // The updated rows with subqueries are produced by a query, see `bind_update_rows`.
+            if !update_columns
+                .iter()
+                .map(|(_, scalar)| scalar)
+                .chain(push_downs.iter())
+                .any(find_subquery_in_expr)
+            {
+                let table = self
+                    .ctx
+                    .get_table(&catalog_name, &database_name, &table_name)
+                    .await?;
+                let update_list = self.update_columns(&table.schema(), update_columns)?;
+                let plan = UpdatePlan {
+                    catalog: catalog_name,
+                    database: database_name,
+                    table: table_name,
+                    update_list,
+                    selection: push_downs,
+                    bind_context: Box::new(context.clone()),
+                    input: None,
+                };
+                return Ok(Plan::Update(Box::new(plan)));
             }
-            update_columns.insert(index, scalar);
         }
 
-        let push_downs = if let Some(expr) = selection {
-            let (scalar, _) = scalar_binder.bind(expr).await?;
-            if matches!(scalar, ScalarExpr::SubqueryExpr(_)) {
-                return Err(ErrorCode::Internal(
-                    "Update does not support subquery temporarily",
-                ));
-            }
-            Some(scalar)
-        } else {
-            None
-        };
-
+        let input = self
+            .bind_update_rows(
+                bind_context,
+                stmt,
+                &catalog_name,
+                &database_name,
+                &table_name,
+            )
+            .await?;
         let plan = UpdatePlan {
             catalog: catalog_name,
             database: database_name,
             table: table_name,
-            update_list: update_columns,
-            selection: push_downs,
-            bind_context: Box::new(context.clone()),
+            update_list: HashMap::new(),
+            selection: None,
+            bind_context: Box::new(BindContext::new()),
+            input: Some(Box::new(input)),
         };
         Ok(Plan::Update(Box::new(plan)))
     }
+
+    /// Binds the query producing the updated rows, which replace the rows of the target table
+    /// located by the internal columns. The target table is joined with the table of FROM
+    /// clause, and the subqueries are decorrelated into joins as well.
+    #[async_backtrace::framed]
+    async fn bind_update_rows(
+        &mut self,
+        bind_context: &mut BindContext,
+        stmt: &UpdateStmt,
+        catalog_name: &str,
+        database_name: &str,
+        table_name: &str,
+    ) -> Result<Plan> {
+        let (catalog, database, table, alias) = match &stmt.table {
+            TableReference::Table {
+                catalog,
+                database,
+                table,
+                alias,
+                ..
+            } => (catalog, database, table, alias),
+            _ => unreachable!(),
+        };
+        let target_name = normalize_identifier(
+            alias.as_ref().map_or(table, |alias| &alias.name),
+            &self.name_resolution_ctx,
+        )
+        .name;
+        let target_reference = target_table_reference(catalog, database, table, alias);
+
+        let (mut s_expr, mut context) = match &stmt.from {
+            Some(from) => {
+                // A row of the target table is updated at most once, the aggregator of the
+                // updated rows reports an error if it's joined with more than one row.
+                let condition = stmt.selection.clone().unwrap_or(Expr::Literal {
+                    span: None,
+                    lit: Literal::Boolean(true),
+                });
+                let join_reference = TableReference::Join {
+                    span: None,
+                    join: Join {
+                        op: JoinOperator::Inner,
+                        condition: JoinCondition::On(Box::new(condition)),
+                        match_condition: None,
+                        left: Box::new(target_reference),
+                        right: Box::new(from.clone()),
+                    },
+                };
+                self.bind_table_reference(bind_context, &join_reference)
+                    .await?
+            }
+            None => {
+                let (table_expr, mut context) = self
+                    .bind_table_reference(bind_context, &target_reference)
+                    .await?;
+                let mut s_expr = table_expr;
+                if let Some(expr) = &stmt.selection {
+                    let mut scalar_binder = ScalarBinder::new(
+                        &mut context,
+                        self.ctx.clone(),
+                        &self.name_resolution_ctx,
+                        self.metadata.clone(),
+                        &[],
+                    );
+                    let (scalar, _) = scalar_binder.bind(expr).await?;
+                    s_expr = SExpr::create_unary(
+                        Filter {
+                            predicates: vec![scalar],
+                            is_having: false,
+                        }
+                        .into(),
+                        s_expr,
+                    );
+                }
+                (s_expr, context)
+            }
+        };
+
+        let mut update_columns = Vec::with_capacity(stmt.update_list.len());
+        for update_expr in &stmt.update_list {
+            let mut scalar_binder = ScalarBinder::new(
+                &mut context,
+                self.ctx.clone(),
+                &self.name_resolution_ctx,
+                self.metadata.clone(),
+                &[],
+            );
+            let (scalar, _) = scalar_binder.bind(&update_expr.expr).await?;
+            update_columns.push((update_expr, scalar));
+        }
+        let schema = self
+            .ctx
+            .get_table(catalog_name, database_name, table_name)
+            .await?
+            .schema();
+        let update_columns = self.update_columns(&schema, update_columns)?;
+
+        let mut output_columns = vec![
+            target_column(&context, &target_name, BLOCK_NAME)?,
+            target_column(&context, &target_name, ROW_ID)?,
+        ];
+        let mut items = Vec::with_capacity(schema.num_fields() + 1);
+        let deleted =
+            self.create_column_binding(None, None, "_deleted".to_string(), DataType::Boolean);
+        items.push(ScalarItem {
+            scalar: constant_bool(false),
+            index: deleted.index,
+        });
+        output_columns.push(deleted);
+        for (index, field) in schema.fields().iter().enumerate() {
+            let data_type = DataType::from(field.data_type());
+            let value = match update_columns.get(&index) {
+                Some(scalar) => scalar.clone(),
+                None => BoundColumnRef {
+                    span: None,
+                    column: target_column(&context, &target_name, field.name())?,
+                }
+                .into(),
+            };
+            let column =
+                self.create_column_binding(None, None, field.name().clone(), data_type.clone());
+            items.push(ScalarItem {
+                scalar: wrap_cast(&value, &data_type),
+                index: column.index,
+            });
+            output_columns.push(column);
+        }
+        s_expr = SExpr::create_unary(EvalScalar { items }.into(), s_expr);
+
+        self.bind_merge_rows_query(s_expr, output_columns)
+    }
+
+    /// Resolves the updated columns, an error is returned if a column is assigned more than once.
+    fn update_columns(
+        &self,
+        schema: &TableSchemaRef,
+        update_columns: Vec<(&UpdateExpr, ScalarExpr)>,
+    ) -> Result<HashMap<FieldIndex, ScalarExpr>> {
+        let mut columns = HashMap::with_capacity(update_columns.len());
+        for (update_expr, scalar) in update_columns {
+            let col_name = normalize_identifier(&update_expr.name, &self.name_resolution_ctx).name;
+            let index = schema.index_of(&col_name)?;
+            if columns.contains_key(&index) {
+                return Err(ErrorCode::BadArguments(format!(
+                    "Multiple assignments in the single statement to column `{}`",
+                    col_name
+                )));
+            }
+            columns.insert(index, scalar);
+        }
+        Ok(columns)
+    }
 }
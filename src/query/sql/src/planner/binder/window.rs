@@ -14,10 +14,16 @@
 
 use std::collections::HashMap;
 
+use common_ast::ast::Expr;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_exception::Span;
 
 use super::select::SelectList;
+use crate::binder::aggregate::AggregateRewriter;
+use crate::binder::split_conjunctions;
+use crate::binder::ExprContext;
+use crate::binder::ScalarBinder;
 use crate::optimizer::SExpr;
 use crate::plans::AggregateFunction;
 use crate::plans::AndExpr;
@@ -25,8 +31,12 @@ use crate::plans::BoundColumnRef;
 use crate::plans::CastExpr;
 use crate::plans::ComparisonExpr;
 use crate::plans::EvalScalar;
+use crate::plans::Filter;
 use crate::plans::FunctionCall;
+use crate::plans::LagLeadFunction;
+use crate::plans::LambdaFunc;
 use crate::plans::NotExpr;
+use crate::plans::NthValueFunction;
 use crate::plans::OrExpr;
 use crate::plans::ScalarExpr;
 use crate::plans::ScalarItem;
@@ -41,6 +51,7 @@ use crate::ColumnBinding;
 use crate::IndexType;
 use crate::MetadataRef;
 use crate::Visibility;
+use crate::WindowChecker;
 
 impl Binder {
     #[async_backtrace::framed]
@@ -163,6 +174,22 @@ impl<'a> WindowRewriter<'a> {
                 }
                 .into())
             }
+            ScalarExpr::LambdaFunction(lambda_func) => {
+                let new_args = lambda_func
+                    .args
+                    .iter()
+                    .map(|arg| self.visit(arg))
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(LambdaFunc {
+                    span: lambda_func.span,
+                    func_name: lambda_func.func_name.clone(),
+                    args: new_args,
+                    lambda_expr: lambda_func.lambda_expr.clone(),
+                    lambda_display: lambda_func.lambda_display.clone(),
+                    return_type: lambda_func.return_type.clone(),
+                }
+                .into())
+            }
             ScalarExpr::CastExpr(cast) => Ok(CastExpr {
                 span: cast.span,
                 is_try: cast.is_try,
@@ -239,41 +266,8 @@ impl<'a> WindowRewriter<'a> {
                 // resolve aggregate function args in window function.
                 let mut replaced_args: Vec<ScalarExpr> = Vec::with_capacity(agg.args.len());
                 for (i, arg) in agg.args.iter().enumerate() {
-                    let arg = self.visit(arg)?;
                     let name = format!("{}_arg_{}", &window_func_name, i);
-                    if let ScalarExpr::BoundColumnRef(column_ref) = &arg {
-                        replaced_args.push(column_ref.clone().into());
-                        agg_args.push(ScalarItem {
-                            index: column_ref.column.index,
-                            scalar: arg.clone(),
-                        });
-                    } else {
-                        let index = self
-                            .metadata
-                            .write()
-                            .add_derived_column(name.clone(), arg.data_type()?);
-
-                        // Generate a ColumnBinding for each argument of aggregates
-                        let column_binding = ColumnBinding {
-                            database_name: None,
-                            table_name: None,
-                            column_name: name,
-                            index,
-                            data_type: Box::new(arg.data_type()?),
-                            visibility: Visibility::Visible,
-                        };
-                        replaced_args.push(
-                            BoundColumnRef {
-                                span: arg.span(),
-                                column: column_binding.clone(),
-                            }
-                            .into(),
-                        );
-                        agg_args.push(ScalarItem {
-                            index,
-                            scalar: arg.clone(),
-                        });
-                    }
+                    replaced_args.push(self.replace_argument(arg, name, &mut agg_args)?);
                 }
                 WindowFuncType::Aggregate(AggregateFunction {
                     display_name: agg.display_name.clone(),
@@ -284,6 +278,39 @@ impl<'a> WindowRewriter<'a> {
                     return_type: agg.return_type.clone(),
                 })
             }
+            WindowFuncType::LagLead(lag_lead) => {
+                let name = format!("{}_arg_0", &window_func_name);
+                let arg = self.replace_argument(&lag_lead.arg, name, &mut agg_args)?;
+                let default = match &lag_lead.default {
+                    Some(default) => {
+                        let name = format!("{}_arg_1", &window_func_name);
+                        Some(Box::new(self.replace_argument(
+                            default,
+                            name,
+                            &mut agg_args,
+                        )?))
+                    }
+                    None => None,
+                };
+                WindowFuncType::LagLead(LagLeadFunction {
+                    is_lag: lag_lead.is_lag,
+                    arg: Box::new(arg),
+                    offset: lag_lead.offset,
+                    default,
+                    ignore_nulls: lag_lead.ignore_nulls,
+                    return_type: lag_lead.return_type.clone(),
+                })
+            }
+            WindowFuncType::NthValue(nth) => {
+                let name = format!("{}_arg_0", &window_func_name);
+                let arg = self.replace_argument(&nth.arg, name, &mut agg_args)?;
+                WindowFuncType::NthValue(NthValueFunction {
+                    n: nth.n,
+                    arg: Box::new(arg),
+                    ignore_nulls: nth.ignore_nulls,
+                    return_type: nth.return_type.clone(),
+                })
+            }
             func => func.clone(),
         };
 
@@ -414,6 +441,48 @@ impl<'a> WindowRewriter<'a> {
 
         Ok(replaced_window.into())
     }
+
+    // Replace the argument of window function with a column, the argument is evaluated
+    // by the `EvalScalar` under the `Window` if it's not a column.
+    fn replace_argument(
+        &mut self,
+        arg: &ScalarExpr,
+        name: String,
+        items: &mut Vec<ScalarItem>,
+    ) -> Result<ScalarExpr> {
+        let arg = self.visit(arg)?;
+        if let ScalarExpr::BoundColumnRef(column_ref) = &arg {
+            items.push(ScalarItem {
+                index: column_ref.column.index,
+                scalar: arg.clone(),
+            });
+            Ok(column_ref.clone().into())
+        } else {
+            let index = self
+                .metadata
+                .write()
+                .add_derived_column(name.clone(), arg.data_type()?);
+
+            // Generate a ColumnBinding for each argument of window functions
+            let column_binding = ColumnBinding {
+                database_name: None,
+                table_name: None,
+                column_name: name,
+                index,
+                data_type: Box::new(arg.data_type()?),
+                visibility: Visibility::Visible,
+            };
+            items.push(ScalarItem {
+                index,
+                scalar: arg.clone(),
+            });
+            Ok(BoundColumnRef {
+                span: arg.span(),
+                column: column_binding,
+            }
+            .into())
+        }
+    }
 }
 
 impl Binder {
@@ -432,4 +501,57 @@ impl Binder {
 
         Ok(())
     }
+
+    /// Analyze `QUALIFY` clause, the aliases of select list can be referenced in it.
+    /// Aggregate functions and window functions only appearing in `QUALIFY` clause
+    /// are rewritten here, so they will be evaluated with the ones in select list.
+    #[async_backtrace::framed]
+    pub(super) async fn analyze_window_qualify<'a>(
+        &mut self,
+        bind_context: &mut BindContext,
+        select_list: &SelectList<'a>,
+        qualify: &Expr,
+    ) -> Result<(ScalarExpr, Span)> {
+        let aliases = select_list
+            .items
+            .iter()
+            .map(|item| (item.alias.clone(), item.scalar.clone()))
+            .collect::<Vec<_>>();
+        let mut scalar_binder = ScalarBinder::new(
+            bind_context,
+            self.ctx.clone(),
+            &self.name_resolution_ctx,
+            self.metadata.clone(),
+            &aliases,
+        );
+        let (scalar, _) = scalar_binder.bind(qualify).await?;
+        let mut rewriter = AggregateRewriter::new(bind_context, self.metadata.clone());
+        let scalar = rewriter.visit(&scalar)?;
+        let mut rewriter = WindowRewriter::new(bind_context, self.metadata.clone());
+        Ok((rewriter.visit(&scalar)?, qualify.span()))
+    }
+
+    /// Bind `QUALIFY` clause as a filter on top of the window functions.
+    #[async_backtrace::framed]
+    pub(super) async fn bind_qualify(
+        &mut self,
+        bind_context: &mut BindContext,
+        qualify: ScalarExpr,
+        span: Span,
+        child: SExpr,
+    ) -> Result<SExpr> {
+        bind_context.set_expr_context(ExprContext::QualifyClause);
+
+        let window_checker = WindowChecker::new(bind_context);
+        let scalar = window_checker.resolve_qualify(&qualify, span)?;
+
+        let predicates = split_conjunctions(&scalar);
+
+        let filter = Filter {
+            predicates,
+            is_having: false,
+        };
+
+        Ok(SExpr::create_unary(filter.into(), child))
+    }
 }
@@ -77,6 +77,8 @@ impl Display for FormatContext {
                 RelOperator::Limit(_) => write!(f, "Limit"),
                 RelOperator::Exchange(op) => format_exchange(f, metadata, op),
                 RelOperator::UnionAll(_) => write!(f, "Union"),
+                RelOperator::RecursiveUnion(_) => write!(f, "RecursiveUnion"),
+                RelOperator::RecursiveCteScan(_) => write!(f, "RecursiveCteScan"),
                 RelOperator::Pattern(_) => write!(f, "Pattern"),
                 RelOperator::DummyTableScan(_) => write!(f, "DummyTableScan"),
                 RelOperator::RuntimeFilterSource(_) => write!(f, "RuntimeFilterSource"),
@@ -150,6 +152,19 @@ pub fn format_scalar(_metadata: &MetadataRef, scalar: &ScalarExpr) -> String {
                     .join(", ")
             )
         }
+        ScalarExpr::LambdaFunction(lambda) => {
+            format!(
+                "{}({}, {})",
+                &lambda.func_name,
+                lambda
+                    .args
+                    .iter()
+                    .map(|arg| { format_scalar(_metadata, arg) })
+                    .collect::<Vec<String>>()
+                    .join(", "),
+                &lambda.lambda_display
+            )
+        }
         ScalarExpr::CastExpr(cast) => {
             format!(
                 "CAST({} AS {})",
@@ -170,6 +185,9 @@ pub fn format_join(
         JoinType::Cross => {
             write!(f, "CrossJoin")
         }
+        JoinType::Asof => {
+            write!(f, "AsofJoin")
+        }
         _ => {
             write!(f, "HashJoin: {}", &op.join_type)
         }
@@ -44,6 +44,7 @@ use crate::plans::Filter;
 use crate::plans::FunctionCall;
 use crate::plans::Join;
 use crate::plans::JoinType;
+use crate::plans::LambdaFunc;
 use crate::plans::NotExpr;
 use crate::plans::OrExpr;
 use crate::plans::PatternPlan;
@@ -787,6 +788,20 @@ impl SubqueryRewriter {
                     func_name: fun_call.func_name.clone(),
                 }))
             }
+            ScalarExpr::LambdaFunction(lambda_func) => {
+                let mut args = Vec::with_capacity(lambda_func.args.len());
+                for arg in &lambda_func.args {
+                    args.push(self.flatten_scalar(arg, correlated_columns)?);
+                }
+                Ok(ScalarExpr::LambdaFunction(LambdaFunc {
+                    span: lambda_func.span,
+                    func_name: lambda_func.func_name.clone(),
+                    args,
+                    lambda_expr: lambda_func.lambda_expr.clone(),
+                    lambda_display: lambda_func.lambda_display.clone(),
+                    return_type: lambda_func.return_type.clone(),
+                }))
+            }
             ScalarExpr::CastExpr(cast_expr) => {
                 let scalar = self.flatten_scalar(&cast_expr.argument, correlated_columns)?;
                 Ok(ScalarExpr::CastExpr(CastExpr {
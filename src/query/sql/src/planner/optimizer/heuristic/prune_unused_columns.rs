@@ -21,7 +21,6 @@ use crate::optimizer::SExpr;
 use crate::plans::Aggregate;
 use crate::plans::EvalScalar;
 use crate::plans::RelOperator;
-use crate::plans::WindowFuncType;
 use crate::MetadataRef;
 
 pub struct UnusedColumnPruner {
@@ -168,11 +167,7 @@ impl UnusedColumnPruner {
             }
             RelOperator::Window(p) => {
                 if required.contains(&p.index) {
-                    if let WindowFuncType::Aggregate(agg) = &p.function {
-                        agg.args.iter().for_each(|item| {
-                            required.extend(item.used_columns());
-                        });
-                    }
+                    required.extend(p.function.used_columns());
                     p.partition_by.iter().for_each(|item| {
                         required.insert(item.index);
                     });
@@ -229,7 +224,18 @@ impl UnusedColumnPruner {
                 ))
             }
 
-            RelOperator::DummyTableScan(_) => Ok(expr.clone()),
+            RelOperator::RecursiveUnion(p) => {
+                // The working table is read by position, so all the unioned columns are kept.
+                let left_used = p.pairs.iter().map(|(left, _)| *left).collect();
+                let right_used = p.pairs.iter().map(|(_, right)| *right).collect();
+                Ok(SExpr::create_binary(
+                    RelOperator::RecursiveUnion(p.clone()),
+                    Self::keep_required_columns(expr.child(0)?, left_used)?,
+                    Self::keep_required_columns(expr.child(1)?, right_used)?,
+                ))
+            }
+
+            RelOperator::DummyTableScan(_) | RelOperator::RecursiveCteScan(_) => Ok(expr.clone()),
 
             _ => Err(ErrorCode::Internal(
                 "Attempting to prune columns of a physical plan is not allowed",
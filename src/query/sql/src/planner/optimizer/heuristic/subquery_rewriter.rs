@@ -40,6 +40,7 @@ use crate::plans::Filter;
 use crate::plans::FunctionCall;
 use crate::plans::Join;
 use crate::plans::JoinType;
+use crate::plans::LambdaFunc;
 use crate::plans::Limit;
 use crate::plans::NotExpr;
 use crate::plans::OrExpr;
@@ -48,7 +49,6 @@ use crate::plans::ScalarExpr;
 use crate::plans::ScalarItem;
 use crate::plans::SubqueryExpr;
 use crate::plans::SubqueryType;
-use crate::plans::WindowFuncType;
 use crate::IndexType;
 use crate::MetadataRef;
 
@@ -135,29 +135,31 @@ impl SubqueryRewriter {
                     item.order_by_item.scalar = res.0;
                 }
 
-                if let WindowFuncType::Aggregate(agg) = &mut plan.function {
-                    for item in agg.args.iter_mut() {
-                        let res = self.try_rewrite_subquery(item, &input, false)?;
-                        input = res.1;
-                        *item = res.0;
-                    }
+                for item in plan.function.arguments_mut() {
+                    let res = self.try_rewrite_subquery(item, &input, false)?;
+                    input = res.1;
+                    *item = res.0;
                 }
 
                 Ok(SExpr::create_unary(plan.into(), input))
             }
 
-            RelOperator::Join(_) | RelOperator::UnionAll(_) => Ok(SExpr::create_binary(
-                s_expr.plan().clone(),
-                self.rewrite(s_expr.child(0)?)?,
-                self.rewrite(s_expr.child(1)?)?,
-            )),
+            RelOperator::Join(_) | RelOperator::UnionAll(_) | RelOperator::RecursiveUnion(_) => {
+                Ok(SExpr::create_binary(
+                    s_expr.plan().clone(),
+                    self.rewrite(s_expr.child(0)?)?,
+                    self.rewrite(s_expr.child(1)?)?,
+                ))
+            }
 
             RelOperator::Limit(_) | RelOperator::Sort(_) => Ok(SExpr::create_unary(
                 s_expr.plan().clone(),
                 self.rewrite(s_expr.child(0)?)?,
             )),
 
-            RelOperator::DummyTableScan(_) | RelOperator::Scan(_) => Ok(s_expr.clone()),
+            RelOperator::DummyTableScan(_)
+            | RelOperator::RecursiveCteScan(_)
+            | RelOperator::Scan(_) => Ok(s_expr.clone()),
 
             _ => Err(ErrorCode::Internal("Invalid plan type")),
         }
@@ -255,6 +257,28 @@ impl SubqueryRewriter {
                 Ok((expr, s_expr))
             }
 
+            ScalarExpr::LambdaFunction(lambda_func) => {
+                let mut args = vec![];
+                let mut s_expr = s_expr.clone();
+                for arg in lambda_func.args.iter() {
+                    let res = self.try_rewrite_subquery(arg, &s_expr, false)?;
+                    s_expr = res.1;
+                    args.push(res.0);
+                }
+
+                let expr: ScalarExpr = LambdaFunc {
+                    span: lambda_func.span,
+                    func_name: lambda_func.func_name.clone(),
+                    args,
+                    lambda_expr: lambda_func.lambda_expr.clone(),
+                    lambda_display: lambda_func.lambda_display.clone(),
+                    return_type: lambda_func.return_type.clone(),
+                }
+                .into();
+
+                Ok((expr, s_expr))
+            }
+
             ScalarExpr::CastExpr(cast) => {
                 let (scalar, s_expr) = self.try_rewrite_subquery(&cast.argument, s_expr, false)?;
                 Ok((
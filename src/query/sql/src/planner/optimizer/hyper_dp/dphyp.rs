@@ -85,6 +85,11 @@ impl DPhpy {
                 self.join_relations.push(join_relation);
                 Ok(true)
             }
+            RelOperator::Join(op) if op.join_type == JoinType::Asof => {
+                // The result of asof join depends on which side a row comes from,
+                // so it can't be reordered.
+                Ok(false)
+            }
             RelOperator::Join(op) => {
                 // Add join conditions
                 for condition_pair in op.left_conditions.iter().zip(op.right_conditions.iter()) {
@@ -117,7 +122,9 @@ impl DPhpy {
             RelOperator::Exchange(_) | RelOperator::Pattern(_) => unreachable!(),
             RelOperator::Window(_)
             | RelOperator::UnionAll(_)
+            | RelOperator::RecursiveUnion(_)
             | RelOperator::DummyTableScan(_)
+            | RelOperator::RecursiveCteScan(_)
             | RelOperator::RuntimeFilterSource(_) => Ok(false),
         }
     }
@@ -44,4 +44,5 @@ pub use rule::try_push_down_filter_join;
 pub use rule::RuleFactory;
 pub use rule::RuleID;
 pub use rule::RuleSet;
+pub(crate) use s_expr::find_subquery_in_expr;
 pub use s_expr::SExpr;
@@ -20,7 +20,6 @@ use crate::optimizer::SExpr;
 use crate::plans::Filter;
 use crate::plans::Join;
 use crate::plans::JoinType;
-use crate::plans::WindowFuncType;
 use crate::IndexType;
 use crate::ScalarExpr;
 
@@ -143,10 +142,8 @@ fn replace_column(scalar: &mut ScalarExpr, col_to_scalar: &HashMap<&IndexType, &
             replace_column(&mut expr.right, col_to_scalar);
         }
         ScalarExpr::WindowFunction(expr) => {
-            if let WindowFuncType::Aggregate(agg) = &mut expr.func {
-                for arg in agg.args.iter_mut() {
-                    replace_column(arg, col_to_scalar);
-                }
+            for arg in expr.func.arguments_mut() {
+                replace_column(arg, col_to_scalar);
             }
             for arg in expr.partition_by.iter_mut() {
                 replace_column(arg, col_to_scalar)
@@ -165,6 +162,11 @@ fn replace_column(scalar: &mut ScalarExpr, col_to_scalar: &HashMap<&IndexType, &
                 replace_column(arg, col_to_scalar)
             }
         }
+        ScalarExpr::LambdaFunction(expr) => {
+            for arg in expr.args.iter_mut() {
+                replace_column(arg, col_to_scalar)
+            }
+        }
         ScalarExpr::CastExpr(expr) => {
             replace_column(&mut expr.argument, col_to_scalar);
         }
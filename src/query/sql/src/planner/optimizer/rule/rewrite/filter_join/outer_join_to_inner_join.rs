@@ -29,10 +29,10 @@ use crate::plans::Filter;
 use crate::plans::FunctionCall;
 use crate::plans::Join;
 use crate::plans::JoinType;
+use crate::plans::LambdaFunc;
 use crate::plans::NotExpr;
 use crate::plans::OrExpr;
 use crate::plans::WindowFunc;
-use crate::plans::WindowFuncType;
 use crate::plans::WindowOrderBy;
 use crate::ColumnBinding;
 use crate::ColumnEntry;
@@ -342,33 +342,9 @@ fn remove_column_nullable(
             })
         }
         ScalarExpr::WindowFunction(expr) => {
-            let func = match &expr.func {
-                WindowFuncType::Aggregate(agg) => {
-                    let args = agg
-                        .args
-                        .iter()
-                        .map(|arg| {
-                            remove_column_nullable(
-                                arg,
-                                left_prop,
-                                right_prop,
-                                join_type,
-                                metadata.clone(),
-                            )
-                        })
-                        .collect::<Result<Vec<_>>>()?;
-
-                    WindowFuncType::Aggregate(AggregateFunction {
-                        display_name: agg.display_name.clone(),
-                        func_name: agg.func_name.clone(),
-                        distinct: agg.distinct,
-                        params: agg.params.clone(),
-                        args,
-                        return_type: agg.return_type.clone(),
-                    })
-                }
-                func => func.clone(),
-            };
+            let func = expr.func.replace_arguments(|arg| {
+                remove_column_nullable(arg, left_prop, right_prop, join_type, metadata.clone())
+            })?;
             let mut partition_by = Vec::with_capacity(expr.partition_by.len());
             for arg in expr.partition_by.iter() {
                 partition_by.push(remove_column_nullable(
@@ -440,6 +416,26 @@ fn remove_column_nullable(
                 func_name: expr.func_name.clone(),
             })
         }
+        ScalarExpr::LambdaFunction(expr) => {
+            let mut args = Vec::with_capacity(expr.args.len());
+            for arg in expr.args.iter() {
+                args.push(remove_column_nullable(
+                    arg,
+                    left_prop,
+                    right_prop,
+                    join_type,
+                    metadata.clone(),
+                )?);
+            }
+            ScalarExpr::LambdaFunction(LambdaFunc {
+                span: expr.span,
+                func_name: expr.func_name.clone(),
+                args,
+                lambda_expr: expr.lambda_expr.clone(),
+                lambda_display: expr.lambda_display.clone(),
+                return_type: expr.return_type.clone(),
+            })
+        }
         ScalarExpr::CastExpr(expr) => {
             let new_expr =
                 remove_column_nullable(&expr.argument, left_prop, right_prop, join_type, metadata)?;
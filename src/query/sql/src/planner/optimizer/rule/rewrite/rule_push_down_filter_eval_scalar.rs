@@ -28,6 +28,7 @@ use crate::plans::ComparisonExpr;
 use crate::plans::EvalScalar;
 use crate::plans::Filter;
 use crate::plans::FunctionCall;
+use crate::plans::LambdaFunc;
 use crate::plans::NotExpr;
 use crate::plans::OrExpr;
 use crate::plans::PatternPlan;
@@ -35,7 +36,6 @@ use crate::plans::RelOp;
 use crate::plans::ScalarExpr;
 use crate::plans::ScalarItem;
 use crate::plans::WindowFunc;
-use crate::plans::WindowFuncType;
 use crate::plans::WindowOrderBy;
 
 pub struct RulePushDownFilterEvalScalar {
@@ -164,32 +164,14 @@ impl RulePushDownFilterEvalScalar {
                     }))
                 }
                 ScalarExpr::WindowFunction(window) => {
-                    let func = match &window.func {
-                        WindowFuncType::Aggregate(agg) => {
-                            let args = agg
-                                .args
-                                .iter()
-                                .map(|arg| {
-                                    Self::replace_predicate(
-                                        arg,
-                                        items,
-                                        eval_scalar_columns,
-                                        eval_scalar_child_columns,
-                                    )
-                                })
-                                .collect::<Result<Vec<ScalarExpr>>>()?;
-
-                            WindowFuncType::Aggregate(AggregateFunction {
-                                func_name: agg.func_name.clone(),
-                                distinct: agg.distinct,
-                                params: agg.params.clone(),
-                                args,
-                                return_type: agg.return_type.clone(),
-                                display_name: agg.display_name.clone(),
-                            })
-                        }
-                        func => func.clone(),
-                    };
+                    let func = window.func.replace_arguments(|arg| {
+                        Self::replace_predicate(
+                            arg,
+                            items,
+                            eval_scalar_columns,
+                            eval_scalar_child_columns,
+                        )
+                    })?;
 
                     let partition_by = window
                         .partition_by
@@ -273,6 +255,29 @@ impl RulePushDownFilterEvalScalar {
                         func_name: func.func_name.clone(),
                     }))
                 }
+                ScalarExpr::LambdaFunction(lambda_func) => {
+                    let args = lambda_func
+                        .args
+                        .iter()
+                        .map(|arg| {
+                            Self::replace_predicate(
+                                arg,
+                                items,
+                                eval_scalar_columns,
+                                eval_scalar_child_columns,
+                            )
+                        })
+                        .collect::<Result<Vec<ScalarExpr>>>()?;
+
+                    Ok(ScalarExpr::LambdaFunction(LambdaFunc {
+                        span: lambda_func.span,
+                        func_name: lambda_func.func_name.clone(),
+                        args,
+                        lambda_expr: lambda_func.lambda_expr.clone(),
+                        lambda_display: lambda_func.lambda_display.clone(),
+                        return_type: lambda_func.return_type.clone(),
+                    }))
+                }
                 ScalarExpr::CastExpr(cast) => {
                     let arg = Self::replace_predicate(
                         &cast.argument,
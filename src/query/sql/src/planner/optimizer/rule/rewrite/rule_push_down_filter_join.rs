@@ -16,6 +16,8 @@ use common_exception::Result;
 use common_expression::type_check::common_super_type;
 use common_functions::BUILTIN_FUNCTIONS;
 
+use crate::binder::contain_subquery;
+use crate::binder::satisfied_by;
 use crate::binder::JoinPredicate;
 use crate::optimizer::rule::rewrite::filter_join::convert_mark_to_semi_join;
 use crate::optimizer::rule::rewrite::filter_join::convert_outer_to_inner_join;
@@ -25,9 +27,12 @@ use crate::optimizer::rule::rewrite::filter_join::try_derive_predicates;
 use crate::optimizer::rule::Rule;
 use crate::optimizer::rule::TransformResult;
 use crate::optimizer::RelExpr;
+use crate::optimizer::RelationalProperty;
 use crate::optimizer::RuleID;
 use crate::optimizer::SExpr;
 use crate::planner::binder::wrap_cast;
+use crate::plans::ComparisonExpr;
+use crate::plans::ComparisonOp;
 use crate::plans::Filter;
 use crate::plans::Join;
 use crate::plans::JoinType;
@@ -147,6 +152,18 @@ pub fn try_push_down_filter_join(
 
     let mut need_push = false;
 
+    // Inequalities between the two sides are pushed into a join without equi conditions,
+    // so that it can be executed by a range join. Decide it before any predicate is pushed,
+    // the equalities among the predicates will make the join a hash join.
+    let push_range_predicates = matches!(join.join_type, JoinType::Cross | JoinType::Inner)
+        && join.left_conditions.is_empty()
+        && !predicates.iter().any(|predicate| {
+            matches!(
+                JoinPredicate::new(predicate, &left_prop, &right_prop),
+                JoinPredicate::Both { .. }
+            )
+        });
+
     for predicate in predicates.into_iter() {
         let pred = JoinPredicate::new(&predicate, &left_prop, &right_prop);
         match pred {
@@ -159,14 +176,24 @@ pub fn try_push_down_filter_join(
                 left_push_down.push(predicate);
             }
             JoinPredicate::Right(_) => {
-                if matches!(join.join_type, JoinType::Left) {
+                if matches!(join.join_type, JoinType::Left | JoinType::Asof) {
                     original_predicates.push(predicate);
                     continue;
                 }
                 need_push = true;
                 right_push_down.push(predicate);
             }
-            JoinPredicate::Other(_) => original_predicates.push(predicate),
+            JoinPredicate::Other(_) => {
+                if push_range_predicates
+                    && is_range_join_predicate(&predicate, &left_prop, &right_prop)
+                {
+                    join.join_type = JoinType::Inner;
+                    join.non_equi_conditions.push(predicate);
+                    need_push = true;
+                    continue;
+                }
+                original_predicates.push(predicate)
+            }
 
             JoinPredicate::Both { left, right } => {
                 let left_type = left.data_type()?;
@@ -192,6 +219,10 @@ pub fn try_push_down_filter_join(
                             join.right_conditions.push(right.clone());
                         }
                         need_push = true;
+                    } else {
+                        // The predicate can't be pushed into other joins, e.g. asof joins,
+                        // keep it to filter the joined rows.
+                        original_predicates.push(predicate);
                     }
                 } else {
                     original_predicates.push(predicate);
@@ -219,3 +250,24 @@ pub fn try_push_down_filter_join(
     }
     Ok((need_push, result))
 }
+
+fn is_range_join_predicate(
+    predicate: &ScalarExpr,
+    left_prop: &RelationalProperty,
+    right_prop: &RelationalProperty,
+) -> bool {
+    if contain_subquery(predicate) {
+        return false;
+    }
+    match predicate {
+        ScalarExpr::ComparisonExpr(ComparisonExpr { op, left, right })
+            if !matches!(op, ComparisonOp::Equal | ComparisonOp::NotEqual) =>
+        {
+            !left.used_columns().is_empty()
+                && !right.used_columns().is_empty()
+                && ((satisfied_by(left, left_prop) && satisfied_by(right, right_prop))
+                    || (satisfied_by(left, right_prop) && satisfied_by(right, left_prop)))
+        }
+        _ => false,
+    }
+}
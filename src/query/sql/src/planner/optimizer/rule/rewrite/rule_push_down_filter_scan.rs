@@ -25,13 +25,13 @@ use crate::plans::CastExpr;
 use crate::plans::ComparisonExpr;
 use crate::plans::Filter;
 use crate::plans::FunctionCall;
+use crate::plans::LambdaFunc;
 use crate::plans::NotExpr;
 use crate::plans::OrExpr;
 use crate::plans::PatternPlan;
 use crate::plans::RelOp;
 use crate::plans::Scan;
 use crate::plans::WindowFunc;
-use crate::plans::WindowFuncType;
 use crate::plans::WindowOrderBy;
 use crate::ColumnBinding;
 use crate::ColumnEntry;
@@ -146,27 +146,9 @@ impl RulePushDownFilterScan {
                 }))
             }
             ScalarExpr::WindowFunction(window) => {
-                let func = match &window.func {
-                    WindowFuncType::Aggregate(agg) => {
-                        let args = agg
-                            .args
-                            .iter()
-                            .map(|arg| {
-                                Self::replace_view_column(arg, table_entries, column_entries)
-                            })
-                            .collect::<Result<Vec<ScalarExpr>>>()?;
-
-                        WindowFuncType::Aggregate(AggregateFunction {
-                            func_name: agg.func_name.clone(),
-                            distinct: agg.distinct,
-                            params: agg.params.clone(),
-                            args,
-                            return_type: agg.return_type.clone(),
-                            display_name: agg.display_name.clone(),
-                        })
-                    }
-                    func => func.clone(),
-                };
+                let func = window.func.replace_arguments(|arg| {
+                    Self::replace_view_column(arg, table_entries, column_entries)
+                })?;
 
                 let partition_by = window
                     .partition_by
@@ -226,6 +208,22 @@ impl RulePushDownFilterScan {
                     func_name: func.func_name.clone(),
                 }))
             }
+            ScalarExpr::LambdaFunction(lambda_func) => {
+                let args = lambda_func
+                    .args
+                    .iter()
+                    .map(|arg| Self::replace_view_column(arg, table_entries, column_entries))
+                    .collect::<Result<Vec<ScalarExpr>>>()?;
+
+                Ok(ScalarExpr::LambdaFunction(LambdaFunc {
+                    span: lambda_func.span,
+                    func_name: lambda_func.func_name.clone(),
+                    args,
+                    lambda_expr: lambda_func.lambda_expr.clone(),
+                    lambda_display: lambda_func.lambda_display.clone(),
+                    return_type: lambda_func.return_type.clone(),
+                }))
+            }
             ScalarExpr::CastExpr(cast) => {
                 let arg = Self::replace_view_column(&cast.argument, table_entries, column_entries)?;
                 Ok(ScalarExpr::CastExpr(CastExpr {
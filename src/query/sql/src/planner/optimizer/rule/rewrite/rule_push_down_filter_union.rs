@@ -27,6 +27,7 @@ use crate::plans::CastExpr;
 use crate::plans::ComparisonExpr;
 use crate::plans::Filter;
 use crate::plans::FunctionCall;
+use crate::plans::LambdaFunc;
 use crate::plans::NotExpr;
 use crate::plans::OrExpr;
 use crate::plans::PatternPlan;
@@ -34,7 +35,6 @@ use crate::plans::RelOp;
 use crate::plans::ScalarExpr;
 use crate::plans::UnionAll;
 use crate::plans::WindowFunc;
-use crate::plans::WindowFuncType;
 use crate::plans::WindowOrderBy;
 use crate::ColumnBinding;
 use crate::IndexType;
@@ -174,21 +174,9 @@ fn replace_column_binding(
         })),
         ScalarExpr::WindowFunction(expr) => Ok(ScalarExpr::WindowFunction(WindowFunc {
             display_name: expr.display_name,
-            func: match expr.func {
-                WindowFuncType::Aggregate(arg) => WindowFuncType::Aggregate(AggregateFunction {
-                    display_name: arg.display_name,
-                    func_name: arg.func_name,
-                    distinct: arg.distinct,
-                    params: arg.params,
-                    args: arg
-                        .args
-                        .into_iter()
-                        .map(|arg| replace_column_binding(index_pairs, arg))
-                        .collect::<Result<Vec<_>>>()?,
-                    return_type: arg.return_type,
-                }),
-                t => t,
-            },
+            func: expr
+                .func
+                .replace_arguments(|arg| replace_column_binding(index_pairs, arg.clone()))?,
             partition_by: expr
                 .partition_by
                 .into_iter()
@@ -231,6 +219,18 @@ fn replace_column_binding(
                 .map(|arg| replace_column_binding(index_pairs, arg))
                 .collect::<Result<Vec<_>>>()?,
         })),
+        ScalarExpr::LambdaFunction(expr) => Ok(ScalarExpr::LambdaFunction(LambdaFunc {
+            span: expr.span,
+            func_name: expr.func_name,
+            args: expr
+                .args
+                .into_iter()
+                .map(|arg| replace_column_binding(index_pairs, arg))
+                .collect::<Result<Vec<_>>>()?,
+            lambda_expr: expr.lambda_expr,
+            lambda_display: expr.lambda_display,
+            return_type: expr.return_type,
+        })),
         ScalarExpr::CastExpr(expr) => Ok(ScalarExpr::CastExpr(CastExpr {
             span: expr.span,
             is_try: expr.is_try,
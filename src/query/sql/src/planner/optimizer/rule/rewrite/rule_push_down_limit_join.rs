@@ -85,7 +85,7 @@ impl Rule for RulePushDownLimitOuterJoin {
             let child = s_expr.child(0)?;
             let join: Join = child.plan().clone().try_into()?;
             match join.join_type {
-                JoinType::Left => {
+                JoinType::Left | JoinType::Asof => {
                     let mut result = s_expr.replace_children(vec![child.replace_children(vec![
                         SExpr::create_unary(RelOperator::Limit(limit), child.child(0)?.clone()),
                         child.child(1)?.clone(),
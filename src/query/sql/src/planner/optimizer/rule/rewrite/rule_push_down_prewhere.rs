@@ -79,6 +79,12 @@ impl RulePushDownPrewhere {
                 }
                 Some(())
             }
+            ScalarExpr::LambdaFunction(lambda_func) => {
+                for arg in lambda_func.args.iter() {
+                    Self::collect_columns_impl(arg, columns)?;
+                }
+                Some(())
+            }
             ScalarExpr::CastExpr(cast) => {
                 Self::collect_columns_impl(cast.argument.as_ref(), columns)
             }
@@ -26,7 +26,6 @@ use crate::plans::Operator;
 use crate::plans::PatternPlan;
 use crate::plans::RelOp;
 use crate::plans::RelOperator;
-use crate::plans::WindowFuncType;
 use crate::IndexType;
 use crate::ScalarExpr;
 
@@ -230,8 +229,10 @@ fn find_subquery(rel_op: &RelOperator) -> bool {
         | RelOperator::Limit(_)
         | RelOperator::Exchange(_)
         | RelOperator::UnionAll(_)
+        | RelOperator::RecursiveUnion(_)
         | RelOperator::Sort(_)
         | RelOperator::DummyTableScan(_)
+        | RelOperator::RecursiveCteScan(_)
         | RelOperator::RuntimeFilterSource(_)
         | RelOperator::Pattern(_) => false,
         RelOperator::Join(op) => {
@@ -261,10 +262,11 @@ fn find_subquery(rel_op: &RelOperator) -> bool {
                     .partition_by
                     .iter()
                     .any(|expr| find_subquery_in_expr(&expr.scalar))
-                || match &op.function {
-                    WindowFuncType::Aggregate(agg) => agg.args.iter().any(find_subquery_in_expr),
-                    _ => false,
-                }
+                || op
+                    .function
+                    .arguments()
+                    .into_iter()
+                    .any(find_subquery_in_expr)
         }
         RelOperator::ProjectSet(op) => op
             .srfs
@@ -273,7 +275,7 @@ fn find_subquery(rel_op: &RelOperator) -> bool {
     }
 }
 
-fn find_subquery_in_expr(expr: &ScalarExpr) -> bool {
+pub(crate) fn find_subquery_in_expr(expr: &ScalarExpr) -> bool {
     match expr {
         ScalarExpr::BoundColumnRef(_)
         | ScalarExpr::BoundInternalColumnRef(_)
@@ -289,15 +291,13 @@ fn find_subquery_in_expr(expr: &ScalarExpr) -> bool {
             find_subquery_in_expr(&expr.left) || find_subquery_in_expr(&expr.right)
         }
         ScalarExpr::WindowFunction(expr) => {
-            let flag = match &expr.func {
-                WindowFuncType::Aggregate(agg) => agg.args.iter().any(find_subquery_in_expr),
-                _ => false,
-            };
+            let flag = expr.func.arguments().into_iter().any(find_subquery_in_expr);
             flag || expr.partition_by.iter().any(find_subquery_in_expr)
                 || expr.order_by.iter().any(|o| find_subquery_in_expr(&o.expr))
         }
         ScalarExpr::AggregateFunction(expr) => expr.args.iter().any(find_subquery_in_expr),
         ScalarExpr::FunctionCall(expr) => expr.arguments.iter().any(find_subquery_in_expr),
+        ScalarExpr::LambdaFunction(expr) => expr.args.iter().any(find_subquery_in_expr),
         ScalarExpr::CastExpr(expr) => find_subquery_in_expr(&expr.argument),
         ScalarExpr::SubqueryExpr(_) => true,
     }
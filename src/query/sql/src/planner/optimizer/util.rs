@@ -31,6 +31,13 @@ pub fn contains_local_table_scan(s_expr: &SExpr, metadata: &MetadataRef) -> bool
         }
 }
 
+/// Check if a query contains a recursive common table expression,
+/// which can only be evaluated on the local node.
+pub fn contains_recursive_union(s_expr: &SExpr) -> bool {
+    matches!(s_expr.plan(), RelOperator::RecursiveUnion(_))
+        || s_expr.children().iter().any(contains_recursive_union)
+}
+
 /// Check the expr contains ProjectSet op.
 pub fn contaions_project_set(s_expr: &SExpr) -> bool {
     if let Some(child) = s_expr.children().iter().next() {
@@ -17,7 +17,7 @@ use std::sync::Arc;
 use common_expression::DataSchema;
 use common_expression::DataSchemaRef;
 
-use crate::optimizer::SExpr;
+use crate::plans::Plan;
 use crate::plans::ScalarExpr;
 
 #[derive(Clone, Debug)]
@@ -26,8 +26,9 @@ pub struct DeletePlan {
     pub database_name: String,
     pub table_name: String,
     pub selection: Option<ScalarExpr>,
-    // The case: selection is subquery
-    pub input_expr: Option<SExpr>,
+    // The case: USING clause or selection with subquery, the rows to be deleted are
+    // produced by a query, see `Table::merge_into` for the layout of the columns.
+    pub input: Option<Box<Plan>>,
 }
 
 impl DeletePlan {
@@ -56,6 +56,9 @@ pub enum JoinType {
     RightMark,
     /// Single Join is a special kind of join that is used to process correlated scalar subquery.
     Single,
+    /// Asof Join matches each left row with the nearest right row that satisfies the match
+    /// condition, it's kept in `non_equi_conditions` and the equi conditions are optional.
+    Asof,
 }
 
 impl JoinType {
@@ -121,6 +124,9 @@ impl Display for JoinType {
             JoinType::Single => {
                 write!(f, "SINGLE")
             }
+            JoinType::Asof => {
+                write!(f, "ASOF")
+            }
         }
     }
 }
@@ -128,7 +134,9 @@ impl Display for JoinType {
 /// Join operator. We will choose hash join by default.
 /// In the case that using hash join, the right child
 /// is always the build side, and the left child is always
-/// the probe side.
+/// the probe side. Inner joins without equi conditions but
+/// with inequality conditions are executed by a range join,
+/// and asof joins are executed by a sort-merge asof join.
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub struct Join {
     pub left_conditions: Vec<ScalarExpr>,
@@ -325,9 +333,11 @@ impl Operator for Join {
                     + f64::max(right_prop.cardinality, inner_join_cardinality)
                     - inner_join_cardinality
             }
-            JoinType::LeftSemi | JoinType::LeftAnti | JoinType::LeftMark | JoinType::Single => {
-                left_prop.cardinality
-            }
+            JoinType::LeftSemi
+            | JoinType::LeftAnti
+            | JoinType::LeftMark
+            | JoinType::Single
+            | JoinType::Asof => left_prop.cardinality,
             JoinType::RightSemi | JoinType::RightAnti | JoinType::RightMark => {
                 right_prop.cardinality
             }
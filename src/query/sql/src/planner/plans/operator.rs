@@ -35,6 +35,8 @@ use crate::optimizer::RequiredProperty;
 use crate::plans::runtime_filter_source::RuntimeFilterSource;
 use crate::plans::Exchange;
 use crate::plans::ProjectSet;
+use crate::plans::RecursiveCteScan;
+use crate::plans::RecursiveUnion;
 use crate::plans::Window;
 
 pub trait Operator {
@@ -69,6 +71,8 @@ pub enum RelOp {
     Limit,
     Exchange,
     UnionAll,
+    RecursiveUnion,
+    RecursiveCteScan,
     DummyTableScan,
     RuntimeFilterSource,
     Window,
@@ -90,6 +94,8 @@ pub enum RelOperator {
     Limit(Limit),
     Exchange(Exchange),
     UnionAll(UnionAll),
+    RecursiveUnion(RecursiveUnion),
+    RecursiveCteScan(RecursiveCteScan),
     DummyTableScan(DummyTableScan),
     RuntimeFilterSource(RuntimeFilterSource),
     Window(Window),
@@ -111,6 +117,8 @@ impl Operator for RelOperator {
             RelOperator::Pattern(rel_op) => rel_op.rel_op(),
             RelOperator::Exchange(rel_op) => rel_op.rel_op(),
             RelOperator::UnionAll(rel_op) => rel_op.rel_op(),
+            RelOperator::RecursiveUnion(rel_op) => rel_op.rel_op(),
+            RelOperator::RecursiveCteScan(rel_op) => rel_op.rel_op(),
             RelOperator::DummyTableScan(rel_op) => rel_op.rel_op(),
             RelOperator::RuntimeFilterSource(rel_op) => rel_op.rel_op(),
             RelOperator::ProjectSet(rel_op) => rel_op.rel_op(),
@@ -130,6 +138,8 @@ impl Operator for RelOperator {
             RelOperator::Pattern(rel_op) => rel_op.derive_relational_prop(rel_expr),
             RelOperator::Exchange(rel_op) => rel_op.derive_relational_prop(rel_expr),
             RelOperator::UnionAll(rel_op) => rel_op.derive_relational_prop(rel_expr),
+            RelOperator::RecursiveUnion(rel_op) => rel_op.derive_relational_prop(rel_expr),
+            RelOperator::RecursiveCteScan(rel_op) => rel_op.derive_relational_prop(rel_expr),
             RelOperator::DummyTableScan(rel_op) => rel_op.derive_relational_prop(rel_expr),
             RelOperator::RuntimeFilterSource(rel_op) => rel_op.derive_relational_prop(rel_expr),
             RelOperator::ProjectSet(rel_op) => rel_op.derive_relational_prop(rel_expr),
@@ -149,6 +159,8 @@ impl Operator for RelOperator {
             RelOperator::Pattern(rel_op) => rel_op.derive_physical_prop(rel_expr),
             RelOperator::Exchange(rel_op) => rel_op.derive_physical_prop(rel_expr),
             RelOperator::UnionAll(rel_op) => rel_op.derive_physical_prop(rel_expr),
+            RelOperator::RecursiveUnion(rel_op) => rel_op.derive_physical_prop(rel_expr),
+            RelOperator::RecursiveCteScan(rel_op) => rel_op.derive_physical_prop(rel_expr),
             RelOperator::DummyTableScan(rel_op) => rel_op.derive_physical_prop(rel_expr),
             RelOperator::RuntimeFilterSource(rel_op) => rel_op.derive_physical_prop(rel_expr),
             RelOperator::ProjectSet(rel_op) => rel_op.derive_physical_prop(rel_expr),
@@ -194,6 +206,12 @@ impl Operator for RelOperator {
             RelOperator::UnionAll(rel_op) => {
                 rel_op.compute_required_prop_child(ctx, rel_expr, child_index, required)
             }
+            RelOperator::RecursiveUnion(rel_op) => {
+                rel_op.compute_required_prop_child(ctx, rel_expr, child_index, required)
+            }
+            RelOperator::RecursiveCteScan(rel_op) => {
+                rel_op.compute_required_prop_child(ctx, rel_expr, child_index, required)
+            }
             RelOperator::DummyTableScan(rel_op) => {
                 rel_op.compute_required_prop_child(ctx, rel_expr, child_index, required)
             }
@@ -412,6 +430,44 @@ impl TryFrom<RelOperator> for UnionAll {
     }
 }
 
+impl From<RecursiveUnion> for RelOperator {
+    fn from(v: RecursiveUnion) -> Self {
+        Self::RecursiveUnion(v)
+    }
+}
+
+impl TryFrom<RelOperator> for RecursiveUnion {
+    type Error = ErrorCode;
+    fn try_from(value: RelOperator) -> Result<Self> {
+        if let RelOperator::RecursiveUnion(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::Internal(
+                "Cannot downcast RelOperator to RecursiveUnion",
+            ))
+        }
+    }
+}
+
+impl From<RecursiveCteScan> for RelOperator {
+    fn from(v: RecursiveCteScan) -> Self {
+        Self::RecursiveCteScan(v)
+    }
+}
+
+impl TryFrom<RelOperator> for RecursiveCteScan {
+    type Error = ErrorCode;
+    fn try_from(value: RelOperator) -> Result<Self> {
+        if let RelOperator::RecursiveCteScan(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::Internal(
+                "Cannot downcast RelOperator to RecursiveCteScan",
+            ))
+        }
+    }
+}
+
 impl From<DummyTableScan> for RelOperator {
     fn from(v: DummyTableScan) -> Self {
         Self::DummyTableScan(v)
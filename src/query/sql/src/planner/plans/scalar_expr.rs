@@ -20,6 +20,7 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_exception::Span;
 use common_expression::types::DataType;
+use common_expression::RawExpr;
 use common_expression::Scalar;
 use educe::Educe;
 
@@ -44,6 +45,7 @@ pub enum ScalarExpr {
     WindowFunction(WindowFunc),
     AggregateFunction(AggregateFunction),
     FunctionCall(FunctionCall),
+    LambdaFunction(LambdaFunc),
     // TODO(leiysky): maybe we don't need this variant any more
     // after making functions static typed?
     CastExpr(CastExpr),
@@ -100,6 +102,13 @@ impl ScalarExpr {
                 }
                 result
             }
+            ScalarExpr::LambdaFunction(scalar) => {
+                let mut result = ColumnSet::new();
+                for scalar in &scalar.args {
+                    result = result.union(&scalar.used_columns()).cloned().collect();
+                }
+                result
+            }
             ScalarExpr::CastExpr(scalar) => scalar.argument.used_columns(),
             ScalarExpr::SubqueryExpr(scalar) => scalar.outer_columns.clone(),
         }
@@ -155,6 +164,13 @@ impl ScalarExpr {
                 }
                 Ok(result)
             }
+            ScalarExpr::LambdaFunction(scalar) => {
+                let mut result = vec![];
+                for scalar in &scalar.args {
+                    result.append(&mut scalar.used_tables(metadata.clone())?);
+                }
+                Ok(result)
+            }
             ScalarExpr::CastExpr(scalar) => scalar.argument.used_tables(metadata),
             ScalarExpr::WindowFunction(_) | ScalarExpr::SubqueryExpr(_) => {
                 Err(ErrorCode::Unimplemented(
@@ -169,6 +185,7 @@ impl ScalarExpr {
             ScalarExpr::BoundColumnRef(expr) => expr.span,
             ScalarExpr::ConstantExpr(expr) => expr.span,
             ScalarExpr::FunctionCall(expr) => expr.span,
+            ScalarExpr::LambdaFunction(expr) => expr.span,
             ScalarExpr::CastExpr(expr) => expr.span,
             ScalarExpr::SubqueryExpr(expr) => expr.span,
             _ => None,
@@ -346,6 +363,23 @@ impl TryFrom<ScalarExpr> for FunctionCall {
     }
 }
 
+impl From<LambdaFunc> for ScalarExpr {
+    fn from(v: LambdaFunc) -> Self {
+        Self::LambdaFunction(v)
+    }
+}
+
+impl TryFrom<ScalarExpr> for LambdaFunc {
+    type Error = ErrorCode;
+    fn try_from(value: ScalarExpr) -> Result<Self> {
+        if let ScalarExpr::LambdaFunction(value) = value {
+            Ok(value)
+        } else {
+            Err(ErrorCode::Internal("Cannot downcast Scalar to LambdaFunc"))
+        }
+    }
+}
+
 impl From<CastExpr> for ScalarExpr {
     fn from(v: CastExpr) -> Self {
         Self::CastExpr(v)
@@ -515,6 +549,25 @@ pub struct FunctionCall {
     pub arguments: Vec<ScalarExpr>,
 }
 
+/// Higher-order function applying a lambda to each element of an array,
+/// e.g. `array_transform(arr, x -> x + 1)`.
+///
+/// The lambda body only references its own parameters, which are bound to
+/// column indexes `0..n` by position, so it's kept as a `RawExpr` and checked
+/// against the element type when the function is lowered.
+#[derive(Clone, Debug, Educe)]
+#[educe(PartialEq, Eq, Hash)]
+pub struct LambdaFunc {
+    #[educe(Hash(ignore), PartialEq(ignore), Eq(ignore))]
+    pub span: Span,
+    pub func_name: String,
+    pub args: Vec<ScalarExpr>,
+    #[educe(Hash(ignore), PartialEq(ignore), Eq(ignore))]
+    pub lambda_expr: Box<RawExpr>,
+    pub lambda_display: String,
+    pub return_type: Box<DataType>,
+}
+
 #[derive(Clone, Debug, Educe)]
 #[educe(PartialEq, Eq, Hash)]
 pub struct CastExpr {
@@ -19,6 +19,7 @@ use common_expression::DataSchema;
 use common_expression::DataSchemaRef;
 use common_expression::FieldIndex;
 
+use crate::plans::Plan;
 use crate::plans::ScalarExpr;
 use crate::BindContext;
 
@@ -30,6 +31,9 @@ pub struct UpdatePlan {
     pub update_list: HashMap<FieldIndex, ScalarExpr>,
     pub selection: Option<ScalarExpr>,
     pub bind_context: Box<BindContext>,
+    // The case: FROM clause or subqueries, the updated rows are produced by a query,
+    // see `Table::merge_into` for the layout of the columns.
+    pub input: Option<Box<Plan>>,
 }
 
 impl UpdatePlan {
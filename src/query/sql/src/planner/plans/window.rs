@@ -22,6 +22,8 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_expression::types::DataType;
 use common_expression::types::NumberDataType;
+use common_expression::types::NumberScalar;
+use common_expression::Scalar;
 
 use super::AggregateFunction;
 use crate::binder::WindowOrderByInfo;
@@ -34,6 +36,7 @@ use crate::optimizer::RequiredProperty;
 use crate::optimizer::Statistics;
 use crate::plans::Operator;
 use crate::plans::RelOp;
+use crate::plans::ScalarExpr;
 use crate::plans::ScalarItem;
 use crate::IndexType;
 
@@ -58,14 +61,7 @@ impl Window {
 
         used_columns.insert(self.index);
 
-        if let WindowFuncType::Aggregate(agg) = &self.function {
-            for scalar in &agg.args {
-                used_columns = used_columns
-                    .union(&scalar.used_columns())
-                    .cloned()
-                    .collect();
-            }
-        }
+        used_columns.extend(self.function.used_columns());
 
         for part in self.partition_by.iter() {
             used_columns.insert(part.index);
@@ -173,7 +169,7 @@ impl Display for WindowFuncFrame {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         write!(
             f,
-            "{:?}: {:?} ~ {:?}",
+            "{:?}: {} ~ {}",
             self.units, self.start_bound, self.end_bound
         )
     }
@@ -186,36 +182,61 @@ pub enum WindowFuncFrameUnits {
     Range,
 }
 
+/// The offset of a bound is the number of rows (`UInt64`) for `ROWS` frames.
+///
+/// For `RANGE` frames, it is the distance from the ORDER BY key of the current row:
+/// `UInt64` for integer keys, `Float64` for float keys, the number of days for date keys
+/// and the number of microseconds for timestamp keys.
 #[derive(Default, Clone, PartialEq, Eq, Hash, Debug, serde::Serialize, serde::Deserialize)]
 pub enum WindowFuncFrameBound {
     /// `CURRENT ROW`
     #[default]
     CurrentRow,
     /// `<N> PRECEDING` or `UNBOUNDED PRECEDING`
-    Preceding(Option<usize>),
+    Preceding(Option<Scalar>),
     /// `<N> FOLLOWING` or `UNBOUNDED FOLLOWING`.
-    Following(Option<usize>),
+    Following(Option<Scalar>),
 }
 
 impl WindowFuncFrameBound {
-    fn to_number(&self) -> i64 {
+    /// `<n> PRECEDING` of `ROWS` frames.
+    pub fn preceding_rows(n: u64) -> Self {
+        WindowFuncFrameBound::Preceding(Some(Scalar::Number(NumberScalar::UInt64(n))))
+    }
+
+    /// `<n> FOLLOWING` of `ROWS` frames.
+    pub fn following_rows(n: u64) -> Self {
+        WindowFuncFrameBound::Following(Some(Scalar::Number(NumberScalar::UInt64(n))))
+    }
+}
+
+impl Display for WindowFuncFrameBound {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         match self {
-            WindowFuncFrameBound::CurrentRow => 0,
-            WindowFuncFrameBound::Preceding(n) => match n {
-                None => i64::MIN,
-                Some(n) => -(*n as i64),
-            },
-            WindowFuncFrameBound::Following(n) => match n {
-                None => i64::MAX,
-                Some(n) => *n as i64,
-            },
+            WindowFuncFrameBound::CurrentRow => write!(f, "CURRENT ROW"),
+            WindowFuncFrameBound::Preceding(None) => write!(f, "UNBOUNDED PRECEDING"),
+            WindowFuncFrameBound::Preceding(Some(n)) => write!(f, "{n} PRECEDING"),
+            WindowFuncFrameBound::Following(None) => write!(f, "UNBOUNDED FOLLOWING"),
+            WindowFuncFrameBound::Following(Some(n)) => write!(f, "{n} FOLLOWING"),
         }
     }
 }
 
 impl PartialOrd for WindowFuncFrameBound {
     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        self.to_number().partial_cmp(&other.to_number())
+        use WindowFuncFrameBound::*;
+        match (self, other) {
+            (CurrentRow, CurrentRow)
+            | (Preceding(None), Preceding(None))
+            | (Following(None), Following(None)) => Some(Ordering::Equal),
+            (Preceding(None), _) | (_, Following(None)) => Some(Ordering::Less),
+            (_, Preceding(None)) | (Following(None), _) => Some(Ordering::Greater),
+            // The larger the offset, the farther the bound is from the current row.
+            (Preceding(Some(lhs)), Preceding(Some(rhs))) => rhs.partial_cmp(lhs),
+            (Following(Some(lhs)), Following(Some(rhs))) => lhs.partial_cmp(rhs),
+            (Preceding(_), _) | (_, Following(_)) => Some(Ordering::Less),
+            (_, Preceding(_)) | (Following(_), _) => Some(Ordering::Greater),
+        }
     }
 }
 
@@ -225,6 +246,76 @@ pub enum WindowFuncType {
     RowNumber,
     Rank,
     DenseRank,
+    LagLead(LagLeadFunction),
+    NthValue(NthValueFunction),
+    /// `NTILE(n)`, the number of buckets is a constant.
+    Ntile(u64),
+    PercentRank,
+    CumeDist,
+}
+
+/// `LAG(arg [, offset [, default]])` or `LEAD(arg [, offset [, default]])`.
+///
+/// They are evaluated over a frame rewritten from the offset,
+/// see [`LagLeadFunction::frame`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct LagLeadFunction {
+    /// `true` for `lag`, `false` for `lead`.
+    pub is_lag: bool,
+    pub arg: Box<ScalarExpr>,
+    pub offset: u64,
+    pub default: Option<Box<ScalarExpr>>,
+    pub ignore_nulls: bool,
+    pub return_type: Box<DataType>,
+}
+
+impl LagLeadFunction {
+    /// The frame that the function is evaluated over, the frame of the window is ignored.
+    ///
+    /// Without `IGNORE NULLS`, the frame contains only the row at the offset.
+    /// Otherwise the frame contains all the rows before (for `lag`) or after (for `lead`)
+    /// the current row, then the offset is counted by the non-NULL rows.
+    pub fn frame(&self) -> WindowFuncFrame {
+        let offset = self.offset;
+        let (start_bound, end_bound) = match (self.is_lag, self.ignore_nulls) {
+            _ if offset == 0 => (
+                WindowFuncFrameBound::CurrentRow,
+                WindowFuncFrameBound::CurrentRow,
+            ),
+            (true, false) => (
+                WindowFuncFrameBound::preceding_rows(offset),
+                WindowFuncFrameBound::preceding_rows(offset),
+            ),
+            (false, false) => (
+                WindowFuncFrameBound::following_rows(offset),
+                WindowFuncFrameBound::following_rows(offset),
+            ),
+            (true, true) => (
+                WindowFuncFrameBound::Preceding(None),
+                WindowFuncFrameBound::preceding_rows(1),
+            ),
+            (false, true) => (
+                WindowFuncFrameBound::following_rows(1),
+                WindowFuncFrameBound::Following(None),
+            ),
+        };
+        WindowFuncFrame {
+            units: WindowFuncFrameUnits::Rows,
+            start_bound,
+            end_bound,
+        }
+    }
+}
+
+/// `FIRST_VALUE(arg)`, `LAST_VALUE(arg)` or `NTH_VALUE(arg, n)`.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct NthValueFunction {
+    /// The position of the row in the frame, starts from 1.
+    /// `None` means the last row, which is `LAST_VALUE`.
+    pub n: Option<u64>,
+    pub arg: Box<ScalarExpr>,
+    pub ignore_nulls: bool,
+    pub return_type: Box<DataType>,
 }
 
 impl WindowFuncType {
@@ -233,6 +324,8 @@ impl WindowFuncType {
             "row_number" => Ok(WindowFuncType::RowNumber),
             "rank" => Ok(WindowFuncType::Rank),
             "dense_rank" => Ok(WindowFuncType::DenseRank),
+            "percent_rank" => Ok(WindowFuncType::PercentRank),
+            "cume_dist" => Ok(WindowFuncType::CumeDist),
             _ => Err(ErrorCode::UnknownFunction(format!(
                 "Unknown window function: {}",
                 name
@@ -245,24 +338,89 @@ impl WindowFuncType {
             WindowFuncType::RowNumber => "row_number".to_string(),
             WindowFuncType::Rank => "rank".to_string(),
             WindowFuncType::DenseRank => "dense_rank".to_string(),
+            WindowFuncType::LagLead(lag_lead) if lag_lead.is_lag => "lag".to_string(),
+            WindowFuncType::LagLead(_) => "lead".to_string(),
+            WindowFuncType::NthValue(nth) => match nth.n {
+                Some(1) => "first_value".to_string(),
+                None => "last_value".to_string(),
+                Some(_) => "nth_value".to_string(),
+            },
+            WindowFuncType::Ntile(_) => "ntile".to_string(),
+            WindowFuncType::PercentRank => "percent_rank".to_string(),
+            WindowFuncType::CumeDist => "cume_dist".to_string(),
         }
     }
 
-    pub fn used_columns(&self) -> ColumnSet {
+    /// The arguments of the window function.
+    pub fn arguments(&self) -> Vec<&ScalarExpr> {
         match self {
-            WindowFuncType::Aggregate(agg) => {
-                agg.args.iter().flat_map(|arg| arg.used_columns()).collect()
+            WindowFuncType::Aggregate(agg) => agg.args.iter().collect(),
+            WindowFuncType::LagLead(lag_lead) => {
+                let mut args = vec![lag_lead.arg.as_ref()];
+                if let Some(default) = &lag_lead.default {
+                    args.push(default.as_ref());
+                }
+                args
             }
-            _ => ColumnSet::new(),
+            WindowFuncType::NthValue(nth) => vec![nth.arg.as_ref()],
+            WindowFuncType::RowNumber
+            | WindowFuncType::Rank
+            | WindowFuncType::DenseRank
+            | WindowFuncType::Ntile(_)
+            | WindowFuncType::PercentRank
+            | WindowFuncType::CumeDist => vec![],
         }
     }
 
+    pub fn arguments_mut(&mut self) -> Vec<&mut ScalarExpr> {
+        match self {
+            WindowFuncType::Aggregate(agg) => agg.args.iter_mut().collect(),
+            WindowFuncType::LagLead(lag_lead) => {
+                let mut args = vec![lag_lead.arg.as_mut()];
+                if let Some(default) = &mut lag_lead.default {
+                    args.push(default.as_mut());
+                }
+                args
+            }
+            WindowFuncType::NthValue(nth) => vec![nth.arg.as_mut()],
+            WindowFuncType::RowNumber
+            | WindowFuncType::Rank
+            | WindowFuncType::DenseRank
+            | WindowFuncType::Ntile(_)
+            | WindowFuncType::PercentRank
+            | WindowFuncType::CumeDist => vec![],
+        }
+    }
+
+    /// Rebuild the window function with each argument replaced by `f`.
+    pub fn replace_arguments<F>(&self, mut f: F) -> Result<WindowFuncType>
+    where F: FnMut(&ScalarExpr) -> Result<ScalarExpr> {
+        let mut func = self.clone();
+        for arg in func.arguments_mut() {
+            *arg = f(arg)?;
+        }
+        Ok(func)
+    }
+
+    pub fn used_columns(&self) -> ColumnSet {
+        self.arguments()
+            .into_iter()
+            .flat_map(|arg| arg.used_columns())
+            .collect()
+    }
+
     pub fn return_type(&self) -> DataType {
         match self {
             WindowFuncType::Aggregate(agg) => *agg.return_type.clone(),
-            WindowFuncType::RowNumber | WindowFuncType::Rank | WindowFuncType::DenseRank => {
-                DataType::Number(NumberDataType::UInt64)
+            WindowFuncType::RowNumber
+            | WindowFuncType::Rank
+            | WindowFuncType::DenseRank
+            | WindowFuncType::Ntile(_) => DataType::Number(NumberDataType::UInt64),
+            WindowFuncType::PercentRank | WindowFuncType::CumeDist => {
+                DataType::Number(NumberDataType::Float64)
             }
+            WindowFuncType::LagLead(lag_lead) => *lag_lead.return_type.clone(),
+            WindowFuncType::NthValue(nth) => *nth.return_type.clone(),
         }
     }
 }
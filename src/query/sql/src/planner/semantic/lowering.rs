@@ -122,6 +122,25 @@ fn resolve_column_type<C: LoweringContext>(
                 args,
             })
         }
+        RawExpr::LambdaFunctionCall {
+            span,
+            name,
+            args,
+            lambda_expr,
+            lambda_display,
+        } => {
+            let args = args
+                .iter()
+                .map(|arg| resolve_column_type(arg, context))
+                .collect::<Result<Vec<_>>>()?;
+            Ok(RawExpr::LambdaFunctionCall {
+                span: *span,
+                name: name.clone(),
+                args,
+                lambda_expr: lambda_expr.clone(),
+                lambda_display: lambda_display.clone(),
+            })
+        }
         RawExpr::Constant { .. } => Ok(raw_expr.clone()),
     }
 }
@@ -275,6 +294,17 @@ impl ScalarExpr {
                     .map(ScalarExpr::as_raw_expr_with_col_name)
                     .collect(),
             },
+            ScalarExpr::LambdaFunction(func) => RawExpr::LambdaFunctionCall {
+                span: func.span,
+                name: func.func_name.clone(),
+                args: func
+                    .args
+                    .iter()
+                    .map(ScalarExpr::as_raw_expr_with_col_name)
+                    .collect(),
+                lambda_expr: func.lambda_expr.clone(),
+                lambda_display: func.lambda_display.clone(),
+            },
             ScalarExpr::CastExpr(cast) => RawExpr::Cast {
                 span: cast.span,
                 is_try: cast.is_try,
@@ -385,6 +415,17 @@ impl ScalarExpr {
                     .map(ScalarExpr::as_raw_expr_with_col_index)
                     .collect(),
             },
+            ScalarExpr::LambdaFunction(func) => RawExpr::LambdaFunctionCall {
+                span: func.span,
+                name: func.func_name.clone(),
+                args: func
+                    .args
+                    .iter()
+                    .map(ScalarExpr::as_raw_expr_with_col_index)
+                    .collect(),
+                lambda_expr: func.lambda_expr.clone(),
+                lambda_display: func.lambda_display.clone(),
+            },
             ScalarExpr::CastExpr(cast) => RawExpr::Cast {
                 span: cast.span,
                 is_try: cast.is_try,
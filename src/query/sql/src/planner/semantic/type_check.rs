@@ -44,9 +44,11 @@ use common_expression::infer_schema_type;
 use common_expression::type_check;
 use common_expression::type_check::check_number;
 use common_expression::type_check::common_super_type;
+use common_expression::type_check::LAMBDA_FUNCTIONS;
 use common_expression::types::decimal::DecimalDataType;
 use common_expression::types::decimal::DecimalScalar;
 use common_expression::types::decimal::DecimalSize;
+use common_expression::types::number::F64;
 use common_expression::types::DataType;
 use common_expression::types::NumberDataType;
 use common_expression::types::NumberScalar;
@@ -68,6 +70,7 @@ use crate::binder::wrap_cast;
 use crate::binder::Binder;
 use crate::binder::ExprContext;
 use crate::binder::NameResolutionResult;
+use crate::binder::Visibility;
 use crate::optimizer::RelExpr;
 use crate::planner::metadata::optimize_remove_count_args;
 use crate::plans::AggregateFunction;
@@ -79,7 +82,10 @@ use crate::plans::ComparisonExpr;
 use crate::plans::ComparisonOp;
 use crate::plans::ConstantExpr;
 use crate::plans::FunctionCall;
+use crate::plans::LagLeadFunction;
+use crate::plans::LambdaFunc;
 use crate::plans::NotExpr;
+use crate::plans::NthValueFunction;
 use crate::plans::OrExpr;
 use crate::plans::ScalarExpr;
 use crate::plans::SubqueryExpr;
@@ -593,6 +599,9 @@ impl<'a> TypeChecker<'a> {
             } => {
                 let func_name = normalize_identifier(name, self.name_resolution_ctx).to_string();
                 let func_name = func_name.as_str();
+                if LAMBDA_FUNCTIONS.contains(&func_name) {
+                    return self.resolve_lambda_function(*span, func_name, args).await;
+                }
                 if !is_builtin_function(func_name)
                     && !Self::all_rewritable_scalar_function().contains(&func_name)
                 {
@@ -671,23 +680,51 @@ impl<'a> TypeChecker<'a> {
                             "window function {name} can only be used in window clause"
                         )));
                     }
-                    if !args.is_empty() {
-                        return Err(ErrorCode::SemanticError(format!(
-                            "window function {name} does not have any argument"
-                        )));
-                    }
                     let window = window.as_ref().unwrap();
+                    let func = match name.as_str() {
+                        "lag" | "lead" | "first_value" | "last_value" | "nth_value" => {
+                            self.resolve_navigation_window_function(
+                                *span,
+                                &name,
+                                &args,
+                                window.ignore_nulls.unwrap_or(false),
+                            )
+                            .await?
+                        }
+                        "ntile" => match args.as_slice() {
+                            [
+                                Expr::Literal {
+                                    lit: Literal::UInt64(n),
+                                    ..
+                                },
+                            ] if *n > 0 => WindowFuncType::Ntile(*n),
+                            _ => {
+                                return Err(ErrorCode::SemanticError(
+                                    "window function ntile expects a positive integer constant as the number of buckets".to_string(),
+                                )
+                                .set_span(*span));
+                            }
+                        },
+                        _ => {
+                            if !args.is_empty() {
+                                return Err(ErrorCode::SemanticError(format!(
+                                    "window function {name} does not have any argument"
+                                )));
+                            }
+                            WindowFuncType::from_name(&name)?
+                        }
+                    };
                     let display_name = format!("{:#}", expr);
-                    let func = WindowFuncType::from_name(&name)?;
                     self.resolve_window(*span, display_name, window, func)
                         .await?
                 } else if AggregateFunctionFactory::instance().contains(&name) {
                     let in_window = self.in_window_function;
                     self.in_window_function = self.in_window_function || window.is_some();
-                    let (new_agg_func, data_type) = self
+                    let result = self
                         .resolve_aggregate_function(*span, &name, expr, *distinct, params, &args)
-                        .await?;
+                        .await;
                     self.in_window_function = in_window;
+                    let (new_agg_func, data_type) = result?;
                     if let Some(window) = window {
                         // aggregate window function
                         let display_name = format!("{:#}", expr);
@@ -885,6 +922,13 @@ impl<'a> TypeChecker<'a> {
             Expr::Map { span, kvs, .. } => self.resolve_map(*span, kvs).await?,
 
             Expr::Tuple { span, exprs, .. } => self.resolve_tuple(*span, exprs).await?,
+
+            Expr::Lambda { span, .. } => {
+                return Err(ErrorCode::SemanticError(
+                    "lambda expression can only be used as the argument of array_transform, array_filter or array_reduce".to_string(),
+                )
+                .set_span(*span));
+            }
         };
 
         Ok(Box::new((scalar, data_type)))
@@ -919,21 +963,46 @@ impl<'a> TypeChecker<'a> {
             )
             .set_span(span));
         }
+        if window.ignore_nulls.is_some()
+            && !matches!(
+                func,
+                WindowFuncType::LagLead(_) | WindowFuncType::NthValue(_)
+            )
+        {
+            return Err(ErrorCode::SemanticError(
+                "IGNORE NULLS and RESPECT NULLS are only supported by lag, lead, first_value, last_value and nth_value".to_string(),
+            )
+            .set_span(span));
+        }
         let mut partitions = Vec::with_capacity(window.partition_by.len());
         for p in window.partition_by.iter() {
             let box (part, _part_type) = self.resolve(p).await?;
             partitions.push(part);
         }
         let mut order_by = Vec::with_capacity(window.order_by.len());
+        let mut order_by_types = Vec::with_capacity(window.order_by.len());
         for o in window.order_by.iter() {
-            let box (order, _) = self.resolve(&o.expr).await?;
+            let box (order, order_type) = self.resolve(&o.expr).await?;
+            order_by_types.push(order_type);
             order_by.push(WindowOrderBy {
                 expr: order,
                 asc: o.asc,
                 nulls_first: o.nulls_first,
             })
         }
-        let frame = Self::check_frame_bound(span, window.window_frame.clone())?;
+        let frame = match &func {
+            // The frame of lag and lead is decided by the offset.
+            WindowFuncType::LagLead(lag_lead) => lag_lead.frame(),
+            // The whole partition is needed to know the number of rows in it.
+            WindowFuncType::Ntile(_) | WindowFuncType::PercentRank | WindowFuncType::CumeDist => {
+                WindowFuncFrame {
+                    units: WindowFuncFrameUnits::Rows,
+                    start_bound: WindowFuncFrameBound::Preceding(None),
+                    end_bound: WindowFuncFrameBound::Following(None),
+                }
+            }
+            _ => Self::check_frame_bound(span, window.window_frame.clone(), &order_by_types)?,
+        };
         let data_type = func.return_type();
         let window_func = WindowFunc {
             display_name,
@@ -945,63 +1014,259 @@ impl<'a> TypeChecker<'a> {
         Ok(Box::new((window_func.into(), data_type)))
     }
 
-    // just support integer
-    #[inline]
-    fn resolve_window_frame(expr: &Expr) -> Option<usize> {
-        match expr {
+    /// Resolve the navigation window functions: `lag`, `lead`, `first_value`, `last_value`
+    /// and `nth_value`, which return the value of a row in the window frame.
+    #[async_backtrace::framed]
+    async fn resolve_navigation_window_function(
+        &mut self,
+        span: Span,
+        func_name: &str,
+        args: &[&Expr],
+        ignore_nulls: bool,
+    ) -> Result<WindowFuncType> {
+        let (min_args, max_args) = match func_name {
+            "lag" | "lead" => (1, 3),
+            "nth_value" => (2, 2),
+            _ => (1, 1),
+        };
+        if args.len() < min_args || args.len() > max_args {
+            return Err(ErrorCode::SemanticError(format!(
+                "window function {func_name} expects {} arguments, but got {}",
+                if min_args == max_args {
+                    min_args.to_string()
+                } else {
+                    format!("{min_args} to {max_args}")
+                },
+                args.len()
+            ))
+            .set_span(span));
+        }
+
+        // Aggregate functions are allowed in the arguments, but window functions are not.
+        // The state is restored whether or not the arguments are resolved.
+        let in_window = self.in_window_function;
+        self.in_window_function = true;
+        let result = self
+            .resolve_navigation_window_args(span, func_name, args, ignore_nulls)
+            .await;
+        self.in_window_function = in_window;
+        result
+    }
+
+    #[async_backtrace::framed]
+    async fn resolve_navigation_window_args(
+        &mut self,
+        span: Span,
+        func_name: &str,
+        args: &[&Expr],
+        ignore_nulls: bool,
+    ) -> Result<WindowFuncType> {
+        // The offset of `lag`/`lead` and the `n` of `nth_value` must be constants.
+        let constant_arg = |index: usize, default: u64| match args.get(index) {
+            None => Ok(default),
+            Some(Expr::Literal {
+                lit: Literal::UInt64(n),
+                ..
+            }) => Ok(*n),
+            Some(arg) => {
+                let msg = format!(
+                    "the argument {} of window function {func_name} must be a non-negative integer constant",
+                    index + 1
+                );
+                Err(ErrorCode::SemanticError(msg).set_span(arg.span()))
+            }
+        };
+
+        let box (arg, arg_type) = self.resolve(args[0]).await?;
+        let func = match func_name {
+            "lag" | "lead" => {
+                let offset = constant_arg(1, 1)?;
+                let (arg, default, return_type) = match args.get(2) {
+                    Some(default) => {
+                        let box (default, default_type) = self.resolve(default).await?;
+                        let return_type = common_super_type(
+                            arg_type.clone(),
+                            default_type.clone(),
+                            &BUILTIN_FUNCTIONS.default_cast_rules,
+                        )
+                        .ok_or_else(|| {
+                            ErrorCode::SemanticError(format!(
+                                "the default value of window function {func_name} has type {default_type}, which cannot be matched with the argument type {arg_type}"
+                            ))
+                            .set_span(span)
+                        })?;
+                        let arg = if arg_type != return_type {
+                            wrap_cast(&arg, &return_type)
+                        } else {
+                            arg
+                        };
+                        let default = if default_type != return_type {
+                            wrap_cast(&default, &return_type)
+                        } else {
+                            default
+                        };
+                        (arg, Some(Box::new(default)), return_type)
+                    }
+                    None => (arg, None, arg_type.wrap_nullable()),
+                };
+                WindowFuncType::LagLead(LagLeadFunction {
+                    is_lag: func_name == "lag",
+                    arg: Box::new(arg),
+                    offset,
+                    default,
+                    ignore_nulls,
+                    return_type: Box::new(return_type),
+                })
+            }
+            _ => {
+                let n = match func_name {
+                    "first_value" => Some(1),
+                    "last_value" => None,
+                    _ => {
+                        let n = constant_arg(1, 1)?;
+                        if n == 0 {
+                            return Err(ErrorCode::SemanticError(format!(
+                                "the argument 2 of window function {func_name} must be greater than 0"
+                            ))
+                            .set_span(args[1].span()));
+                        }
+                        Some(n)
+                    }
+                };
+                WindowFuncType::NthValue(NthValueFunction {
+                    n,
+                    arg: Box::new(arg),
+                    ignore_nulls,
+                    return_type: Box::new(arg_type.wrap_nullable()),
+                })
+            }
+        };
+        Ok(func)
+    }
+
+    /// Resolve the offset of a frame bound, see [`WindowFuncFrameBound`] for how it is represented.
+    fn resolve_frame_offset(
+        units: &WindowFuncFrameUnits,
+        expr: &Expr,
+        order_by_types: &[DataType],
+    ) -> Result<Scalar> {
+        if *units == WindowFuncFrameUnits::Rows {
+            return match expr {
+                Expr::Literal {
+                    lit: Literal::UInt64(n),
+                    ..
+                } => Ok(Scalar::Number(NumberScalar::UInt64(*n))),
+                _ => Err(ErrorCode::SemanticError(
+                    "the offset of ROWS frame must be a non-negative integer constant".to_string(),
+                )
+                .set_span(expr.span())),
+            };
+        }
+
+        let order_by_type = match order_by_types {
+            [data_type] => data_type.remove_nullable(),
+            _ => {
+                return Err(ErrorCode::SemanticError(
+                    "RANGE frame with an offset requires exactly one ORDER BY key".to_string(),
+                )
+                .set_span(expr.span()));
+            }
+        };
+        // The number in `INTERVAL '<n>' <unit>`.
+        let interval_value = |expr: &Expr| match expr {
             Expr::Literal {
-                lit: Literal::UInt64(value),
+                lit: Literal::UInt64(n),
                 ..
-            } => Some(*value as usize),
+            } => Some(*n),
+            Expr::Literal {
+                lit: Literal::String(s),
+                ..
+            } => s.trim().parse::<u64>().ok(),
             _ => None,
-        }
+        };
+        let offset = match (&order_by_type, expr) {
+            (DataType::Number(number_type), Expr::Literal { lit, .. })
+                if number_type.is_float() =>
+            {
+                let n = match lit {
+                    Literal::UInt64(n) => Some(*n as f64),
+                    Literal::Float(n) if *n >= 0.0 => Some(*n),
+                    Literal::Decimal128 { value, scale, .. } if *value >= 0 => {
+                        Some(*value as f64 / 10f64.powi(*scale as i32))
+                    }
+                    _ => None,
+                };
+                n.map(|n| Scalar::Number(NumberScalar::Float64(F64::from(n))))
+            }
+            (
+                DataType::Number(_),
+                Expr::Literal {
+                    lit: Literal::UInt64(n),
+                    ..
+                },
+            ) => Some(Scalar::Number(NumberScalar::UInt64(*n))),
+            (
+                DataType::Date,
+                Expr::Interval {
+                    expr,
+                    unit: ASTIntervalKind::Day,
+                    ..
+                },
+            ) => interval_value(expr).map(|days| Scalar::Number(NumberScalar::UInt64(days))),
+            (DataType::Timestamp, Expr::Interval { expr, unit, .. }) => {
+                let micros_of_unit: Option<u64> = match unit {
+                    ASTIntervalKind::Day => Some(24 * 3600 * 1_000_000),
+                    ASTIntervalKind::Hour => Some(3600 * 1_000_000),
+                    ASTIntervalKind::Minute => Some(60 * 1_000_000),
+                    ASTIntervalKind::Second => Some(1_000_000),
+                    _ => None,
+                };
+                micros_of_unit
+                    .zip(interval_value(expr))
+                    .and_then(|(micros, n)| micros.checked_mul(n))
+                    .map(|micros| Scalar::Number(NumberScalar::UInt64(micros)))
+            }
+            _ => None,
+        };
+        offset.ok_or_else(|| {
+            ErrorCode::SemanticError(format!(
+                "invalid offset of RANGE frame for the ORDER BY key of type {order_by_type}, \
+                 expect a non-negative number constant for numeric keys, \
+                 an interval of days for date keys, \
+                 or an interval of days, hours, minutes or seconds for timestamp keys"
+            ))
+            .set_span(expr.span())
+        })
     }
 
-    fn check_frame_bound(span: Span, window_frame: Option<WindowFrame>) -> Result<WindowFuncFrame> {
+    fn check_frame_bound(
+        span: Span,
+        window_frame: Option<WindowFrame>,
+        order_by_types: &[DataType],
+    ) -> Result<WindowFuncFrame> {
         let (units, start, end) = if let Some(frame) = window_frame {
             let units = match frame.units {
                 WindowFrameUnits::Rows => WindowFuncFrameUnits::Rows,
                 WindowFrameUnits::Range => WindowFuncFrameUnits::Range,
             };
-            let start = match frame.start_bound {
-                WindowFrameBound::CurrentRow => WindowFuncFrameBound::CurrentRow,
-                WindowFrameBound::Preceding(f) => {
-                    if let Some(box expr) = f {
-                        let result = Self::resolve_window_frame(&expr);
-                        WindowFuncFrameBound::Preceding(result)
-                    } else {
-                        WindowFuncFrameBound::Preceding(None)
-                    }
-                }
-                WindowFrameBound::Following(f) => {
-                    if let Some(box expr) = f {
-                        let result = Self::resolve_window_frame(&expr);
-                        WindowFuncFrameBound::Following(result)
-                    } else {
-                        WindowFuncFrameBound::Following(None)
-                    }
-                }
-            };
-
-            let end = match frame.end_bound {
-                WindowFrameBound::CurrentRow => WindowFuncFrameBound::CurrentRow,
-                WindowFrameBound::Preceding(f) => {
-                    if let Some(box expr) = f {
-                        let result = Self::resolve_window_frame(&expr);
-                        WindowFuncFrameBound::Preceding(result)
-                    } else {
-                        WindowFuncFrameBound::Preceding(None)
+            let resolve_bound = |bound: WindowFrameBound| -> Result<WindowFuncFrameBound> {
+                let resolve_offset = |offset: Option<Box<Expr>>| {
+                    offset
+                        .map(|expr| Self::resolve_frame_offset(&units, &expr, order_by_types))
+                        .transpose()
+                };
+                Ok(match bound {
+                    WindowFrameBound::CurrentRow => WindowFuncFrameBound::CurrentRow,
+                    WindowFrameBound::Preceding(offset) => {
+                        WindowFuncFrameBound::Preceding(resolve_offset(offset)?)
                     }
-                }
-                WindowFrameBound::Following(f) => {
-                    if let Some(box expr) = f {
-                        let result = Self::resolve_window_frame(&expr);
-                        WindowFuncFrameBound::Following(result)
-                    } else {
-                        WindowFuncFrameBound::Following(None)
+                    WindowFrameBound::Following(offset) => {
+                        WindowFuncFrameBound::Following(resolve_offset(offset)?)
                     }
-                }
+                })
             };
+            let start = resolve_bound(frame.start_bound)?;
+            let end = resolve_bound(frame.end_bound)?;
             (units, start, end)
         } else {
             let units = WindowFuncFrameUnits::Rows;
@@ -2059,6 +2324,79 @@ impl<'a> TypeChecker<'a> {
         Ok(Some(self.resolve(&udf_expr).await?))
     }
 
+    #[async_recursion::async_recursion]
+    #[async_backtrace::framed]
+    async fn resolve_lambda_function(
+        &mut self,
+        span: Span,
+        func_name: &str,
+        args: &[Expr],
+    ) -> Result<Box<(ScalarExpr, DataType)>> {
+        let (arg, lambda, params, lambda_body) = match args {
+            [arg, lambda @ Expr::Lambda { params, expr, .. }] => (arg, lambda, params, expr),
+            _ => {
+                return Err(ErrorCode::SemanticError(format!(
+                    "function {func_name} expects an array and a lambda expression as arguments"
+                ))
+                .set_span(span));
+            }
+        };
+
+        let box (arg, arg_type) = self.resolve(arg).await?;
+        let params_type = type_check::lambda_params_type(func_name, &arg_type)
+            .map_err(|err| err.set_span(span))?;
+        if params.len() != params_type.len() {
+            return Err(ErrorCode::SemanticError(format!(
+                "the lambda expression of function {func_name} expects {} parameters, but got {}",
+                params_type.len(),
+                params.len()
+            ))
+            .set_span(lambda.span()));
+        }
+
+        // The lambda body is resolved in a standalone context, in which only the
+        // lambda parameters are visible. They are bound to the columns `0..n`,
+        // which are filled with the array elements on evaluation.
+        let mut lambda_context = BindContext::new();
+        for (index, (param, data_type)) in params.iter().zip(params_type).enumerate() {
+            lambda_context.add_column_binding(ColumnBinding {
+                database_name: None,
+                table_name: None,
+                column_name: normalize_identifier(param, self.name_resolution_ctx).name,
+                index,
+                data_type: Box::new(data_type),
+                visibility: Visibility::Visible,
+            });
+        }
+        let mut lambda_type_checker = TypeChecker::new(
+            &mut lambda_context,
+            self.ctx.clone(),
+            self.name_resolution_ctx,
+            self.metadata.clone(),
+            &[],
+        );
+        let box (lambda_scalar, _) = lambda_type_checker.resolve(lambda_body).await?;
+
+        let lambda_func = LambdaFunc {
+            span,
+            func_name: func_name.to_string(),
+            args: vec![arg],
+            lambda_expr: Box::new(lambda_scalar.as_raw_expr_with_col_index()),
+            lambda_display: format!("{:#}", lambda),
+            return_type: Box::new(DataType::Null),
+        };
+        let return_type = ScalarExpr::LambdaFunction(lambda_func.clone()).data_type()?;
+
+        Ok(Box::new((
+            LambdaFunc {
+                return_type: Box::new(return_type.clone()),
+                ..lambda_func
+            }
+            .into(),
+            return_type,
+        )))
+    }
+
     #[async_recursion::async_recursion]
     #[async_backtrace::framed]
     async fn resolve_map_access(
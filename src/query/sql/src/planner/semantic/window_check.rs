@@ -14,12 +14,15 @@
 
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_exception::Span;
 
+use crate::planner::semantic::GroupingChecker;
 use crate::plans::AndExpr;
 use crate::plans::BoundColumnRef;
 use crate::plans::CastExpr;
 use crate::plans::ComparisonExpr;
 use crate::plans::FunctionCall;
+use crate::plans::LambdaFunc;
 use crate::plans::NotExpr;
 use crate::plans::OrExpr;
 use crate::BindContext;
@@ -36,6 +39,24 @@ impl<'a> WindowChecker<'a> {
         Self { bind_context }
     }
 
+    /// Check the predicate of `QUALIFY` clause and replace the window functions
+    /// in it with the columns produced by window evaluation.
+    pub fn resolve_qualify(&self, scalar: &ScalarExpr, span: Span) -> Result<ScalarExpr> {
+        if self.bind_context.windows.window_functions.is_empty() {
+            return Err(ErrorCode::SemanticError(
+                "QUALIFY clause requires at least one window function in SELECT list or QUALIFY clause",
+            )
+            .set_span(span));
+        }
+
+        if self.bind_context.in_grouping {
+            let grouping_checker = GroupingChecker::new(self.bind_context);
+            grouping_checker.resolve(scalar, span)
+        } else {
+            self.resolve(scalar)
+        }
+    }
+
     pub fn resolve(&self, scalar: &ScalarExpr) -> Result<ScalarExpr> {
         match scalar {
             ScalarExpr::BoundColumnRef(_)
@@ -75,6 +96,22 @@ impl<'a> WindowChecker<'a> {
                 }
                 .into())
             }
+            ScalarExpr::LambdaFunction(lambda) => {
+                let args = lambda
+                    .args
+                    .iter()
+                    .map(|arg| self.resolve(arg))
+                    .collect::<Result<Vec<ScalarExpr>>>()?;
+                Ok(LambdaFunc {
+                    span: lambda.span,
+                    func_name: lambda.func_name.clone(),
+                    args,
+                    lambda_expr: lambda.lambda_expr.clone(),
+                    lambda_display: lambda.lambda_display.clone(),
+                    return_type: lambda.return_type.clone(),
+                }
+                .into())
+            }
             ScalarExpr::CastExpr(cast) => Ok(CastExpr {
                 span: cast.span,
                 is_try: cast.is_try,
@@ -0,0 +1,166 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! this module reads actions from checkpoint files
+//!
+//! each row of a checkpoint holds one action, in the column named after the action.
+//! rows are converted to the json form of actions, the same as in commit files.
+
+use std::io::Cursor;
+
+use common_arrow::arrow::array::Array;
+use common_arrow::arrow::array::BooleanArray;
+use common_arrow::arrow::array::ListArray;
+use common_arrow::arrow::array::MapArray;
+use common_arrow::arrow::array::PrimitiveArray;
+use common_arrow::arrow::array::StructArray;
+use common_arrow::arrow::array::Utf8Array;
+use common_arrow::arrow::datatypes::DataType as ArrowDataType;
+use common_arrow::arrow::io::parquet::read::infer_schema;
+use common_arrow::arrow::io::parquet::read::{self as pread};
+use common_arrow::parquet::read::read_metadata;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use opendal::Operator;
+use serde_json::Map;
+use serde_json::Value;
+
+use crate::delta_log::Action;
+
+/// actions needed for resolving the active files,
+/// `remove` is not needed for files removed are not in checkpoints.
+const CHECKPOINT_ACTIONS: [&str; 3] = ["add", "metaData", "protocol"];
+
+/// read actions of a checkpoint file
+#[async_backtrace::framed]
+pub(crate) async fn read_checkpoint(op: &Operator, path: &str) -> Result<Vec<Action>> {
+    let data = op.read(path).await?;
+    let mut reader = Cursor::new(data);
+    let meta = read_metadata(&mut reader)?;
+    let arrow_schema =
+        infer_schema(&meta)?.filter(|_, field| CHECKPOINT_ACTIONS.contains(&field.name.as_str()));
+    let names = arrow_schema
+        .fields
+        .iter()
+        .map(|f| f.name.clone())
+        .collect::<Vec<_>>();
+
+    let mut actions = vec![];
+    let chunks = pread::FileReader::new(reader, meta.row_groups, arrow_schema, None, None, None);
+    for chunk in chunks {
+        let chunk = chunk?;
+        for row in 0..chunk.len() {
+            let mut action = Map::new();
+            for (name, array) in names.iter().zip(chunk.arrays()) {
+                if !array.is_null(row) {
+                    action.insert(name.clone(), array_value_to_json(array.as_ref(), row));
+                }
+            }
+            if action.is_empty() {
+                continue;
+            }
+            let action = serde_json::from_value(Value::Object(action)).map_err(|e| {
+                ErrorCode::ReadTableDataError(format!("invalid action in {path}: {e:?}"))
+            })?;
+            actions.push(action);
+        }
+    }
+    Ok(actions)
+}
+
+/// convert a value of arrow array to json
+///
+/// values of types not used by actions are converted to null.
+fn array_value_to_json(array: &dyn Array, row: usize) -> Value {
+    if array.is_null(row) {
+        return Value::Null;
+    }
+
+    macro_rules! primitive {
+        ($ty:ty) => {
+            array
+                .as_any()
+                .downcast_ref::<PrimitiveArray<$ty>>()
+                .map(|a| Value::from(a.value(row)))
+        };
+    }
+
+    let value = match array.data_type().to_logical_type() {
+        ArrowDataType::Boolean => array
+            .as_any()
+            .downcast_ref::<BooleanArray>()
+            .map(|a| Value::from(a.value(row))),
+        ArrowDataType::Int8 => primitive!(i8),
+        ArrowDataType::Int16 => primitive!(i16),
+        ArrowDataType::Int32 => primitive!(i32),
+        ArrowDataType::Int64 => primitive!(i64),
+        ArrowDataType::Float32 => primitive!(f32),
+        ArrowDataType::Float64 => primitive!(f64),
+        ArrowDataType::Utf8 => array
+            .as_any()
+            .downcast_ref::<Utf8Array<i32>>()
+            .map(|a| Value::from(a.value(row))),
+        ArrowDataType::LargeUtf8 => array
+            .as_any()
+            .downcast_ref::<Utf8Array<i64>>()
+            .map(|a| Value::from(a.value(row))),
+        ArrowDataType::Struct(fields) => array.as_any().downcast_ref::<StructArray>().map(|a| {
+            let object = fields
+                .iter()
+                .zip(a.values())
+                .map(|(f, v)| (f.name.clone(), array_value_to_json(v.as_ref(), row)))
+                .collect::<Map<_, _>>();
+            Value::Object(object)
+        }),
+        ArrowDataType::List(_) => array
+            .as_any()
+            .downcast_ref::<ListArray<i32>>()
+            .map(|a| list_to_json(a.value(row).as_ref())),
+        ArrowDataType::LargeList(_) => array
+            .as_any()
+            .downcast_ref::<ListArray<i64>>()
+            .map(|a| list_to_json(a.value(row).as_ref())),
+        ArrowDataType::Map(_, _) => array
+            .as_any()
+            .downcast_ref::<MapArray>()
+            .and_then(|a| map_to_json(a.value(row).as_ref())),
+        _ => None,
+    };
+    value.unwrap_or(Value::Null)
+}
+
+fn list_to_json(values: &dyn Array) -> Value {
+    Value::Array(
+        (0..values.len())
+            .map(|i| array_value_to_json(values, i))
+            .collect(),
+    )
+}
+
+/// maps are converted to objects, their keys must be strings
+fn map_to_json(entries: &dyn Array) -> Option<Value> {
+    let entries = entries.as_any().downcast_ref::<StructArray>()?;
+    let (keys, values) = match entries.values() {
+        [keys, values] => (keys, values),
+        _ => return None,
+    };
+
+    let mut object = Map::new();
+    for i in 0..entries.len() {
+        if let Value::String(key) = array_value_to_json(keys.as_ref(), i) {
+            object.insert(key, array_value_to_json(values.as_ref(), i));
+        }
+    }
+    Some(Value::Object(object))
+}
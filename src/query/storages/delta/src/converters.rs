@@ -0,0 +1,446 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! this module is used for converting delta lake schemas, statistics and other metadata
+//! to databend
+
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use chrono::Utc;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::serialize::uniform_date;
+use common_expression::types::decimal::DecimalSize;
+use common_expression::types::number::NumberScalar;
+use common_expression::types::number::F32;
+use common_expression::types::number::F64;
+use common_expression::types::DecimalDataType;
+use common_expression::types::NumberDataType;
+use common_expression::Scalar;
+use common_expression::TableDataType;
+use common_expression::TableField;
+use common_expression::TableSchema;
+use common_meta_app::schema::TableMeta;
+use common_meta_app::storage::StorageParams;
+use serde::Deserialize;
+
+/// a struct type in the `schemaString` of delta table metadata
+#[derive(Debug, Deserialize)]
+struct StructType {
+    fields: Vec<StructField>,
+}
+
+#[derive(Debug, Deserialize)]
+struct StructField {
+    name: String,
+    #[serde(rename = "type")]
+    data_type: DeltaDataType,
+    nullable: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum DeltaDataType {
+    /// primitive types are simply their names, e.g. `long` or `decimal(10,2)`
+    Primitive(String),
+    Complex(ComplexType),
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(tag = "type", rename_all = "lowercase")]
+enum ComplexType {
+    Struct {
+        fields: Vec<StructField>,
+    },
+    #[serde(rename_all = "camelCase")]
+    Array {
+        element_type: Box<DeltaDataType>,
+        contains_null: bool,
+    },
+    #[serde(rename_all = "camelCase")]
+    Map {
+        key_type: Box<DeltaDataType>,
+        value_type: Box<DeltaDataType>,
+        value_contains_null: bool,
+    },
+}
+
+/// generate TableMeta from the schema of delta table
+pub(crate) fn meta_delta_to_databend(
+    catalog: &str,
+    storage_params: &StorageParams,
+    schema: TableSchema,
+) -> TableMeta {
+    TableMeta {
+        schema: schema.into(),
+        catalog: catalog.to_string(),
+        engine: "delta".to_string(),
+        created_on: Utc::now(),
+        storage_params: Some(storage_params.clone()),
+        ..Default::default()
+    }
+}
+
+/// generate databend TableSchema from the `schemaString` of delta table metadata
+///
+/// partition columns are not stored in data files,
+/// they are placed after the other columns, in the order of `partition_columns`.
+pub(crate) fn schema_delta_to_databend(
+    schema_string: &str,
+    partition_columns: &[String],
+) -> Result<TableSchema> {
+    let schema: StructType = serde_json::from_str(schema_string)
+        .map_err(|e| ErrorCode::ReadTableDataError(format!("invalid delta table schema: {e:?}")))?;
+    let (partition_fields, mut fields): (Vec<_>, Vec<_>) = schema
+        .fields
+        .iter()
+        .partition(|f| partition_columns.contains(&f.name));
+    for column in partition_columns {
+        match partition_fields.iter().find(|f| &f.name == column) {
+            Some(field) => fields.push(field),
+            None => {
+                return Err(ErrorCode::ReadTableDataError(format!(
+                    "partition column {column} is not in the delta table schema"
+                )));
+            }
+        }
+    }
+    let fields = fields
+        .into_iter()
+        .map(struct_field_delta_to_databend)
+        .collect::<Result<Vec<_>>>()?;
+    Ok(TableSchema::new(fields))
+}
+
+fn struct_field_delta_to_databend(field: &StructField) -> Result<TableField> {
+    // column names are lower cased, the same as what parquet reader does
+    let name = &field.name.to_lowercase();
+    let ty = type_delta_to_databend(&field.data_type)?;
+    if field.nullable {
+        Ok(TableField::new(name, ty.wrap_nullable()))
+    } else {
+        Ok(TableField::new(name, ty))
+    }
+}
+
+fn type_delta_to_databend(ty: &DeltaDataType) -> Result<TableDataType> {
+    let ty = match ty {
+        DeltaDataType::Primitive(name) => match name.as_str() {
+            "boolean" => TableDataType::Boolean,
+            "byte" => TableDataType::Number(NumberDataType::Int8),
+            "short" => TableDataType::Number(NumberDataType::Int16),
+            "integer" => TableDataType::Number(NumberDataType::Int32),
+            "long" => TableDataType::Number(NumberDataType::Int64),
+            "float" => TableDataType::Number(NumberDataType::Float32),
+            "double" => TableDataType::Number(NumberDataType::Float64),
+            "string" | "binary" => TableDataType::String,
+            "date" => TableDataType::Date,
+            "timestamp" | "timestamp_ntz" => TableDataType::Timestamp,
+            decimal if decimal.starts_with("decimal(") => {
+                let size = decimal["decimal(".len()..]
+                    .trim_end_matches(')')
+                    .split_once(',')
+                    .and_then(|(p, s)| Some((p.trim().parse().ok()?, s.trim().parse().ok()?)));
+                match size {
+                    Some((precision, scale)) => {
+                        TableDataType::Decimal(DecimalDataType::from_size(DecimalSize {
+                            precision,
+                            scale,
+                        })?)
+                    }
+                    None => {
+                        return Err(ErrorCode::ReadTableDataError(format!(
+                            "invalid delta data type {decimal}"
+                        )));
+                    }
+                }
+            }
+            other => {
+                return Err(ErrorCode::Unimplemented(format!(
+                    "delta data type {other} is not supported yet"
+                )));
+            }
+        },
+        DeltaDataType::Complex(ComplexType::Struct { fields }) => {
+            let fields = fields
+                .iter()
+                .map(struct_field_delta_to_databend)
+                .collect::<Result<Vec<_>>>()?;
+            TableDataType::Tuple {
+                fields_name: fields.iter().map(|f| f.name().clone()).collect(),
+                fields_type: fields.iter().map(|f| f.data_type().clone()).collect(),
+            }
+        }
+        DeltaDataType::Complex(ComplexType::Array {
+            element_type,
+            contains_null,
+        }) => {
+            let element_type = type_delta_to_databend(element_type)?;
+            if *contains_null {
+                TableDataType::Array(Box::new(element_type.wrap_nullable()))
+            } else {
+                TableDataType::Array(Box::new(element_type))
+            }
+        }
+        DeltaDataType::Complex(ComplexType::Map {
+            key_type,
+            value_type,
+            value_contains_null,
+        }) => {
+            let key_type = type_delta_to_databend(key_type)?;
+            let value_type = type_delta_to_databend(value_type)?;
+            let value_type = if *value_contains_null {
+                value_type.wrap_nullable()
+            } else {
+                value_type
+            };
+            TableDataType::Map(Box::new(TableDataType::Tuple {
+                fields_name: vec!["key".to_string(), "value".to_string()],
+                fields_type: vec![key_type, value_type],
+            }))
+        }
+    };
+    Ok(ty)
+}
+
+/// convert a value in the `minValues` or `maxValues` of file statistics to a databend scalar
+///
+/// `None` is returned for types not supported. Strings are truncated
+/// and timestamps are in milliseconds in statistics, they are not used.
+pub(crate) fn stat_delta_to_databend(
+    ty: &TableDataType,
+    value: &serde_json::Value,
+) -> Option<Scalar> {
+    let scalar = match ty.remove_nullable() {
+        TableDataType::Boolean => Scalar::Boolean(value.as_bool()?),
+        TableDataType::Number(NumberDataType::Int8) => {
+            Scalar::Number(NumberScalar::Int8(value.as_i64()?.try_into().ok()?))
+        }
+        TableDataType::Number(NumberDataType::Int16) => {
+            Scalar::Number(NumberScalar::Int16(value.as_i64()?.try_into().ok()?))
+        }
+        TableDataType::Number(NumberDataType::Int32) => {
+            Scalar::Number(NumberScalar::Int32(value.as_i64()?.try_into().ok()?))
+        }
+        TableDataType::Number(NumberDataType::Int64) => {
+            Scalar::Number(NumberScalar::Int64(value.as_i64()?))
+        }
+        TableDataType::Number(NumberDataType::Float32) => {
+            Scalar::Number(NumberScalar::Float32(F32::from(value.as_f64()? as f32)))
+        }
+        TableDataType::Number(NumberDataType::Float64) => {
+            Scalar::Number(NumberScalar::Float64(F64::from(value.as_f64()?)))
+        }
+        // days from unix epoch
+        TableDataType::Date => {
+            let date = NaiveDate::parse_from_str(value.as_str()?, "%Y-%m-%d").ok()?;
+            let epoch = NaiveDate::from_ymd_opt(1970, 1, 1)?;
+            Scalar::Date((date - epoch).num_days() as i32)
+        }
+        _ => return None,
+    };
+    Some(scalar)
+}
+
+/// convert a value in the `partitionValues` of `add` actions to a databend scalar
+///
+/// values are serialized as strings, a missing or empty value is null.
+pub(crate) fn partition_value_delta_to_databend(
+    ty: &TableDataType,
+    value: Option<&str>,
+) -> Result<Scalar> {
+    let value = match value {
+        Some(value) if !value.is_empty() => value,
+        _ if ty.is_nullable() => return Ok(Scalar::Null),
+        _ => {
+            return Err(ErrorCode::ReadTableDataError(
+                "null partition value of a non-nullable column",
+            ));
+        }
+    };
+    let invalid = || ErrorCode::ReadTableDataError(format!("invalid partition value {value}"));
+    let scalar = match ty.remove_nullable() {
+        TableDataType::Boolean => Scalar::Boolean(value.parse().map_err(|_| invalid())?),
+        TableDataType::Number(NumberDataType::Int8) => {
+            Scalar::Number(NumberScalar::Int8(value.parse().map_err(|_| invalid())?))
+        }
+        TableDataType::Number(NumberDataType::Int16) => {
+            Scalar::Number(NumberScalar::Int16(value.parse().map_err(|_| invalid())?))
+        }
+        TableDataType::Number(NumberDataType::Int32) => {
+            Scalar::Number(NumberScalar::Int32(value.parse().map_err(|_| invalid())?))
+        }
+        TableDataType::Number(NumberDataType::Int64) => {
+            Scalar::Number(NumberScalar::Int64(value.parse().map_err(|_| invalid())?))
+        }
+        TableDataType::Number(NumberDataType::Float32) => {
+            Scalar::Number(NumberScalar::Float32(value.parse().map_err(|_| invalid())?))
+        }
+        TableDataType::Number(NumberDataType::Float64) => {
+            Scalar::Number(NumberScalar::Float64(value.parse().map_err(|_| invalid())?))
+        }
+        TableDataType::String => Scalar::String(value.as_bytes().to_vec()),
+        // days from unix epoch
+        TableDataType::Date => {
+            let date = NaiveDate::parse_from_str(value, "%Y-%m-%d").map_err(|_| invalid())?;
+            Scalar::Date(uniform_date(date))
+        }
+        // microseconds from unix epoch
+        TableDataType::Timestamp => {
+            let ts = NaiveDateTime::parse_from_str(value, "%Y-%m-%d %H:%M:%S%.f")
+                .map_err(|_| invalid())?;
+            Scalar::Timestamp(ts.timestamp_micros())
+        }
+        other => {
+            return Err(ErrorCode::Unimplemented(format!(
+                "partition columns of type {other} are not supported yet"
+            )));
+        }
+    };
+    Ok(scalar)
+}
+
+#[cfg(test)]
+mod convert_test {
+    use super::*;
+
+    #[test]
+    fn test_schema_delta_to_databend() {
+        let schema_string = r#"{"type":"struct","fields":[
+            {"name":"ID","type":"long","nullable":false,"metadata":{}},
+            {"name":"price","type":"decimal(10,2)","nullable":true,"metadata":{}},
+            {"name":"tags","type":{"type":"array","elementType":"string","containsNull":true},"nullable":true,"metadata":{}},
+            {"name":"attrs","type":{"type":"map","keyType":"string","valueType":"integer","valueContainsNull":false},"nullable":true,"metadata":{}},
+            {"name":"point","type":{"type":"struct","fields":[{"name":"x","type":"double","nullable":false,"metadata":{}}]},"nullable":false,"metadata":{}}
+        ]}"#;
+
+        let schema = schema_delta_to_databend(schema_string, &[]).unwrap();
+        let expected = TableSchema::new(vec![
+            TableField::new("id", TableDataType::Number(NumberDataType::Int64)),
+            TableField::new(
+                "price",
+                TableDataType::Decimal(
+                    DecimalDataType::from_size(DecimalSize {
+                        precision: 10,
+                        scale: 2,
+                    })
+                    .unwrap(),
+                )
+                .wrap_nullable(),
+            ),
+            TableField::new(
+                "tags",
+                TableDataType::Array(Box::new(TableDataType::String.wrap_nullable()))
+                    .wrap_nullable(),
+            ),
+            TableField::new(
+                "attrs",
+                TableDataType::Map(Box::new(TableDataType::Tuple {
+                    fields_name: vec!["key".to_string(), "value".to_string()],
+                    fields_type: vec![
+                        TableDataType::String,
+                        TableDataType::Number(NumberDataType::Int32),
+                    ],
+                }))
+                .wrap_nullable(),
+            ),
+            TableField::new("point", TableDataType::Tuple {
+                fields_name: vec!["x".to_string()],
+                fields_type: vec![TableDataType::Number(NumberDataType::Float64)],
+            }),
+        ]);
+        assert_eq!(schema, expected);
+
+        let unsupported = r#"{"type":"struct","fields":[{"name":"t","type":"void","nullable":true,"metadata":{}}]}"#;
+        assert!(schema_delta_to_databend(unsupported, &[]).is_err());
+    }
+
+    #[test]
+    fn test_partitioned_schema_delta_to_databend() {
+        let schema_string = r#"{"type":"struct","fields":[
+            {"name":"dt","type":"date","nullable":true,"metadata":{}},
+            {"name":"id","type":"long","nullable":true,"metadata":{}},
+            {"name":"region","type":"string","nullable":true,"metadata":{}}
+        ]}"#;
+        let partition_columns = vec!["region".to_string(), "dt".to_string()];
+
+        let schema = schema_delta_to_databend(schema_string, &partition_columns).unwrap();
+        let names = schema
+            .fields()
+            .iter()
+            .map(|f| f.name().as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["id", "region", "dt"]);
+
+        let missing = vec!["other".to_string()];
+        assert!(schema_delta_to_databend(schema_string, &missing).is_err());
+    }
+
+    #[test]
+    fn test_partition_value_delta_to_databend() {
+        let int = TableDataType::Number(NumberDataType::Int32).wrap_nullable();
+        assert_eq!(
+            partition_value_delta_to_databend(&int, Some("42")).unwrap(),
+            Scalar::Number(NumberScalar::Int32(42))
+        );
+        assert_eq!(
+            partition_value_delta_to_databend(&int, None).unwrap(),
+            Scalar::Null
+        );
+        assert_eq!(
+            partition_value_delta_to_databend(&int, Some("")).unwrap(),
+            Scalar::Null
+        );
+        assert!(partition_value_delta_to_databend(&int, Some("a")).is_err());
+
+        let string = TableDataType::String;
+        assert_eq!(
+            partition_value_delta_to_databend(&string, Some("abc")).unwrap(),
+            Scalar::String(b"abc".to_vec())
+        );
+        assert!(partition_value_delta_to_databend(&string, None).is_err());
+
+        assert_eq!(
+            partition_value_delta_to_databend(&TableDataType::Date, Some("1970-01-11")).unwrap(),
+            Scalar::Date(10)
+        );
+        assert_eq!(
+            partition_value_delta_to_databend(
+                &TableDataType::Timestamp,
+                Some("1970-01-01 00:00:01.5")
+            )
+            .unwrap(),
+            Scalar::Timestamp(1_500_000)
+        );
+    }
+
+    #[test]
+    fn test_stat_delta_to_databend() {
+        let int = TableDataType::Number(NumberDataType::Int32).wrap_nullable();
+        assert_eq!(
+            stat_delta_to_databend(&int, &serde_json::json!(42)),
+            Some(Scalar::Number(NumberScalar::Int32(42)))
+        );
+        assert_eq!(stat_delta_to_databend(&int, &serde_json::json!("42")), None);
+
+        assert_eq!(
+            stat_delta_to_databend(&TableDataType::Date, &serde_json::json!("1970-01-11")),
+            Some(Scalar::Date(10))
+        );
+        assert_eq!(
+            stat_delta_to_databend(&TableDataType::String, &serde_json::json!("abc")),
+            None
+        );
+    }
+}
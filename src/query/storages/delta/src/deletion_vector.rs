@@ -0,0 +1,338 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! this module reads deletion vectors of data files
+//!
+//! a deletion vector is a 64-bit roaring bitmap of deleted row positions,
+//! stored inline in the log, or in a `.bin` file under the table directory.
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use opendal::Operator;
+use serde::Deserialize;
+use uuid::Uuid;
+
+/// magic number of deletion vectors serialized as portable `RoaringBitmapArray`
+const DV_MAGIC_NUMBER: u32 = 1681511377;
+
+/// cookie of 32-bit roaring bitmaps without run containers
+const SERIAL_COOKIE_NO_RUNCONTAINER: u32 = 12346;
+/// cookie of 32-bit roaring bitmaps with run containers
+const SERIAL_COOKIE: u16 = 12347;
+/// bitmaps with less containers and run containers have no offset header
+const NO_OFFSET_THRESHOLD: usize = 4;
+/// containers with more values are stored as bitmaps
+const ARRAY_CONTAINER_MAX_SIZE: usize = 4096;
+
+/// characters of Z85 encoding, in the order of their values
+const Z85_CHARS: &[u8] =
+    b"0123456789abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ.-:+=^!/*?&<>()[]{}@%$#";
+
+/// the `deletionVector` field of `add` and `remove` actions
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct DeletionVectorDescriptor {
+    /// `u` for relative paths, `i` for inline and `p` for absolute paths
+    pub storage_type: String,
+    pub path_or_inline_dv: String,
+    /// start of the deletion vector in the file, absent for inline ones
+    pub offset: Option<i32>,
+    /// size of the serialized deletion vector
+    pub size_in_bytes: i32,
+    /// number of deleted rows
+    pub cardinality: i64,
+}
+
+impl DeletionVectorDescriptor {
+    /// identifies the deletion vector of a file in the log
+    pub fn unique_id(&self) -> String {
+        match self.offset {
+            Some(offset) => format!("{}{}@{}", self.storage_type, self.path_or_inline_dv, offset),
+            None => format!("{}{}", self.storage_type, self.path_or_inline_dv),
+        }
+    }
+
+    /// positions of deleted rows, in ascending order
+    #[async_backtrace::framed]
+    pub async fn read_deleted_rows(&self, op: &Operator) -> Result<Vec<u64>> {
+        let size = self.size_in_bytes as usize;
+        let data = match self.storage_type.as_str() {
+            "i" => {
+                let mut data = z85_decode(&self.path_or_inline_dv)?;
+                if data.len() < size {
+                    return Err(invalid_dv("inline data is too short"));
+                }
+                data.truncate(size);
+                data
+            }
+            "u" => {
+                // each deletion vector is stored as its size, data and checksum,
+                // all in big endian.
+                let path = self.relative_path()?;
+                let offset = self.offset.unwrap_or(1) as u64;
+                let bytes = op
+                    .range_read(&path, offset..offset + 4 + size as u64)
+                    .await?;
+                let stored_size = u32::from_be_bytes(bytes[..4].try_into().unwrap()) as usize;
+                if stored_size != size || bytes.len() != 4 + size {
+                    return Err(invalid_dv(format!(
+                        "size of {path} at {offset} is {stored_size}, but {size} is expected",
+                    )));
+                }
+                bytes[4..].to_vec()
+            }
+            "p" => {
+                return Err(ErrorCode::Unimplemented(format!(
+                    "deletion vectors with absolute paths are not supported yet: {}",
+                    self.path_or_inline_dv
+                )));
+            }
+            other => {
+                return Err(invalid_dv(format!("unknown storage type {other}")));
+            }
+        };
+
+        let rows = deserialize_deleted_rows(&data)?;
+        if rows.len() as i64 != self.cardinality {
+            return Err(invalid_dv(format!(
+                "{} rows are deleted, but cardinality is {}",
+                rows.len(),
+                self.cardinality
+            )));
+        }
+        Ok(rows)
+    }
+
+    /// the path relative to the table root of deletion vectors with storage type `u`
+    ///
+    /// the last 20 characters are the Z85 encoded UUID of the file name,
+    /// and the characters before are the name of the sub directory.
+    fn relative_path(&self) -> Result<String> {
+        let encoded = &self.path_or_inline_dv;
+        if encoded.len() < 20 {
+            return Err(invalid_dv(format!("invalid path {encoded}")));
+        }
+        let (prefix, uuid) = encoded.split_at(encoded.len() - 20);
+        let uuid = Uuid::from_slice(&z85_decode(uuid)?)
+            .map_err(|e| invalid_dv(format!("invalid path {encoded}: {e}")))?;
+        let file_name = format!("deletion_vector_{}.bin", uuid.hyphenated());
+        if prefix.is_empty() {
+            Ok(file_name)
+        } else {
+            Ok(format!("{prefix}/{file_name}"))
+        }
+    }
+}
+
+fn invalid_dv(msg: impl std::fmt::Display) -> ErrorCode {
+    ErrorCode::ReadTableDataError(format!("invalid deletion vector: {msg}"))
+}
+
+/// decode Z85 encoded data, of which the length must be a multiple of 5
+fn z85_decode(encoded: &str) -> Result<Vec<u8>> {
+    let encoded = encoded.as_bytes();
+    if encoded.len() % 5 != 0 {
+        return Err(invalid_dv(
+            "length of Z85 encoded data is not a multiple of 5",
+        ));
+    }
+
+    let mut decoded = Vec::with_capacity(encoded.len() / 5 * 4);
+    for chunk in encoded.chunks(5) {
+        let mut value: u32 = 0;
+        for c in chunk {
+            let digit = Z85_CHARS
+                .iter()
+                .position(|z| z == c)
+                .ok_or_else(|| invalid_dv(format!("invalid Z85 character {}", *c as char)))?;
+            value = value
+                .checked_mul(85)
+                .and_then(|v| v.checked_add(digit as u32))
+                .ok_or_else(|| invalid_dv("overflow in Z85 encoded data"))?;
+        }
+        decoded.extend_from_slice(&value.to_be_bytes());
+    }
+    Ok(decoded)
+}
+
+/// a little endian reader over serialized bitmaps
+struct BitmapReader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> BitmapReader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        if self.pos + len > self.data.len() {
+            return Err(invalid_dv("unexpected end of data"));
+        }
+        let bytes = &self.data[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    fn u16(&mut self) -> Result<u16> {
+        Ok(u16::from_le_bytes(self.take(2)?.try_into().unwrap()))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        Ok(u32::from_le_bytes(self.take(4)?.try_into().unwrap()))
+    }
+
+    fn u64(&mut self) -> Result<u64> {
+        Ok(u64::from_le_bytes(self.take(8)?.try_into().unwrap()))
+    }
+}
+
+/// deserialize deleted rows from a deletion vector
+///
+/// the data is the magic number followed by a 64-bit roaring bitmap in the portable
+/// format, which is a list of 32-bit bitmaps with the high 32 bits of their values.
+pub(crate) fn deserialize_deleted_rows(data: &[u8]) -> Result<Vec<u64>> {
+    let mut reader = BitmapReader { data, pos: 0 };
+    let magic = reader.u32()?;
+    if magic != DV_MAGIC_NUMBER {
+        return Err(invalid_dv(format!("unexpected magic number {magic}")));
+    }
+
+    let mut rows = vec![];
+    let num_bitmaps = reader.u64()?;
+    for _ in 0..num_bitmaps {
+        let high = (reader.u32()? as u64) << 32;
+        deserialize_bitmap(&mut reader, |low| rows.push(high | low as u64))?;
+    }
+    Ok(rows)
+}
+
+/// deserialize a 32-bit roaring bitmap in the portable format,
+/// values are visited in ascending order.
+fn deserialize_bitmap(reader: &mut BitmapReader, mut visit: impl FnMut(u32)) -> Result<()> {
+    let cookie = reader.u32()?;
+    let (num_containers, run_flags) = if cookie == SERIAL_COOKIE_NO_RUNCONTAINER {
+        (reader.u32()? as usize, None)
+    } else if cookie as u16 == SERIAL_COOKIE {
+        let num_containers = (cookie >> 16) as usize + 1;
+        let flags = reader.take((num_containers + 7) / 8)?;
+        (num_containers, Some(flags))
+    } else {
+        return Err(invalid_dv(format!(
+            "unknown roaring bitmap cookie {cookie}"
+        )));
+    };
+
+    // keys and cardinalities of containers
+    let mut headers = Vec::with_capacity(num_containers);
+    for _ in 0..num_containers {
+        let key = reader.u16()?;
+        let cardinality = reader.u16()? as usize + 1;
+        headers.push((key, cardinality));
+    }
+
+    // offsets of containers are skipped, for containers are read sequentially
+    if run_flags.is_none() || num_containers >= NO_OFFSET_THRESHOLD {
+        reader.take(num_containers * 4)?;
+    }
+
+    for (idx, (key, cardinality)) in headers.into_iter().enumerate() {
+        let high = (key as u32) << 16;
+        let is_run = run_flags.map_or(false, |flags| flags[idx / 8] & (1 << (idx % 8)) != 0);
+        if is_run {
+            let num_runs = reader.u16()?;
+            for _ in 0..num_runs {
+                let start = reader.u16()? as u32;
+                let length = reader.u16()? as u32;
+                for low in start..=start + length {
+                    visit(high | low);
+                }
+            }
+        } else if cardinality <= ARRAY_CONTAINER_MAX_SIZE {
+            for _ in 0..cardinality {
+                visit(high | reader.u16()? as u32);
+            }
+        } else {
+            for (word_idx, word) in reader.take(8192)?.chunks(8).enumerate() {
+                let mut word = u64::from_le_bytes(word.try_into().unwrap());
+                while word != 0 {
+                    let bit = word.trailing_zeros();
+                    visit(high | (word_idx as u32 * 64 + bit));
+                    word &= word - 1;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod deletion_vector_test {
+    use super::*;
+
+    #[test]
+    fn test_z85_decode() {
+        // the example of the Z85 specification
+        let decoded = z85_decode("HelloWorld").unwrap();
+        assert_eq!(decoded, vec![
+            0x86, 0x4F, 0xD2, 0x6F, 0xB5, 0x59, 0xF7, 0x5B
+        ]);
+
+        assert!(z85_decode("Hello").is_ok());
+        assert!(z85_decode("Hell").is_err());
+        assert!(z85_decode("Hell~").is_err());
+    }
+
+    #[test]
+    fn test_relative_path() {
+        let dv = DeletionVectorDescriptor {
+            storage_type: "u".to_string(),
+            path_or_inline_dv: "ab^-aqEH.-t@S}K{vb[*k^".to_string(),
+            offset: Some(4),
+            size_in_bytes: 40,
+            cardinality: 6,
+        };
+        assert_eq!(
+            dv.relative_path().unwrap(),
+            "ab/deletion_vector_d2c639aa-8816-431a-aaf6-d3fe2512ff61.bin"
+        );
+        assert_eq!(dv.unique_id(), "uab^-aqEH.-t@S}K{vb[*k^@4");
+    }
+
+    #[test]
+    fn test_deserialize_deleted_rows() {
+        let mut data = DV_MAGIC_NUMBER.to_le_bytes().to_vec();
+        // two 32-bit bitmaps
+        data.extend_from_slice(&2u64.to_le_bytes());
+
+        // high bits 0, an array container [3, 5]
+        data.extend_from_slice(&0u32.to_le_bytes());
+        data.extend_from_slice(&SERIAL_COOKIE_NO_RUNCONTAINER.to_le_bytes());
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&[0, 0, 1, 0]);
+        data.extend_from_slice(&[0, 0, 0, 0]);
+        data.extend_from_slice(&[3, 0, 5, 0]);
+
+        // high bits 1, a run container [7, 9] with key 2
+        data.extend_from_slice(&1u32.to_le_bytes());
+        data.extend_from_slice(&(SERIAL_COOKIE as u32).to_le_bytes());
+        data.push(1);
+        data.extend_from_slice(&[2, 0, 2, 0]);
+        data.extend_from_slice(&[1, 0, 7, 0, 2, 0]);
+
+        let rows = deserialize_deleted_rows(&data).unwrap();
+        let high = 1u64 << 32 | 2 << 16;
+        assert_eq!(rows, vec![3, 5, high | 7, high | 8, high | 9]);
+
+        assert!(deserialize_deleted_rows(&data[..data.len() - 1]).is_err());
+        assert!(deserialize_deleted_rows(&data[4..]).is_err());
+    }
+}
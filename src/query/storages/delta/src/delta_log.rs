@@ -0,0 +1,412 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! this module replays the `_delta_log` of delta tables
+//!
+//! the log is made of commit files named `<version>.json`, each line of which
+//! is an action, and checkpoint files summarizing actions up to a version.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use futures::TryStreamExt;
+use opendal::Operator;
+use percent_encoding::percent_decode_str;
+use serde::Deserialize;
+
+use crate::checkpoint::read_checkpoint;
+use crate::deletion_vector::DeletionVectorDescriptor;
+
+/// directory of the transaction log, relative to the table root
+pub(crate) const DELTA_LOG_DIR: &str = "_delta_log/";
+
+/// reader features supported
+const SUPPORTED_READER_FEATURES: [&str; 3] =
+    ["deletionVectors", "timestampNtz", "vacuumProtocolCheck"];
+
+/// an action in the log, only one of the fields is present.
+///
+/// actions not needed for reading, e.g. `commitInfo` and `txn`, are ignored.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Action {
+    pub add: Option<Add>,
+    pub remove: Option<Remove>,
+    pub meta_data: Option<Metadata>,
+    pub protocol: Option<Protocol>,
+}
+
+/// a data file added to the table
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Add {
+    /// URI encoded path of the data file, relative to the table root
+    pub path: String,
+    #[serde(default)]
+    pub partition_values: HashMap<String, Option<String>>,
+    pub size: i64,
+    /// statistics of the data file in json
+    pub stats: Option<String>,
+    pub deletion_vector: Option<DeletionVectorDescriptor>,
+}
+
+/// a data file removed from the table
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Remove {
+    pub path: String,
+    pub deletion_vector: Option<DeletionVectorDescriptor>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Metadata {
+    pub id: String,
+    pub schema_string: String,
+    #[serde(default)]
+    pub partition_columns: Vec<String>,
+    #[serde(default)]
+    pub configuration: HashMap<String, Option<String>>,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct Protocol {
+    pub min_reader_version: i32,
+    /// features required by readers, for reader version 3
+    pub reader_features: Option<Vec<String>>,
+}
+
+/// the state of a delta table at a version
+#[derive(Clone, Debug)]
+pub(crate) struct DeltaSnapshot {
+    pub version: i64,
+    pub protocol: Protocol,
+    pub metadata: Metadata,
+    /// active data files of the table
+    pub files: Vec<Add>,
+}
+
+/// files of the log at a version
+#[derive(Default)]
+struct LogSegment {
+    commit: Option<String>,
+    /// parts of the checkpoint, by index of the part
+    checkpoint_parts: BTreeMap<u32, String>,
+    /// number of checkpoint parts expected
+    checkpoint_num_parts: u32,
+}
+
+impl LogSegment {
+    fn has_complete_checkpoint(&self) -> bool {
+        self.checkpoint_num_parts > 0
+            && self.checkpoint_parts.len() == self.checkpoint_num_parts as usize
+    }
+}
+
+/// the key of a log file, the version and the number of checkpoint parts
+///
+/// - `00000000000000000010.json`
+/// - `00000000000000000010.checkpoint.parquet`
+/// - `00000000000000000010.checkpoint.0000000001.0000000002.parquet`
+enum LogFile {
+    Commit(i64),
+    Checkpoint { version: i64, part: u32, parts: u32 },
+}
+
+impl LogFile {
+    fn parse(name: &str) -> Option<LogFile> {
+        let (version, rest) = name.split_once('.')?;
+        if version.len() != 20 {
+            return None;
+        }
+        let version = version.parse::<i64>().ok()?;
+        match rest.split('.').collect::<Vec<_>>().as_slice() {
+            ["json"] => Some(LogFile::Commit(version)),
+            ["checkpoint", "parquet"] => Some(LogFile::Checkpoint {
+                version,
+                part: 1,
+                parts: 1,
+            }),
+            ["checkpoint", part, parts, "parquet"] => Some(LogFile::Checkpoint {
+                version,
+                part: part.parse().ok()?,
+                parts: parts.parse().ok()?,
+            }),
+            _ => None,
+        }
+    }
+}
+
+/// replays actions, keeping the latest protocol, metadata and active files
+#[derive(Default)]
+struct LogReplay {
+    protocol: Option<Protocol>,
+    metadata: Option<Metadata>,
+    /// active files by their paths and unique ids of deletion vectors
+    files: HashMap<(String, Option<String>), Add>,
+}
+
+impl LogReplay {
+    fn apply(&mut self, action: Action) {
+        if let Some(protocol) = action.protocol {
+            self.protocol = Some(protocol);
+        }
+        if let Some(metadata) = action.meta_data {
+            self.metadata = Some(metadata);
+        }
+        if let Some(remove) = action.remove {
+            let dv = remove.deletion_vector.as_ref().map(|dv| dv.unique_id());
+            self.files.remove(&(remove.path, dv));
+        }
+        if let Some(add) = action.add {
+            let dv = add.deletion_vector.as_ref().map(|dv| dv.unique_id());
+            self.files.insert((add.path.clone(), dv), add);
+        }
+    }
+}
+
+impl DeltaSnapshot {
+    /// replay the log of table to the latest version
+    #[async_backtrace::framed]
+    pub async fn try_load(op: &Operator) -> Result<DeltaSnapshot> {
+        let mut segments: BTreeMap<i64, LogSegment> = BTreeMap::new();
+        let mut lister = op.list(DELTA_LOG_DIR).await?;
+        while let Some(entry) = lister.try_next().await? {
+            let path = format!("{DELTA_LOG_DIR}{}", entry.name());
+            match LogFile::parse(entry.name()) {
+                Some(LogFile::Commit(version)) => {
+                    segments.entry(version).or_default().commit = Some(path);
+                }
+                Some(LogFile::Checkpoint {
+                    version,
+                    part,
+                    parts,
+                }) => {
+                    let segment = segments.entry(version).or_default();
+                    segment.checkpoint_num_parts = parts;
+                    segment.checkpoint_parts.insert(part, path);
+                }
+                None => continue,
+            }
+        }
+
+        let version = match segments.keys().next_back() {
+            Some(version) => *version,
+            None => {
+                return Err(ErrorCode::ReadTableDataError(
+                    "no commits found in the delta log",
+                ));
+            }
+        };
+
+        // start from the latest complete checkpoint
+        let checkpoint = segments
+            .iter()
+            .rev()
+            .find(|(_, segment)| segment.has_complete_checkpoint())
+            .map(|(version, _)| *version);
+
+        let mut replay = LogReplay::default();
+        let first_commit = match checkpoint {
+            Some(checkpoint) => {
+                let parts = segments[&checkpoint].checkpoint_parts.values();
+                let actions = futures::future::try_join_all(
+                    parts.map(|path| async move { read_checkpoint(op, path).await }),
+                )
+                .await?;
+                for action in actions.into_iter().flatten() {
+                    replay.apply(action);
+                }
+                checkpoint + 1
+            }
+            None => 0,
+        };
+
+        let mut commits = Vec::with_capacity((version - first_commit + 1) as usize);
+        for v in first_commit..=version {
+            match segments.get(&v).and_then(|s| s.commit.as_ref()) {
+                Some(path) => commits.push(path),
+                None => {
+                    return Err(ErrorCode::ReadTableDataError(format!(
+                        "commit of version {v} is missing in the delta log"
+                    )));
+                }
+            }
+        }
+        let commits = futures::future::try_join_all(
+            commits
+                .into_iter()
+                .map(|path| async move { read_commit(op, path).await }),
+        )
+        .await?;
+        for action in commits.into_iter().flatten() {
+            replay.apply(action);
+        }
+
+        let (protocol, metadata) = match (replay.protocol, replay.metadata) {
+            (Some(protocol), Some(metadata)) => (protocol, metadata),
+            _ => {
+                return Err(ErrorCode::ReadTableDataError(format!(
+                    "protocol or metadata is missing in the delta log of version {version}"
+                )));
+            }
+        };
+
+        Ok(DeltaSnapshot {
+            version,
+            protocol,
+            metadata,
+            files: replay.files.into_values().collect(),
+        })
+    }
+
+    /// check if the table can be read
+    pub fn check_readable(&self) -> Result<()> {
+        let protocol = &self.protocol;
+        if protocol.min_reader_version > 3 {
+            return Err(ErrorCode::Unimplemented(format!(
+                "delta reader version {} is not supported yet",
+                protocol.min_reader_version
+            )));
+        }
+        for feature in protocol.reader_features.iter().flatten() {
+            if !SUPPORTED_READER_FEATURES.contains(&feature.as_str()) {
+                return Err(ErrorCode::Unimplemented(format!(
+                    "delta reader feature {feature} is not supported yet"
+                )));
+            }
+        }
+
+        let column_mapping = self
+            .metadata
+            .configuration
+            .get("delta.columnMapping.mode")
+            .cloned()
+            .flatten();
+        if matches!(column_mapping.as_deref(), Some(mode) if mode != "none") {
+            return Err(ErrorCode::Unimplemented(
+                "delta tables with column mapping are not supported yet",
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// read actions of a commit file
+#[async_backtrace::framed]
+async fn read_commit(op: &Operator, path: &str) -> Result<Vec<Action>> {
+    let data = op.read(path).await?;
+    data.split(|b| *b == b'\n')
+        .filter(|line| !line.iter().all(u8::is_ascii_whitespace))
+        .map(|line| {
+            serde_json::from_slice(line).map_err(|e| {
+                ErrorCode::ReadTableDataError(format!("invalid action in {path}: {e:?}"))
+            })
+        })
+        .collect()
+}
+
+impl Add {
+    /// path of the data file relative to the table root
+    pub fn relative_path(&self) -> Result<String> {
+        if self.path.contains("://") {
+            return Err(ErrorCode::Unimplemented(format!(
+                "data files with absolute paths are not supported yet: {}",
+                self.path
+            )));
+        }
+        let path = percent_decode_str(&self.path).decode_utf8().map_err(|e| {
+            ErrorCode::ReadTableDataError(format!("invalid path {}: {e}", self.path))
+        })?;
+        Ok(path.trim_start_matches('/').to_string())
+    }
+}
+
+#[cfg(test)]
+mod delta_log_test {
+    use super::*;
+
+    fn parse_actions(lines: &str) -> Vec<Action> {
+        lines
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn test_parse_log_file() {
+        assert!(matches!(
+            LogFile::parse("00000000000000000010.json"),
+            Some(LogFile::Commit(10))
+        ));
+        assert!(matches!(
+            LogFile::parse("00000000000000000010.checkpoint.parquet"),
+            Some(LogFile::Checkpoint {
+                version: 10,
+                part: 1,
+                parts: 1
+            })
+        ));
+        assert!(matches!(
+            LogFile::parse("00000000000000000010.checkpoint.0000000002.0000000003.parquet"),
+            Some(LogFile::Checkpoint {
+                version: 10,
+                part: 2,
+                parts: 3
+            })
+        ));
+        assert!(LogFile::parse("_last_checkpoint").is_none());
+        assert!(LogFile::parse("00000000000000000010.crc").is_none());
+        assert!(LogFile::parse("10.json").is_none());
+    }
+
+    #[test]
+    fn test_log_replay() {
+        let commit_0 = r#"{"commitInfo":{"timestamp":1680000000000,"operation":"WRITE"}}
+{"protocol":{"minReaderVersion":3,"minWriterVersion":7,"readerFeatures":["deletionVectors"],"writerFeatures":["deletionVectors"]}}
+{"metaData":{"id":"f4c5d1d1","format":{"provider":"parquet","options":{}},"schemaString":"{}","partitionColumns":[],"configuration":{},"createdTime":1680000000000}}
+{"add":{"path":"a.parquet","partitionValues":{},"size":10,"modificationTime":1680000000000,"dataChange":true}}
+{"add":{"path":"b%20c.parquet","partitionValues":{},"size":20,"modificationTime":1680000000000,"dataChange":true}}"#;
+        let commit_1 = r#"{"remove":{"path":"a.parquet","dataChange":true}}
+{"add":{"path":"a.parquet","partitionValues":{},"size":10,"modificationTime":1680000000001,"dataChange":true,"deletionVector":{"storageType":"i","pathOrInlineDv":"wi5b=000010000siXQKl0rr91000f55c8Xg0@@D72lkbi5=-{L","sizeInBytes":40,"cardinality":6}}}
+{"remove":{"path":"b%20c.parquet","dataChange":true}}"#;
+
+        let mut replay = LogReplay::default();
+        for action in parse_actions(commit_0) {
+            replay.apply(action);
+        }
+        assert_eq!(replay.files.len(), 2);
+        assert_eq!(replay.protocol.as_ref().unwrap().min_reader_version, 3);
+
+        for action in parse_actions(commit_1) {
+            replay.apply(action);
+        }
+        let files = replay.files.into_values().collect::<Vec<_>>();
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path, "a.parquet");
+        assert_eq!(files[0].deletion_vector.as_ref().unwrap().cardinality, 6);
+    }
+
+    #[test]
+    fn test_relative_path() {
+        let add: Add = serde_json::from_str(
+            r#"{"path":"b%20c%3Dd.parquet","partitionValues":{},"size":20,"modificationTime":0,"dataChange":true}"#,
+        )
+        .unwrap();
+        assert_eq!(add.relative_path().unwrap(), "b c=d.parquet");
+    }
+}
@@ -0,0 +1,72 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! This is the Delta Lake catalog support for databend.
+//!
+//! Like Iceberg, Delta Lake only offers tables, the hierarchy of databases
+//! is taken from directories of the remote storage.
+//!
+//! For example, accessing a delta catalog on `s3://bkt/path/to/delta`
+//! with following file tree:
+//! ```text
+//! /path/to/delta/
+//! ┝-- /path/to/delta/db0/
+//! |   ┝-- /path/to/delta/db0/tbl0/_delta_log/
+//! |   └-- /path/to/delta/db0/tbl1/_delta_log/
+//! └-- /path/to/delta/db1/    <- empty directory
+//! ```
+//!
+//! with the following SQL:
+//!
+//! ```sql
+//! CREATE CATALOG delta_ctl TYPE=DELTA CONNECTION=( URL='s3://bkt/path/to/delta' ... )
+//! ```
+//!
+//! This will create such database hierarchy:
+//! - catalog: delta_ctl
+//!     - database: db0
+//!         - table: tbl0
+//!         - table: tbl1
+//!     - database: db1
+//!
+//! Storages barely storing tables in the root directory can be accessed
+//! with the `FLATTEN=true` option, tables will be put in a database named `default`.
+//!
+//! # Reading
+//!
+//! The active files of the latest version are resolved by replaying the
+//! `_delta_log`, starting from the latest checkpoint. Data files are read
+//! by the parquet reader, rows removed by deletion vectors are skipped.
+//!
+//! Partitioned tables and tables with column mapping are not supported yet.
+
+/// the Delta Lake Catalog implementation
+mod catalog;
+/// reading checkpoint files
+mod checkpoint;
+/// data converters
+mod converters;
+/// database implementation
+mod database;
+/// reading deletion vectors
+mod deletion_vector;
+/// replaying the transaction log
+#[allow(unused)]
+mod delta_log;
+/// table implementation
+mod table;
+
+pub use catalog::DeltaCatalog;
+pub use catalog::DELTA_CATALOG;
+pub use table::DeltaTable;
@@ -0,0 +1,351 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use common_catalog::plan::DataSourceInfo;
+use common_catalog::plan::ParquetReadOptions;
+use common_catalog::plan::ParquetTableInfo;
+use common_catalog::plan::PartStatistics;
+use common_catalog::plan::Partitions;
+use common_catalog::plan::PushDownInfo;
+use common_catalog::table::Table;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::FieldIndex;
+use common_expression::Scalar;
+use common_functions::BUILTIN_FUNCTIONS;
+use common_meta_app::principal::StageInfo;
+use common_meta_app::schema::TableIdent;
+use common_meta_app::schema::TableInfo;
+use common_storage::DataOperator;
+use common_storage::StageFileInfo;
+use common_storage::StageFileStatus;
+use common_storage::StageFilesInfo;
+use common_storages_parquet::ParquetTable;
+use opendal::Operator;
+use serde::Deserialize;
+use storages_common_pruner::RangePrunerCreator;
+use storages_common_table_meta::meta::ColumnStatistics;
+use storages_common_table_meta::meta::StatisticsOfColumns;
+
+use crate::converters::meta_delta_to_databend;
+use crate::converters::partition_value_delta_to_databend;
+use crate::converters::schema_delta_to_databend;
+use crate::converters::stat_delta_to_databend;
+use crate::delta_log::Add;
+use crate::delta_log::DeltaSnapshot;
+
+/// statistics of a data file, recorded in the `stats` of `add` actions
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct FileStatistics {
+    #[serde(default)]
+    num_records: u64,
+    #[serde(default)]
+    min_values: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    max_values: HashMap<String, serde_json::Value>,
+    #[serde(default)]
+    null_count: HashMap<String, serde_json::Value>,
+}
+
+/// accessor wrapper as a table
+///
+/// the log is replayed to the latest version when the table is created.
+pub struct DeltaTable {
+    /// operator on the root of the table
+    operator: Operator,
+    /// the latest version of the table
+    snapshot: DeltaSnapshot,
+    /// table information
+    info: TableInfo,
+}
+
+impl DeltaTable {
+    /// create a new table on the table directory
+    #[async_backtrace::framed]
+    pub async fn try_create_table_from_read(
+        catalog: &str,
+        database: &str,
+        table_name: &str,
+        tbl_root: DataOperator,
+    ) -> Result<DeltaTable> {
+        let op = tbl_root.operator();
+        let snapshot = DeltaSnapshot::try_load(&op).await?;
+        let schema = schema_delta_to_databend(
+            &snapshot.metadata.schema_string,
+            &snapshot.metadata.partition_columns,
+        )?;
+
+        // construct table info
+        let info = TableInfo {
+            ident: TableIdent::new(0, 0),
+            desc: format!("DeltaTable: '{database}'.'{table_name}'"),
+            name: table_name.to_string(),
+            meta: meta_delta_to_databend(catalog, &tbl_root.params(), schema),
+            ..Default::default()
+        };
+
+        Ok(Self {
+            operator: op,
+            snapshot,
+            info,
+        })
+    }
+
+    /// if rows of any active file are deleted by deletion vectors
+    fn has_deletion_vectors(&self) -> bool {
+        self.snapshot
+            .files
+            .iter()
+            .any(|f| f.deletion_vector.is_some())
+    }
+
+    /// prune data files with the statistics recorded in the log
+    fn prune_data_files(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        push_downs: &Option<PushDownInfo>,
+    ) -> Result<Vec<&Add>> {
+        let files = self.snapshot.files.iter().collect::<Vec<_>>();
+        let filter = push_downs
+            .as_ref()
+            .and_then(|p| p.filter.as_ref().map(|f| f.as_expr(&BUILTIN_FUNCTIONS)));
+        if filter.is_none() {
+            return Ok(files);
+        }
+
+        let schema = self.info.schema();
+        let pruner =
+            RangePrunerCreator::try_create(ctx.get_function_context()?, &schema, filter.as_ref())?;
+
+        let mut kept = Vec::with_capacity(files.len());
+        for file in files {
+            if pruner.should_keep(&self.column_statistics(file)?) {
+                kept.push(file);
+            }
+        }
+        Ok(kept)
+    }
+
+    /// values of partition columns of a data file, keyed by field index
+    fn partition_values(&self, file: &Add) -> Result<HashMap<FieldIndex, Scalar>> {
+        let values = file
+            .partition_values
+            .iter()
+            .map(|(k, v)| (k.to_lowercase(), v.as_deref()))
+            .collect::<HashMap<_, _>>();
+
+        let schema = self.info.schema();
+        let mut partition_values = HashMap::new();
+        for column in &self.snapshot.metadata.partition_columns {
+            let name = column.to_lowercase();
+            let idx = schema.index_of(&name)?;
+            let value = values.get(&name).copied().flatten();
+            let scalar = partition_value_delta_to_databend(schema.field(idx).data_type(), value)?;
+            partition_values.insert(idx, scalar);
+        }
+        Ok(partition_values)
+    }
+
+    /// convert statistics of a data file to [`StatisticsOfColumns`]
+    ///
+    /// statistics are keyed by the original column names,
+    /// and only statistics of top level columns are used.
+    /// partition columns are not in the statistics, their values are used instead.
+    fn column_statistics(&self, file: &Add) -> Result<StatisticsOfColumns> {
+        let stats = match &file.stats {
+            Some(stats) => serde_json::from_str::<FileStatistics>(stats).unwrap_or_default(),
+            None => FileStatistics::default(),
+        };
+        let lower = |values: HashMap<String, serde_json::Value>| {
+            values
+                .into_iter()
+                .map(|(k, v)| (k.to_lowercase(), v))
+                .collect::<HashMap<_, _>>()
+        };
+        let min_values = lower(stats.min_values);
+        let max_values = lower(stats.max_values);
+        let null_count = lower(stats.null_count);
+
+        let schema = self.info.schema();
+        let mut column_stats = HashMap::new();
+        for (idx, field) in schema.fields().iter().enumerate() {
+            let (min, max) = match (min_values.get(field.name()), max_values.get(field.name())) {
+                (Some(min), Some(max)) => (min, max),
+                _ => continue,
+            };
+            let ty = field.data_type();
+            if let (Some(min), Some(max)) = (
+                stat_delta_to_databend(ty, min),
+                stat_delta_to_databend(ty, max),
+            ) {
+                let null_count = null_count
+                    .get(field.name())
+                    .and_then(|v| v.as_u64())
+                    .unwrap_or_default();
+                column_stats.insert(schema.column_id_of_index(idx)?, ColumnStatistics {
+                    min,
+                    max,
+                    null_count,
+                    in_memory_size: 0,
+                    distinct_of_values: None,
+                });
+            }
+        }
+
+        for (idx, value) in self.partition_values(file)? {
+            let null_count = if value.is_null() {
+                stats.num_records
+            } else {
+                0
+            };
+            column_stats.insert(schema.column_id_of_index(idx)?, ColumnStatistics {
+                min: value.clone(),
+                max: value,
+                null_count,
+                in_memory_size: 0,
+                distinct_of_values: Some(1),
+            });
+        }
+        Ok(column_stats)
+    }
+
+    /// read deletion vectors of data files, keyed by the relative paths of files
+    #[async_backtrace::framed]
+    async fn read_deleted_rows(&self, files: &[&Add]) -> Result<HashMap<String, Vec<u64>>> {
+        let deletes = futures::future::try_join_all(files.iter().filter_map(|file| {
+            let dv = file.deletion_vector.as_ref()?;
+            let op = self.operator.clone();
+            Some(async move {
+                let rows = dv.read_deleted_rows(&op).await?;
+                Ok::<_, ErrorCode>((file.relative_path()?, rows))
+            })
+        }))
+        .await?;
+        Ok(deletes.into_iter().collect())
+    }
+
+    /// data files are read by a parquet table rooted at the table directory
+    fn parquet_table_info(&self, files_to_read: Option<Vec<StageFileInfo>>) -> ParquetTableInfo {
+        let sp = self.info.meta.storage_params.clone().unwrap_or_default();
+        let stage_info = StageInfo::new_external_stage(sp, "/").with_stage_name(&self.info.desc);
+        ParquetTableInfo {
+            read_options: ParquetReadOptions::default(),
+            stage_info,
+            files_info: StageFilesInfo {
+                path: "/".to_string(),
+                files: None,
+                pattern: None,
+            },
+            table_info: self.info.clone(),
+            arrow_schema: self.info.schema().to_arrow(),
+            files_to_read,
+        }
+    }
+
+    #[async_backtrace::framed]
+    async fn do_read_partitions(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        push_downs: Option<PushDownInfo>,
+    ) -> Result<(PartStatistics, Partitions)> {
+        self.snapshot.check_readable()?;
+
+        let files = self.prune_data_files(ctx.clone(), &push_downs)?;
+        if files.is_empty() {
+            return Ok((PartStatistics::default(), Partitions::default()));
+        }
+
+        let files_to_read = files
+            .iter()
+            .map(|f| {
+                Ok(StageFileInfo {
+                    path: f.relative_path()?,
+                    size: f.size as u64,
+                    md5: None,
+                    last_modified: Default::default(),
+                    etag: None,
+                    status: StageFileStatus::NeedCopy,
+                    creator: None,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let deleted_rows = self.read_deleted_rows(&files).await?;
+        let partition_values = if self.snapshot.metadata.partition_columns.is_empty() {
+            HashMap::new()
+        } else {
+            files
+                .iter()
+                .map(|f| Ok((f.relative_path()?, self.partition_values(f)?)))
+                .collect::<Result<HashMap<_, _>>>()?
+        };
+
+        let parquet_table = ParquetTable::create(&self.parquet_table_info(Some(files_to_read)))?;
+        parquet_table
+            .read_partitions_with_extras(ctx, push_downs, deleted_rows, partition_values)
+            .await
+    }
+}
+
+#[async_trait]
+impl Table for DeltaTable {
+    fn is_local(&self) -> bool {
+        false
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn get_table_info(&self) -> &TableInfo {
+        &self.info
+    }
+
+    fn name(&self) -> &str {
+        &self.get_table_info().name
+    }
+
+    fn get_data_source_info(&self) -> DataSourceInfo {
+        DataSourceInfo::ParquetSource(self.parquet_table_info(None))
+    }
+
+    fn benefit_column_prune(&self) -> bool {
+        true
+    }
+
+    /// Prewhere is disabled if there are deletion vectors,
+    /// for deleted rows are skipped by row selections of partitions,
+    /// which are not applied to prewhere columns read with dictionaries.
+    fn support_prewhere(&self) -> bool {
+        !self.has_deletion_vectors() && ParquetReadOptions::default().do_prewhere()
+    }
+
+    /// Partitions are row groups of active data files,
+    /// data files are pruned with statistics in the log first,
+    /// then row groups and pages are pruned by the parquet reader.
+    #[async_backtrace::framed]
+    async fn read_partitions(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        push_downs: Option<PushDownInfo>,
+    ) -> Result<(PartStatistics, Partitions)> {
+        self.do_read_partitions(ctx, push_downs).await
+    }
+}
@@ -620,6 +620,11 @@ impl Table for FuseTable {
             .await
     }
 
+    #[async_backtrace::framed]
+    async fn merge_into(&self, ctx: Arc<dyn TableContext>, pipeline: &mut Pipeline) -> Result<()> {
+        self.build_merge_into_pipeline(ctx, pipeline).await
+    }
+
     fn get_block_compact_thresholds(&self) -> BlockThresholds {
         let max_rows_per_block =
             self.get_option(FUSE_OPT_KEY_ROW_PER_BLOCK, DEFAULT_BLOCK_MAX_ROWS);
@@ -0,0 +1,67 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::sync::Arc;
+
+use common_catalog::table_context::TableContext;
+use common_exception::Result;
+use common_pipeline_core::processors::processor::ProcessorPtr;
+use common_pipeline_transforms::processors::transforms::AsyncAccumulatingTransformer;
+
+use crate::io::ReadSettings;
+use crate::operations::merge_into::MergeRowsAggregator;
+use crate::pipelines::Pipeline;
+use crate::FuseTable;
+
+impl FuseTable {
+    // The pipeline going to be constructed
+    //
+    //  ┌─────────────┐      ┌───────────────────┐       ┌───────────────────┐       ┌───────────────────────┐         ┌───────────────────┐
+    //  │ MergedRows  ├─────►│ResizeProcessor(1) ├──────►│MergeRowsAggregator├──────►│TableMutationAggregator├────────►│     CommitSink    │
+    //  └─────────────┘      └───────────────────┘       └───────────────────┘       └───────────────────────┘         └───────────────────┘
+    //
+    // The matched rows of a target block are collected by the same aggregator, thus the input is resized to 1.
+    #[async_backtrace::framed]
+    pub async fn build_merge_into_pipeline(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        pipeline: &mut Pipeline,
+    ) -> Result<()> {
+        let base_snapshot = self
+            .read_table_snapshot()
+            .await?
+            .unwrap_or_else(|| Arc::new(self.new_empty_snapshot()));
+
+        pipeline.resize(1)?;
+
+        let read_settings = ReadSettings::from_ctx(&ctx)?;
+        pipeline.add_transform(|input, output| {
+            let aggregator = MergeRowsAggregator::try_create(
+                ctx.clone(),
+                self.create_append_transform(ctx.clone()),
+                base_snapshot.clone(),
+                self.operator.clone(),
+                self.table_info.schema(),
+                self.get_write_settings(),
+                read_settings.clone(),
+            )?;
+            Ok(ProcessorPtr::create(AsyncAccumulatingTransformer::create(
+                input, output, aggregator,
+            )))
+        })?;
+
+        self.chain_mutation_pipes(&ctx, pipeline, base_snapshot)
+            .await
+    }
+}
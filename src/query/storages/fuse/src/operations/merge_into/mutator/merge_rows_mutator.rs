@@ -0,0 +1,288 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use common_arrow::arrow::bitmap::Bitmap;
+use common_arrow::arrow::bitmap::MutableBitmap;
+use common_catalog::plan::block_row_id_base;
+use common_catalog::plan::Projection;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::types::NumberScalar;
+use common_expression::DataBlock;
+use common_expression::ScalarRef;
+use common_expression::TableSchema;
+use common_pipeline_transforms::processors::transforms::AsyncAccumulatingTransform;
+use opendal::Operator;
+use storages_common_cache::LoadParams;
+use storages_common_table_meta::meta::BlockMeta;
+use storages_common_table_meta::meta::TableSnapshot;
+use tracing::info;
+
+use crate::io::write_data;
+use crate::io::BlockBuilder;
+use crate::io::BlockReader;
+use crate::io::MetaReaders;
+use crate::io::ReadSettings;
+use crate::io::SegmentInfoReader;
+use crate::io::WriteSettings;
+use crate::operations::merge_into::mutation_meta::mutation_log::BlockMetaIndex;
+use crate::operations::merge_into::mutation_meta::mutation_log::MutationLogEntry;
+use crate::operations::merge_into::mutation_meta::mutation_log::MutationLogs;
+use crate::operations::merge_into::mutation_meta::mutation_log::Replacement;
+use crate::operations::merge_into::mutation_meta::mutation_log::ReplacementLogEntry;
+use crate::operations::merge_into::AppendTransform;
+use crate::operations::mutation::base_mutator::BlockIndex;
+use crate::operations::mutation::base_mutator::SegmentIndex;
+
+// offsets of the leading columns of the merged rows
+const BLOCK_NAME_OFFSET: usize = 0;
+const ROW_ID_OFFSET: usize = 1;
+const DELETED_OFFSET: usize = 2;
+const NUM_LEADING_COLUMNS: usize = 3;
+
+// Apply the rows of MERGE INTO to the table: the rows located by `_block_name` and `_row_id`
+// are removed from their blocks, the unmatched rows and the new versions of the updated rows
+// are appended. Only the row ids of the matched rows are kept until all rows are received.
+pub struct MergeRowsAggregator {
+    append_transform: AppendTransform,
+    base_snapshot: Arc<TableSnapshot>,
+    // row ids of the matched rows of each block, which are removed from the block
+    matched_row_ids: HashMap<String, HashSet<u64>>,
+    block_reader: Arc<BlockReader>,
+    data_accessor: Operator,
+    write_settings: WriteSettings,
+    read_settings: ReadSettings,
+    segment_reader: SegmentInfoReader,
+    block_builder: BlockBuilder,
+}
+
+impl MergeRowsAggregator {
+    pub fn try_create(
+        ctx: Arc<dyn TableContext>,
+        append_transform: AppendTransform,
+        base_snapshot: Arc<TableSnapshot>,
+        data_accessor: Operator,
+        table_schema: Arc<TableSchema>,
+        write_settings: WriteSettings,
+        read_settings: ReadSettings,
+    ) -> Result<Self> {
+        let segment_reader =
+            MetaReaders::segment_info_reader(data_accessor.clone(), table_schema.clone());
+        let indices = (0..table_schema.fields().len()).collect::<Vec<usize>>();
+        let projection = Projection::Columns(indices);
+        let block_reader =
+            BlockReader::create(data_accessor.clone(), table_schema, projection, ctx, false)?;
+        let block_builder = append_transform.get_block_builder();
+
+        Ok(Self {
+            append_transform,
+            base_snapshot,
+            matched_row_ids: HashMap::new(),
+            block_reader,
+            data_accessor,
+            write_settings,
+            read_settings,
+            segment_reader,
+            block_builder,
+        })
+    }
+}
+
+// aggregate the merged rows, the unmatched rows and the updated rows are appended immediately
+impl MergeRowsAggregator {
+    #[async_backtrace::framed]
+    pub async fn accumulate(&mut self, data_block: DataBlock) -> Result<Option<MutationLogs>> {
+        let data_block = data_block.convert_to_full();
+        let num_rows = data_block.num_rows();
+
+        let mut appended = MutableBitmap::with_capacity(num_rows);
+        for row in 0..num_rows {
+            let block_name = match data_block.get_by_offset(BLOCK_NAME_OFFSET).value.index(row) {
+                Some(ScalarRef::String(name)) => String::from_utf8_lossy(name).into_owned(),
+                _ => {
+                    appended.push(true);
+                    continue;
+                }
+            };
+
+            let row_id = match data_block.get_by_offset(ROW_ID_OFFSET).value.index(row) {
+                Some(ScalarRef::Number(NumberScalar::UInt64(row_id))) => row_id,
+                _ => {
+                    return Err(ErrorCode::Internal(
+                        "unexpected, the row id of a matched row is not UInt64",
+                    ));
+                }
+            };
+            let deleted = matches!(
+                data_block.get_by_offset(DELETED_OFFSET).value.index(row),
+                Some(ScalarRef::Boolean(true))
+            );
+
+            if !self
+                .matched_row_ids
+                .entry(block_name)
+                .or_default()
+                .insert(row_id)
+            {
+                return Err(ErrorCode::BadArguments(
+                    "MERGE INTO matches a row of the target table more than once",
+                ));
+            }
+            // the new version of an updated row doesn't have to be in the block of the old one
+            appended.push(!deleted);
+        }
+
+        let appended: Bitmap = appended.into();
+        if appended.unset_bits() == num_rows {
+            return Ok(None);
+        }
+        let values = DataBlock::new(
+            data_block.columns()[NUM_LEADING_COLUMNS..].to_vec(),
+            num_rows,
+        );
+        let inserted_rows = values.filter_with_bitmap(&appended)?;
+        self.append_transform
+            .transform(inserted_rows)
+            .await?
+            .map(MutationLogs::try_from)
+            .transpose()
+    }
+}
+
+// apply the mutations and generate mutation log
+impl MergeRowsAggregator {
+    #[async_backtrace::framed]
+    pub async fn apply(&mut self) -> Result<Option<MutationLogs>> {
+        let mut mutation_logs = match self.append_transform.on_finish(true).await? {
+            Some(data_block) => MutationLogs::try_from(data_block)?.entries,
+            None => vec![],
+        };
+
+        let num_segments = self.base_snapshot.segments.len();
+        for (segment_idx, (path, ver)) in self.base_snapshot.segments.iter().enumerate() {
+            if self.matched_row_ids.is_empty() {
+                break;
+            }
+            let load_param = LoadParams {
+                location: path.clone(),
+                len_hint: None,
+                ver: *ver,
+                put_cache: true,
+            };
+            let segment_info = self.segment_reader.read(&load_param).await?;
+
+            let num_blocks = segment_info.blocks.len();
+            for (block_idx, block_meta) in segment_info.blocks.iter().enumerate() {
+                let matched_row_ids = match self.matched_row_ids.remove(&block_meta.location.0) {
+                    Some(matched_row_ids) => matched_row_ids,
+                    None => continue,
+                };
+                // the same as the ids used to generate the internal column `_row_id`
+                let row_id_base =
+                    block_row_id_base(num_segments - segment_idx - 1, num_blocks - block_idx - 1);
+                let log_entry = self
+                    .apply_to_data_block(
+                        segment_idx,
+                        block_idx,
+                        block_meta,
+                        row_id_base,
+                        &matched_row_ids,
+                    )
+                    .await?;
+                mutation_logs.push(MutationLogEntry::Replacement(log_entry));
+            }
+        }
+
+        if let Some(block_name) = self.matched_row_ids.keys().next() {
+            return Err(ErrorCode::Internal(format!(
+                "unexpected, block {} not found, during applying MERGE INTO",
+                block_name
+            )));
+        }
+
+        Ok(Some(MutationLogs {
+            entries: mutation_logs,
+        }))
+    }
+
+    #[async_backtrace::framed]
+    async fn apply_to_data_block(
+        &self,
+        segment_index: SegmentIndex,
+        block_index: BlockIndex,
+        block_meta: &BlockMeta,
+        row_id_base: u64,
+        matched_row_ids: &HashSet<u64>,
+    ) -> Result<ReplacementLogEntry> {
+        info!(
+            "apply merge to segment idx {}, block idx {}",
+            segment_index, block_index
+        );
+        let index = BlockMetaIndex {
+            segment_idx: segment_index,
+            block_idx: block_index,
+            range: None,
+        };
+
+        let data_block = self
+            .block_reader
+            .read_by_meta(
+                &self.read_settings,
+                block_meta,
+                &self.write_settings.storage_format,
+            )
+            .await?;
+        let num_rows = data_block.num_rows();
+
+        let mut bitmap = MutableBitmap::with_capacity(num_rows);
+        for row in 0..num_rows {
+            let row_id = row_id_base + row as u64;
+            bitmap.push(!matched_row_ids.contains(&row_id));
+        }
+        let new_block = data_block.filter_with_bitmap(&bitmap.into())?;
+
+        if new_block.num_rows() == 0 {
+            info!("whole block deletion");
+            return Ok(ReplacementLogEntry {
+                index,
+                op: Replacement::Deleted,
+            });
+        }
+
+        // serialization and compression is cpu intensive, send them to dedicated thread pool
+        // and wait (asyncly, which will NOT block the executor thread)
+        let block_builder = self.block_builder.clone();
+        let serialized = tokio_rayon::spawn(move || block_builder.build(new_block)).await?;
+
+        // persistent data
+        let new_block_meta = serialized.block_meta;
+        let new_block_location = new_block_meta.location.0.clone();
+        let new_block_raw_data = serialized.block_raw_data;
+        let data_accessor = self.data_accessor.clone();
+        write_data(new_block_raw_data, &data_accessor, &new_block_location).await?;
+        if let Some(index_state) = serialized.bloom_index_state {
+            write_data(index_state.data, &data_accessor, &index_state.location.0).await?;
+        }
+
+        Ok(ReplacementLogEntry {
+            index,
+            op: Replacement::Replaced(Arc::new(new_block_meta)),
+        })
+    }
+}
@@ -15,4 +15,5 @@
 
 pub mod deletion_accumulator;
 pub mod merge_into_mutator;
+pub mod merge_rows_mutator;
 pub mod mutation_accumulator;
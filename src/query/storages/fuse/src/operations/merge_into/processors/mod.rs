@@ -18,6 +18,7 @@ mod processor_broadcast;
 mod sink_commit;
 mod transform_append;
 mod transform_merge_into_mutation_aggregator;
+mod transform_merge_rows_aggregator;
 mod transform_mutation_aggregator;
 
 use common_expression::FieldIndex;
@@ -26,6 +27,7 @@ pub use processor_broadcast::*;
 pub use sink_commit::CommitSink;
 pub use transform_append::AppendTransform;
 pub use transform_merge_into_mutation_aggregator::*;
+pub use transform_merge_rows_aggregator::*;
 pub use transform_mutation_aggregator::*;
 
 #[derive(Clone)]
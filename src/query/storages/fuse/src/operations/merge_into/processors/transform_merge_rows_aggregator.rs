@@ -0,0 +1,39 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use common_exception::Result;
+use common_expression::DataBlock;
+use common_pipeline_transforms::processors::transforms::transform_accumulating_async::AsyncAccumulatingTransform;
+
+pub use crate::operations::merge_into::mutator::merge_rows_mutator::MergeRowsAggregator;
+
+/// Takes the rows produced by MERGE INTO in, appends the unmatched and updated rows on the fly,
+/// and removes the matched rows from the data blocks they belong to in the `final` stage.
+/// Outputs [MutationLogs] logs(to be committed).
+#[async_trait::async_trait]
+impl AsyncAccumulatingTransform for MergeRowsAggregator {
+    const NAME: &'static str = "MergeRowsAggregator";
+
+    #[async_backtrace::framed]
+    async fn transform(&mut self, data: DataBlock) -> Result<Option<DataBlock>> {
+        let mutation_logs = self.accumulate(data).await?;
+        Ok(mutation_logs.map(|logs| logs.into()))
+    }
+
+    #[async_backtrace::framed]
+    async fn on_finish(&mut self, _output: bool) -> Result<Option<DataBlock>> {
+        let mutation_logs = self.apply().await?;
+        Ok(mutation_logs.map(|logs| logs.into()))
+    }
+}
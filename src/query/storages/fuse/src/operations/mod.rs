@@ -19,6 +19,7 @@ mod compact;
 mod delete;
 mod fuse_sink;
 mod gc;
+mod merge;
 mod merge_into;
 mod mutation;
 mod navigate;
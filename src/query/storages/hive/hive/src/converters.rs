@@ -15,6 +15,7 @@
 use std::sync::Arc;
 
 use chrono::Utc;
+use common_exception::ErrorCode;
 use common_exception::Result;
 use common_expression::types::decimal::DecimalSize;
 use common_expression::types::DecimalDataType;
@@ -35,8 +36,11 @@ use crate::hive_catalog::HIVE_CATALOG;
 use crate::hive_database::HiveDatabase;
 use crate::hive_database::HIVE_DATABASE_ENGIE;
 use crate::hive_table::HIVE_TABLE_ENGIE;
+use crate::hive_table_options::HiveFileFormat;
 use crate::hive_table_options::HiveTableOptions;
 
+const LAZY_SIMPLE_SERDE: &str = "org.apache.hadoop.hive.serde2.lazy.LazySimpleSerDe";
+
 /// ! Skeleton of mappers
 impl From<hms::Database> for HiveDatabase {
     fn from(hms_database: hms::Database) -> Self {
@@ -81,9 +85,12 @@ pub fn try_into_table_info(
         None
     };
 
+    let file_format = try_into_file_format(hms_table.sd.as_ref())?;
+
     let table_options = HiveTableOptions {
         partition_keys,
         location,
+        file_format,
     };
 
     let meta = TableMeta {
@@ -115,6 +122,28 @@ pub fn try_into_table_info(
     Ok(table_info)
 }
 
+fn try_into_file_format(sd: Option<&hms::StorageDescriptor>) -> Result<HiveFileFormat> {
+    let input_format = sd.and_then(|sd| sd.input_format.as_deref());
+    let serde_info = sd.and_then(|sd| sd.serde_info.as_ref());
+    let file_format = HiveFileFormat::try_create(
+        input_format,
+        serde_info.and_then(|info| info.parameters.as_ref()),
+    )?;
+
+    // text files could be read by other serdes, such as OpenCSVSerde and JsonSerDe
+    if let HiveFileFormat::Text { .. } = file_format {
+        if let Some(lib) = serde_info.and_then(|info| info.serialization_lib.as_ref()) {
+            if lib != LAZY_SIMPLE_SERDE {
+                return Err(ErrorCode::Unimplemented(format!(
+                    "only support text files of {}, {} not support",
+                    LAZY_SIMPLE_SERDE, lib
+                )));
+            }
+        }
+    }
+    Ok(file_format)
+}
+
 fn try_into_schema(hive_fields: Vec<hms::FieldSchema>) -> Result<TableSchema> {
     let mut fields = Vec::new();
     for field in hive_fields {
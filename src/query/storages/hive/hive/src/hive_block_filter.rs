@@ -15,6 +15,7 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use common_arrow::arrow::io::orc::format::proto::ColumnStatistics as OrcColumnStatistics;
 use common_arrow::parquet::metadata::RowGroupMetaData;
 use common_arrow::parquet::statistics::BinaryStatistics;
 use common_arrow::parquet::statistics::BooleanStatistics;
@@ -23,6 +24,7 @@ use common_arrow::parquet::statistics::Statistics;
 use common_expression::types::number::F32;
 use common_expression::types::number::F64;
 use common_expression::types::BooleanType;
+use common_expression::types::DateType;
 use common_expression::types::NumberDataType;
 use common_expression::types::NumberType;
 use common_expression::types::StringType;
@@ -95,36 +97,181 @@ impl HiveBlockFilter {
                 }
             }
 
-            for (p_key, p_value) in part_columns {
-                if let Ok(idx) = self.data_schema.index_of(&p_key) {
-                    let mut null_count = 0;
-                    let v = if p_value == HIVE_DEFAULT_PARTITION {
-                        null_count = row_group.num_rows();
-                        Scalar::Null
-                    } else {
-                        Scalar::String(p_value.as_bytes().to_vec())
-                    };
+            return self.filter_statistics(
+                filter,
+                statistics,
+                Some(row_group.num_rows()),
+                part_columns,
+            );
+        }
+        false
+    }
 
-                    let col_stats = ColumnStatistics {
-                        min: v.clone(),
-                        max: v,
-                        null_count: null_count as u64,
-                        in_memory_size: 0,
-                        distinct_of_values: None,
-                    };
-                    statistics.insert(idx as u32, col_stats);
+    // true: orc stripe is filtered by predict
+    //
+    // column_statistics are the statistics of the stripe, indexed by orc column id
+    pub fn filter_orc_stripe(
+        &self,
+        column_statistics: &[OrcColumnStatistics],
+        column_ids: &HashMap<String, u32>,
+        num_rows: usize,
+        part_columns: HashMap<String, String>,
+    ) -> bool {
+        if let Some(filter) = &self.range_filter {
+            let mut statistics = StatisticsOfColumns::new();
+            for col in self.projections.iter() {
+                let stats = column_ids
+                    .get(col.name())
+                    .and_then(|id| column_statistics.get(*id as usize));
+                if let Some(stats) = stats {
+                    if let Some((max, min, null_count)) =
+                        Self::get_orc_max_min_stats(col.data_type(), stats, num_rows)
+                    {
+                        let col_stats = ColumnStatistics {
+                            min,
+                            max,
+                            null_count: null_count as u64,
+                            in_memory_size: 0,
+                            distinct_of_values: None,
+                        };
+                        if let Ok(idx) = self.data_schema.index_of(col.name()) {
+                            statistics.insert(idx as u32, col_stats);
+                        }
+                    }
                 }
             }
 
-            if let Ok(ret) = filter.apply(&statistics) {
-                if !ret {
-                    return true;
-                }
+            return self.filter_statistics(filter, statistics, Some(num_rows), part_columns);
+        }
+        false
+    }
+
+    // true: file is filtered by predict on partition columns, used by files without statistics
+    pub fn filter_partition(&self, part_columns: HashMap<String, String>) -> bool {
+        match &self.range_filter {
+            Some(filter) => {
+                self.filter_statistics(filter, StatisticsOfColumns::new(), None, part_columns)
+            }
+            None => false,
+        }
+    }
+
+    // num_rows is none if the number of rows is unknown,
+    // then the statistics of default partitions are unknown too
+    fn filter_statistics(
+        &self,
+        filter: &RangeIndex,
+        mut statistics: StatisticsOfColumns,
+        num_rows: Option<usize>,
+        part_columns: HashMap<String, String>,
+    ) -> bool {
+        for (p_key, p_value) in part_columns {
+            if let Ok(idx) = self.data_schema.index_of(&p_key) {
+                let mut null_count = 0;
+                let v = if p_value == HIVE_DEFAULT_PARTITION {
+                    match num_rows {
+                        Some(num_rows) => null_count = num_rows,
+                        None => continue,
+                    }
+                    Scalar::Null
+                } else {
+                    Scalar::String(p_value.as_bytes().to_vec())
+                };
+
+                let col_stats = ColumnStatistics {
+                    min: v.clone(),
+                    max: v,
+                    null_count: null_count as u64,
+                    in_memory_size: 0,
+                    distinct_of_values: None,
+                };
+                statistics.insert(idx as u32, col_stats);
+            }
+        }
+
+        if let Ok(ret) = filter.apply(&statistics) {
+            if !ret {
+                return true;
             }
         }
         false
     }
 
+    // the null count is the number of rows minus the number of values
+    fn get_orc_max_min_stats(
+        column_type: &TableDataType,
+        stats: &OrcColumnStatistics,
+        num_rows: usize,
+    ) -> Option<(Scalar, Scalar, i64)> {
+        let number_of_values = stats.number_of_values? as usize;
+        let null_count = num_rows.checked_sub(number_of_values)? as i64;
+        match column_type {
+            TableDataType::Number(NumberDataType::Int8) => {
+                let s = stats.int_statistics.as_ref()?;
+                let max = NumberType::<i8>::upcast_scalar(s.maximum? as i8);
+                let min = NumberType::<i8>::upcast_scalar(s.minimum? as i8);
+                Some((max, min, null_count))
+            }
+            TableDataType::Number(NumberDataType::Int16) => {
+                let s = stats.int_statistics.as_ref()?;
+                let max = NumberType::<i16>::upcast_scalar(s.maximum? as i16);
+                let min = NumberType::<i16>::upcast_scalar(s.minimum? as i16);
+                Some((max, min, null_count))
+            }
+            TableDataType::Number(NumberDataType::Int32) => {
+                let s = stats.int_statistics.as_ref()?;
+                let max = NumberType::<i32>::upcast_scalar(s.maximum? as i32);
+                let min = NumberType::<i32>::upcast_scalar(s.minimum? as i32);
+                Some((max, min, null_count))
+            }
+            TableDataType::Number(NumberDataType::Int64) => {
+                let s = stats.int_statistics.as_ref()?;
+                let max = NumberType::<i64>::upcast_scalar(s.maximum?);
+                let min = NumberType::<i64>::upcast_scalar(s.minimum?);
+                Some((max, min, null_count))
+            }
+            TableDataType::Number(NumberDataType::Float32) => {
+                let s = stats.double_statistics.as_ref()?;
+                let max = NumberType::<F32>::upcast_scalar((s.maximum? as f32).into());
+                let min = NumberType::<F32>::upcast_scalar((s.minimum? as f32).into());
+                Some((max, min, null_count))
+            }
+            TableDataType::Number(NumberDataType::Float64) => {
+                let s = stats.double_statistics.as_ref()?;
+                let max = NumberType::<F64>::upcast_scalar(s.maximum?.into());
+                let min = NumberType::<F64>::upcast_scalar(s.minimum?.into());
+                Some((max, min, null_count))
+            }
+            TableDataType::Boolean => {
+                // the count of true values
+                let trues = *stats.bucket_statistics.as_ref()?.count.first()? as usize;
+                if number_of_values == 0 {
+                    return None;
+                }
+                let max = BooleanType::upcast_scalar(trues > 0);
+                let min = BooleanType::upcast_scalar(trues == number_of_values);
+                Some((max, min, null_count))
+            }
+            TableDataType::String => {
+                // minimum and maximum are absent if they are too long, only bounds are kept
+                let s = stats.string_statistics.as_ref()?;
+                let max = StringType::upcast_scalar(s.maximum.clone()?.into_bytes());
+                let min = StringType::upcast_scalar(s.minimum.clone()?.into_bytes());
+                Some((max, min, null_count))
+            }
+            TableDataType::Date => {
+                let s = stats.date_statistics.as_ref()?;
+                let max = DateType::upcast_scalar(s.maximum?);
+                let min = DateType::upcast_scalar(s.minimum?);
+                Some((max, min, null_count))
+            }
+            TableDataType::Nullable(inner_ty) => {
+                Self::get_orc_max_min_stats(inner_ty.as_ref(), stats, num_rows)
+            }
+            _ => None,
+        }
+    }
+
     fn get_max_min_stats(
         column_type: &TableDataType,
         stats: &dyn Statistics,
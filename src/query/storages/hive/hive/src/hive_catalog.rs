@@ -60,6 +60,7 @@ use thrift::transport::*;
 
 use super::hive_database::HiveDatabase;
 use crate::hive_table::HiveTable;
+use crate::hive_table_options::HiveFileFormat;
 
 pub const HIVE_CATALOG: &str = "hive";
 
@@ -177,9 +178,9 @@ impl HiveCatalog {
 
         if let Some(sd) = table_meta.sd.as_ref() {
             if let Some(input_format) = sd.input_format.as_ref() {
-                if input_format != "org.apache.hadoop.hive.ql.io.parquet.MapredParquetInputFormat" {
+                if !HiveFileFormat::is_supported(input_format) {
                     return Err(ErrorCode::Unimplemented(format!(
-                        "only support parquet, {} not support",
+                        "only support parquet, orc and text, {} not support",
                         input_format
                     )));
                 }
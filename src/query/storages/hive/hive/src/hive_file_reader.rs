@@ -0,0 +1,242 @@
+//  Copyright 2023 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::sync::Arc;
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::DataBlock;
+use common_expression::DataSchema;
+use common_expression::DataSchemaRef;
+use common_expression::FunctionContext;
+use common_expression::TableField;
+use common_expression::TableSchema;
+use common_expression::TableSchemaRef;
+use opendal::Operator;
+
+use crate::filter_hive_partition_from_partition_keys;
+use crate::hive_orc_block_reader::deserialize_orc_stripe;
+use crate::hive_orc_block_reader::prune_orc_stripes;
+use crate::hive_orc_block_reader::read_orc_file_meta;
+use crate::hive_orc_block_reader::read_orc_stripe;
+use crate::hive_orc_block_reader::OrcFileMeta;
+use crate::hive_table_options::HiveFileFormat;
+use crate::hive_text_block_reader::check_text_file;
+use crate::hive_text_block_reader::deserialize_text;
+use crate::hive_text_block_reader::read_text_split;
+use crate::HiveBlockFilter;
+use crate::HivePartInfo;
+use crate::HivePartitionFiller;
+
+pub enum HiveFileMeta {
+    Orc(Arc<OrcFileMeta>),
+    Text,
+}
+
+// blocks of a hive file to read, which are stripes of orc files,
+// or the whole split of text files
+pub struct HiveFileBlocks {
+    pub part: HivePartInfo,
+    pub meta: HiveFileMeta,
+    pub valid_blocks: Vec<usize>,
+    pub current_index: usize,
+}
+
+impl HiveFileBlocks {
+    pub fn advance(&mut self) {
+        self.current_index += 1;
+    }
+
+    pub fn has_blocks(&self) -> bool {
+        self.current_index < self.valid_blocks.len()
+    }
+
+    fn get_current_block_index(&self) -> usize {
+        self.valid_blocks[self.current_index]
+    }
+}
+
+// reads orc and text files, the parquet files are read by HiveBlockReader
+//
+// all the projected columns are read at once, since prewhere columns
+// couldn't be read separately from text files and orc stripes are read as a whole.
+#[derive(Clone)]
+pub struct HiveFileReader {
+    operator: Operator,
+    file_format: HiveFileFormat,
+    // the columns read from files, without partition columns
+    fields: Vec<TableField>,
+    // positions of the columns in text files
+    positions: Vec<usize>,
+    // have partition columns
+    output_schema: DataSchemaRef,
+    hive_partition_filler: Option<HivePartitionFiller>,
+    chunk_size: usize,
+}
+
+impl HiveFileReader {
+    pub fn create(
+        operator: Operator,
+        schema: TableSchemaRef,
+        projection: Vec<usize>,
+        partition_keys: &Option<Vec<String>>,
+        file_format: HiveFileFormat,
+        chunk_size: usize,
+    ) -> Result<HiveFileReader> {
+        let (projection, partition_fields) =
+            filter_hive_partition_from_partition_keys(schema.clone(), projection, partition_keys);
+
+        // the columns of text files are in the order of the table schema, without partition columns
+        let (data_columns, _) = filter_hive_partition_from_partition_keys(
+            schema.clone(),
+            (0..schema.num_fields()).collect(),
+            partition_keys,
+        );
+        let positions = projection
+            .iter()
+            .map(|i| data_columns.iter().position(|c| c == i).unwrap_or_default())
+            .collect();
+        let fields = projection
+            .into_iter()
+            .map(|i| schema.field(i).clone())
+            .collect::<Vec<_>>();
+
+        // partition columns are filled after the columns read from files
+        let mut output_fields = fields.clone();
+        output_fields.extend(partition_fields.iter().cloned());
+        let output_schema = DataSchemaRef::new(DataSchema::from(&TableSchema::new(output_fields)));
+
+        let hive_partition_filler = if !partition_fields.is_empty() {
+            Some(HivePartitionFiller::create(
+                schema.clone(),
+                partition_fields,
+            ))
+        } else {
+            None
+        };
+
+        Ok(HiveFileReader {
+            operator,
+            file_format,
+            fields,
+            positions,
+            output_schema,
+            hive_partition_filler,
+            chunk_size,
+        })
+    }
+
+    #[async_backtrace::framed]
+    pub async fn read_meta(
+        &self,
+        part: &HivePartInfo,
+        hive_block_filter: &HiveBlockFilter,
+    ) -> Result<HiveFileBlocks> {
+        let (meta, valid_blocks) = match &self.file_format {
+            HiveFileFormat::Orc => {
+                let meta = read_orc_file_meta(&self.operator, part).await?;
+                let valid_blocks = prune_orc_stripes(&meta, part, hive_block_filter);
+                (HiveFileMeta::Orc(Arc::new(meta)), valid_blocks)
+            }
+            HiveFileFormat::Text { .. } => {
+                check_text_file(part)?;
+                let valid_blocks =
+                    match hive_block_filter.filter_partition(part.get_partition_map()) {
+                        true => vec![],
+                        false => vec![0],
+                    };
+                (HiveFileMeta::Text, valid_blocks)
+            }
+            HiveFileFormat::Parquet => {
+                return Err(ErrorCode::Internal(
+                    "It's a bug. Parquet files are read by HiveBlockReader",
+                ));
+            }
+        };
+
+        Ok(HiveFileBlocks {
+            part: part.clone(),
+            meta,
+            valid_blocks,
+            current_index: 0,
+        })
+    }
+
+    #[async_backtrace::framed]
+    pub async fn read_block_data(&self, blocks: &HiveFileBlocks) -> Result<Vec<u8>> {
+        match &blocks.meta {
+            HiveFileMeta::Orc(meta) => {
+                read_orc_stripe(
+                    &self.operator,
+                    meta,
+                    blocks.get_current_block_index(),
+                    &blocks.part,
+                )
+                .await
+            }
+            HiveFileMeta::Text => read_text_split(&self.operator, &blocks.part).await,
+        }
+    }
+
+    // deserialize the current block, then split it by the chunk size
+    pub fn deserialize_block_data(
+        &self,
+        blocks: &HiveFileBlocks,
+        data: Vec<u8>,
+        func_ctx: FunctionContext,
+    ) -> Result<Vec<DataBlock>> {
+        let block = match (&blocks.meta, &self.file_format) {
+            (HiveFileMeta::Orc(meta), _) => {
+                deserialize_orc_stripe(meta, blocks.get_current_block_index(), data, &self.fields)
+            }
+            (
+                HiveFileMeta::Text,
+                HiveFileFormat::Text {
+                    field_delimiter,
+                    null_format,
+                },
+            ) => deserialize_text(
+                &data,
+                &self.fields,
+                &self.positions,
+                *field_delimiter,
+                null_format,
+                func_ctx,
+            ),
+            _ => Err(ErrorCode::Internal(
+                "It's a bug. Text files are read without text format",
+            )),
+        }
+        .map_err(|e| e.add_message(format!(" filename of hive part {}", blocks.part.filename)))?;
+
+        let block = match &self.hive_partition_filler {
+            Some(filler) => {
+                let num_rows = block.num_rows();
+                filler.fill_data(block, &blocks.part, num_rows)?
+            }
+            None => block,
+        };
+
+        let num_rows = block.num_rows();
+        let chunk_size = self.chunk_size.max(1);
+        Ok((0..num_rows)
+            .step_by(chunk_size)
+            .map(|start| block.slice(start..(start + chunk_size).min(num_rows)))
+            .collect())
+    }
+
+    pub fn get_output_schema(&self) -> DataSchemaRef {
+        self.output_schema.clone()
+    }
+}
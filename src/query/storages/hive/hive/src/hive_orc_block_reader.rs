@@ -0,0 +1,202 @@
+//  Copyright 2023 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use std::collections::HashMap;
+
+use common_arrow::arrow::datatypes::Field as ArrowField;
+use common_arrow::arrow::error::Error as ArrowError;
+use common_arrow::arrow::io::orc::format::proto::r#type::Kind;
+use common_arrow::arrow::io::orc::format::proto::StripeInformation;
+use common_arrow::arrow::io::orc::format::read::read_stripe_footer;
+use common_arrow::arrow::io::orc::format::read::FileMetadata;
+use common_arrow::deserialize_orc_stripe_column;
+use common_arrow::infer_orc_data_type;
+use common_arrow::orc_tail_length;
+use common_arrow::read_orc_metadata;
+use common_arrow::OrcStripeReader;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::types::DataType;
+use common_expression::BlockEntry;
+use common_expression::Column;
+use common_expression::DataBlock;
+use common_expression::TableDataType;
+use common_expression::TableField;
+use common_expression::Value;
+use opendal::Operator;
+
+use crate::HiveBlockFilter;
+use crate::HivePartInfo;
+
+// the postscript and footer of most orc files are in the last 16KB
+const ORC_TAIL_SIZE_HINT: u64 = 16 * 1024;
+
+pub struct OrcFileMeta {
+    pub meta: FileMetadata,
+    // orc column ids of the top level columns, by lower cased column names
+    pub column_ids: HashMap<String, u32>,
+}
+
+#[async_backtrace::framed]
+pub async fn read_orc_file_meta(operator: &Operator, part: &HivePartInfo) -> Result<OrcFileMeta> {
+    let filesize = part.filesize;
+    let hint = ORC_TAIL_SIZE_HINT.min(filesize);
+    let mut tail = operator
+        .range_read(&part.filename, filesize - hint..filesize)
+        .await?;
+
+    let tail_length = orc_tail_length(&tail)? as u64;
+    if tail_length > filesize {
+        return Err(ErrorCode::BadBytes(format!(
+            "invalid orc file {}, tail length {} is larger than file size {}",
+            part.filename, tail_length, filesize
+        )));
+    }
+    if tail_length > hint {
+        tail = operator
+            .range_read(&part.filename, filesize - tail_length..filesize)
+            .await?;
+    }
+    let meta = read_orc_metadata(&tail)?;
+
+    let root = match meta.footer.types.first() {
+        Some(root) if root.kind() == Kind::Struct => root,
+        _ => {
+            return Err(ErrorCode::BadBytes(format!(
+                "invalid orc file {}, the root type must be struct",
+                part.filename
+            )));
+        }
+    };
+    let column_ids = root
+        .field_names
+        .iter()
+        .map(|name| name.to_lowercase())
+        .zip(root.subtypes.iter().cloned())
+        .collect();
+
+    Ok(OrcFileMeta { meta, column_ids })
+}
+
+fn stripe_length(stripe: &StripeInformation) -> u64 {
+    stripe.index_length() + stripe.data_length() + stripe.footer_length()
+}
+
+// there are some conditions to filter invalid stripes, the same as row groups of parquet files:
+// 1. the stripe doesn't belong to the partition
+// 2. filtered by predict pushdown
+pub fn prune_orc_stripes(
+    meta: &OrcFileMeta,
+    part: &HivePartInfo,
+    hive_block_filter: &HiveBlockFilter,
+) -> Vec<usize> {
+    let mut valid_stripes = vec![];
+    let mut pruned_stripe_cnt = 0;
+    for (idx, stripe) in meta.meta.footer.stripes.iter().enumerate() {
+        let mid = stripe.offset() + stripe_length(stripe) / 2;
+        if !part.range.contains(&mid) {
+            continue;
+        }
+
+        let column_statistics = meta
+            .meta
+            .metadata
+            .stripe_stats
+            .get(idx)
+            .map(|stats| stats.col_stats.as_slice())
+            .unwrap_or_default();
+        if hive_block_filter.filter_orc_stripe(
+            column_statistics,
+            &meta.column_ids,
+            stripe.number_of_rows() as usize,
+            part.get_partition_map(),
+        ) {
+            pruned_stripe_cnt += 1;
+        } else {
+            valid_stripes.push(idx);
+        }
+    }
+    tracing::debug!(
+        "hive orc predict pushdown have pruned {} stripes",
+        pruned_stripe_cnt
+    );
+    valid_stripes
+}
+
+#[async_backtrace::framed]
+pub async fn read_orc_stripe(
+    operator: &Operator,
+    meta: &OrcFileMeta,
+    stripe: usize,
+    part: &HivePartInfo,
+) -> Result<Vec<u8>> {
+    let stripe = &meta.meta.footer.stripes[stripe];
+    let start = stripe.offset();
+    let data = operator
+        .range_read(&part.filename, start..start + stripe_length(stripe))
+        .await?;
+    Ok(data)
+}
+
+pub fn deserialize_orc_stripe(
+    meta: &OrcFileMeta,
+    stripe: usize,
+    data: Vec<u8>,
+    fields: &[TableField],
+) -> Result<DataBlock> {
+    let stripe_info = &meta.meta.footer.stripes[stripe];
+    let num_rows = stripe_info.number_of_rows() as usize;
+    let mut reader = OrcStripeReader::new(stripe_info.offset(), data);
+    let footer = read_stripe_footer(&mut reader, &meta.meta, stripe, &mut vec![])
+        .map_err(ArrowError::from)?;
+
+    let mut columns = Vec::with_capacity(fields.len());
+    for field in fields {
+        let column_id = *meta.column_ids.get(field.name()).ok_or_else(|| {
+            ErrorCode::TableInfoError(format!("couldn't find column:{} in orc file", field.name()))
+        })?;
+        let orc_type = infer_orc_data_type(&meta.meta.footer.types, column_id)?;
+
+        // columns are converted by the arrow types, which must be the same as the table
+        let data_type: DataType = field.data_type().into();
+        let orc_table_type = TableDataType::from(&ArrowField::new(
+            field.name(),
+            orc_type.clone(),
+            field.is_nullable(),
+        ));
+        if DataType::from(&orc_table_type) != data_type {
+            return Err(ErrorCode::TableInfoError(format!(
+                "column:{} of type {} mismatches the type {} in orc file",
+                field.name(),
+                data_type,
+                orc_table_type
+            )));
+        }
+
+        let array = deserialize_orc_stripe_column(
+            &mut reader,
+            &meta.meta,
+            stripe,
+            &footer,
+            column_id,
+            orc_type,
+        )?;
+        columns.push(BlockEntry {
+            value: Value::Column(Column::from_arrow(array.as_ref(), &data_type)),
+            data_type,
+        });
+    }
+
+    Ok(DataBlock::new(columns, num_rows))
+}
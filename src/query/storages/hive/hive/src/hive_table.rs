@@ -58,8 +58,10 @@ use storages_common_index::RangeIndex;
 
 use super::hive_catalog::HiveCatalog;
 use super::hive_partition_pruner::HivePartitionPruner;
+use super::hive_table_options::HiveFileFormat;
 use super::hive_table_options::HiveTableOptions;
 use crate::filter_hive_partition_from_partition_keys;
+use crate::hive_file_reader::HiveFileReader;
 use crate::hive_parquet_block_reader::HiveBlockReader;
 use crate::hive_table_source::HiveTableSource;
 use crate::HiveBlockFilter;
@@ -195,17 +197,27 @@ impl HiveTable {
         let prewhere_reader =
             self.build_prewhere_reader(plan, chunk_size, prewhere_all_partitions)?;
         let remain_reader = self.build_remain_reader(plan, chunk_size, prewhere_all_partitions)?;
-        let prewhere_filter =
-            self.build_prewhere_filter_executor(plan, prewhere_reader.get_output_schema())?;
+        // orc and text files are read by the file reader, with prewhere and remain columns together
+        let file_reader = self.build_file_reader(plan, chunk_size, prewhere_all_partitions)?;
+        let prewhere_schema = match file_reader.as_ref() {
+            Some(reader) => reader.get_output_schema(),
+            None => prewhere_reader.get_output_schema(),
+        };
+        let prewhere_filter = self.build_prewhere_filter_executor(plan, prewhere_schema)?;
 
         let hive_block_filter = self.get_block_filter(ctx.clone(), push_downs)?;
 
-        let mut src_fields = prewhere_reader.get_output_schema().fields().clone();
-        if let Some(reader) = remain_reader.as_ref() {
-            let remain_field = reader.get_output_schema().fields().clone();
-            src_fields.extend_from_slice(&remain_field);
-        }
-        let src_schema = DataSchemaRefExt::create(src_fields);
+        let src_schema = match file_reader.as_ref() {
+            Some(reader) => reader.get_output_schema(),
+            None => {
+                let mut src_fields = prewhere_reader.get_output_schema().fields().clone();
+                if let Some(reader) = remain_reader.as_ref() {
+                    let remain_field = reader.get_output_schema().fields().clone();
+                    src_fields.extend_from_slice(&remain_field);
+                }
+                DataSchemaRefExt::create(src_fields)
+            }
+        };
 
         for index in 0..std::cmp::max(1, max_threads) {
             let output = OutputPort::create();
@@ -217,6 +229,7 @@ impl HiveTable {
                     output,
                     prewhere_reader.clone(),
                     remain_reader.clone(),
+                    file_reader.clone(),
                     prewhere_filter.clone(),
                     delay_timer(index),
                     hive_block_filter.clone(),
@@ -368,6 +381,51 @@ impl HiveTable {
         )
     }
 
+    // Build the file reader for orc and text files, none for parquet files.
+    fn build_file_reader(
+        &self,
+        plan: &DataSourcePlan,
+        chunk_size: usize,
+        prewhere_all_partitions: bool,
+    ) -> Result<Arc<Option<HiveFileReader>>> {
+        if self.table_options.file_format == HiveFileFormat::Parquet {
+            return Ok(Arc::new(None));
+        }
+
+        let projections = match (
+            prewhere_all_partitions,
+            PushDownInfo::prewhere_of_push_downs(&plan.push_downs),
+        ) {
+            (true, _) | (_, None) => vec![PushDownInfo::projection_of_push_downs(
+                &plan.schema(),
+                &plan.push_downs,
+            )],
+            (false, Some(v)) => vec![v.prewhere_columns, v.remain_columns],
+        };
+        let mut columns = vec![];
+        for projection in projections {
+            match projection {
+                Projection::Columns(projection) => columns.extend(projection),
+                Projection::InnerColumns(b) => {
+                    return Err(ErrorCode::Unimplemented(format!(
+                        "not support inter columns in hive file reader,{:?}",
+                        b
+                    )));
+                }
+            }
+        }
+
+        let reader = HiveFileReader::create(
+            self.dal.clone(),
+            self.table_info.schema(),
+            columns,
+            &self.table_options.partition_keys,
+            self.table_options.file_format.clone(),
+            chunk_size,
+        )?;
+        Ok(Arc::new(Some(reader)))
+    }
+
     fn get_column_schemas(&self, columns: Vec<String>) -> Result<Arc<TableSchema>> {
         let mut fields = Vec::with_capacity(columns.len());
         for column in columns {
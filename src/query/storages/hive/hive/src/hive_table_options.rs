@@ -20,15 +20,98 @@ use common_exception::Result;
 
 pub const PARTITION_KEYS: &str = "partition_keys";
 pub const LOCATION: &str = "location";
+pub const INPUT_FORMAT: &str = "input_format";
+pub const FIELD_DELIMITER: &str = "field_delimiter";
+pub const NULL_FORMAT: &str = "null_format";
+
+pub const PARQUET_INPUT_FORMAT: &str =
+    "org.apache.hadoop.hive.ql.io.parquet.MapredParquetInputFormat";
+pub const ORC_INPUT_FORMAT: &str = "org.apache.hadoop.hive.ql.io.orc.OrcInputFormat";
+pub const TEXT_INPUT_FORMAT: &str = "org.apache.hadoop.mapred.TextInputFormat";
+
+// the default delimiter and null format of hive text files, see LazySerDeParameters
+pub const DEFAULT_FIELD_DELIMITER: u8 = b'\x01';
+pub const DEFAULT_NULL_FORMAT: &str = "\\N";
 
 // represents hive table schema info
 //
 // partition_keys,  hive partition keys, such as:  "p_date", "p_hour"
 // location,  hive table location, such as: hdfs://namenode:8020/user/hive/warehouse/a.db/b.table/
+// file_format, format of the data files, decided by the input format of the table
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub struct HiveTableOptions {
     pub partition_keys: Option<Vec<String>>,
     pub location: Option<String>,
+    pub file_format: HiveFileFormat,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum HiveFileFormat {
+    Parquet,
+    Orc,
+    // delimited text files read by LazySimpleSerDe
+    Text {
+        field_delimiter: u8,
+        null_format: String,
+    },
+}
+
+impl HiveFileFormat {
+    pub fn is_supported(input_format: &str) -> bool {
+        [PARQUET_INPUT_FORMAT, ORC_INPUT_FORMAT, TEXT_INPUT_FORMAT].contains(&input_format)
+    }
+
+    // create from the input format and serde parameters of hive storage descriptor
+    pub fn try_create(
+        input_format: Option<&str>,
+        serde_parameters: Option<&BTreeMap<String, String>>,
+    ) -> Result<HiveFileFormat> {
+        match input_format {
+            None | Some(PARQUET_INPUT_FORMAT) => Ok(HiveFileFormat::Parquet),
+            Some(ORC_INPUT_FORMAT) => Ok(HiveFileFormat::Orc),
+            Some(TEXT_INPUT_FORMAT) => {
+                let field_delimiter = serde_parameters
+                    .and_then(|p| {
+                        p.get("field.delim")
+                            .or_else(|| p.get("serialization.format"))
+                    })
+                    .map(|v| parse_delimiter(v))
+                    .unwrap_or(DEFAULT_FIELD_DELIMITER);
+                let null_format = serde_parameters
+                    .and_then(|p| p.get("serialization.null.format"))
+                    .cloned()
+                    .unwrap_or_else(|| DEFAULT_NULL_FORMAT.to_string());
+                Ok(HiveFileFormat::Text {
+                    field_delimiter,
+                    null_format,
+                })
+            }
+            Some(other) => Err(ErrorCode::Unimplemented(format!(
+                "only support parquet, orc and text, {} not support",
+                other
+            ))),
+        }
+    }
+
+    fn input_format(&self) -> &'static str {
+        match self {
+            HiveFileFormat::Parquet => PARQUET_INPUT_FORMAT,
+            HiveFileFormat::Orc => ORC_INPUT_FORMAT,
+            HiveFileFormat::Text { .. } => TEXT_INPUT_FORMAT,
+        }
+    }
+}
+
+// the same as LazySerDeParameters#getByte, delimiters may be given as numbers, like '1' for '\x01'
+fn parse_delimiter(delimiter: &str) -> u8 {
+    match delimiter.parse::<i8>() {
+        Ok(v) => v as u8,
+        Err(_) => delimiter
+            .as_bytes()
+            .first()
+            .cloned()
+            .unwrap_or(DEFAULT_FIELD_DELIMITER),
+    }
 }
 
 impl From<HiveTableOptions> for BTreeMap<String, String> {
@@ -42,6 +125,18 @@ impl From<HiveTableOptions> for BTreeMap<String, String> {
         options
             .location
             .map(|v| map.insert(LOCATION.to_string(), v));
+        map.insert(
+            INPUT_FORMAT.to_string(),
+            options.file_format.input_format().to_string(),
+        );
+        if let HiveFileFormat::Text {
+            field_delimiter,
+            null_format,
+        } = options.file_format
+        {
+            map.insert(FIELD_DELIMITER.to_string(), field_delimiter.to_string());
+            map.insert(NULL_FORMAT.to_string(), null_format);
+        }
         map
     }
 }
@@ -61,9 +156,40 @@ impl TryFrom<&BTreeMap<String, String>> for HiveTableOptions {
             .get(LOCATION)
             .ok_or_else(|| ErrorCode::Internal("Hive engine table missing location key"))?
             .clone();
+
+        // tables created before the input format is recorded are all parquet
+        let file_format = match options.get(INPUT_FORMAT).map(String::as_str) {
+            None | Some(PARQUET_INPUT_FORMAT) => HiveFileFormat::Parquet,
+            Some(ORC_INPUT_FORMAT) => HiveFileFormat::Orc,
+            Some(TEXT_INPUT_FORMAT) => {
+                let field_delimiter = match options.get(FIELD_DELIMITER) {
+                    Some(v) => v.parse::<u8>().map_err(|_| {
+                        ErrorCode::Internal(format!(
+                            "Hive engine table invalid field delimiter {v}"
+                        ))
+                    })?,
+                    None => DEFAULT_FIELD_DELIMITER,
+                };
+                let null_format = options
+                    .get(NULL_FORMAT)
+                    .cloned()
+                    .unwrap_or_else(|| DEFAULT_NULL_FORMAT.to_string());
+                HiveFileFormat::Text {
+                    field_delimiter,
+                    null_format,
+                }
+            }
+            Some(other) => {
+                return Err(ErrorCode::Internal(format!(
+                    "Hive engine table unknown input format {other}"
+                )));
+            }
+        };
+
         let options = HiveTableOptions {
             partition_keys,
             location: Some(location),
+            file_format,
         };
         Ok(options)
     }
@@ -73,7 +199,10 @@ impl TryFrom<&BTreeMap<String, String>> for HiveTableOptions {
 mod tests {
     use std::collections::BTreeMap;
 
+    use super::HiveFileFormat;
     use super::HiveTableOptions;
+    use super::ORC_INPUT_FORMAT;
+    use super::TEXT_INPUT_FORMAT;
 
     fn do_test_hive_table_options(hive_table_options: HiveTableOptions) {
         let m: BTreeMap<String, String> = hive_table_options.clone().into();
@@ -86,6 +215,7 @@ mod tests {
         let hive_table_options = HiveTableOptions {
             partition_keys: Some(vec!["a".to_string(), "b".to_string()]),
             location: Some("test".to_string()),
+            file_format: HiveFileFormat::Parquet,
         };
 
         do_test_hive_table_options(hive_table_options);
@@ -93,7 +223,64 @@ mod tests {
         let empty = HiveTableOptions {
             partition_keys: None,
             location: Some("test".to_string()),
+            file_format: HiveFileFormat::Parquet,
         };
         do_test_hive_table_options(empty);
+
+        let orc = HiveTableOptions {
+            partition_keys: None,
+            location: Some("test".to_string()),
+            file_format: HiveFileFormat::Orc,
+        };
+        do_test_hive_table_options(orc);
+
+        let text = HiveTableOptions {
+            partition_keys: Some(vec!["a".to_string()]),
+            location: Some("test".to_string()),
+            file_format: HiveFileFormat::Text {
+                field_delimiter: b',',
+                null_format: "".to_string(),
+            },
+        };
+        do_test_hive_table_options(text);
+    }
+
+    #[test]
+    fn test_hive_file_format() {
+        let format = HiveFileFormat::try_create(Some(ORC_INPUT_FORMAT), None).unwrap();
+        assert_eq!(format, HiveFileFormat::Orc);
+
+        let format = HiveFileFormat::try_create(Some(TEXT_INPUT_FORMAT), None).unwrap();
+        assert_eq!(format, HiveFileFormat::Text {
+            field_delimiter: b'\x01',
+            null_format: "\\N".to_string(),
+        });
+
+        let parameters = BTreeMap::from([
+            ("field.delim".to_string(), "|".to_string()),
+            ("serialization.null.format".to_string(), "".to_string()),
+        ]);
+        let format =
+            HiveFileFormat::try_create(Some(TEXT_INPUT_FORMAT), Some(&parameters)).unwrap();
+        assert_eq!(format, HiveFileFormat::Text {
+            field_delimiter: b'|',
+            null_format: "".to_string(),
+        });
+
+        let parameters = BTreeMap::from([("field.delim".to_string(), "9".to_string())]);
+        let format =
+            HiveFileFormat::try_create(Some(TEXT_INPUT_FORMAT), Some(&parameters)).unwrap();
+        assert_eq!(format, HiveFileFormat::Text {
+            field_delimiter: b'\t',
+            null_format: "\\N".to_string(),
+        });
+
+        assert!(
+            HiveFileFormat::try_create(
+                Some("org.apache.hadoop.mapred.SequenceFileInputFormat"),
+                None
+            )
+            .is_err()
+        );
     }
 }
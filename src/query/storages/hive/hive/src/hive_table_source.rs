@@ -39,6 +39,8 @@ use common_pipeline_core::processors::processor::ProcessorPtr;
 use common_pipeline_core::processors::Processor;
 use opendal::Operator;
 
+use crate::hive_file_reader::HiveFileBlocks;
+use crate::hive_file_reader::HiveFileReader;
 use crate::hive_parquet_block_reader::DataBlockDeserializer;
 use crate::hive_parquet_block_reader::HiveBlockReader;
 use crate::HiveBlockFilter;
@@ -51,7 +53,7 @@ struct PreWhereData {
 }
 
 enum State {
-    /// Read parquet file meta data
+    /// Read parquet, orc or text file meta data
     /// IO bound
     ReadMeta(Option<PartInfoPtr>),
 
@@ -74,6 +76,17 @@ enum State {
 
     /// indicates that data blocks are ready, and needs to be consumed
     Generated(HiveBlocks, Vec<DataBlock>),
+
+    /// Read a stripe of orc file or the split of text file (without deserialization)
+    /// IO bound
+    ReadFileData(HiveFileBlocks),
+
+    /// Deserialize the data of orc or text file, and do prewhere filter
+    /// CPU bound
+    DeserializeFileData(HiveFileBlocks, Vec<u8>),
+
+    /// indicates that data blocks of orc or text file are ready, and needs to be consumed
+    FileGenerated(HiveFileBlocks, Vec<DataBlock>),
     Finish,
 }
 
@@ -84,6 +97,8 @@ pub struct HiveTableSource {
     scan_progress: Arc<Progress>,
     prewhere_block_reader: Arc<HiveBlockReader>,
     remain_reader: Arc<Option<HiveBlockReader>>,
+    // reads orc and text files, none for parquet files
+    file_reader: Arc<Option<HiveFileReader>>,
     prewhere_filter: Arc<Option<Expr>>,
     output: Arc<OutputPort>,
     delay: usize,
@@ -103,6 +118,7 @@ impl HiveTableSource {
         output: Arc<OutputPort>,
         prewhere_block_reader: Arc<HiveBlockReader>,
         remain_reader: Arc<Option<HiveBlockReader>>,
+        file_reader: Arc<Option<HiveFileReader>>,
         prewhere_filter: Arc<Option<Expr>>,
         delay: usize,
         hive_block_filter: Arc<HiveBlockFilter>,
@@ -116,6 +132,7 @@ impl HiveTableSource {
             output,
             prewhere_block_reader,
             remain_reader,
+            file_reader,
             prewhere_filter,
             hive_block_filter,
             scan_progress,
@@ -263,6 +280,55 @@ impl HiveTableSource {
         self.state = State::Generated(hive_blocks, datablocks);
         Ok(())
     }
+
+    fn do_deserialize_file_data(
+        &mut self,
+        file_blocks: HiveFileBlocks,
+        data: Vec<u8>,
+    ) -> Result<()> {
+        let file_reader = match self.file_reader.as_ref() {
+            Some(file_reader) => file_reader,
+            None => return Err(ErrorCode::Internal("It's a bug. No file reader")),
+        };
+
+        // 1. deserialize the block to datablocks
+        let func_ctx = self.ctx.get_function_context()?;
+        let datablocks = file_reader.deserialize_block_data(&file_blocks, data, func_ctx)?;
+
+        let progress_values = ProgressValues {
+            rows: datablocks.iter().map(|x| x.num_rows()).sum(),
+            bytes: datablocks.iter().map(|x| x.memory_size()).sum(),
+        };
+        self.scan_progress.incr(&progress_values);
+
+        // 2. do filter, prewhere and remain columns are read together
+        let datablocks = if let Some(filter) = self.prewhere_filter.as_ref() {
+            let (exists, valids) = self.exec_prewhere_filter(filter, &datablocks)?;
+            if !exists {
+                vec![]
+            } else {
+                datablocks
+                    .into_iter()
+                    .zip(valids.iter())
+                    .map(|(datablock, valid)| DataBlock::filter_boolean_value(datablock, valid))
+                    .collect::<Result<Vec<_>>>()?
+            }
+        } else {
+            datablocks
+        };
+
+        let datablocks = datablocks
+            .into_iter()
+            .map(|datablock| datablock.resort(&self.source_schema, &self.output_schema))
+            .collect::<Result<Vec<_>>>()?
+            .into_iter()
+            .filter(|x| !x.is_empty())
+            .collect();
+
+        // 3. trans to generate state
+        self.state = State::FileGenerated(file_blocks, datablocks);
+        Ok(())
+    }
 }
 
 #[async_trait::async_trait]
@@ -323,6 +389,31 @@ impl Processor for HiveTableSource {
             }
         }
 
+        if matches!(self.state, State::FileGenerated(_, _)) {
+            if let State::FileGenerated(mut file_blocks, mut data_blocks) =
+                std::mem::replace(&mut self.state, State::Finish)
+            {
+                // 1. consume all generated blocks,
+                if let Some(data_block) = data_blocks.pop() {
+                    self.output.push_data(Ok(data_block));
+                    // 2. if not all consumed, retain generated state
+                    self.state = State::FileGenerated(file_blocks, data_blocks);
+                    return Ok(Event::NeedConsume);
+                }
+
+                // 3. if all consumed, try next stripe
+                file_blocks.advance();
+                match file_blocks.has_blocks() {
+                    true => {
+                        self.state = State::ReadFileData(file_blocks);
+                    }
+                    false => {
+                        self.try_get_partitions()?;
+                    }
+                }
+            }
+        }
+
         match self.state {
             State::Finish => {
                 self.output.finish();
@@ -333,7 +424,11 @@ impl Processor for HiveTableSource {
             State::ReadRemainData(_, _) => Ok(Event::Async),
             State::PrewhereFilter(_, _) => Ok(Event::Sync),
             State::Deserialize(_, _, _) => Ok(Event::Sync),
-            State::Generated(_, _) => Err(ErrorCode::Internal("It's a bug.")),
+            State::ReadFileData(_) => Ok(Event::Async),
+            State::DeserializeFileData(_, _) => Ok(Event::Sync),
+            State::Generated(_, _) | State::FileGenerated(_, _) => {
+                Err(ErrorCode::Internal("It's a bug."))
+            }
         }
     }
 
@@ -345,6 +440,9 @@ impl Processor for HiveTableSource {
             State::Deserialize(hive_blocks, rowgroup_deserializer, prewhere_data) => {
                 self.do_deserialize(hive_blocks, rowgroup_deserializer, prewhere_data)
             }
+            State::DeserializeFileData(file_blocks, data) => {
+                self.do_deserialize_file_data(file_blocks, data)
+            }
             _ => Err(ErrorCode::Internal("It's a bug.")),
         }
     }
@@ -359,6 +457,19 @@ impl Processor for HiveTableSource {
                     self.delay = 0;
                 }
                 let part = HivePartInfo::from_part(&part)?;
+                if let Some(file_reader) = self.file_reader.as_ref() {
+                    let file_blocks = file_reader.read_meta(part, &self.hive_block_filter).await?;
+                    match file_blocks.has_blocks() {
+                        true => {
+                            self.state = State::ReadFileData(file_blocks);
+                        }
+                        false => {
+                            self.try_get_partitions()?;
+                        }
+                    }
+                    return Ok(());
+                }
+
                 let file_meta = self
                     .prewhere_block_reader
                     .read_meta_data(self.dal.clone(), &part.filename, part.filesize)
@@ -405,6 +516,15 @@ impl Processor for HiveTableSource {
                     Err(ErrorCode::Internal("It's a bug. No remain reader"))
                 }
             }
+            State::ReadFileData(file_blocks) => {
+                if let Some(file_reader) = self.file_reader.as_ref() {
+                    let data = file_reader.read_block_data(&file_blocks).await?;
+                    self.state = State::DeserializeFileData(file_blocks, data);
+                    Ok(())
+                } else {
+                    Err(ErrorCode::Internal("It's a bug. No file reader"))
+                }
+            }
             _ => Err(ErrorCode::Internal("It's a bug.")),
         }
     }
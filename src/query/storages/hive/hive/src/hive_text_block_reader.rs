@@ -0,0 +1,181 @@
+//  Copyright 2023 Datafuse Labs.
+//
+//  Licensed under the Apache License, Version 2.0 (the "License");
+//  you may not use this file except in compliance with the License.
+//  You may obtain a copy of the License at
+//
+//      http://www.apache.org/licenses/LICENSE-2.0
+//
+//  Unless required by applicable law or agreed to in writing, software
+//  distributed under the License is distributed on an "AS IS" BASIS,
+//  WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+//  See the License for the specific language governing permissions and
+//  limitations under the License.
+
+use common_arrow::arrow::bitmap::MutableBitmap;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::type_check::check_cast;
+use common_expression::types::nullable::NullableColumn;
+use common_expression::types::string::StringColumnBuilder;
+use common_expression::types::DataType;
+use common_expression::BlockEntry;
+use common_expression::Column;
+use common_expression::DataBlock;
+use common_expression::Evaluator;
+use common_expression::Expr;
+use common_expression::FunctionContext;
+use common_expression::TableField;
+use common_expression::Value;
+use common_functions::BUILTIN_FUNCTIONS;
+use opendal::Operator;
+
+use crate::HivePartInfo;
+
+// the size to read each time when finishing the last line of a split
+const TEXT_READ_STEP: u64 = 64 * 1024;
+
+// text files are compressed by codecs of hadoop, decided by the file extensions
+const COMPRESSED_EXTENSIONS: [&str; 6] = [".gz", ".bz2", ".deflate", ".snappy", ".lz4", ".zst"];
+
+pub fn check_text_file(part: &HivePartInfo) -> Result<()> {
+    match COMPRESSED_EXTENSIONS
+        .iter()
+        .find(|ext| part.filename.ends_with(*ext))
+    {
+        Some(ext) => Err(ErrorCode::Unimplemented(format!(
+            "compressed hive text file is not supported, {} compressed by {}",
+            part.filename, ext
+        ))),
+        None => Ok(()),
+    }
+}
+
+// read the lines belong to the split, the same as LineRecordReader of hadoop,
+// a line belongs to the split if it starts in the range of the split.
+#[async_backtrace::framed]
+pub async fn read_text_split(operator: &Operator, part: &HivePartInfo) -> Result<Vec<u8>> {
+    let filesize = part.filesize;
+    let start = part.range.start;
+    let end = part.range.end.min(filesize);
+    if start >= end {
+        return Ok(vec![]);
+    }
+
+    // read from the last byte of the previous split, to know whether the first line starts at `start`
+    let mut data = operator
+        .range_read(&part.filename, start.saturating_sub(1)..end)
+        .await?;
+
+    // skip the line belongs to the previous split
+    if start > 0 {
+        match data.iter().position(|b| *b == b'\n') {
+            Some(pos) => {
+                data.drain(..=pos);
+            }
+            None => return Ok(vec![]),
+        }
+        if data.is_empty() {
+            return Ok(vec![]);
+        }
+    }
+
+    // finish the last line, which may end in the next split
+    let mut offset = end;
+    while offset < filesize && data.last() != Some(&b'\n') {
+        let next = (offset + TEXT_READ_STEP).min(filesize);
+        let chunk = operator.range_read(&part.filename, offset..next).await?;
+        match chunk.iter().position(|b| *b == b'\n') {
+            Some(pos) => {
+                data.extend_from_slice(&chunk[..=pos]);
+                break;
+            }
+            None => data.extend_from_slice(&chunk),
+        }
+        offset = next;
+    }
+    Ok(data)
+}
+
+// deserialize lines of text file, the same as LazySimpleSerDe:
+// 1. missing fields and fields equal to the null format are nulls
+// 2. fields are cast to the column types, and invalid values are nulls
+pub fn deserialize_text(
+    data: &[u8],
+    fields: &[TableField],
+    positions: &[usize],
+    field_delimiter: u8,
+    null_format: &str,
+    func_ctx: FunctionContext,
+) -> Result<DataBlock> {
+    let mut lines = data.split(|b| *b == b'\n').collect::<Vec<_>>();
+    // the empty slice after the last line break
+    if data.is_empty() || data.last() == Some(&b'\n') {
+        lines.pop();
+    }
+    let num_rows = lines.len();
+
+    let mut builders = positions
+        .iter()
+        .map(|_| {
+            (
+                StringColumnBuilder::with_capacity(num_rows, 0),
+                MutableBitmap::with_capacity(num_rows),
+            )
+        })
+        .collect::<Vec<_>>();
+    for line in lines {
+        let line = line.strip_suffix(b"\r").unwrap_or(line);
+        let values = line.split(|b| *b == field_delimiter).collect::<Vec<_>>();
+        for (position, (builder, validity)) in positions.iter().zip(builders.iter_mut()) {
+            match values.get(*position) {
+                Some(value) if *value != null_format.as_bytes() => {
+                    builder.put_slice(value);
+                    validity.push(true);
+                }
+                _ => validity.push(false),
+            }
+            builder.commit_row();
+        }
+    }
+
+    let string_type = DataType::String.wrap_nullable();
+    let strings = builders
+        .into_iter()
+        .map(|(builder, validity)| BlockEntry {
+            data_type: string_type.clone(),
+            value: Value::Column(Column::Nullable(Box::new(NullableColumn {
+                column: Column::String(builder.build()),
+                validity: validity.into(),
+            }))),
+        })
+        .collect::<Vec<_>>();
+    let strings = DataBlock::new(strings, num_rows);
+
+    let evaluator = Evaluator::new(&strings, func_ctx, &BUILTIN_FUNCTIONS);
+    let mut columns = Vec::with_capacity(fields.len());
+    for (index, field) in fields.iter().enumerate() {
+        let expr = Expr::ColumnRef {
+            span: None,
+            id: index,
+            data_type: string_type.clone(),
+            display_name: field.name().clone(),
+        };
+        let expr = check_cast(
+            None,
+            true,
+            expr,
+            &field.data_type().into(),
+            &BUILTIN_FUNCTIONS,
+        )?;
+        let value = evaluator
+            .run(&expr)
+            .map_err(|e| e.add_message(format!("cast column {} of text file", field.name())))?;
+        columns.push(BlockEntry {
+            data_type: expr.data_type().clone(),
+            value,
+        });
+    }
+
+    Ok(DataBlock::new(columns, num_rows))
+}
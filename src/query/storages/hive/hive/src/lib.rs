@@ -20,8 +20,10 @@ mod hive_block_filter;
 mod hive_blocks;
 mod hive_catalog;
 mod hive_database;
+mod hive_file_reader;
 mod hive_file_splitter;
 mod hive_meta_data_reader;
+mod hive_orc_block_reader;
 mod hive_parquet_block_reader;
 mod hive_partition;
 mod hive_partition_filler;
@@ -29,6 +31,7 @@ mod hive_partition_pruner;
 mod hive_table;
 mod hive_table_options;
 mod hive_table_source;
+mod hive_text_block_reader;
 mod utils;
 
 pub use hive_block_filter::HiveBlockFilter;
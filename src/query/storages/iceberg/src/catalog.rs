@@ -57,6 +57,7 @@ use futures::TryStreamExt;
 use opendal::Metakey;
 
 use crate::database::IcebergDatabase;
+use crate::table::IcebergTable;
 
 pub const ICEBERG_CATALOG: &str = "iceberg";
 
@@ -194,8 +195,9 @@ impl Catalog for IcebergCatalog {
         unimplemented!()
     }
 
-    fn get_table_by_info(&self, _table_info: &TableInfo) -> Result<Arc<dyn Table>> {
-        unimplemented!()
+    fn get_table_by_info(&self, table_info: &TableInfo) -> Result<Arc<dyn Table>> {
+        let res: Arc<dyn Table> = Arc::new(IcebergTable::try_create(table_info.clone())?);
+        Ok(res)
     }
 
     #[async_backtrace::framed]
@@ -296,7 +298,12 @@ impl Catalog for IcebergCatalog {
         _db_name: &str,
         _req: GetTableCopiedFileReq,
     ) -> Result<GetTableCopiedFileReply> {
-        unimplemented!()
+        // copied files are not tracked for Iceberg tables,
+        // reject instead of loading the same files again.
+        Err(ErrorCode::Unimplemented(
+            "copied files are not tracked for Iceberg tables, \
+             COPY INTO Iceberg tables requires FORCE = true",
+        ))
     }
 
     #[async_backtrace::framed]
@@ -0,0 +1,674 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! this module commits appended data files as new snapshots of iceberg tables
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::Utc;
+use common_base::base::tokio::sync::Mutex as AsyncMutex;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use once_cell::sync::Lazy;
+use opendal::Operator;
+use parking_lot::Mutex;
+use serde_json::json;
+use serde_json::Map;
+use serde_json::Value as JsonValue;
+use tracing::info;
+use uuid::Uuid;
+
+use crate::manifest::read_manifest_list;
+use crate::manifest::write_manifest;
+use crate::manifest::write_manifest_list;
+use crate::manifest::DataFile;
+use crate::manifest::KeyValue;
+use crate::manifest::Manifest;
+use crate::manifest::ManifestPtr;
+use crate::manifest::CONTENT_DATA;
+use crate::manifest::MANIFEST_ENTRY_ADDED;
+use crate::meta_reader::Snapshot;
+use crate::meta_reader::SnapshotMeta;
+use crate::sink::AppendedDataFile;
+use crate::table::IcebergTable;
+use crate::table::META_PTR;
+
+/// times of committing before giving up on conflicts with other writers
+const MAX_COMMIT_ATTEMPTS: usize = 10;
+
+/// table property of the name mapping, resolving columns of data files
+/// written without iceberg field ids, such as the ones written by databend.
+const NAME_MAPPING_PROPERTY: &str = "schema.name-mapping.default";
+
+/// locks of tables being committed on this node, keyed by table locations
+static COMMIT_LOCKS: Lazy<Mutex<HashMap<String, Arc<AsyncMutex<()>>>>> =
+    Lazy::new(|| Mutex::new(HashMap::new()));
+
+/// commit data files written by [`IcebergTableSink`](crate::sink::IcebergTableSink)
+/// as a new snapshot of the table.
+///
+/// `schema_id` and `field_ids` describe the schema the data files are written with,
+/// with `overwrite`, the new snapshot contains the appended data files only.
+///
+/// Object storages provide no atomic renaming or conditional writing through opendal,
+/// so the commit is optimistic: metadata files are named by sequential versions,
+/// and the next version is created under the commit lock of the table only if
+/// no other writer has taken it since the base version was read,
+/// otherwise the commit is rebased and retried on the latest version.
+///
+/// The commit lock only serializes writers of this node, so writing is rejected
+/// in cluster mode. Writers outside databend are not serialized by it either,
+/// a version written by both is read back to detect most of the conflicts,
+/// but a commit may still be lost if the other writer overwrites the version
+/// after it is verified. Tables must not be written by other engines concurrently.
+#[async_backtrace::framed]
+pub(crate) async fn commit_data_files(
+    op: &Operator,
+    schema_id: Option<i64>,
+    field_ids: &[i32],
+    appended: Vec<AppendedDataFile>,
+    overwrite: bool,
+) -> Result<()> {
+    if appended.is_empty() && !overwrite {
+        return Ok(());
+    }
+
+    let snapshot_id = new_snapshot_id();
+    let commit_uuid = Uuid::new_v4();
+    let mut manifest = None;
+
+    for attempt in 1..=MAX_COMMIT_ATTEMPTS {
+        let base = TableVersion::read_latest(op).await?;
+        base.check_writable(schema_id)?;
+
+        // the manifest of appended data files is shared by all attempts
+        if manifest.is_none() && !appended.is_empty() {
+            let path = format!("metadata/{commit_uuid}-m0.avro");
+            manifest = Some(
+                base.write_manifest(op, &path, snapshot_id, field_ids, &appended)
+                    .await?,
+            );
+        }
+
+        let manifest_list = format!("metadata/snap-{snapshot_id}-{attempt}-{commit_uuid}.avro");
+        let next = base
+            .next_version(
+                op,
+                &manifest_list,
+                snapshot_id,
+                schema_id,
+                manifest.clone(),
+                &appended,
+                overwrite,
+            )
+            .await?;
+        if base.try_commit(op, next).await? {
+            return Ok(());
+        }
+        info!(
+            "iceberg commit of snapshot {} conflicts with other writers, attempt: {}",
+            snapshot_id, attempt
+        );
+    }
+
+    Err(ErrorCode::TableVersionMismatched(format!(
+        "cannot commit snapshot {snapshot_id} after {MAX_COMMIT_ATTEMPTS} attempts, \
+         the table is being written by other writers"
+    )))
+}
+
+/// generate a positive snapshot id, the same way as the Java implementation
+fn new_snapshot_id() -> i64 {
+    let (hi, lo) = Uuid::new_v4().as_u64_pair();
+    ((hi ^ lo) & i64::MAX as u64) as i64
+}
+
+/// a version of table metadata, on which a new snapshot is committed
+struct TableVersion {
+    /// path of the metadata file, relative to the table root
+    path: String,
+    /// raw metadata, fields unknown to databend are kept as they are
+    json: Map<String, JsonValue>,
+    snapshots: SnapshotMeta,
+}
+
+impl TableVersion {
+    #[async_backtrace::framed]
+    async fn read_latest(op: &Operator) -> Result<Self> {
+        let path = IcebergTable::version_detect(op).await?;
+        let content = op.read(&path).await.map_err(|e| {
+            ErrorCode::ReadTableDataError(format!("invalid metadata in {path}: {e:?}"))
+        })?;
+        let json = serde_json::from_slice(&content).map_err(|e| {
+            ErrorCode::ReadTableDataError(format!("invalid metadata in {path}: {e:?}"))
+        })?;
+        let snapshots = SnapshotMeta::try_from_json(&content)?;
+        Ok(Self {
+            path,
+            json,
+            snapshots,
+        })
+    }
+
+    fn get_i64(&self, key: &str) -> Option<i64> {
+        self.json.get(key).and_then(JsonValue::as_i64)
+    }
+
+    fn format_version(&self) -> i64 {
+        self.get_i64("format-version").unwrap_or(1)
+    }
+
+    fn spec_id(&self) -> i32 {
+        self.get_i64("default-spec-id").unwrap_or_default() as i32
+    }
+
+    /// json of the current schema
+    fn current_schema(&self) -> Option<&JsonValue> {
+        let schemas = self.json.get("schemas").and_then(JsonValue::as_array);
+        schemas
+            .and_then(|schemas| {
+                schemas.iter().find(|s| {
+                    s.get("schema-id").and_then(JsonValue::as_i64)
+                        == self.snapshots.current_schema_id
+                })
+            })
+            .or_else(|| self.json.get("schema"))
+    }
+
+    /// data files are written with the current schema, without partitioning
+    fn check_writable(&self, schema_id: Option<i64>) -> Result<()> {
+        if self.snapshots.current_schema_id != schema_id {
+            return Err(ErrorCode::TableVersionMismatched(
+                "schema of the Iceberg table is changed during writing",
+            ));
+        }
+
+        let spec_id = self.get_i64("default-spec-id");
+        let partitioned = match self
+            .json
+            .get("partition-specs")
+            .and_then(JsonValue::as_array)
+        {
+            Some(specs) => specs
+                .iter()
+                .filter(|spec| spec.get("spec-id").and_then(JsonValue::as_i64) == spec_id)
+                .any(|spec| has_items(spec.get("fields"))),
+            // early format v1 metadata only has the default spec
+            None => has_items(self.json.get("partition-spec")),
+        };
+        if partitioned {
+            return Err(ErrorCode::Unimplemented(
+                "writing partitioned Iceberg tables is not supported yet",
+            ));
+        }
+        Ok(())
+    }
+
+    /// write the manifest of appended data files,
+    /// sequence numbers of the returned pointer are assigned by each attempt.
+    #[async_backtrace::framed]
+    async fn write_manifest(
+        &self,
+        op: &Operator,
+        path: &str,
+        snapshot_id: i64,
+        field_ids: &[i32],
+        appended: &[AppendedDataFile],
+    ) -> Result<ManifestPtr> {
+        let entries = appended
+            .iter()
+            .map(|f| Manifest {
+                status: MANIFEST_ENTRY_ADDED,
+                snapshot_id: Some(snapshot_id),
+                data_file: self.data_file(field_ids, f),
+            })
+            .collect::<Vec<_>>();
+
+        let schema = self.current_schema().cloned().unwrap_or_default();
+        let metadata = [
+            ("schema", schema.to_string()),
+            (
+                "schema-id",
+                schema
+                    .get("schema-id")
+                    .cloned()
+                    .unwrap_or_default()
+                    .to_string(),
+            ),
+            ("partition-spec", "[]".to_string()),
+            ("partition-spec-id", self.spec_id().to_string()),
+            ("format-version", self.format_version().to_string()),
+            ("content", "data".to_string()),
+        ];
+        let length = write_manifest(op, path, &entries, &metadata).await?;
+
+        Ok(ManifestPtr {
+            manifest_path: self.snapshots.absolute_path(path),
+            manifest_length: length,
+            partition_spec_id: self.spec_id(),
+            content: CONTENT_DATA,
+            added_snapshot_id: Some(snapshot_id),
+            added_data_files_count: Some(appended.len() as i32),
+            existing_data_files_count: Some(0),
+            deleted_data_files_count: Some(0),
+            partitions: Some(vec![]),
+            added_rows_count: Some(appended.iter().map(|f| f.record_count as i64).sum()),
+            existing_rows_count: Some(0),
+            deleted_rows_count: Some(0),
+            sequence_number: 0,
+            min_sequence_number: 0,
+        })
+    }
+
+    /// statistics of top level columns are keyed by their field ids
+    fn data_file(&self, field_ids: &[i32], appended: &AppendedDataFile) -> DataFile {
+        let value_counts = field_ids
+            .iter()
+            .map(|id| KeyValue {
+                key: *id,
+                value: appended.record_count as i64,
+            })
+            .collect();
+        let null_value_counts = field_ids
+            .iter()
+            .zip(&appended.null_counts)
+            .map(|(id, count)| KeyValue {
+                key: *id,
+                value: *count as i64,
+            })
+            .collect();
+        let (lower_bounds, upper_bounds) = field_ids
+            .iter()
+            .zip(&appended.bounds)
+            .filter_map(|(id, bounds)| {
+                bounds.as_ref().map(|(lower, upper)| {
+                    (
+                        KeyValue {
+                            key: *id,
+                            value: lower.clone(),
+                        },
+                        KeyValue {
+                            key: *id,
+                            value: upper.clone(),
+                        },
+                    )
+                })
+            })
+            .unzip();
+
+        DataFile {
+            content: CONTENT_DATA,
+            file_path: self.snapshots.absolute_path(&appended.path),
+            file_format: "PARQUET".to_string(),
+            record_count: appended.record_count as i64,
+            file_size_in_bytes: appended.file_size as i64,
+            column_sizes: None,
+            value_counts: Some(value_counts),
+            null_value_counts: Some(null_value_counts),
+            lower_bounds: Some(lower_bounds),
+            upper_bounds: Some(upper_bounds),
+            split_offsets: None,
+        }
+    }
+
+    /// manifests of a snapshot, to be inherited by the new snapshot
+    #[async_backtrace::framed]
+    async fn manifests_of(&self, op: &Operator, snapshot: &Snapshot) -> Result<Vec<ManifestPtr>> {
+        if let Some(manifest_list) = &snapshot.manifest_list {
+            let manifest_list = self.snapshots.relative_path(manifest_list)?;
+            return read_manifest_list(op, &manifest_list).await;
+        }
+
+        // manifests listed in metadata by early format v1 writers
+        let mut ptrs = vec![];
+        for path in snapshot.manifests.iter().flatten() {
+            let meta = op.stat(&self.snapshots.relative_path(path)?).await?;
+            ptrs.push(ManifestPtr {
+                manifest_path: path.clone(),
+                manifest_length: meta.content_length() as i64,
+                partition_spec_id: self.spec_id(),
+                content: CONTENT_DATA,
+                added_snapshot_id: Some(snapshot.snapshot_id),
+                added_data_files_count: None,
+                existing_data_files_count: None,
+                deleted_data_files_count: None,
+                partitions: None,
+                added_rows_count: None,
+                existing_rows_count: None,
+                deleted_rows_count: None,
+                sequence_number: 0,
+                min_sequence_number: 0,
+            });
+        }
+        Ok(ptrs)
+    }
+
+    /// write the manifest list of the new snapshot,
+    /// returning the next version of metadata with the snapshot as the current one.
+    #[allow(clippy::too_many_arguments)]
+    #[async_backtrace::framed]
+    async fn next_version(
+        &self,
+        op: &Operator,
+        manifest_list: &str,
+        snapshot_id: i64,
+        schema_id: Option<i64>,
+        manifest: Option<ManifestPtr>,
+        appended: &[AppendedDataFile],
+        overwrite: bool,
+    ) -> Result<Map<String, JsonValue>> {
+        let format_version = self.format_version();
+        let sequence_number = match format_version {
+            1 => 0,
+            _ => self.get_i64("last-sequence-number").unwrap_or_default() + 1,
+        };
+        let parent = self.snapshots.current_snapshot();
+
+        let mut manifests = match parent {
+            Some(parent) if !overwrite => self.manifests_of(op, parent).await?,
+            _ => vec![],
+        };
+        if let Some(mut manifest) = manifest {
+            manifest.sequence_number = sequence_number;
+            manifest.min_sequence_number = sequence_number;
+            manifests.insert(0, manifest);
+        }
+        let parent_id = parent.map(|s| s.snapshot_id);
+        let metadata = [
+            ("snapshot-id", snapshot_id.to_string()),
+            ("parent-snapshot-id", json!(parent_id).to_string()),
+            ("sequence-number", sequence_number.to_string()),
+            ("format-version", format_version.to_string()),
+        ];
+        write_manifest_list(op, manifest_list, &manifests, &metadata).await?;
+
+        let now = Utc::now().timestamp_millis();
+        let operation = if overwrite { "overwrite" } else { "append" };
+        let mut snapshot = json!({
+            "snapshot-id": snapshot_id,
+            "timestamp-ms": now,
+            "summary": {
+                "operation": operation,
+                "added-data-files": appended.len().to_string(),
+                "added-records": appended.iter().map(|f| f.record_count).sum::<u64>().to_string(),
+                "added-files-size": appended.iter().map(|f| f.file_size).sum::<u64>().to_string(),
+            },
+            "manifest-list": self.snapshots.absolute_path(manifest_list),
+            "schema-id": schema_id,
+        });
+        if let Some(parent_id) = parent_id {
+            snapshot["parent-snapshot-id"] = json!(parent_id);
+        }
+        if format_version > 1 {
+            snapshot["sequence-number"] = json!(sequence_number);
+        }
+
+        let mut next = self.json.clone();
+        if format_version > 1 {
+            next.insert("last-sequence-number".to_string(), json!(sequence_number));
+        }
+        next.insert("last-updated-ms".to_string(), json!(now));
+        next.insert("current-snapshot-id".to_string(), json!(snapshot_id));
+        push_item(&mut next, "snapshots", snapshot);
+        push_item(
+            &mut next,
+            "snapshot-log",
+            json!({ "timestamp-ms": now, "snapshot-id": snapshot_id }),
+        );
+        push_item(
+            &mut next,
+            "metadata-log",
+            json!({
+                "timestamp-ms": self.get_i64("last-updated-ms"),
+                "metadata-file": self.snapshots.absolute_path(&self.path),
+            }),
+        );
+        if let Some(refs) = next
+            .entry("refs")
+            .or_insert_with(|| json!({}))
+            .as_object_mut()
+        {
+            refs.insert(
+                "main".to_string(),
+                json!({ "snapshot-id": snapshot_id, "type": "branch" }),
+            );
+        }
+        if let (Some(properties), Some(schema)) = (
+            next.entry("properties")
+                .or_insert_with(|| json!({}))
+                .as_object_mut(),
+            self.current_schema(),
+        ) {
+            properties
+                .entry(NAME_MAPPING_PROPERTY)
+                .or_insert_with(|| name_mapping(schema).to_string().into());
+        }
+        Ok(next)
+    }
+
+    /// write the next version of metadata,
+    /// returning false if other writers have committed since this version.
+    ///
+    /// checking the latest version and creating the next one are not atomic on
+    /// object storages, they are serialized by the commit lock of the table,
+    /// so that a version is never taken by two writers of this node.
+    /// The version is read back after writing, in case other writers created it
+    /// between the check and the write, the later write wins.
+    #[async_backtrace::framed]
+    async fn try_commit(&self, op: &Operator, next: Map<String, JsonValue>) -> Result<bool> {
+        let (path, version) = self.next_path()?;
+        let lock = COMMIT_LOCKS
+            .lock()
+            .entry(self.snapshots.location.clone())
+            .or_default()
+            .clone();
+        let _guard = lock.lock().await;
+
+        if IcebergTable::version_detect(op).await? != self.path || op.is_exist(&path).await? {
+            return Ok(false);
+        }
+        op.write(&path, serde_json::to_vec(&next)?).await?;
+        if !is_written(op, &path, &next).await? {
+            return Ok(false);
+        }
+        op.write(META_PTR, version.to_string()).await?;
+        Ok(true)
+    }
+
+    /// metadata files are named either `v<version>.metadata.json`
+    /// or `<version>-<uuid>.metadata.json`, the next version is always named
+    /// `v<version>.metadata.json` and recorded in the version hint file.
+    fn next_path(&self) -> Result<(String, u64)> {
+        let name = self
+            .path
+            .trim_start_matches("metadata/")
+            .trim_end_matches(".metadata.json");
+
+        let version = match name.strip_prefix('v') {
+            Some(version) => version.parse::<u64>().ok(),
+            None => name
+                .split_once('-')
+                .and_then(|(version, _)| version.parse::<u64>().ok()),
+        };
+        match version {
+            Some(version) => Ok((
+                format!("metadata/v{}.metadata.json", version + 1),
+                version + 1,
+            )),
+            None => Err(ErrorCode::Unimplemented(format!(
+                "cannot name the next version of Iceberg metadata file {}",
+                self.path
+            ))),
+        }
+    }
+}
+
+/// whether the metadata file at `path` is still the version written by this commit,
+/// the snapshot ids are unique to commits.
+#[async_backtrace::framed]
+async fn is_written(op: &Operator, path: &str, written: &Map<String, JsonValue>) -> Result<bool> {
+    let content = op.read(path).await?;
+    let json: Map<String, JsonValue> = serde_json::from_slice(&content)
+        .map_err(|e| ErrorCode::ReadTableDataError(format!("invalid metadata in {path}: {e:?}")))?;
+    Ok(json.get("current-snapshot-id") == written.get("current-snapshot-id"))
+}
+
+fn has_items(value: Option<&JsonValue>) -> bool {
+    value
+        .and_then(JsonValue::as_array)
+        .map_or(false, |items| !items.is_empty())
+}
+
+fn push_item(json: &mut Map<String, JsonValue>, key: &str, item: JsonValue) {
+    if let Some(items) = json.entry(key).or_insert_with(|| json!([])).as_array_mut() {
+        items.push(item);
+    }
+}
+
+/// map top level fields to the names of columns in data files,
+/// databend writes column names in lower case.
+fn name_mapping(schema: &JsonValue) -> JsonValue {
+    let fields = schema
+        .get("fields")
+        .and_then(JsonValue::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|field| {
+            let name = field.get("name")?.as_str()?;
+            let mut names = vec![name.to_string()];
+            if name.to_lowercase() != name {
+                names.push(name.to_lowercase());
+            }
+            Some(json!({ "field-id": field.get("id")?, "names": names }))
+        })
+        .collect::<Vec<_>>();
+    JsonValue::Array(fields)
+}
+
+#[cfg(test)]
+mod commit_test {
+    use common_exception::Result;
+    use opendal::services::Memory;
+    use opendal::Operator;
+
+    use super::commit_data_files;
+    use super::is_written;
+    use super::TableVersion;
+    use crate::table::META_PTR;
+
+    const METADATA_FILE: &str = r#"
+    {
+        "format-version": 2,
+        "table-uuid": "fb072c92-a02b-11e9-ae9c-1bb7bc9eca94",
+        "location": "s3://bkt/tbl",
+        "last-sequence-number": 0,
+        "last-updated-ms": 1515100955770,
+        "last-column-id": 1,
+        "current-schema-id": 0,
+        "schemas": [
+            {
+                "schema-id": 0,
+                "type": "struct",
+                "fields": [{"id": 1, "name": "a", "required": false, "type": "int"}]
+            }
+        ],
+        "default-spec-id": 0,
+        "partition-specs": [{"spec-id": 0, "fields": []}],
+        "last-partition-id": 999,
+        "default-sort-order-id": 0,
+        "sort-orders": [{"order-id": 0, "fields": []}],
+        "properties": {},
+        "current-snapshot-id": -1,
+        "snapshots": []
+    }
+"#;
+
+    async fn create_table() -> Result<Operator> {
+        let op = Operator::new(Memory::default())?.finish();
+        op.write(
+            "metadata/v1.metadata.json",
+            METADATA_FILE.as_bytes().to_vec(),
+        )
+        .await?;
+        op.write(META_PTR, "1".to_string()).await?;
+        Ok(op)
+    }
+
+    #[tokio::test]
+    async fn test_commit_conflict() -> Result<()> {
+        let op = create_table().await?;
+        let first = TableVersion::read_latest(&op).await?;
+        let second = TableVersion::read_latest(&op).await?;
+
+        let next = first
+            .next_version(&op, "metadata/snap-1.avro", 1, Some(0), None, &[], true)
+            .await?;
+        assert!(first.try_commit(&op, next).await?);
+
+        // the version is taken by the first commit
+        let next = second
+            .next_version(&op, "metadata/snap-2.avro", 2, Some(0), None, &[], true)
+            .await?;
+        assert!(!second.try_commit(&op, next).await?);
+
+        let latest = TableVersion::read_latest(&op).await?;
+        assert_eq!(latest.path, "metadata/v2.metadata.json");
+        assert_eq!(latest.snapshots.current_snapshot_id, Some(1));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_commit_overwritten() -> Result<()> {
+        let op = create_table().await?;
+        let first = TableVersion::read_latest(&op).await?;
+        let second = TableVersion::read_latest(&op).await?;
+
+        let first_next = first
+            .next_version(&op, "metadata/snap-1.avro", 1, Some(0), None, &[], true)
+            .await?;
+        let second_next = second
+            .next_version(&op, "metadata/snap-2.avro", 2, Some(0), None, &[], true)
+            .await?;
+
+        // both writers passed the check of the version, the later write wins
+        let path = "metadata/v2.metadata.json";
+        op.write(path, serde_json::to_vec(&first_next)?).await?;
+        op.write(path, serde_json::to_vec(&second_next)?).await?;
+        assert!(!is_written(&op, path, &first_next).await?);
+        assert!(is_written(&op, path, &second_next).await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_concurrent_commits() -> Result<()> {
+        let op = create_table().await?;
+        futures::try_join!(
+            commit_data_files(&op, Some(0), &[], vec![], true),
+            commit_data_files(&op, Some(0), &[], vec![], true),
+        )?;
+
+        // the conflicting commit is rebased on the latest version
+        let latest = TableVersion::read_latest(&op).await?;
+        assert_eq!(latest.path, "metadata/v3.metadata.json");
+        assert_eq!(latest.snapshots.snapshots.len(), 2);
+        let parent = latest
+            .snapshots
+            .current_snapshot()
+            .unwrap()
+            .parent_snapshot_id;
+        assert_eq!(parent, Some(latest.snapshots.snapshots[0].snapshot_id));
+        Ok(())
+    }
+}
@@ -16,9 +16,14 @@
 //! to databend
 
 use chrono::Utc;
+use common_expression::types::decimal::DecimalScalar;
 use common_expression::types::decimal::DecimalSize;
+use common_expression::types::number::NumberScalar;
+use common_expression::types::number::F32;
+use common_expression::types::number::F64;
 use common_expression::types::DecimalDataType;
 use common_expression::types::NumberDataType;
+use common_expression::Scalar;
 use common_expression::TableDataType;
 use common_expression::TableField;
 use common_expression::TableSchema;
@@ -38,7 +43,12 @@ pub(crate) fn meta_iceberg_to_databend(
     meta: &TableMetadata,
 ) -> TableMeta {
     let meta = meta.clone().to_latest();
-    let schema = match meta.schemas.last() {
+    let current_schema = meta
+        .schemas
+        .iter()
+        .find(|s| s.schema_id == meta.current_schema_id)
+        .or_else(|| meta.schemas.last());
+    let schema = match current_schema {
         Some(scm) => schema_iceberg_to_databend(scm),
         // empty schema
         None => TableSchema::empty(),
@@ -68,7 +78,8 @@ pub(super) fn schema_iceberg_to_databend(schema: &SchemaV2) -> TableSchema {
 }
 
 fn struct_field_iceberg_to_databend(sf: &StructField) -> TableField {
-    let name = &sf.name;
+    // column names are lower cased, the same as what parquet reader does
+    let name = &sf.name.to_lowercase();
     let ty = primitive_iceberg_to_databend(&sf.field_type);
 
     if sf.required {
@@ -84,7 +95,7 @@ fn primitive_iceberg_to_databend(prim: &AllType) -> TableDataType {
         iceberg_rs::model::schema::AllType::Primitive(p) => match p {
             iceberg_rs::model::schema::PrimitiveType::Boolean => TableDataType::Boolean,
             iceberg_rs::model::schema::PrimitiveType::Int => {
-                TableDataType::Number(NumberDataType::UInt32)
+                TableDataType::Number(NumberDataType::Int32)
             }
             iceberg_rs::model::schema::PrimitiveType::Long => {
                 TableDataType::Number(NumberDataType::Int64)
@@ -127,7 +138,7 @@ fn primitive_iceberg_to_databend(prim: &AllType) -> TableDataType {
                 .sorted_by_key(|f| f.id)
                 .map(|field| {
                     (
-                        field.name.clone(),
+                        field.name.to_lowercase(),
                         primitive_iceberg_to_databend(&field.field_type),
                     )
                 })
@@ -157,12 +168,91 @@ fn primitive_iceberg_to_databend(prim: &AllType) -> TableDataType {
     }
 }
 
+/// decode lower and upper bounds recorded in manifests to databend scalars
+///
+/// bounds are stored following the "Binary single-value serialization"
+/// of the iceberg spec, `None` is returned for types not supported.
+pub(crate) fn bound_iceberg_to_databend(ty: &TableDataType, bytes: &[u8]) -> Option<Scalar> {
+    let scalar = match ty.remove_nullable() {
+        TableDataType::Boolean => Scalar::Boolean(*bytes.first()? != 0),
+        TableDataType::Number(NumberDataType::Int32) => Scalar::Number(NumberScalar::Int32(
+            i32::from_le_bytes(bytes.try_into().ok()?),
+        )),
+        TableDataType::Number(NumberDataType::Int64) => Scalar::Number(NumberScalar::Int64(
+            i64::from_le_bytes(bytes.try_into().ok()?),
+        )),
+        TableDataType::Number(NumberDataType::Float32) => Scalar::Number(NumberScalar::Float32(
+            F32::from(f32::from_le_bytes(bytes.try_into().ok()?)),
+        )),
+        TableDataType::Number(NumberDataType::Float64) => Scalar::Number(NumberScalar::Float64(
+            F64::from(f64::from_le_bytes(bytes.try_into().ok()?)),
+        )),
+        // days from unix epoch
+        TableDataType::Date => Scalar::Date(i32::from_le_bytes(bytes.try_into().ok()?)),
+        // microseconds from unix epoch
+        TableDataType::Timestamp => Scalar::Timestamp(i64::from_le_bytes(bytes.try_into().ok()?)),
+        TableDataType::String => Scalar::String(bytes.to_vec()),
+        // unscaled value in big-endian two's-complement, using the minimum number of bytes
+        TableDataType::Decimal(DecimalDataType::Decimal128(size)) => {
+            if bytes.is_empty() || bytes.len() > 16 {
+                return None;
+            }
+            let fill = if bytes[0] & 0x80 != 0 { 0xff } else { 0x00 };
+            let mut buf = [fill; 16];
+            buf[16 - bytes.len()..].copy_from_slice(bytes);
+            Scalar::Decimal(DecimalScalar::Decimal128(i128::from_be_bytes(buf), size))
+        }
+        _ => return None,
+    };
+    Some(scalar)
+}
+
+/// encode a databend scalar as an iceberg column bound,
+/// the inverse of [`bound_iceberg_to_databend`]
+pub(crate) fn bound_databend_to_iceberg(scalar: &Scalar) -> Option<Vec<u8>> {
+    let bytes = match scalar {
+        Scalar::Boolean(v) => vec![*v as u8],
+        Scalar::Number(NumberScalar::Int32(v)) => v.to_le_bytes().to_vec(),
+        Scalar::Number(NumberScalar::Int64(v)) => v.to_le_bytes().to_vec(),
+        Scalar::Number(NumberScalar::Float32(v)) => v.0.to_le_bytes().to_vec(),
+        Scalar::Number(NumberScalar::Float64(v)) => v.0.to_le_bytes().to_vec(),
+        Scalar::Date(v) => v.to_le_bytes().to_vec(),
+        Scalar::Timestamp(v) => v.to_le_bytes().to_vec(),
+        Scalar::String(v) => v.clone(),
+        Scalar::Decimal(DecimalScalar::Decimal128(v, _)) => {
+            let bytes = v.to_be_bytes();
+            // strip redundant sign bytes, keeping the sign bit of the first byte
+            let mut start = 0;
+            while start < 15 {
+                let redundant = (bytes[start] == 0x00 && bytes[start + 1] & 0x80 == 0)
+                    || (bytes[start] == 0xff && bytes[start + 1] & 0x80 != 0);
+                if !redundant {
+                    break;
+                }
+                start += 1;
+            }
+            bytes[start..].to_vec()
+        }
+        _ => return None,
+    };
+    Some(bytes)
+}
+
 #[cfg(test)]
 mod convert_test {
+    use common_expression::types::decimal::DecimalScalar;
+    use common_expression::types::decimal::DecimalSize;
+    use common_expression::types::number::NumberScalar;
+    use common_expression::types::DecimalDataType;
+    use common_expression::types::NumberDataType;
+    use common_expression::Scalar;
+    use common_expression::TableDataType;
     use common_meta_app::storage::StorageFsConfig;
     use common_meta_app::storage::StorageParams;
     use iceberg_rs::model::table::TableMetadata;
 
+    use super::bound_databend_to_iceberg;
+    use super::bound_iceberg_to_databend;
     use super::meta_iceberg_to_databend;
 
     /// example metadata file
@@ -234,4 +324,55 @@ mod convert_test {
         assert_eq!(converted.engine, "iceberg");
         assert_eq!(converted.catalog, "ctl");
     }
+
+    #[test]
+    fn test_decode_bounds() {
+        let int_ty = TableDataType::Number(NumberDataType::Int32).wrap_nullable();
+        assert_eq!(
+            bound_iceberg_to_databend(&int_ty, &(-7i32).to_le_bytes()),
+            Some(Scalar::Number(NumberScalar::Int32(-7)))
+        );
+        assert_eq!(
+            bound_iceberg_to_databend(&TableDataType::Timestamp, &42i64.to_le_bytes()),
+            Some(Scalar::Timestamp(42))
+        );
+        assert_eq!(
+            bound_iceberg_to_databend(&TableDataType::String, b"abc"),
+            Some(Scalar::String(b"abc".to_vec()))
+        );
+
+        let size = DecimalSize {
+            precision: 10,
+            scale: 2,
+        };
+        let decimal_ty = TableDataType::Decimal(DecimalDataType::Decimal128(size));
+        // -1234 in minimal big-endian two's complement
+        assert_eq!(
+            bound_iceberg_to_databend(&decimal_ty, &[0xfb, 0x2e]),
+            Some(Scalar::Decimal(DecimalScalar::Decimal128(-1234, size)))
+        );
+
+        // malformed bounds are ignored
+        assert_eq!(bound_iceberg_to_databend(&int_ty, &[1, 2]), None);
+    }
+
+    #[test]
+    fn test_encode_bounds() {
+        let int_ty = TableDataType::Number(NumberDataType::Int32).wrap_nullable();
+        let int = Scalar::Number(NumberScalar::Int32(-7));
+        let encoded = bound_databend_to_iceberg(&int).unwrap();
+        assert_eq!(bound_iceberg_to_databend(&int_ty, &encoded), Some(int));
+
+        let size = DecimalSize {
+            precision: 10,
+            scale: 2,
+        };
+        let decimal = Scalar::Decimal(DecimalScalar::Decimal128(-1234, size));
+        assert_eq!(bound_databend_to_iceberg(&decimal), Some(vec![0xfb, 0x2e]));
+        let decimal = Scalar::Decimal(DecimalScalar::Decimal128(128, size));
+        assert_eq!(bound_databend_to_iceberg(&decimal), Some(vec![0x00, 0x80]));
+
+        // nested types have no bounds
+        assert_eq!(bound_databend_to_iceberg(&Scalar::EmptyArray), None);
+    }
 }
@@ -89,16 +89,20 @@
 
 /// the Iceberg Catalog implementation
 mod catalog;
+/// committing new snapshots
+mod commit;
 /// data converters
 mod converters;
 /// database implementation
 mod database;
-/// reading manifestlist and manifest files
+/// reading and writing manifestlist and manifest files
 #[allow(unused)]
 mod manifest;
 /// table metadata reader
 #[allow(unused)]
 mod meta_reader;
+/// writing data files
+mod sink;
 /// table implementation
 mod table;
 
@@ -14,54 +14,517 @@
 
 use std::collections::HashMap;
 
+use apache_avro::types::Value as AvroValue;
+use apache_avro::Reader as AvroReader;
+use apache_avro::Schema as AvroSchema;
+use apache_avro::Writer as AvroWriter;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use opendal::Operator;
+use serde::de::DeserializeOwned;
 use serde::Deserialize;
+use serde::Serialize;
+
+/// `status` of a manifest entry marking the data file as added in the snapshot
+pub(crate) const MANIFEST_ENTRY_ADDED: i32 = 1;
+/// `status` of a manifest entry marking the data file as deleted
+pub(crate) const MANIFEST_ENTRY_DELETED: i32 = 2;
+/// `content` of manifests and data files holding data rows
+pub(crate) const CONTENT_DATA: i32 = 0;
+
+/// avro schema of manifest entries written by databend
+///
+/// it is a superset of the schemas of format v1 and v2,
+/// readers project fields they know by field ids.
+const MANIFEST_ENTRY_SCHEMA: &str = r#"{
+    "type": "record",
+    "name": "manifest_entry",
+    "fields": [
+        {"name": "status", "type": "int", "field-id": 0},
+        {"name": "snapshot_id", "type": ["null", "long"], "default": null, "field-id": 1},
+        {"name": "sequence_number", "type": ["null", "long"], "default": null, "field-id": 3},
+        {"name": "file_sequence_number", "type": ["null", "long"], "default": null, "field-id": 4},
+        {"name": "data_file", "field-id": 2, "type": {
+            "type": "record",
+            "name": "r2",
+            "fields": [
+                {"name": "content", "type": "int", "default": 0, "field-id": 134},
+                {"name": "file_path", "type": "string", "field-id": 100},
+                {"name": "file_format", "type": "string", "field-id": 101},
+                {"name": "partition", "type": {"type": "record", "name": "r102", "fields": []}, "field-id": 102},
+                {"name": "record_count", "type": "long", "field-id": 103},
+                {"name": "file_size_in_bytes", "type": "long", "field-id": 104},
+                {"name": "block_size_in_bytes", "type": "long", "field-id": 105},
+                {"name": "column_sizes", "type": ["null", {"type": "array", "logicalType": "map", "items": {
+                    "type": "record", "name": "k117_v118", "fields": [
+                        {"name": "key", "type": "int", "field-id": 117},
+                        {"name": "value", "type": "long", "field-id": 118}
+                    ]}}], "default": null, "field-id": 108},
+                {"name": "value_counts", "type": ["null", {"type": "array", "logicalType": "map", "items": {
+                    "type": "record", "name": "k119_v120", "fields": [
+                        {"name": "key", "type": "int", "field-id": 119},
+                        {"name": "value", "type": "long", "field-id": 120}
+                    ]}}], "default": null, "field-id": 109},
+                {"name": "null_value_counts", "type": ["null", {"type": "array", "logicalType": "map", "items": {
+                    "type": "record", "name": "k121_v122", "fields": [
+                        {"name": "key", "type": "int", "field-id": 121},
+                        {"name": "value", "type": "long", "field-id": 122}
+                    ]}}], "default": null, "field-id": 110},
+                {"name": "lower_bounds", "type": ["null", {"type": "array", "logicalType": "map", "items": {
+                    "type": "record", "name": "k126_v127", "fields": [
+                        {"name": "key", "type": "int", "field-id": 126},
+                        {"name": "value", "type": "bytes", "field-id": 127}
+                    ]}}], "default": null, "field-id": 125},
+                {"name": "upper_bounds", "type": ["null", {"type": "array", "logicalType": "map", "items": {
+                    "type": "record", "name": "k129_v130", "fields": [
+                        {"name": "key", "type": "int", "field-id": 129},
+                        {"name": "value", "type": "bytes", "field-id": 130}
+                    ]}}], "default": null, "field-id": 128},
+                {"name": "split_offsets", "type": ["null", {"type": "array", "items": "long", "element-id": 133}],
+                    "default": null, "field-id": 132}
+            ]
+        }}
+    ]
+}"#;
+
+/// avro schema of manifest lists written by databend,
+/// a superset of the schemas of format v1 and v2 as well.
+const MANIFEST_FILE_SCHEMA: &str = r#"{
+    "type": "record",
+    "name": "manifest_file",
+    "fields": [
+        {"name": "manifest_path", "type": "string", "field-id": 500},
+        {"name": "manifest_length", "type": "long", "field-id": 501},
+        {"name": "partition_spec_id", "type": "int", "field-id": 502},
+        {"name": "content", "type": "int", "default": 0, "field-id": 517},
+        {"name": "sequence_number", "type": "long", "default": 0, "field-id": 515},
+        {"name": "min_sequence_number", "type": "long", "default": 0, "field-id": 516},
+        {"name": "added_snapshot_id", "type": ["null", "long"], "default": null, "field-id": 503},
+        {"name": "added_data_files_count", "type": ["null", "int"], "default": null, "field-id": 504},
+        {"name": "existing_data_files_count", "type": ["null", "int"], "default": null, "field-id": 505},
+        {"name": "deleted_data_files_count", "type": ["null", "int"], "default": null, "field-id": 506},
+        {"name": "added_rows_count", "type": ["null", "long"], "default": null, "field-id": 512},
+        {"name": "existing_rows_count", "type": ["null", "long"], "default": null, "field-id": 513},
+        {"name": "deleted_rows_count", "type": ["null", "long"], "default": null, "field-id": 514},
+        {"name": "partitions", "type": ["null", {"type": "array", "element-id": 508, "items": {
+            "type": "record", "name": "r508", "fields": [
+                {"name": "contains_null", "type": "boolean", "field-id": 509},
+                {"name": "contains_nan", "type": ["null", "boolean"], "default": null, "field-id": 518},
+                {"name": "lower_bound", "type": ["null", "bytes"], "default": null, "field-id": 510},
+                {"name": "upper_bound", "type": ["null", "bytes"], "default": null, "field-id": 511}
+            ]}}], "default": null, "field-id": 507}
+    ]
+}"#;
+
+/// `block_size_in_bytes` is required by format v1 but never used,
+/// the default value of Java implementation is written.
+const DEFAULT_BLOCK_SIZE: i64 = 64 * 1024 * 1024;
 
 /// item in manifest list file
 /// read manifest file by this struct
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct ManifestPtr {
     pub manifest_path: String,
-    pub manifest_length: usize,
-    pub partition_spec_id: usize,
-    pub added_snapshot_id: usize,
-    pub existing_data_files_count: usize,
-    pub deleted_data_files_count: usize,
-    pub partitions: Vec<ManiPart>,
-    pub added_rows_count: usize,
-    pub existing_rows_count: usize,
-    pub deleted_rows_count: usize,
+    pub manifest_length: i64,
+    pub partition_spec_id: i32,
+    /// 0 for data manifests, 1 for delete manifests, absent in format v1
+    #[serde(default)]
+    pub content: i32,
+    pub added_snapshot_id: Option<i64>,
+    #[serde(alias = "added_files_count")]
+    pub added_data_files_count: Option<i32>,
+    #[serde(alias = "existing_files_count")]
+    pub existing_data_files_count: Option<i32>,
+    #[serde(alias = "deleted_files_count")]
+    pub deleted_data_files_count: Option<i32>,
+    pub partitions: Option<Vec<ManiPart>>,
+    pub added_rows_count: Option<i64>,
+    pub existing_rows_count: Option<i64>,
+    pub deleted_rows_count: Option<i64>,
+    /// sequence numbers are absent in format v1
+    #[serde(default)]
+    pub sequence_number: i64,
+    #[serde(default)]
+    pub min_sequence_number: i64,
 }
 
 /// item of manifest spec in `ManifestPtr`
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct ManiPart {
     pub contains_null: bool,
-    pub lower_bound: String,
-    pub upper_bound: String,
+    pub contains_nan: Option<bool>,
+    pub lower_bound: Option<Vec<u8>>,
+    pub upper_bound: Option<Vec<u8>>,
 }
 
 /// manifest file
 #[derive(Clone, Debug, Deserialize)]
 pub(crate) struct Manifest {
-    pub status: isize,
-    pub snapshot_id: i64,
+    /// 0: EXISTING, 1: ADDED, 2: DELETED
+    pub status: i32,
+    pub snapshot_id: Option<i64>,
     pub data_file: DataFile,
 }
 
 /// data file
-#[derive(Clone, Debug, Deserialize)]
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
 pub(crate) struct DataFile {
+    /// 0 for data files, 1 for position deletes, 2 for equality deletes,
+    /// absent in format v1
+    #[serde(default)]
+    pub content: i32,
     pub file_path: String,
     pub file_format: String,
-    pub partition: HashMap<String, u64>,
-    pub record_count: usize,
-    pub file_size_in_bytes: usize,
-    pub block_size_in_bytes: usize,
-    pub column_sizes: HashMap<u64, u64>,
-    pub value_counts: HashMap<u64, u64>,
-    pub null_value_counts: HashMap<u64, u64>,
-    pub lower_bounds: HashMap<u64, String>,
-    pub upper_bounds: HashMap<u64, String>,
-    pub key_metadata: Option<String>,
-    pub split_offsets: Vec<u64>,
+    pub record_count: i64,
+    pub file_size_in_bytes: i64,
+    pub column_sizes: Option<Vec<KeyValue<i64>>>,
+    pub value_counts: Option<Vec<KeyValue<i64>>>,
+    pub null_value_counts: Option<Vec<KeyValue<i64>>>,
+    pub lower_bounds: Option<Vec<KeyValue<Vec<u8>>>>,
+    pub upper_bounds: Option<Vec<KeyValue<Vec<u8>>>>,
+    pub split_offsets: Option<Vec<i64>>,
+}
+
+/// maps keyed by field ids are stored as arrays of key-value records in avro
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub(crate) struct KeyValue<V> {
+    pub key: i32,
+    pub value: V,
+}
+
+impl ManifestPtr {
+    fn to_avro(&self) -> AvroValue {
+        let partitions = self.partitions.as_ref().map(|parts| {
+            AvroValue::Array(
+                parts
+                    .iter()
+                    .map(|part| {
+                        avro_record(vec![
+                            ("contains_null", AvroValue::Boolean(part.contains_null)),
+                            (
+                                "contains_nan",
+                                avro_option(part.contains_nan.map(AvroValue::Boolean)),
+                            ),
+                            (
+                                "lower_bound",
+                                avro_option(part.lower_bound.clone().map(AvroValue::Bytes)),
+                            ),
+                            (
+                                "upper_bound",
+                                avro_option(part.upper_bound.clone().map(AvroValue::Bytes)),
+                            ),
+                        ])
+                    })
+                    .collect(),
+            )
+        });
+        avro_record(vec![
+            (
+                "manifest_path",
+                AvroValue::String(self.manifest_path.clone()),
+            ),
+            ("manifest_length", AvroValue::Long(self.manifest_length)),
+            ("partition_spec_id", AvroValue::Int(self.partition_spec_id)),
+            ("content", AvroValue::Int(self.content)),
+            ("sequence_number", AvroValue::Long(self.sequence_number)),
+            (
+                "min_sequence_number",
+                AvroValue::Long(self.min_sequence_number),
+            ),
+            (
+                "added_snapshot_id",
+                avro_option(self.added_snapshot_id.map(AvroValue::Long)),
+            ),
+            (
+                "added_data_files_count",
+                avro_option(self.added_data_files_count.map(AvroValue::Int)),
+            ),
+            (
+                "existing_data_files_count",
+                avro_option(self.existing_data_files_count.map(AvroValue::Int)),
+            ),
+            (
+                "deleted_data_files_count",
+                avro_option(self.deleted_data_files_count.map(AvroValue::Int)),
+            ),
+            (
+                "added_rows_count",
+                avro_option(self.added_rows_count.map(AvroValue::Long)),
+            ),
+            (
+                "existing_rows_count",
+                avro_option(self.existing_rows_count.map(AvroValue::Long)),
+            ),
+            (
+                "deleted_rows_count",
+                avro_option(self.deleted_rows_count.map(AvroValue::Long)),
+            ),
+            ("partitions", avro_option(partitions)),
+        ])
+    }
+}
+
+impl Manifest {
+    fn to_avro(&self) -> AvroValue {
+        avro_record(vec![
+            ("status", AvroValue::Int(self.status)),
+            (
+                "snapshot_id",
+                avro_option(self.snapshot_id.map(AvroValue::Long)),
+            ),
+            // sequence numbers are inherited from the manifest list
+            ("sequence_number", avro_option(None)),
+            ("file_sequence_number", avro_option(None)),
+            ("data_file", self.data_file.to_avro()),
+        ])
+    }
+}
+
+impl DataFile {
+    fn to_avro(&self) -> AvroValue {
+        let long_map = |kvs: &Option<Vec<KeyValue<i64>>>| {
+            avro_option(
+                kvs.as_ref()
+                    .map(|kvs| avro_map(kvs.iter().map(|kv| (kv.key, AvroValue::Long(kv.value))))),
+            )
+        };
+        let bytes_map = |kvs: &Option<Vec<KeyValue<Vec<u8>>>>| {
+            avro_option(kvs.as_ref().map(|kvs| {
+                avro_map(
+                    kvs.iter()
+                        .map(|kv| (kv.key, AvroValue::Bytes(kv.value.clone()))),
+                )
+            }))
+        };
+        let split_offsets = self
+            .split_offsets
+            .as_ref()
+            .map(|offsets| AvroValue::Array(offsets.iter().map(|o| AvroValue::Long(*o)).collect()));
+
+        avro_record(vec![
+            ("content", AvroValue::Int(self.content)),
+            ("file_path", AvroValue::String(self.file_path.clone())),
+            ("file_format", AvroValue::String(self.file_format.clone())),
+            ("partition", avro_record(vec![])),
+            ("record_count", AvroValue::Long(self.record_count)),
+            (
+                "file_size_in_bytes",
+                AvroValue::Long(self.file_size_in_bytes),
+            ),
+            ("block_size_in_bytes", AvroValue::Long(DEFAULT_BLOCK_SIZE)),
+            ("column_sizes", long_map(&self.column_sizes)),
+            ("value_counts", long_map(&self.value_counts)),
+            ("null_value_counts", long_map(&self.null_value_counts)),
+            ("lower_bounds", bytes_map(&self.lower_bounds)),
+            ("upper_bounds", bytes_map(&self.upper_bounds)),
+            ("split_offsets", avro_option(split_offsets)),
+        ])
+    }
+
+    pub fn null_value_counts(&self) -> HashMap<i32, i64> {
+        Self::to_map(&self.null_value_counts)
+    }
+
+    pub fn lower_bounds(&self) -> HashMap<i32, Vec<u8>> {
+        Self::to_map(&self.lower_bounds)
+    }
+
+    pub fn upper_bounds(&self) -> HashMap<i32, Vec<u8>> {
+        Self::to_map(&self.upper_bounds)
+    }
+
+    fn to_map<V: Clone>(kvs: &Option<Vec<KeyValue<V>>>) -> HashMap<i32, V> {
+        kvs.iter()
+            .flatten()
+            .map(|kv| (kv.key, kv.value.clone()))
+            .collect()
+    }
+}
+
+/// read all entries of a manifest list file
+#[async_backtrace::framed]
+pub(crate) async fn read_manifest_list(op: &Operator, path: &str) -> Result<Vec<ManifestPtr>> {
+    read_avro_records(op, path).await
+}
+
+/// read all entries of a manifest file
+#[async_backtrace::framed]
+pub(crate) async fn read_manifest(op: &Operator, path: &str) -> Result<Vec<Manifest>> {
+    read_avro_records(op, path).await
+}
+
+/// write a manifest file, returning its length in bytes
+///
+/// `metadata` is stored as the key-value metadata of the avro file,
+/// such as `schema` and `partition-spec` required by the spec.
+#[async_backtrace::framed]
+pub(crate) async fn write_manifest(
+    op: &Operator,
+    path: &str,
+    entries: &[Manifest],
+    metadata: &[(&str, String)],
+) -> Result<i64> {
+    let records = entries.iter().map(Manifest::to_avro).collect();
+    let content = encode_avro_records(MANIFEST_ENTRY_SCHEMA, metadata, records)?;
+    let length = content.len() as i64;
+    op.write(path, content).await?;
+    Ok(length)
+}
+
+/// write a manifest list file
+#[async_backtrace::framed]
+pub(crate) async fn write_manifest_list(
+    op: &Operator,
+    path: &str,
+    ptrs: &[ManifestPtr],
+    metadata: &[(&str, String)],
+) -> Result<()> {
+    let records = ptrs.iter().map(ManifestPtr::to_avro).collect();
+    let content = encode_avro_records(MANIFEST_FILE_SCHEMA, metadata, records)?;
+    op.write(path, content).await?;
+    Ok(())
+}
+
+fn encode_avro_records(
+    schema: &str,
+    metadata: &[(&str, String)],
+    records: Vec<AvroValue>,
+) -> Result<Vec<u8>> {
+    let invalid = |e: apache_avro::Error| {
+        ErrorCode::Internal(format!("cannot encode iceberg avro records: {e}"))
+    };
+
+    let schema = AvroSchema::parse_str(schema).map_err(invalid)?;
+    let mut writer = AvroWriter::new(&schema, vec![]);
+    for (key, value) in metadata {
+        writer
+            .add_user_metadata(key.to_string(), value)
+            .map_err(invalid)?;
+    }
+    for record in records {
+        writer.append(record).map_err(invalid)?;
+    }
+    writer.into_inner().map_err(invalid)
+}
+
+fn avro_record(fields: Vec<(&str, AvroValue)>) -> AvroValue {
+    AvroValue::Record(
+        fields
+            .into_iter()
+            .map(|(name, value)| (name.to_string(), value))
+            .collect(),
+    )
+}
+
+/// optional fields are unions of `null` and the value type
+fn avro_option(value: Option<AvroValue>) -> AvroValue {
+    match value {
+        Some(value) => AvroValue::Union(1, Box::new(value)),
+        None => AvroValue::Union(0, Box::new(AvroValue::Null)),
+    }
+}
+
+/// maps keyed by field ids, see [`KeyValue`]
+fn avro_map(kvs: impl Iterator<Item = (i32, AvroValue)>) -> AvroValue {
+    AvroValue::Array(
+        kvs.map(|(key, value)| avro_record(vec![("key", AvroValue::Int(key)), ("value", value)]))
+            .collect(),
+    )
+}
+
+/// records in manifest lists and manifests are decoded into json values first,
+/// letting serde take care of the differences between format v1 and v2,
+/// such as optional fields and widened integers.
+#[async_backtrace::framed]
+async fn read_avro_records<T: DeserializeOwned>(op: &Operator, path: &str) -> Result<Vec<T>> {
+    let content = op.read(path).await.map_err(|e| {
+        ErrorCode::ReadTableDataError(format!("cannot read avro file {path}: {e:?}"))
+    })?;
+    decode_avro_records(path, &content)
+}
+
+fn decode_avro_records<T: DeserializeOwned>(path: &str, content: &[u8]) -> Result<Vec<T>> {
+    let invalid =
+        |e: String| ErrorCode::ReadTableDataError(format!("invalid avro file {path}: {e}"));
+
+    let reader = AvroReader::new(content).map_err(|e| invalid(e.to_string()))?;
+    let mut records = vec![];
+    for value in reader {
+        let value = value.map_err(|e| invalid(e.to_string()))?;
+        let json = serde_json::Value::try_from(value).map_err(|e| invalid(e.to_string()))?;
+        let record = serde_json::from_value(json).map_err(|e| invalid(e.to_string()))?;
+        records.push(record);
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod manifest_test {
+    use super::decode_avro_records;
+    use super::encode_avro_records;
+    use super::DataFile;
+    use super::KeyValue;
+    use super::Manifest;
+    use super::ManifestPtr;
+    use super::MANIFEST_ENTRY_ADDED;
+    use super::MANIFEST_ENTRY_SCHEMA;
+    use super::MANIFEST_FILE_SCHEMA;
+
+    #[test]
+    fn test_manifest_round_trip() {
+        let entry = Manifest {
+            status: MANIFEST_ENTRY_ADDED,
+            snapshot_id: Some(42),
+            data_file: DataFile {
+                content: 0,
+                file_path: "s3://bkt/tbl/data/0.parquet".to_string(),
+                file_format: "PARQUET".to_string(),
+                record_count: 3,
+                file_size_in_bytes: 1024,
+                column_sizes: None,
+                value_counts: Some(vec![KeyValue { key: 1, value: 3 }]),
+                null_value_counts: Some(vec![KeyValue { key: 1, value: 0 }]),
+                lower_bounds: Some(vec![KeyValue {
+                    key: 1,
+                    value: 1i32.to_le_bytes().to_vec(),
+                }]),
+                upper_bounds: Some(vec![KeyValue {
+                    key: 1,
+                    value: 3i32.to_le_bytes().to_vec(),
+                }]),
+                split_offsets: None,
+            },
+        };
+        let metadata = [("format-version", "1".to_string())];
+        let content =
+            encode_avro_records(MANIFEST_ENTRY_SCHEMA, &metadata, vec![entry.to_avro()]).unwrap();
+        let decoded: Vec<Manifest> = decode_avro_records("manifest", &content).unwrap();
+        assert_eq!(decoded.len(), 1);
+        assert_eq!(decoded[0].status, MANIFEST_ENTRY_ADDED);
+        assert_eq!(decoded[0].snapshot_id, Some(42));
+        assert_eq!(decoded[0].data_file, entry.data_file);
+
+        let ptr = ManifestPtr {
+            manifest_path: "s3://bkt/tbl/metadata/m0.avro".to_string(),
+            manifest_length: content.len() as i64,
+            partition_spec_id: 0,
+            content: 0,
+            added_snapshot_id: Some(42),
+            added_data_files_count: Some(1),
+            existing_data_files_count: Some(0),
+            deleted_data_files_count: Some(0),
+            partitions: None,
+            added_rows_count: Some(3),
+            existing_rows_count: Some(0),
+            deleted_rows_count: Some(0),
+            sequence_number: 1,
+            min_sequence_number: 1,
+        };
+        let content = encode_avro_records(MANIFEST_FILE_SCHEMA, &[], vec![ptr.to_avro()]).unwrap();
+        let decoded: Vec<ManifestPtr> = decode_avro_records("manifest list", &content).unwrap();
+        assert_eq!(decoded.len(), 1);
+        assert_eq!(decoded[0].manifest_path, ptr.manifest_path);
+        assert_eq!(decoded[0].added_rows_count, Some(3));
+        assert_eq!(decoded[0].sequence_number, 1);
+    }
 }
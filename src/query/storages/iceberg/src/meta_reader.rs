@@ -13,3 +13,181 @@
 // limitations under the License.
 
 //! this module contains metadata reader utilities for table metadata
+
+use common_exception::ErrorCode;
+use common_exception::Result;
+use serde::Deserialize;
+
+/// the part of iceberg table metadata describing snapshots
+///
+/// only fields needed for scanning are kept here,
+/// the schema is still converted from `iceberg_rs::model::table::TableMetadata`
+#[derive(Clone, Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct SnapshotMeta {
+    /// base location of the table, e.g. `s3://bkt/path/to/table`
+    pub location: String,
+    /// id of the current snapshot, absent or `-1` for empty tables
+    pub current_snapshot_id: Option<i64>,
+    /// id of the current schema, absent in early format v1 metadata
+    pub current_schema_id: Option<i64>,
+    /// all valid snapshots of the table
+    #[serde(default)]
+    pub snapshots: Vec<Snapshot>,
+    /// schemas of the table, only field ids are kept
+    #[serde(default)]
+    pub schemas: Vec<SchemaIds>,
+    /// history of the current snapshot, in ascending order of time
+    #[serde(default)]
+    pub snapshot_log: Vec<SnapshotLogEntry>,
+}
+
+/// a snapshot of the table
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct Snapshot {
+    pub snapshot_id: i64,
+    pub parent_snapshot_id: Option<i64>,
+    pub timestamp_ms: i64,
+    /// location of the manifest list file
+    pub manifest_list: Option<String>,
+    /// manifest files of the snapshot, only used by format v1 writers
+    /// not providing a manifest list
+    pub manifests: Option<Vec<String>>,
+    /// id of the schema used when the snapshot was created
+    pub schema_id: Option<i64>,
+}
+
+/// an entry of snapshot log, recording when a snapshot became the current one
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct SnapshotLogEntry {
+    pub snapshot_id: i64,
+    pub timestamp_ms: i64,
+}
+
+/// field ids of a table schema
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(crate) struct SchemaIds {
+    pub schema_id: Option<i64>,
+    pub fields: Vec<FieldId>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub(crate) struct FieldId {
+    pub id: i32,
+}
+
+impl SnapshotMeta {
+    /// parse snapshot information from raw metadata json
+    pub fn try_from_json(meta_json: &[u8]) -> Result<Self> {
+        serde_json::de::from_slice(meta_json).map_err(|e| {
+            ErrorCode::ReadTableDataError(format!("invalid snapshots in metadata: {e:?}"))
+        })
+    }
+
+    /// the current snapshot, `None` if the table has no data yet
+    pub fn current_snapshot(&self) -> Option<&Snapshot> {
+        self.current_snapshot_id
+            .and_then(|id| self.snapshots.iter().find(|s| s.snapshot_id == id))
+    }
+
+    /// Iceberg records absolute locations of metadata and data files,
+    /// this function gives their paths relative to the table root.
+    ///
+    /// files outside the table location are not accessible by the operator
+    /// of the table, an error is returned for them.
+    pub fn relative_path(&self, path: &str) -> Result<String> {
+        let location = self.location.trim_end_matches('/');
+        match path.strip_prefix(location) {
+            Some(rel) if rel.starts_with('/') => Ok(rel.trim_start_matches('/').to_string()),
+            _ => Err(ErrorCode::ReadTableDataError(format!(
+                "file {path} is not in the table location {location}"
+            ))),
+        }
+    }
+
+    /// the absolute location of a file relative to the table root
+    pub fn absolute_path(&self, path: &str) -> String {
+        format!("{}/{}", self.location.trim_end_matches('/'), path)
+    }
+
+    /// find a snapshot by its id
+    pub fn snapshot_by_id(&self, snapshot_id: i64) -> Option<&Snapshot> {
+        self.snapshots.iter().find(|s| s.snapshot_id == snapshot_id)
+    }
+
+    /// the snapshot which was the current one at the given time
+    ///
+    /// snapshot log is preferred, for it records rollbacks of the table,
+    /// creation time of snapshots is used if the log is absent.
+    pub fn snapshot_as_of(&self, timestamp_ms: i64) -> Option<&Snapshot> {
+        if self.snapshot_log.is_empty() {
+            return self
+                .snapshots
+                .iter()
+                .filter(|s| s.timestamp_ms <= timestamp_ms)
+                .max_by_key(|s| s.timestamp_ms);
+        }
+        self.snapshot_log
+            .iter()
+            .filter(|log| log.timestamp_ms <= timestamp_ms)
+            .max_by_key(|log| log.timestamp_ms)
+            .and_then(|log| self.snapshot_by_id(log.snapshot_id))
+    }
+
+    /// ids of top level fields, in the same order as the converted databend schema
+    ///
+    /// the current schema is used if `schema_id` is not given, which is not always
+    /// the last one for schemas may be rolled back.
+    /// the last schema is used only if the schema is not found.
+    pub fn field_ids(&self, schema_id: Option<i64>) -> Vec<i32> {
+        let schema = schema_id
+            .or(self.current_schema_id)
+            .and_then(|id| self.schemas.iter().find(|s| s.schema_id == Some(id)))
+            .or_else(|| self.schemas.last());
+        let mut ids = schema
+            .map(|s| s.fields.iter().map(|f| f.id).collect::<Vec<_>>())
+            .unwrap_or_default();
+        ids.sort();
+        ids
+    }
+}
+
+#[cfg(test)]
+mod meta_reader_test {
+    use super::SnapshotMeta;
+
+    #[test]
+    fn test_relative_path() {
+        let meta = SnapshotMeta {
+            location: "s3://bkt/path/to/table/".to_string(),
+            ..Default::default()
+        };
+        assert_eq!(
+            meta.relative_path("s3://bkt/path/to/table/data/a.parquet")
+                .unwrap(),
+            "data/a.parquet"
+        );
+        assert_eq!(
+            meta.relative_path("s3://bkt/path/to/table/metadata/snap-1.avro")
+                .unwrap(),
+            "metadata/snap-1.avro"
+        );
+
+        // outside the table location
+        assert!(
+            meta.relative_path("s3://bkt/path/to/table2/data/a.parquet")
+                .is_err()
+        );
+        assert!(
+            meta.relative_path("s3://other/table/data/a.parquet")
+                .is_err()
+        );
+        assert!(
+            meta.relative_path("/tmp/table/metadata/v1.metadata.json")
+                .is_err()
+        );
+    }
+}
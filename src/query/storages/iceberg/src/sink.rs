@@ -0,0 +1,276 @@
+// Copyright 2023 Datafuse Labs.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! this module writes data blocks into parquet data files of iceberg tables
+
+use std::any::Any;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use common_catalog::table_context::TableContext;
+use common_exception::ErrorCode;
+use common_exception::Result;
+use common_expression::BlockMetaInfo;
+use common_expression::BlockMetaInfoDowncast;
+use common_expression::Column;
+use common_expression::DataBlock;
+use common_expression::TableDataType;
+use common_expression::TableSchemaRef;
+use common_functions::aggregates::eval_aggr;
+use common_io::constants::DEFAULT_BLOCK_BUFFER_SIZE;
+use common_pipeline_core::processors::port::InputPort;
+use common_pipeline_core::processors::port::OutputPort;
+use common_pipeline_core::processors::processor::Event;
+use common_pipeline_core::processors::processor::ProcessorPtr;
+use common_pipeline_core::processors::Processor;
+use opendal::Operator;
+use serde::Deserialize;
+use serde::Serialize;
+use storages_common_blocks::blocks_to_parquet;
+use storages_common_table_meta::table::TableCompression;
+use uuid::Uuid;
+
+use crate::converters::bound_databend_to_iceberg;
+
+/// a parquet data file written by [`IcebergTableSink`], waiting to be committed
+///
+/// sinks may run on other nodes of the cluster, knowing nothing about
+/// the iceberg metadata of the table, so statistics are indexed by
+/// positions of top level columns in the table schema, and mapped to
+/// iceberg field ids while committing.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, Eq)]
+pub struct AppendedDataFile {
+    /// path of the data file, relative to the table root
+    pub path: String,
+    pub record_count: u64,
+    pub file_size: u64,
+    /// number of nulls of each top level column
+    pub null_counts: Vec<u64>,
+    /// encoded lower and upper bounds of each top level column, if supported
+    pub bounds: Vec<Option<(Vec<u8>, Vec<u8>)>>,
+}
+
+impl TryFrom<AppendedDataFile> for DataBlock {
+    type Error = ErrorCode;
+    fn try_from(value: AppendedDataFile) -> Result<Self, Self::Error> {
+        Ok(DataBlock::new_with_meta(vec![], 0, Some(Box::new(value))))
+    }
+}
+
+impl TryFrom<&DataBlock> for AppendedDataFile {
+    type Error = ErrorCode;
+    fn try_from(block: &DataBlock) -> Result<Self, Self::Error> {
+        block
+            .get_meta()
+            .and_then(AppendedDataFile::downcast_ref_from)
+            .cloned()
+            .ok_or_else(|| {
+                ErrorCode::Internal(format!(
+                    "invalid data block meta of appended iceberg data file, {:?}",
+                    block.get_meta()
+                ))
+            })
+    }
+}
+
+#[typetag::serde(name = "iceberg_appended_data_file")]
+impl BlockMetaInfo for AppendedDataFile {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn equals(&self, info: &Box<dyn BlockMetaInfo>) -> bool {
+        match AppendedDataFile::downcast_ref_from(info) {
+            None => false,
+            Some(other) => self == other,
+        }
+    }
+
+    fn clone_self(&self) -> Box<dyn BlockMetaInfo> {
+        Box::new(self.clone())
+    }
+}
+
+enum State {
+    None,
+    NeedSerialize(DataBlock),
+    Serialized {
+        data: Vec<u8>,
+        data_file: AppendedDataFile,
+    },
+    Finished,
+}
+
+/// write each incoming block as a parquet data file under `data/` of the table,
+/// the written files are pushed as precommit blocks.
+pub struct IcebergTableSink {
+    state: State,
+    input: Arc<InputPort>,
+    ctx: Arc<dyn TableContext>,
+    data_accessor: Operator,
+    schema: TableSchemaRef,
+    // A dummy output port for distributed insert select to connect Exchange Sink.
+    output: Option<Arc<OutputPort>>,
+}
+
+impl IcebergTableSink {
+    pub fn try_create(
+        input: Arc<InputPort>,
+        ctx: Arc<dyn TableContext>,
+        data_accessor: Operator,
+        schema: TableSchemaRef,
+        output: Option<Arc<OutputPort>>,
+    ) -> Result<ProcessorPtr> {
+        Ok(ProcessorPtr::create(Box::new(IcebergTableSink {
+            state: State::None,
+            input,
+            ctx,
+            data_accessor,
+            schema,
+            output,
+        })))
+    }
+
+    /// count nulls and encode bounds of top level columns
+    fn column_statistics(&self, block: &DataBlock) -> Result<AppendedDataFile> {
+        let rows = block.num_rows();
+        let mut null_counts = Vec::with_capacity(block.num_columns());
+        let mut bounds = Vec::with_capacity(block.num_columns());
+
+        for (field, entry) in self.schema.fields().iter().zip(block.columns()) {
+            let column = entry.value.convert_to_full_column(&entry.data_type, rows);
+            let null_count = match column.validity() {
+                (true, _) => rows,
+                (false, Some(bitmap)) => bitmap.unset_bits(),
+                (false, None) => 0,
+            };
+            null_counts.push(null_count as u64);
+
+            let bound = if has_bounds(field.data_type()) && null_count < rows {
+                let min = first_scalar(eval_aggr("min", vec![], &[column.clone()], rows)?.0);
+                let max = first_scalar(eval_aggr("max", vec![], &[column], rows)?.0);
+                min.zip(max)
+            } else {
+                None
+            };
+            bounds.push(bound);
+        }
+
+        Ok(AppendedDataFile {
+            path: String::new(),
+            record_count: rows as u64,
+            file_size: 0,
+            null_counts,
+            bounds,
+        })
+    }
+}
+
+/// iceberg only records bounds of primitive types
+fn has_bounds(ty: &TableDataType) -> bool {
+    matches!(
+        ty.remove_nullable(),
+        TableDataType::Boolean
+            | TableDataType::String
+            | TableDataType::Number(_)
+            | TableDataType::Decimal(_)
+            | TableDataType::Timestamp
+            | TableDataType::Date
+    )
+}
+
+/// encode the result of an aggregation as an iceberg bound
+fn first_scalar(column: Column) -> Option<Vec<u8>> {
+    let scalar = column.index(0)?.to_owned();
+    bound_databend_to_iceberg(&scalar)
+}
+
+#[async_trait]
+impl Processor for IcebergTableSink {
+    fn name(&self) -> String {
+        "IcebergSink".to_string()
+    }
+
+    fn as_any(&mut self) -> &mut dyn Any {
+        self
+    }
+
+    fn event(&mut self) -> Result<Event> {
+        if matches!(&self.state, State::NeedSerialize(_)) {
+            return Ok(Event::Sync);
+        }
+
+        if matches!(&self.state, State::Serialized { .. }) {
+            return Ok(Event::Async);
+        }
+
+        if self.input.is_finished() {
+            if let Some(output) = &self.output {
+                output.finish();
+            }
+            self.state = State::Finished;
+            return Ok(Event::Finished);
+        }
+
+        if !self.input.has_data() {
+            self.input.set_need_data();
+            return Ok(Event::NeedData);
+        }
+
+        self.state = State::NeedSerialize(self.input.pull_data().unwrap()?);
+        Ok(Event::Sync)
+    }
+
+    fn process(&mut self) -> Result<()> {
+        match std::mem::replace(&mut self.state, State::None) {
+            State::NeedSerialize(block) => {
+                if block.is_empty() {
+                    return Ok(());
+                }
+
+                let mut data_file = self.column_statistics(&block)?;
+                let mut data = Vec::with_capacity(DEFAULT_BLOCK_BUFFER_SIZE);
+                let (size, _) = blocks_to_parquet(
+                    &self.schema,
+                    vec![block],
+                    &mut data,
+                    TableCompression::Zstd,
+                )?;
+                data_file.path = format!("data/{}.parquet", Uuid::new_v4().simple());
+                data_file.file_size = size;
+
+                self.state = State::Serialized { data, data_file };
+            }
+            _state => {
+                return Err(ErrorCode::Internal("Unknown state for iceberg table sink"));
+            }
+        }
+        Ok(())
+    }
+
+    #[async_backtrace::framed]
+    async fn async_process(&mut self) -> Result<()> {
+        match std::mem::replace(&mut self.state, State::None) {
+            State::Serialized { data, data_file } => {
+                self.data_accessor.write(&data_file.path, data).await?;
+                self.ctx
+                    .push_precommit_block(DataBlock::try_from(data_file)?);
+            }
+            _state => {
+                return Err(ErrorCode::Internal("Unknown state for iceberg table sink."));
+            }
+        }
+        Ok(())
+    }
+}
@@ -19,39 +19,81 @@
 //! once the table created we don't update it.
 
 use std::any::Any;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use async_trait::async_trait;
+use common_catalog::plan::DataSourceInfo;
+use common_catalog::plan::ParquetReadOptions;
+use common_catalog::plan::ParquetTableInfo;
 use common_catalog::plan::PartStatistics;
 use common_catalog::plan::Partitions;
 use common_catalog::plan::PushDownInfo;
+use common_catalog::table::AppendMode;
+use common_catalog::table::NavigationPoint;
 use common_catalog::table::Table;
 use common_catalog::table_context::TableContext;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_expression::BlockThresholds;
+use common_expression::DataBlock;
+use common_functions::BUILTIN_FUNCTIONS;
+use common_meta_app::principal::StageInfo;
 use common_meta_app::schema::TableIdent;
 use common_meta_app::schema::TableInfo;
+use common_meta_app::schema::UpsertTableCopiedFileReq;
+use common_pipeline_core::processors::processor::ProcessorPtr;
+use common_pipeline_core::Pipeline;
+use common_pipeline_transforms::processors::transforms::transform_block_compact_no_split::BlockCompactorNoSplit;
+use common_pipeline_transforms::processors::transforms::BlockCompactor;
+use common_pipeline_transforms::processors::transforms::TransformCompact;
+use common_storage::init_operator;
 use common_storage::DataOperator;
+use common_storage::StageFileInfo;
+use common_storage::StageFileStatus;
+use common_storage::StageFilesInfo;
+use common_storages_parquet::ParquetTable;
 use futures::StreamExt;
+use iceberg_rs::model::schema::SchemaV2;
 use iceberg_rs::model::table::TableMetadata;
 use opendal::Operator;
+use storages_common_pruner::RangePrunerCreator;
+use storages_common_table_meta::meta::ColumnStatistics;
+use storages_common_table_meta::meta::StatisticsOfColumns;
 
+use crate::commit::commit_data_files;
+use crate::converters::bound_iceberg_to_databend;
 use crate::converters::meta_iceberg_to_databend;
+use crate::converters::schema_iceberg_to_databend;
+use crate::manifest::read_manifest;
+use crate::manifest::read_manifest_list;
+use crate::manifest::DataFile;
+use crate::manifest::CONTENT_DATA;
+use crate::manifest::MANIFEST_ENTRY_DELETED;
+use crate::meta_reader::Snapshot;
+use crate::meta_reader::SnapshotMeta;
+use crate::sink::AppendedDataFile;
+use crate::sink::IcebergTableSink;
 
 /// file marking the current version of metadata file
-const META_PTR: &str = "metadata/version_hint.text";
+pub(crate) const META_PTR: &str = "metadata/version_hint.text";
 
 /// accessor wrapper as a table
-#[allow(unused)]
+///
+/// tables rebuilt from [`TableInfo`] load no metadata,
+/// they are only used for building pipelines writing data files.
+#[derive(Clone)]
 pub struct IcebergTable {
-    /// database that belongs to
-    database: String,
-    /// name of the current table
-    name: String,
-    /// root of the table
-    tbl_root: DataOperator,
-    /// table metadata
-    manifests: TableMetadata,
+    /// operator on the root of the table
+    operator: Operator,
+    /// schemas of the table, used by time travel
+    schemas: Vec<SchemaV2>,
+    /// snapshots of the table
+    snapshots: SnapshotMeta,
+    /// id of the snapshot to be scanned, the current snapshot if not navigated
+    snapshot_id: Option<i64>,
+    /// iceberg field ids of the top level columns, parallel to the table schema
+    field_ids: Vec<i32>,
     /// table information
     info: TableInfo,
 }
@@ -82,6 +124,9 @@ impl IcebergTable {
                     &latest_manifest, e
                 ))
             })?;
+        let snapshots = SnapshotMeta::try_from_json(meta_json.as_slice())?;
+        let snapshot_id = snapshots.current_snapshot().map(|s| s.snapshot_id);
+        let field_ids = snapshots.field_ids(None);
 
         let sp = tbl_root.params();
 
@@ -96,10 +141,27 @@ impl IcebergTable {
 
         // finish making table
         Ok(Self {
-            database: database.to_string(),
-            name: table_name.to_string(),
-            tbl_root,
-            manifests: metadata,
+            operator: tbl_root.operator(),
+            schemas: metadata.to_latest().schemas,
+            snapshots,
+            snapshot_id,
+            field_ids,
+            info,
+        })
+    }
+
+    /// rebuild the table from table info
+    pub fn try_create(info: TableInfo) -> Result<IcebergTable> {
+        let sp = info.meta.storage_params.clone().ok_or_else(|| {
+            ErrorCode::Internal(format!("storage params of {} is missing", info.desc))
+        })?;
+        let operator = init_operator(&sp)?;
+        Ok(Self {
+            operator,
+            schemas: vec![],
+            snapshots: SnapshotMeta::default(),
+            snapshot_id: None,
+            field_ids: vec![],
             info,
         })
     }
@@ -108,7 +170,7 @@ impl IcebergTable {
     /// and gives the relative path from table root directory
     /// to latest metadata json file
     #[async_backtrace::framed]
-    async fn version_detect(tbl_root: &Operator) -> Result<String> {
+    pub(crate) async fn version_detect(tbl_root: &Operator) -> Result<String> {
         // try Dremio's way
         // Dremio has an `version_hint.txt` file
         // recording the latest snapshot version number
@@ -145,6 +207,296 @@ impl IcebergTable {
             .map(|s| format!("metadata/{s}"))
             .ok_or_else(|| ErrorCode::ReadTableDataError("Cannot get the latest manifest file"))
     }
+
+    /// the snapshot to be scanned, `None` for empty tables
+    fn snapshot(&self) -> Option<&Snapshot> {
+        self.snapshot_id
+            .and_then(|id| self.snapshots.snapshot_by_id(id))
+    }
+
+    /// the table as of the given snapshot,
+    /// with the schema the snapshot was written in.
+    fn with_snapshot(&self, snapshot: &Snapshot) -> IcebergTable {
+        let mut table = self.clone();
+        table.snapshot_id = Some(snapshot.snapshot_id);
+
+        if let Some(schema_id) = snapshot.schema_id {
+            if let Some(schema) = self
+                .schemas
+                .iter()
+                .find(|s| i64::from(s.schema_id) == schema_id)
+            {
+                table.info.meta.schema = Arc::new(schema_iceberg_to_databend(schema));
+                table.field_ids = self.snapshots.field_ids(Some(schema_id));
+            }
+        }
+        table
+    }
+
+    /// find the snapshot at the navigation point
+    fn navigate(&self, point: &NavigationPoint) -> Result<IcebergTable> {
+        let snapshot = match point {
+            NavigationPoint::SnapshotID(snapshot_id) => {
+                let snapshot_id = snapshot_id.trim().parse::<i64>().map_err(|e| {
+                    ErrorCode::BadArguments(format!(
+                        "invalid Iceberg snapshot id {snapshot_id}: {e}"
+                    ))
+                })?;
+                self.snapshots.snapshot_by_id(snapshot_id)
+            }
+            NavigationPoint::TimePoint(time_point) => {
+                self.snapshots.snapshot_as_of(time_point.timestamp_millis())
+            }
+        };
+
+        match snapshot {
+            Some(snapshot) => Ok(self.with_snapshot(snapshot)),
+            None => Err(ErrorCode::TableHistoricalDataNotFound(
+                "No historical data found at given point",
+            )),
+        }
+    }
+
+    /// read manifest list and manifests of the snapshot,
+    /// returning all alive data files.
+    #[async_backtrace::framed]
+    async fn list_data_files(&self, snapshot: &Snapshot) -> Result<Vec<DataFile>> {
+        let op = self.operator.clone();
+
+        let manifest_paths = match (&snapshot.manifest_list, &snapshot.manifests) {
+            (Some(manifest_list), _) => {
+                let manifest_list = self.snapshots.relative_path(manifest_list)?;
+                let mut paths = vec![];
+                for ptr in read_manifest_list(&op, &manifest_list).await? {
+                    if ptr.content != CONTENT_DATA {
+                        return Err(ErrorCode::Unimplemented(
+                            "Iceberg tables with row-level deletes are not supported yet",
+                        ));
+                    }
+                    paths.push(ptr.manifest_path);
+                }
+                paths
+            }
+            (None, Some(manifests)) => manifests.clone(),
+            (None, None) => vec![],
+        };
+
+        let manifests = futures::future::try_join_all(manifest_paths.iter().map(|path| {
+            let op = op.clone();
+            async move { read_manifest(&op, &self.snapshots.relative_path(path)?).await }
+        }))
+        .await?;
+
+        let mut data_files = vec![];
+        for entry in manifests.into_iter().flatten() {
+            if entry.status == MANIFEST_ENTRY_DELETED {
+                continue;
+            }
+            let data_file = entry.data_file;
+            if data_file.content != CONTENT_DATA {
+                return Err(ErrorCode::Unimplemented(
+                    "Iceberg tables with row-level deletes are not supported yet",
+                ));
+            }
+            if !data_file.file_format.eq_ignore_ascii_case("parquet") {
+                return Err(ErrorCode::Unimplemented(format!(
+                    "Iceberg data file format {} is not supported yet, data file: {}",
+                    data_file.file_format, data_file.file_path
+                )));
+            }
+            data_files.push(data_file);
+        }
+        Ok(data_files)
+    }
+
+    /// prune data files with the column bounds recorded in manifests
+    fn prune_data_files(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        push_downs: &Option<PushDownInfo>,
+        data_files: Vec<DataFile>,
+    ) -> Result<Vec<DataFile>> {
+        let filter = push_downs
+            .as_ref()
+            .and_then(|p| p.filter.as_ref().map(|f| f.as_expr(&BUILTIN_FUNCTIONS)));
+        if filter.is_none() {
+            return Ok(data_files);
+        }
+
+        let schema = self.info.schema();
+        let pruner =
+            RangePrunerCreator::try_create(ctx.get_function_context()?, &schema, filter.as_ref())?;
+
+        let mut kept = Vec::with_capacity(data_files.len());
+        for data_file in data_files {
+            if pruner.should_keep(&self.column_statistics(&data_file)?) {
+                kept.push(data_file);
+            }
+        }
+        Ok(kept)
+    }
+
+    /// convert column bounds of a data file to [`StatisticsOfColumns`]
+    fn column_statistics(&self, data_file: &DataFile) -> Result<StatisticsOfColumns> {
+        let schema = self.info.schema();
+        let lower_bounds = data_file.lower_bounds();
+        let upper_bounds = data_file.upper_bounds();
+        let null_counts = data_file.null_value_counts();
+
+        let mut stats = HashMap::new();
+        for (idx, (field, field_id)) in schema.fields().iter().zip(&self.field_ids).enumerate() {
+            let (lower, upper) = match (lower_bounds.get(field_id), upper_bounds.get(field_id)) {
+                (Some(lower), Some(upper)) => (lower, upper),
+                _ => continue,
+            };
+            let ty = field.data_type();
+            if let (Some(min), Some(max)) = (
+                bound_iceberg_to_databend(ty, lower),
+                bound_iceberg_to_databend(ty, upper),
+            ) {
+                stats.insert(schema.column_id_of_index(idx)?, ColumnStatistics {
+                    min,
+                    max,
+                    null_count: null_counts.get(field_id).copied().unwrap_or_default() as u64,
+                    in_memory_size: 0,
+                    distinct_of_values: None,
+                });
+            }
+        }
+        Ok(stats)
+    }
+
+    /// data files are read by a parquet table rooted at the table directory
+    fn parquet_table_info(&self, files_to_read: Option<Vec<StageFileInfo>>) -> ParquetTableInfo {
+        let sp = self.info.meta.storage_params.clone().unwrap_or_default();
+        let stage_info = StageInfo::new_external_stage(sp, "/").with_stage_name(&self.info.desc);
+        ParquetTableInfo {
+            read_options: ParquetReadOptions::default(),
+            stage_info,
+            files_info: StageFilesInfo {
+                path: "/".to_string(),
+                files: None,
+                pattern: None,
+            },
+            table_info: self.info.clone(),
+            arrow_schema: self.info.schema().to_arrow(),
+            files_to_read,
+        }
+    }
+
+    #[async_backtrace::framed]
+    async fn do_read_partitions(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        push_downs: Option<PushDownInfo>,
+    ) -> Result<(PartStatistics, Partitions)> {
+        let snapshot = match self.snapshot() {
+            Some(snapshot) => snapshot,
+            None => return Ok((PartStatistics::default(), Partitions::default())),
+        };
+
+        let data_files = self.list_data_files(snapshot).await?;
+        let data_files = self.prune_data_files(ctx.clone(), &push_downs, data_files)?;
+        if data_files.is_empty() {
+            return Ok((PartStatistics::default(), Partitions::default()));
+        }
+
+        let files_to_read = data_files
+            .iter()
+            .map(|f| {
+                Ok(StageFileInfo {
+                    path: self.snapshots.relative_path(&f.file_path)?,
+                    size: f.file_size_in_bytes as u64,
+                    md5: None,
+                    last_modified: Default::default(),
+                    etag: None,
+                    status: StageFileStatus::NeedCopy,
+                    creator: None,
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let parquet_table = ParquetTable::from_info(&self.parquet_table_info(Some(files_to_read)))?;
+        parquet_table.read_partitions(ctx, push_downs).await
+    }
+
+    fn do_append_data(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        pipeline: &mut Pipeline,
+        append_mode: AppendMode,
+        need_output: bool,
+    ) -> Result<()> {
+        // the commits are serialized by the lock of this node only, see `commit_data_files`
+        if !ctx.get_cluster().is_empty() {
+            return Err(ErrorCode::Unimplemented(
+                "writing Iceberg tables in cluster mode is not supported yet",
+            ));
+        }
+        let block_compact_thresholds = BlockThresholds::default();
+        match append_mode {
+            AppendMode::Normal => {
+                pipeline.add_transform(|transform_input_port, transform_output_port| {
+                    Ok(ProcessorPtr::create(TransformCompact::try_create(
+                        transform_input_port,
+                        transform_output_port,
+                        BlockCompactor::new(block_compact_thresholds, false),
+                    )?))
+                })?;
+            }
+            AppendMode::Copy => {
+                let size = pipeline.output_len();
+                pipeline.resize(1)?;
+                pipeline.add_transform(|transform_input_port, transform_output_port| {
+                    Ok(ProcessorPtr::create(TransformCompact::try_create(
+                        transform_input_port,
+                        transform_output_port,
+                        BlockCompactorNoSplit::new(block_compact_thresholds),
+                    )?))
+                })?;
+                pipeline.resize(size)?;
+            }
+        }
+
+        if need_output {
+            pipeline.add_transform(|transform_input_port, transform_output_port| {
+                IcebergTableSink::try_create(
+                    transform_input_port,
+                    ctx.clone(),
+                    self.operator.clone(),
+                    self.info.schema(),
+                    Some(transform_output_port),
+                )
+            })
+        } else {
+            pipeline.add_sink(|input| {
+                IcebergTableSink::try_create(
+                    input,
+                    ctx.clone(),
+                    self.operator.clone(),
+                    self.info.schema(),
+                    None,
+                )
+            })
+        }
+    }
+
+    /// commit data files written by sinks as a new snapshot
+    #[async_backtrace::framed]
+    async fn do_commit(&self, operations: Vec<DataBlock>, overwrite: bool) -> Result<()> {
+        let appended = operations
+            .iter()
+            .map(AppendedDataFile::try_from)
+            .collect::<Result<Vec<_>>>()?;
+        commit_data_files(
+            &self.operator,
+            self.snapshots.current_schema_id,
+            &self.field_ids,
+            appended,
+            overwrite,
+        )
+        .await
+    }
 }
 
 #[async_trait]
@@ -165,12 +517,178 @@ impl Table for IcebergTable {
         &self.get_table_info().name
     }
 
+    fn get_data_source_info(&self) -> DataSourceInfo {
+        DataSourceInfo::ParquetSource(self.parquet_table_info(None))
+    }
+
+    fn benefit_column_prune(&self) -> bool {
+        true
+    }
+
+    fn support_prewhere(&self) -> bool {
+        ParquetReadOptions::default().do_prewhere()
+    }
+
+    /// Partitions are row groups of data files in the snapshot,
+    /// data files are pruned with column bounds in manifests first,
+    /// then row groups and pages are pruned by the parquet reader.
     #[async_backtrace::framed]
     async fn read_partitions(
         &self,
-        _ctx: Arc<dyn TableContext>,
-        _push_downs: Option<PushDownInfo>,
+        ctx: Arc<dyn TableContext>,
+        push_downs: Option<PushDownInfo>,
     ) -> Result<(PartStatistics, Partitions)> {
-        todo!()
+        self.do_read_partitions(ctx, push_downs).await
+    }
+
+    fn append_data(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        pipeline: &mut Pipeline,
+        append_mode: AppendMode,
+        need_output: bool,
+    ) -> Result<()> {
+        self.do_append_data(ctx, pipeline, append_mode, need_output)
+    }
+
+    /// Copied files are not tracked for Iceberg tables, so COPY INTO requires `FORCE`,
+    /// the data files are committed as a new snapshot of the table.
+    #[async_backtrace::framed]
+    async fn commit_insertion(
+        &self,
+        _ctx: Arc<dyn TableContext>,
+        operations: Vec<DataBlock>,
+        _copied_files: Option<UpsertTableCopiedFileReq>,
+        overwrite: bool,
+    ) -> Result<()> {
+        self.do_commit(operations, overwrite).await
+    }
+
+    #[async_backtrace::framed]
+    async fn navigate_to(&self, point: &NavigationPoint) -> Result<Arc<dyn Table>> {
+        Ok(Arc::new(self.navigate(point)?))
+    }
+}
+
+#[cfg(test)]
+mod table_test {
+    use std::sync::Arc;
+
+    use common_exception::Result;
+    use common_expression::type_check::check_function;
+    use common_expression::types::number::NumberScalar;
+    use common_expression::types::DataType;
+    use common_expression::types::NumberDataType;
+    use common_expression::Expr;
+    use common_expression::FunctionContext;
+    use common_expression::Scalar;
+    use common_expression::TableDataType;
+    use common_expression::TableField;
+    use common_expression::TableSchema;
+    use common_functions::BUILTIN_FUNCTIONS;
+    use common_meta_app::schema::TableInfo;
+    use common_meta_app::schema::TableMeta;
+    use opendal::services::Memory;
+    use opendal::Operator;
+    use storages_common_pruner::RangePrunerCreator;
+
+    use super::IcebergTable;
+    use crate::manifest::DataFile;
+    use crate::manifest::KeyValue;
+    use crate::meta_reader::SnapshotMeta;
+
+    /// schema 1 added column `c` and was rolled back,
+    /// so the current schema is not the last one.
+    const METADATA_FILE: &str = r#"
+    {
+        "location": "s3://bkt/tbl",
+        "current-snapshot-id": -1,
+        "current-schema-id": 0,
+        "schemas": [
+            {"schema-id": 0, "fields": [{"id": 1}, {"id": 3}]},
+            {"schema-id": 1, "fields": [{"id": 1}, {"id": 2}]}
+        ]
+    }
+"#;
+
+    fn bounds(bounds: &[(i32, i32)]) -> Option<Vec<KeyValue<Vec<u8>>>> {
+        Some(
+            bounds
+                .iter()
+                .map(|(key, value)| KeyValue {
+                    key: *key,
+                    value: value.to_le_bytes().to_vec(),
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn test_prune_with_current_schema() -> Result<()> {
+        let snapshots = SnapshotMeta::try_from_json(METADATA_FILE.as_bytes())?;
+        let field_ids = snapshots.field_ids(None);
+        assert_eq!(field_ids, vec![1, 3]);
+
+        let int_ty = TableDataType::Number(NumberDataType::Int32).wrap_nullable();
+        let schema = Arc::new(TableSchema::new(vec![
+            TableField::new("a", int_ty.clone()),
+            TableField::new("b", int_ty),
+        ]));
+        let info = TableInfo {
+            meta: TableMeta {
+                schema: schema.clone(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let table = IcebergTable {
+            operator: Operator::new(Memory::default())?.finish(),
+            schemas: vec![],
+            snapshots,
+            snapshot_id: None,
+            field_ids,
+            info,
+        };
+
+        let data_file = DataFile {
+            content: 0,
+            file_path: "s3://bkt/tbl/data/0.parquet".to_string(),
+            file_format: "PARQUET".to_string(),
+            record_count: 3,
+            file_size_in_bytes: 1024,
+            column_sizes: None,
+            value_counts: None,
+            null_value_counts: None,
+            lower_bounds: bounds(&[(1, 1), (2, 100), (3, 10)]),
+            upper_bounds: bounds(&[(1, 3), (2, 200), (3, 20)]),
+            split_offsets: None,
+        };
+
+        // b = 15
+        let filter = check_function(
+            None,
+            "eq",
+            &[],
+            &[
+                Expr::ColumnRef {
+                    span: None,
+                    id: "b".to_string(),
+                    data_type: DataType::Nullable(Box::new(DataType::Number(
+                        NumberDataType::Int32,
+                    ))),
+                    display_name: "b".to_string(),
+                },
+                Expr::Constant {
+                    span: None,
+                    scalar: Scalar::Number(NumberScalar::Int32(15)),
+                    data_type: DataType::Number(NumberDataType::Int32),
+                },
+            ],
+            &BUILTIN_FUNCTIONS,
+        )?;
+        let pruner =
+            RangePrunerCreator::try_create(FunctionContext::default(), &schema, Some(&filter))?;
+        assert!(pruner.should_keep(&table.column_statistics(&data_file)?));
+        Ok(())
     }
 }
@@ -223,9 +223,14 @@ impl Processor for ParquetDeserializeTransform {
                 .as_ref()
                 .map(|sel| intervals_to_bitmap(sel, part.num_rows));
 
-            // this means it's empty projection
-            if readers.is_empty() {
-                let data_block = DataBlock::new(vec![], part.num_rows);
+            // this means it's empty projection,
+            // partition columns have no readers either, for they are not stored in the file.
+            if readers.is_empty() && self.src_schema.fields().is_empty() {
+                let num_rows = match &row_selection {
+                    Some(bitmap) => bitmap.len() - bitmap.unset_bits(),
+                    None => part.num_rows,
+                };
+                let data_block = DataBlock::new(vec![], num_rows);
                 self.add_block(data_block)?;
                 return Ok(());
             }
@@ -356,7 +361,7 @@ impl Processor for ParquetDeserializeTransform {
 fn intervals_to_bitmap(interval: &[Interval], num_rows: usize) -> Bitmap {
     debug_assert!(
         interval.is_empty()
-            || interval.last().unwrap().start + interval.last().unwrap().length < num_rows
+            || interval.last().unwrap().start + interval.last().unwrap().length <= num_rows
     );
 
     let mut bitmap = MutableBitmap::with_capacity(num_rows);
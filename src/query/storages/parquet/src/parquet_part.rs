@@ -45,6 +45,9 @@ pub struct ParquetRowGroupPart {
     pub num_rows: usize,
     pub column_metas: HashMap<FieldIndex, ColumnMeta>,
     pub row_selection: Option<Vec<Interval>>,
+    /// Values of partition columns which are not stored in the file, keyed by leaf index.
+    /// They are filled as constant columns while deserializing.
+    pub partition_values: HashMap<FieldIndex, Scalar>,
 
     pub sort_min_max: Option<(Scalar, Scalar)>,
 }
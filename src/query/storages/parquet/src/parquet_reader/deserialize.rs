@@ -29,9 +29,12 @@ use common_arrow::parquet::read::PageMetaData;
 use common_arrow::parquet::read::PageReader;
 use common_exception::ErrorCode;
 use common_exception::Result;
+use common_expression::BlockEntry;
+use common_expression::ColumnBuilder;
 use common_expression::DataBlock;
 use common_expression::DataSchema;
 use common_expression::FieldIndex;
+use common_expression::Value;
 use common_storage::ColumnNode;
 
 use super::filter::FilterState;
@@ -46,6 +49,15 @@ impl ParquetReader {
         chunks: Vec<(FieldIndex, Vec<u8>)>,
         filter: Option<Bitmap>,
     ) -> Result<DataBlock> {
+        if self
+            .projected_column_nodes
+            .column_nodes
+            .iter()
+            .any(|node| part.partition_values.contains_key(&node.leaf_indices[0]))
+        {
+            return self.deserialize_with_partition_values(part, chunks, filter);
+        }
+
         if chunks.is_empty() {
             return Ok(DataBlock::new(vec![], part.num_rows));
         }
@@ -138,6 +150,52 @@ impl ParquetReader {
         final_block.resort(&src_schema, &self.output_schema)
     }
 
+    /// Deserialize the columns stored in the file,
+    /// and fill the partition columns with their constant values.
+    fn deserialize_with_partition_values(
+        &self,
+        part: &ParquetRowGroupPart,
+        chunks: Vec<(FieldIndex, Vec<u8>)>,
+        filter: Option<Bitmap>,
+    ) -> Result<DataBlock> {
+        let column_nodes = &self.projected_column_nodes.column_nodes;
+        let num_rows = match &filter {
+            Some(bitmap) => bitmap.len() - bitmap.unset_bits(),
+            None => part.num_rows,
+        };
+
+        let file_indices = column_nodes
+            .iter()
+            .enumerate()
+            .filter(|(_, node)| !part.partition_values.contains_key(&node.leaf_indices[0]))
+            .map(|(idx, _)| idx)
+            .collect::<Vec<_>>();
+        let file_block = if file_indices.is_empty() {
+            DataBlock::new(vec![], num_rows)
+        } else {
+            self.sub_reader(&file_indices)
+                .deserialize(part, chunks, filter)?
+        };
+
+        let mut file_columns = file_block.columns().iter();
+        let mut columns = Vec::with_capacity(column_nodes.len());
+        for (idx, node) in column_nodes.iter().enumerate() {
+            match part.partition_values.get(&node.leaf_indices[0]) {
+                Some(value) => {
+                    let data_type = self.output_schema.field(idx).data_type();
+                    let column =
+                        ColumnBuilder::repeat(&value.as_ref(), num_rows, data_type).build();
+                    columns.push(BlockEntry {
+                        data_type: data_type.clone(),
+                        value: Value::Column(column),
+                    });
+                }
+                None => columns.push(file_columns.next().unwrap().clone()),
+            }
+        }
+        Ok(DataBlock::new(columns, num_rows))
+    }
+
     /// The number of columns can be greater than 1 because the it may be a nested type.
     /// Combine multiple columns into one arrow array.
     fn to_array_iter(
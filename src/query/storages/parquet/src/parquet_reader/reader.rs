@@ -183,11 +183,53 @@ impl ParquetReader {
         ))
     }
 
+    /// Create a reader of the columns at `indices` of the projected schema.
+    pub(crate) fn sub_reader(&self, indices: &[usize]) -> ParquetReader {
+        let column_nodes = indices
+            .iter()
+            .map(|i| self.projected_column_nodes.column_nodes[*i].clone())
+            .collect::<Vec<_>>();
+        let columns_to_read = column_nodes
+            .iter()
+            .flat_map(|node| node.leaf_indices.iter().copied())
+            .collect::<HashSet<_>>();
+        let projected_column_descriptors = self
+            .projected_column_descriptors
+            .iter()
+            .filter(|(index, _)| columns_to_read.contains(index))
+            .map(|(index, descriptor)| (*index, descriptor.clone()))
+            .collect();
+        let output_schema = DataSchema::new(
+            indices
+                .iter()
+                .map(|i| self.output_schema.field(*i).clone())
+                .collect(),
+        );
+        let projected_arrow_schema = ArrowSchema::from(
+            indices
+                .iter()
+                .map(|i| self.projected_arrow_schema.fields[*i].clone())
+                .collect::<Vec<_>>(),
+        );
+        ParquetReader {
+            operator: self.operator.clone(),
+            columns_to_read,
+            output_schema: Arc::new(output_schema),
+            projected_arrow_schema,
+            projected_column_nodes: ColumnNodes { column_nodes },
+            projected_column_descriptors,
+        }
+    }
+
     pub fn read_from_readers(&self, readers: &mut IndexedReaders) -> Result<Vec<IndexedChunk>> {
         let mut chunks = Vec::with_capacity(self.columns_to_read.len());
 
         for index in &self.columns_to_read {
-            let reader = readers.get_mut(index).unwrap();
+            // partition columns are not stored in the file, there is no reader for them.
+            let reader = match readers.get_mut(index) {
+                Some(reader) => reader,
+                None => continue,
+            };
             let data = reader.read_all()?;
 
             chunks.push((*index, data));
@@ -204,6 +246,9 @@ impl ParquetReader {
 
         let op = self.operator.blocking();
         for index in &self.columns_to_read {
+            if part.partition_values.contains_key(index) {
+                continue;
+            }
             let meta = &part.column_metas[index];
             let reader = op.range_reader(&part.location, meta.offset..meta.offset + meta.length)?;
             readers.insert(
@@ -222,6 +267,9 @@ impl ParquetReader {
         let path = Arc::new(part.location.to_string());
 
         for index in self.columns_to_read.iter() {
+            if part.partition_values.contains_key(index) {
+                continue;
+            }
             let op = self.operator.clone();
             let path = path.clone();
 
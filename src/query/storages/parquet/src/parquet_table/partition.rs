@@ -12,6 +12,7 @@
 //  See the License for the specific language governing permissions and
 //  limitations under the License.
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use common_catalog::plan::PartStatistics;
@@ -20,7 +21,10 @@ use common_catalog::plan::Projection;
 use common_catalog::plan::PushDownInfo;
 use common_catalog::table_context::TableContext;
 use common_exception::Result;
+use common_expression::FieldIndex;
+use common_expression::Scalar;
 use common_functions::BUILTIN_FUNCTIONS;
+use common_storage::ColumnNodes;
 use storages_common_index::Index;
 use storages_common_index::RangeIndex;
 use storages_common_pruner::RangePrunerCreator;
@@ -32,12 +36,46 @@ use crate::pruning::PartitionPruner;
 use crate::ParquetTable;
 
 impl ParquetTable {
+    /// Read partitions of tables managed by table formats, e.g. Delta Lake.
+    ///
+    /// `deleted_rows` are positions of deleted rows of each file, in ascending order,
+    /// they are excluded by the row selections of partitions.
+    ///
+    /// `partition_values` are values of partition columns of each file, keyed by field index.
+    /// Partition columns are not stored in the files, they must be top level columns
+    /// placed after all the columns stored in the files.
+    #[async_backtrace::framed]
+    pub async fn read_partitions_with_extras(
+        &self,
+        ctx: Arc<dyn TableContext>,
+        push_down: Option<PushDownInfo>,
+        deleted_rows: HashMap<String, Vec<u64>>,
+        partition_values: HashMap<String, HashMap<FieldIndex, Scalar>>,
+    ) -> Result<(PartStatistics, Partitions)> {
+        // parts and the reader address columns by leaf index
+        let column_nodes = ColumnNodes::new_from_schema(&self.arrow_schema, None);
+        let partition_values = partition_values
+            .into_iter()
+            .map(|(location, values)| {
+                let values = values
+                    .into_iter()
+                    .map(|(idx, value)| (column_nodes.column_nodes[idx].leaf_indices[0], value))
+                    .collect();
+                (location, values)
+            })
+            .collect();
+        self.do_read_partitions(ctx, push_down, deleted_rows, partition_values)
+            .await
+    }
+
     #[inline]
     #[async_backtrace::framed]
     pub(super) async fn do_read_partitions(
         &self,
         ctx: Arc<dyn TableContext>,
         push_down: Option<PushDownInfo>,
+        deleted_rows: HashMap<String, Vec<u64>>,
+        partition_values: HashMap<String, HashMap<FieldIndex, Scalar>>,
     ) -> Result<(PartStatistics, Partitions)> {
         // `plan.source_info.schema()` is the same as `TableSchema::from(&self.arrow_schema)`
         let projection = if let Some(PushDownInfo {
@@ -133,6 +171,8 @@ impl ParquetTable {
             column_nodes: projected_column_nodes,
             skip_pruning,
             top_k,
+            deleted_rows,
+            partition_values,
         };
 
         pruner.read_and_prune_partitions().await
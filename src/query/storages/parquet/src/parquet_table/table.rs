@@ -13,6 +13,7 @@
 //  limitations under the License.
 
 use std::any::Any;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use chrono::NaiveDateTime;
@@ -56,9 +57,13 @@ pub struct ParquetTable {
 
 impl ParquetTable {
     pub fn from_info(info: &ParquetTableInfo) -> Result<Arc<dyn Table>> {
+        Ok(Arc::new(Self::create(info)?))
+    }
+
+    pub fn create(info: &ParquetTableInfo) -> Result<ParquetTable> {
         let operator = init_stage_operator(&info.stage_info)?;
 
-        Ok(Arc::new(ParquetTable {
+        Ok(ParquetTable {
             table_info: info.table_info.clone(),
             arrow_schema: info.arrow_schema.clone(),
             operator,
@@ -66,7 +71,7 @@ impl ParquetTable {
             stage_info: info.stage_info.clone(),
             files_info: info.files_info.clone(),
             files_to_read: info.files_to_read.clone(),
-        }))
+        })
     }
 }
 
@@ -115,7 +120,8 @@ impl Table for ParquetTable {
         ctx: Arc<dyn TableContext>,
         push_down: Option<PushDownInfo>,
     ) -> Result<(PartStatistics, Partitions)> {
-        self.do_read_partitions(ctx, push_down).await
+        self.do_read_partitions(ctx, push_down, HashMap::new(), HashMap::new())
+            .await
     }
 
     fn read_data(
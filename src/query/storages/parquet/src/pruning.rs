@@ -36,6 +36,7 @@ use common_exception::Result;
 use common_expression::Expr;
 use common_expression::FieldIndex;
 use common_expression::FunctionContext;
+use common_expression::Scalar;
 use common_expression::TableSchemaRef;
 use common_storage::read_parquet_metas_in_parallel;
 use common_storage::ColumnNodes;
@@ -68,6 +69,10 @@ pub struct PartitionPruner {
     pub skip_pruning: bool,
     /// top k information from pushed down information. The usize is the offset of top k column in `schema`.
     pub top_k: Option<(TopK, usize)>,
+    /// Positions of deleted rows of each file, in ascending order.
+    pub deleted_rows: HashMap<String, Vec<u64>>,
+    /// Values of partition columns of each file, keyed by leaf index.
+    pub partition_values: HashMap<String, HashMap<FieldIndex, Scalar>>,
     // TODO: use limit information for pruning
     // /// Limit of this query. If there is order by and filter, it will not be used (assign to `usize::MAX`).
     // pub limit: usize,
@@ -88,6 +93,8 @@ impl PartitionPruner {
             column_nodes,
             skip_pruning,
             top_k,
+            deleted_rows,
+            partition_values,
         } = self;
 
         // part stats
@@ -126,6 +133,10 @@ impl PartitionPruner {
         for (file_id, file_meta) in file_metas.iter().enumerate() {
             partitions_total += file_meta.row_groups.len();
             let mut row_group_pruned = vec![false; file_meta.row_groups.len()];
+            let file_partition_values = partition_values
+                .get(&locations[file_id].0)
+                .cloned()
+                .unwrap_or_default();
 
             let no_stats = file_meta.row_groups.iter().any(|r| {
                 r.columns()
@@ -139,9 +150,11 @@ impl PartitionPruner {
                 let pruner = row_group_pruner.as_ref().unwrap();
                 // If collecting stats fails or `should_keep` is true, we still read the row group.
                 // Otherwise, the row group will be pruned.
-                if let Ok(row_group_stats) =
-                    collect_row_group_stats(column_nodes, &file_meta.row_groups)
-                {
+                if let Ok(row_group_stats) = collect_row_group_stats(
+                    column_nodes,
+                    &file_meta.row_groups,
+                    &file_partition_values,
+                ) {
                     for (idx, (stats, _rg)) in row_group_stats
                         .iter()
                         .zip(file_meta.row_groups.iter())
@@ -154,26 +167,47 @@ impl PartitionPruner {
                     None
                 }
             } else if top_k.is_some() {
-                collect_row_group_stats(column_nodes, &file_meta.row_groups).ok()
+                collect_row_group_stats(column_nodes, &file_meta.row_groups, &file_partition_values)
+                    .ok()
             } else {
                 None
             };
 
             // If one row group does not have stats, we cannot use the stats for topk optimization.
-            all_have_minmax &= row_group_stats.is_some();
+            // Partition columns are not in `column_metas`, neither are their stats.
+            all_have_minmax &= row_group_stats.is_some()
+                && top_k.as_ref().map_or(true, |(tk, _)| {
+                    !file_partition_values.contains_key(&(tk.column_id as usize))
+                });
+
+            let file_deleted_rows = deleted_rows.get(&locations[file_id].0);
+            let mut first_row = 0;
 
             for (rg_idx, rg) in file_meta.row_groups.iter().enumerate() {
+                let rg_first_row = first_row;
+                first_row += rg.num_rows() as u64;
+
                 if row_group_pruned[rg_idx] {
                     continue;
                 }
 
+                let undeleted = file_deleted_rows
+                    .and_then(|rows| undeleted_rows(rows, rg_first_row, rg.num_rows()));
+                if matches!(&undeleted, Some(sel) if sel.is_empty()) {
+                    // all rows of the row group are deleted.
+                    continue;
+                }
+
                 read_rows += rg.num_rows();
                 read_bytes += rg.total_byte_size();
                 partitions_scanned += 1;
 
                 // Currently, only blocking io is allowed to prune pages.
+                // Pages of files with partition values are not pruned,
+                // for partition columns have no page indexes.
                 let row_selection = if page_pruners.is_some()
                     && is_blocking_io
+                    && file_partition_values.is_empty()
                     && rg.columns().iter().all(|c| {
                         c.column_chunk().column_index_offset.is_some()
                             && c.column_chunk().column_index_length.is_some()
@@ -188,8 +222,16 @@ impl PartitionPruner {
                     None
                 };
 
+                let row_selection = match (row_selection, undeleted) {
+                    (Some(sel), Some(undeleted)) => Some(combine_intervals(vec![sel, undeleted])),
+                    (sel, undeleted) => sel.or(undeleted),
+                };
+
                 let mut column_metas = HashMap::with_capacity(columns_to_read.len());
                 for index in columns_to_read {
+                    if file_partition_values.contains_key(index) {
+                        continue;
+                    }
                     let c = &rg.columns()[*index];
                     let (offset, length) = c.byte_range();
 
@@ -216,6 +258,7 @@ impl PartitionPruner {
                     num_rows: rg.num_rows(),
                     column_metas,
                     row_selection,
+                    partition_values: file_partition_values.clone(),
                     sort_min_max: None,
                 })
             }
@@ -392,6 +435,34 @@ fn combine_intervals(row_selections: Vec<Vec<Interval>>) -> Vec<Interval> {
     res
 }
 
+/// Select rows of a row group which are not deleted.
+///
+/// `deleted_rows` are positions of deleted rows in the file, in ascending order,
+/// and `first_row` is the position of the first row of the row group.
+/// Returns `None` if no row of the row group is deleted.
+fn undeleted_rows(deleted_rows: &[u64], first_row: u64, num_rows: usize) -> Option<Vec<Interval>> {
+    let end = first_row + num_rows as u64;
+    let from = deleted_rows.partition_point(|row| *row < first_row);
+    let to = deleted_rows.partition_point(|row| *row < end);
+    if from == to {
+        return None;
+    }
+
+    let mut selection = vec![];
+    let mut start = 0;
+    for row in &deleted_rows[from..to] {
+        let pos = (row - first_row) as usize;
+        if pos > start {
+            selection.push(Interval::new(start, pos - start));
+        }
+        start = pos + 1;
+    }
+    if start < num_rows {
+        selection.push(Interval::new(start, num_rows - start));
+    }
+    Some(selection)
+}
+
 /// Do "and" operation on two row selections.
 /// Select the rows which both `sel1` and `sel2` select.
 fn and_intervals(sel1: &[Interval], sel2: &[Interval]) -> Vec<Interval> {
@@ -426,6 +497,7 @@ fn is_in(probe: Interval, intervals: &[Interval]) -> Vec<Interval> {
 
 #[cfg(test)]
 mod tests {
+    use std::collections::HashMap;
     use std::io::Cursor;
 
     use common_arrow::parquet::compression::CompressionOptions;
@@ -474,6 +546,7 @@ mod tests {
     use crate::pruning::build_column_page_pruners;
     use crate::pruning::combine_intervals;
     use crate::pruning::filter_pages;
+    use crate::pruning::undeleted_rows;
     use crate::statistics::collect_row_group_stats;
 
     #[test]
@@ -547,6 +620,30 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_undeleted_rows() {
+        // deleted rows of the file: 3, 10, 11, 19, 25
+        let deleted_rows = vec![3, 10, 11, 19, 25];
+
+        // row group [0, 10)
+        let expected = vec![Interval::new(0, 3), Interval::new(4, 6)];
+        let actual = undeleted_rows(&deleted_rows, 0, 10);
+        assert_eq!(Some(expected), actual);
+
+        // row group [10, 20)
+        let expected = vec![Interval::new(2, 7)];
+        let actual = undeleted_rows(&deleted_rows, 10, 10);
+        assert_eq!(Some(expected), actual);
+
+        // row group [20, 25), no rows deleted
+        let actual = undeleted_rows(&deleted_rows, 20, 5);
+        assert_eq!(None, actual);
+
+        // row group [25, 26), all rows deleted
+        let actual = undeleted_rows(&deleted_rows, 25, 1);
+        assert_eq!(Some(vec![]), actual);
+    }
+
     fn unzip_option<T: NativeType>(
         array: &[Option<T>],
     ) -> common_arrow::parquet::error::Result<(Vec<u8>, Vec<u8>)> {
@@ -669,7 +766,7 @@ mod tests {
         let arrow_schema = schema.to_arrow();
         let column_nodes = ColumnNodes::new_from_schema(&arrow_schema, None);
 
-        let row_group_stats = collect_row_group_stats(&column_nodes, &rgs)?;
+        let row_group_stats = collect_row_group_stats(&column_nodes, &rgs, &HashMap::new())?;
 
         // col1 > 12
         {
@@ -764,6 +861,60 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_prune_row_group_with_partition_values() -> Result<()> {
+        let (_, data) = write_test_parquet()?;
+        let mut reader = Cursor::new(data);
+        let metadata = read_metadata(&mut reader)?;
+        let rgs = metadata.row_groups;
+
+        // `part` is a partition column not stored in the file
+        let schema = TableSchemaRefExt::create(vec![
+            TableField::new("col1", TableDataType::Number(NumberDataType::Int32)),
+            TableField::new("part", TableDataType::Number(NumberDataType::Int32)),
+        ]);
+        let arrow_schema = schema.to_arrow();
+        let column_nodes = ColumnNodes::new_from_schema(&arrow_schema, None);
+        let partition_values = HashMap::from([(1, Scalar::Number(NumberScalar::Int32(3)))]);
+
+        let row_group_stats = collect_row_group_stats(&column_nodes, &rgs, &partition_values)?;
+        let stat = &row_group_stats[0][&1];
+        assert_eq!(stat.min, Scalar::Number(NumberScalar::Int32(3)));
+        assert_eq!(stat.max, Scalar::Number(NumberScalar::Int32(3)));
+        assert_eq!(stat.null_count, 0);
+
+        for (value, keep) in [(3, true), (4, false)] {
+            let filter = ScalarExpr::FunctionCall(FunctionCall {
+                span: None,
+                func_name: "eq".to_string(),
+                params: vec![],
+                arguments: vec![
+                    ScalarExpr::BoundColumnRef(BoundColumnRef {
+                        span: None,
+                        column: ColumnBinding {
+                            database_name: None,
+                            table_name: None,
+                            column_name: "part".to_string(),
+                            index: 1,
+                            data_type: Box::new(DataType::Number(NumberDataType::Int32)),
+                            visibility: Visibility::Visible,
+                        },
+                    }),
+                    ScalarExpr::ConstantExpr(ConstantExpr {
+                        span: None,
+                        value: Scalar::Number(NumberScalar::Int32(value)),
+                    }),
+                ],
+            });
+            let filter = filter.as_expr_with_col_name()?;
+            let pruner =
+                RangePrunerCreator::try_create(FunctionContext::default(), &schema, Some(&filter))?;
+            assert_eq!(keep, pruner.should_keep(&row_group_stats[0]));
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn test_filter_pages() -> Result<()> {
         let (schema, data) = write_test_parquet()?;
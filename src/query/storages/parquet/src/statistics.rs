@@ -22,6 +22,8 @@ use common_exception::ErrorCode;
 use common_exception::Result;
 use common_expression::types::DataType;
 use common_expression::Column;
+use common_expression::FieldIndex;
+use common_expression::Scalar;
 use common_expression::TableDataType;
 use common_storage::ColumnNodes;
 use storages_common_table_meta::meta::ColumnStatistics;
@@ -30,9 +32,13 @@ use storages_common_table_meta::meta::StatisticsOfColumns;
 /// Collect statistics of a batch of row groups of the specified columns.
 ///
 /// The retuened vector's length is the same as `rgs`.
+///
+/// Columns in `partition_values` (keyed by leaf index) are not stored in the file,
+/// their statistics are the constant values.
 pub fn collect_row_group_stats(
     column_nodes: &ColumnNodes,
     rgs: &[RowGroupMetaData],
+    partition_values: &HashMap<FieldIndex, Scalar>,
 ) -> Result<Vec<StatisticsOfColumns>> {
     let mut stats = Vec::with_capacity(rgs.len());
     let mut stats_of_row_groups = HashMap::with_capacity(rgs.len());
@@ -42,6 +48,9 @@ pub fn collect_row_group_stats(
     // and the second element is the statistics of the column (according to the offset)
     // `column_nodes` is parallel to the schema, so we can iterate `column_nodes` directly.
     for (index, column_node) in column_nodes.column_nodes.iter().enumerate() {
+        if partition_values.contains_key(&column_node.leaf_indices[0]) {
+            continue;
+        }
         let field = &column_node.field;
         let table_type: TableDataType = field.into();
         let data_type = (&table_type).into();
@@ -52,10 +61,23 @@ pub fn collect_row_group_stats(
         );
     }
 
-    for (rg_idx, _) in rgs.iter().enumerate() {
+    for (rg_idx, rg) in rgs.iter().enumerate() {
         let mut cols_stats = HashMap::with_capacity(stats.capacity());
-        for index in 0..column_nodes.column_nodes.len() {
-            let col_stats = stats_of_row_groups[&index].get(rg_idx);
+        for (index, column_node) in column_nodes.column_nodes.iter().enumerate() {
+            let col_stats = match partition_values.get(&column_node.leaf_indices[0]) {
+                Some(value) => ColumnStatistics {
+                    min: value.clone(),
+                    max: value.clone(),
+                    null_count: if value.is_null() {
+                        rg.num_rows() as u64
+                    } else {
+                        0
+                    },
+                    in_memory_size: 0,
+                    distinct_of_values: Some(1),
+                },
+                None => stats_of_row_groups[&index].get(rg_idx),
+            };
             cols_stats.insert(index as u32, col_stats);
         }
         stats.push(cols_stats);
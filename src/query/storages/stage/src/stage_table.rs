@@ -183,6 +183,7 @@ impl Table for StageTable {
             splits,
             ctx.get_scan_progress(),
             compact_threshold,
+            stage_table_info.is_select,
         )?);
 
         input_ctx.format.exec_copy(input_ctx.clone(), pipeline)?;
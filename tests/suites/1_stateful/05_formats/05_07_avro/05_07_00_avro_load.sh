@@ -0,0 +1,49 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../../shell_env.sh
+
+DATADIR_PATH="/tmp/05_07_00/"
+rm -rf ${DATADIR_PATH}
+mkdir ${DATADIR_PATH}
+DATADIR="fs://$DATADIR_PATH/"
+
+cp "$CURDIR"/../../../../data/sample.avro ${DATADIR_PATH}
+
+echo "drop stage if exists s_avro;" | $MYSQL_CLIENT_CONNECT
+echo "create stage s_avro url = '${DATADIR}' FILE_FORMAT = (type = AVRO);" | $MYSQL_CLIENT_CONNECT
+
+echo "--- infer_schema"
+echo "select * from infer_schema(location => '@s_avro/sample.avro');" | $MYSQL_CLIENT_CONNECT
+
+echo "drop table if exists test_avro" | $MYSQL_CLIENT_CONNECT
+echo "CREATE TABLE test_avro
+(
+    id INT,
+    name VARCHAR,
+    score DOUBLE NULL,
+    active BOOLEAN,
+    tags ARRAY(STRING),
+    attrs MAP(STRING, INT64),
+    address TUPLE(city STRING, zip INT NULL),
+    kind VARCHAR,
+    birthday DATE,
+    created TIMESTAMP,
+    amount DECIMAL(10, 2),
+    extra VARIANT NULL
+);" | $MYSQL_CLIENT_CONNECT
+
+echo "--- copy"
+echo "copy into test_avro from @s_avro FILES = ('sample.avro');" | $MYSQL_CLIENT_CONNECT
+echo "select * from test_avro order by id;" | $MYSQL_CLIENT_CONNECT
+
+echo "--- streaming load"
+echo "truncate table test_avro" | $MYSQL_CLIENT_CONNECT
+curl -sH "insert_sql:insert into test_avro file_format = (type = AVRO)" \
+	-F "upload=@${DATADIR_PATH}/sample.avro" \
+	-u root: -XPUT "http://localhost:${QUERY_HTTP_HANDLER_PORT}/v1/streaming_load" | grep -c "SUCCESS"
+echo "select id, name, extra from test_avro order by id;" | $MYSQL_CLIENT_CONNECT
+
+echo "drop table test_avro" | $MYSQL_CLIENT_CONNECT
+echo "drop stage s_avro;" | $MYSQL_CLIENT_CONNECT
+rm -rf ${DATADIR_PATH}
@@ -0,0 +1,60 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../../shell_env.sh
+
+DATADIR_PATH="/tmp/05_08_00/"
+rm -rf ${DATADIR_PATH}
+mkdir ${DATADIR_PATH}
+DATADIR="fs://$DATADIR_PATH/"
+
+# two stripes, with nested columns
+cp "$CURDIR"/../../../../data/sample_nested.orc ${DATADIR_PATH}
+
+echo "drop stage if exists s_orc;" | $MYSQL_CLIENT_CONNECT
+echo "create stage s_orc url = '${DATADIR}' FILE_FORMAT = (type = ORC);" | $MYSQL_CLIENT_CONNECT
+
+echo "--- infer_schema"
+echo "select * from infer_schema(location => '@s_orc/sample_nested.orc');" | $MYSQL_CLIENT_CONNECT
+
+echo "--- stage table function"
+echo "select * from @s_orc order by id;" | $MYSQL_CLIENT_CONNECT
+echo "select id, address from @s_orc (files => ('sample_nested.orc')) where id > 1 order by id;" | $MYSQL_CLIENT_CONNECT
+
+echo "drop table if exists test_orc" | $MYSQL_CLIENT_CONNECT
+echo "CREATE TABLE test_orc
+(
+    id INT NULL,
+    name VARCHAR NULL,
+    tags ARRAY(STRING NULL) NULL,
+    attrs MAP(STRING, INT NULL) NULL,
+    address TUPLE(city STRING NULL, zip INT NULL) NULL
+);" | $MYSQL_CLIENT_CONNECT
+
+echo "--- copy"
+echo "copy into test_orc from @s_orc FILES = ('sample_nested.orc');" | $MYSQL_CLIENT_CONNECT
+echo "select * from test_orc order by id;" | $MYSQL_CLIENT_CONNECT
+
+echo "--- copy projected columns"
+echo "drop table if exists test_orc_projected" | $MYSQL_CLIENT_CONNECT
+echo "CREATE TABLE test_orc_projected (address TUPLE(city STRING NULL, zip INT NULL) NULL, id BIGINT NULL);" | $MYSQL_CLIENT_CONNECT
+echo "copy into test_orc_projected from @s_orc FILES = ('sample_nested.orc');" | $MYSQL_CLIENT_CONNECT
+echo "select * from test_orc_projected order by id;" | $MYSQL_CLIENT_CONNECT
+
+echo "--- streaming load"
+echo "truncate table test_orc" | $MYSQL_CLIENT_CONNECT
+curl -sH "insert_sql:insert into test_orc file_format = (type = ORC)" \
+	-F "upload=@${DATADIR_PATH}/sample_nested.orc" \
+	-u root: -XPUT "http://localhost:${QUERY_HTTP_HANDLER_PORT}/v1/streaming_load" | grep -c "SUCCESS"
+echo "select id, tags, attrs from test_orc order by id;" | $MYSQL_CLIENT_CONNECT
+
+echo "--- format version 0.11"
+# integers are encoded with RLE v1, strings with DIRECT and DICTIONARY encodings
+cp "$CURDIR"/../../../../data/sample_v0_11.orc ${DATADIR_PATH}
+echo "select * from @s_orc (files => ('sample_v0_11.orc')) where id < 100 order by id;" | $MYSQL_CLIENT_CONNECT
+echo "select count(*), count(id), count(name), count(note), sum(id) from @s_orc (files => ('sample_v0_11.orc'));" | $MYSQL_CLIENT_CONNECT
+
+echo "drop table test_orc" | $MYSQL_CLIENT_CONNECT
+echo "drop table test_orc_projected" | $MYSQL_CLIENT_CONNECT
+echo "drop stage s_orc;" | $MYSQL_CLIENT_CONNECT
+rm -rf ${DATADIR_PATH}
@@ -0,0 +1,67 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../../shell_env.sh
+
+DATADIR_PATH="/tmp/05_09_00/"
+rm -rf ${DATADIR_PATH}
+mkdir ${DATADIR_PATH}
+DATADIR="fs://$DATADIR_PATH/"
+
+echo "drop table if exists test_arrow" | $MYSQL_CLIENT_CONNECT
+echo "CREATE TABLE test_arrow
+(
+    a INT,
+    b VARCHAR NULL,
+    c DOUBLE,
+    d DATE,
+    e TIMESTAMP,
+    f DECIMAL(15, 2) NULL
+);" | $MYSQL_CLIENT_CONNECT
+
+echo "insert into test_arrow values
+(1, 'a', 1.5, '2023-01-01', '2023-01-01 01:02:03.456789', 1.23),
+(2, NULL, -2.25, '1999-12-31', '1970-01-01 00:00:00', NULL),
+(3, 'c\"d', 0, '2038-01-19', '2038-01-19 03:14:07', -0.5)" | $MYSQL_CLIENT_CONNECT
+
+echo "drop stage if exists s_arrow;" | $MYSQL_CLIENT_CONNECT
+echo "create stage s_arrow url = '${DATADIR}' FILE_FORMAT = (type = ARROW);" | $MYSQL_CLIENT_CONNECT
+
+echo "--- unload"
+echo "copy into @s_arrow from test_arrow" | $MYSQL_CLIENT_CONNECT
+ls ${DATADIR_PATH} | grep -c "arrowipc"
+
+echo "--- infer_schema"
+echo "select * from infer_schema(location => '@s_arrow', pattern => '.*arrowipc');" | $MYSQL_CLIENT_CONNECT
+
+echo "--- stage table function"
+echo "select * from @s_arrow order by a;" | $MYSQL_CLIENT_CONNECT
+echo "select f, a from @s_arrow where a > 1 order by a;" | $MYSQL_CLIENT_CONNECT
+
+echo "--- copy"
+echo "drop table if exists test_arrow_copy" | $MYSQL_CLIENT_CONNECT
+echo "CREATE TABLE test_arrow_copy (e TIMESTAMP, a BIGINT, b VARCHAR NULL);" | $MYSQL_CLIENT_CONNECT
+echo "copy into test_arrow_copy from @s_arrow pattern = '.*arrowipc';" | $MYSQL_CLIENT_CONNECT
+echo "select * from test_arrow_copy order by a;" | $MYSQL_CLIENT_CONNECT
+
+echo "--- clickhouse handler and streaming load"
+curl -s -u root: -XPOST "http://localhost:${QUERY_CLICKHOUSE_HTTP_HANDLER_PORT}" \
+	-d "select * from test_arrow FORMAT Arrow" > ${DATADIR_PATH}/test_arrow.arrow
+echo "truncate table test_arrow" | $MYSQL_CLIENT_CONNECT
+curl -sH "insert_sql:insert into test_arrow file_format = (type = ARROW)" \
+	-F "upload=@${DATADIR_PATH}/test_arrow.arrow" \
+	-u root: -XPUT "http://localhost:${QUERY_HTTP_HANDLER_PORT}/v1/streaming_load" | grep -c "SUCCESS"
+echo "select * from test_arrow order by a;" | $MYSQL_CLIENT_CONNECT
+
+echo "--- http handler"
+curl -s -u root: -XPOST "http://localhost:${QUERY_HTTP_HANDLER_PORT}/v1/query" --header 'Content-Type: application/json' \
+	-d '{"sql": "select a, b from @s_arrow (pattern => '"'"'.*arrowipc'"'"') order by a"}' | jq -c '.data, .error'
+echo "truncate table test_arrow_copy" | $MYSQL_CLIENT_CONNECT
+curl -s -u root: -XPOST "http://localhost:${QUERY_HTTP_HANDLER_PORT}/v1/query" --header 'Content-Type: application/json' \
+	-d '{"sql": "insert into test_arrow_copy (e, a, b) values", "stage_attachment": {"location": "@s_arrow/test_arrow.arrow", "file_format_options": {"type": "ARROW"}}}' | jq -r '.error'
+echo "select * from test_arrow_copy order by a;" | $MYSQL_CLIENT_CONNECT
+
+echo "drop table test_arrow" | $MYSQL_CLIENT_CONNECT
+echo "drop table test_arrow_copy" | $MYSQL_CLIENT_CONNECT
+echo "drop stage s_arrow;" | $MYSQL_CLIENT_CONNECT
+rm -rf ${DATADIR_PATH}
@@ -32,6 +32,14 @@ echo '--- copy csv'
 echo "copy into t1 from (select (t.id+1), age from @s1 t)  FILE_FORMAT = (type = csv)  PATTERN='.*csv' force=true;" | $MYSQL_CLIENT_CONNECT
 echo "select * from t1 order by id;" | $MYSQL_CLIENT_CONNECT
 
+echo '--- copy arrow'
+echo "copy into '${DATADIR}' from t1 FILE_FORMAT = (type = ARROW);" | $MYSQL_CLIENT_CONNECT
+echo "copy into t1 from (select (t.id+1), age from @s1 t)  FILE_FORMAT = (type = arrow)  PATTERN='.*arrowipc';" | $MYSQL_CLIENT_CONNECT
+echo "select count(*), sum(id) from t1;" | $MYSQL_CLIENT_CONNECT
+
+echo '--- copy xml'
+echo "copy into t1 from (select (t.id+1), age from @s1 t)  FILE_FORMAT = (type = xml)  PATTERN='.*csv' force=true;" | $MYSQL_CLIENT_CONNECT
+
 echo '--- copy from s3'
 echo "drop table if exists t2;" | $MYSQL_CLIENT_CONNECT
 echo "CREATE TABLE t2 (a INT32);" | $MYSQL_CLIENT_CONNECT
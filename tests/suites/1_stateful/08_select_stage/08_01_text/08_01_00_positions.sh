@@ -0,0 +1,37 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../../shell_env.sh
+
+DATADIR_PATH="/tmp/08_01_00"
+rm -rf ${DATADIR_PATH}
+mkdir ${DATADIR_PATH}
+DATADIR="fs://$DATADIR_PATH/"
+
+printf '1,alice,3\n2,bob\n3,carol,5,extra\n' > ${DATADIR_PATH}/data.csv
+printf '1\talice\n2\tbob\n' > ${DATADIR_PATH}/data.tsv
+printf '{"id":1,"name":"alice"}\n{"id":2,"name":"bob","tags":[1,2]}\n' > ${DATADIR_PATH}/data.ndjson
+
+echo "drop stage if exists s3;" | $MYSQL_CLIENT_CONNECT
+echo "create stage s3 url = '${DATADIR}' FILE_FORMAT = (type = CSV);"  | $MYSQL_CLIENT_CONNECT
+
+echo "--- csv"
+echo "select \$1, \$2, \$3 from @s3 (files => ('data.csv')) order by \$1;" | $MYSQL_CLIENT_CONNECT
+echo "select \$2 from @s3 (files => ('data.csv')) where \$1::int > 1 order by \$2;" | $MYSQL_CLIENT_CONNECT
+echo "select * from @s3 (files => ('data.csv'));" | $MYSQL_CLIENT_CONNECT
+
+echo "--- tsv"
+echo "select \$2, \$1 from @s3 (files => ('data.tsv'), file_format => 'tsv') order by \$1;" | $MYSQL_CLIENT_CONNECT
+
+echo "--- ndjson"
+echo "select \$1:id, \$1:name, \$1:tags from @s3 (files => ('data.ndjson'), file_format => 'ndjson') order by \$1:id::int;" | $MYSQL_CLIENT_CONNECT
+
+echo "--- copy csv"
+echo "drop table if exists t3;" | $MYSQL_CLIENT_CONNECT
+echo "create table t3 (id int, name string);" | $MYSQL_CLIENT_CONNECT
+echo "copy into t3 from (select \$1::int, \$2 from @s3) files = ('data.csv');" | $MYSQL_CLIENT_CONNECT
+echo "select * from t3 order by id;" | $MYSQL_CLIENT_CONNECT
+
+echo "drop table if exists t3;" | $MYSQL_CLIENT_CONNECT
+echo "drop stage if exists s3;" | $MYSQL_CLIENT_CONNECT
+rm -rf ${DATADIR_PATH}
@@ -0,0 +1,28 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../../shell_env.sh
+
+DATADIR_PATH="/tmp/08_01_01"
+rm -rf ${DATADIR_PATH}
+mkdir ${DATADIR_PATH}
+DATADIR="fs://$DATADIR_PATH/"
+
+printf 'id,name,score,day,ts,ok\n1,alice,1.5,2023-01-02,2023-01-02 10:00:00,true\n2,"bob, jr",3,2023-01-03,2023-01-03 01:02:03,false\n3,,,,,\n' > ${DATADIR_PATH}/header.csv
+printf '1,x\n2,y\n' > ${DATADIR_PATH}/no_header.csv
+printf '{"id":1,"name":"alice","tags":["a"],"score":1}\n{"id":2,"name":"bob","score":2.5,"extra":{"k":1},"mixed":1}\n{"id":3,"mixed":"x","day":"2023-01-01"}\n' > ${DATADIR_PATH}/data.ndjson
+
+echo "drop stage if exists s4;" | $MYSQL_CLIENT_CONNECT
+echo "create stage s4 url = '${DATADIR}' FILE_FORMAT = (type = CSV);"  | $MYSQL_CLIENT_CONNECT
+
+echo "--- csv with header"
+echo "select * from infer_schema(location => '@s4/header.csv');" | $MYSQL_CLIENT_CONNECT
+
+echo "--- csv without header"
+echo "select * from infer_schema(location => '@s4/no_header.csv');" | $MYSQL_CLIENT_CONNECT
+
+echo "--- ndjson"
+echo "select * from infer_schema(location => '@s4/data.ndjson', file_format => 'NDJSON');" | $MYSQL_CLIENT_CONNECT
+
+echo "drop stage if exists s4;" | $MYSQL_CLIENT_CONNECT
+rm -rf ${DATADIR_PATH}
@@ -0,0 +1,43 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+echo "DROP CATALOG IF EXISTS iceberg_ctl" | $MYSQL_CLIENT_CONNECT
+
+## Create iceberg catalog
+cat <<EOF | $MYSQL_CLIENT_CONNECT
+CREATE CATALOG iceberg_ctl
+TYPE=ICEBERG
+CONNECTION=(
+    URL='s3://testbucket/iceberg_data/iceberg_ctl/'
+    AWS_KEY_ID='minioadmin'
+    AWS_SECRET_KEY='minioadmin'
+    ENDPOINT_URL='${STORAGE_S3_ENDPOINT_URL}'
+);
+EOF
+
+echo "INSERT INTO iceberg_ctl.iceberg_write_db.iceberg_write_tbl VALUES (1, 'a'), (2, 'b');" | $MYSQL_CLIENT_CONNECT
+
+echo "INSERT INTO iceberg_ctl.iceberg_write_db.iceberg_write_tbl SELECT number + 3, 'c' FROM numbers(2);" | $MYSQL_CLIENT_CONNECT
+
+echo "SELECT id, data FROM iceberg_ctl.iceberg_write_db.iceberg_write_tbl ORDER BY id;" | $MYSQL_CLIENT_CONNECT
+
+echo "SELECT count(*) FROM iceberg_ctl.iceberg_write_db.iceberg_write_tbl WHERE id > 2;" | $MYSQL_CLIENT_CONNECT
+
+echo "INSERT OVERWRITE iceberg_ctl.iceberg_write_db.iceberg_write_tbl VALUES (5, 'e');" | $MYSQL_CLIENT_CONNECT
+
+echo "SELECT id, data FROM iceberg_ctl.iceberg_write_db.iceberg_write_tbl ORDER BY id;" | $MYSQL_CLIENT_CONNECT
+
+echo "DROP STAGE IF EXISTS iceberg_write_stage;" | $MYSQL_CLIENT_CONNECT
+echo "CREATE STAGE iceberg_write_stage;" | $MYSQL_CLIENT_CONNECT
+echo "COPY INTO @iceberg_write_stage FROM (SELECT 6, 'f') FILE_FORMAT = (TYPE = CSV);" | $MYSQL_CLIENT_CONNECT
+
+## copied files are not tracked, COPY INTO requires FORCE
+echo "COPY INTO iceberg_ctl.iceberg_write_db.iceberg_write_tbl FROM @iceberg_write_stage FILE_FORMAT = (TYPE = CSV);" | $MYSQL_CLIENT_CONNECT 2>&1 | grep -c "requires FORCE"
+
+echo "COPY INTO iceberg_ctl.iceberg_write_db.iceberg_write_tbl FROM @iceberg_write_stage FILE_FORMAT = (TYPE = CSV) FORCE = true;" | $MYSQL_CLIENT_CONNECT
+
+echo "SELECT id, data FROM iceberg_ctl.iceberg_write_db.iceberg_write_tbl ORDER BY id;" | $MYSQL_CLIENT_CONNECT
+
+echo "DROP STAGE iceberg_write_stage;" | $MYSQL_CLIENT_CONNECT
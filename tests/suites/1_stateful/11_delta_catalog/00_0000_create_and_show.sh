@@ -0,0 +1,22 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+echo "DROP CATALOG IF EXISTS delta_ctl" | $MYSQL_CLIENT_CONNECT
+
+## Create delta catalog
+cat <<EOF | $MYSQL_CLIENT_CONNECT
+CREATE CATALOG delta_ctl
+TYPE=DELTA
+CONNECTION=(
+    URL='s3://testbucket/delta_data/delta_ctl/'
+    AWS_KEY_ID='minioadmin'
+    AWS_SECRET_KEY='minioadmin'
+    ENDPOINT_URL='${STORAGE_S3_ENDPOINT_URL}'
+);
+EOF
+
+echo "SHOW DATABASES IN delta_ctl;" | $MYSQL_CLIENT_CONNECT
+
+echo "SHOW TABLES IN delta_ctl.delta_db;" | $MYSQL_CLIENT_CONNECT
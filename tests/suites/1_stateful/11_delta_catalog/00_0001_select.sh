@@ -0,0 +1,25 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+echo "DROP CATALOG IF EXISTS delta_ctl" | $MYSQL_CLIENT_CONNECT
+
+## Create delta catalog
+cat <<EOF | $MYSQL_CLIENT_CONNECT
+CREATE CATALOG delta_ctl
+TYPE=DELTA
+CONNECTION=(
+    URL='s3://testbucket/delta_data/delta_ctl/'
+    AWS_KEY_ID='minioadmin'
+    AWS_SECRET_KEY='minioadmin'
+    ENDPOINT_URL='${STORAGE_S3_ENDPOINT_URL}'
+);
+EOF
+
+## rows with id 2 and 4 are deleted by deletion vectors
+echo "SELECT id, data FROM delta_ctl.delta_db.delta_tbl ORDER BY id;" | $MYSQL_CLIENT_CONNECT
+
+echo "SELECT count(*) FROM delta_ctl.delta_db.delta_tbl;" | $MYSQL_CLIENT_CONNECT
+
+echo "SELECT data FROM delta_ctl.delta_db.delta_tbl WHERE id > 4 ORDER BY id;" | $MYSQL_CLIENT_CONNECT
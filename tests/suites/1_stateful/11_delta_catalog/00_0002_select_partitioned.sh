@@ -0,0 +1,27 @@
+#!/usr/bin/env bash
+
+CURDIR=$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)
+. "$CURDIR"/../../../shell_env.sh
+
+echo "DROP CATALOG IF EXISTS delta_ctl" | $MYSQL_CLIENT_CONNECT
+
+## Create delta catalog
+cat <<EOF | $MYSQL_CLIENT_CONNECT
+CREATE CATALOG delta_ctl
+TYPE=DELTA
+CONNECTION=(
+    URL='s3://testbucket/delta_data/delta_ctl/'
+    AWS_KEY_ID='minioadmin'
+    AWS_SECRET_KEY='minioadmin'
+    ENDPOINT_URL='${STORAGE_S3_ENDPOINT_URL}'
+);
+EOF
+
+## region is a partition column, which is not stored in the data files
+echo "SELECT id, data, region FROM delta_ctl.delta_db.delta_part_tbl ORDER BY id;" | $MYSQL_CLIENT_CONNECT
+
+echo "SELECT id FROM delta_ctl.delta_db.delta_part_tbl WHERE region = 'eu' ORDER BY id;" | $MYSQL_CLIENT_CONNECT
+
+echo "SELECT data FROM delta_ctl.delta_db.delta_part_tbl WHERE region IS NULL;" | $MYSQL_CLIENT_CONNECT
+
+echo "SELECT region, count(*) FROM delta_ctl.delta_db.delta_part_tbl WHERE id > 1 AND region IS NOT NULL GROUP BY region ORDER BY region;" | $MYSQL_CLIENT_CONNECT
@@ -0,0 +1,8 @@
+select 'orc';
+select * from hive.default.t_orc order by big;
+select id, name from hive.default.t_orc where big > 30 order by id;
+select count(*) from hive.default.t_orc where id is null;
+select 'text';
+select * from hive.default.t_text order by name;
+select id, day from hive.default.t_text where price > 2 order by id;
+select count(*) from hive.default.t_text where day is null;